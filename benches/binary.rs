@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use serde_bytes::ByteBuf;
+
+fn binary_elements(c: &mut Criterion) {
+    let elements = (0..100_000)
+        .map(|i| ByteBuf::from((0..40).map(|j| (i + j) as u8).collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let buf = serde_smile::to_vec(&elements).unwrap();
+
+    let mut group = c.benchmark_group("binary_elements");
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("from_slice", |b| {
+        b.iter(|| serde_smile::from_slice::<Vec<ByteBuf>>(&buf).unwrap())
+    });
+    group.bench_function("from_reader", |b| {
+        b.iter(|| serde_smile::from_reader::<Vec<ByteBuf>, _>(&buf[..]).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, binary_elements);
+criterion_main!(benches);
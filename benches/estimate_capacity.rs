@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde::Serialize;
+use serde_smile::ser::Builder;
+
+#[derive(Serialize)]
+struct Record {
+    id: u64,
+    name: String,
+    score: f64,
+    tags: Vec<String>,
+}
+
+fn records(n: usize) -> Vec<Record> {
+    (0..n)
+        .map(|i| Record {
+            id: i as u64,
+            name: format!("record number {}", i),
+            score: i as f64 / 3.,
+            tags: vec![format!("tag{}", i % 7), format!("group{}", i % 3)],
+        })
+        .collect()
+}
+
+fn to_vec(c: &mut Criterion) {
+    let mut builder = Builder::jackson_defaults();
+    builder.estimate_capacity(true);
+
+    let mut group = c.benchmark_group("to_vec");
+    // a single record, and documents of roughly 1, 8, and 64 KiB
+    for n in [1, 16, 128, 1024] {
+        let value = records(n);
+        let len = serde_smile::to_vec(&value).unwrap().len();
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("default", len), &value, |b, value| {
+            b.iter(|| serde_smile::to_vec(value).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("estimate_capacity", len),
+            &value,
+            |b, value| b.iter(|| builder.to_vec(value).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, to_vec);
+criterion_main!(benches);
@@ -0,0 +1,30 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_smile::ser::Builder;
+
+fn repeated_map(c: &mut Criterion) {
+    let map = (0..50)
+        .map(|i| (format!("property_{}", i), i))
+        .collect::<IndexMap<_, _>>();
+
+    let mut group = c.benchmark_group("repeated_map");
+    for memoize_keys in [false, true] {
+        let mut builder = Builder::jackson_defaults();
+        builder.memoize_keys(memoize_keys);
+        let name = if memoize_keys { "memoized" } else { "plain" };
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut ser = builder.build(vec![]);
+                for _ in 0..10_000 {
+                    map.serialize(&mut ser).unwrap();
+                }
+                ser.into_inner()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, repeated_map);
+criterion_main!(benches);
@@ -0,0 +1,51 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use serde::{Deserialize, Serialize};
+use serde_smile::ser::Builder;
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    level: String,
+    target: String,
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct BorrowedEntry<'a> {
+    level: &'a str,
+    target: &'a str,
+    message: &'a str,
+}
+
+fn log_corpus(c: &mut Criterion) {
+    let levels = ["INFO", "WARN", "ERROR", "DEBUG"];
+    let targets = ["server::http", "server::db", "worker::queue"];
+    let entries = (0..100_000)
+        .map(|i| Entry {
+            level: levels[i % levels.len()].to_string(),
+            target: targets[i % targets.len()].to_string(),
+            message: format!("request {} completed", i % 10),
+        })
+        .collect::<Vec<_>>();
+    let mut ser = Builder::jackson_defaults()
+        .shared_strings(true)
+        .build(vec![]);
+    entries.serialize(&mut ser).unwrap();
+    let buf = ser.into_inner();
+
+    let mut group = c.benchmark_group("log_corpus");
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("from_slice", |b| {
+        b.iter(|| serde_smile::from_slice::<Vec<Entry>>(&buf).unwrap())
+    });
+    group.bench_function("from_slice_borrowed", |b| {
+        b.iter(|| serde_smile::from_slice::<Vec<BorrowedEntry<'_>>>(&buf).unwrap())
+    });
+    group.bench_function("from_reader", |b| {
+        b.iter(|| serde_smile::from_reader::<Vec<Entry>, _>(&buf[..]).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, log_corpus);
+criterion_main!(benches);
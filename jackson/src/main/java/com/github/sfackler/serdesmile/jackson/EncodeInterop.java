@@ -0,0 +1,148 @@
+package com.github.sfackler.serdesmile.jackson;
+
+import java.io.IOException;
+import java.math.BigDecimal;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.Arrays;
+import java.util.Date;
+import java.util.List;
+import java.util.Map;
+import java.util.TreeMap;
+
+import com.fasterxml.jackson.dataformat.smile.databind.SmileMapper;
+
+/**
+ * Encodes the POJO fixtures in tests/interop with Jackson's default Smile configuration.
+ *
+ * Each class mirrors a struct in src/test/interop.rs. Fields are public so Jackson writes them in declaration order.
+ */
+public final class EncodeInterop {
+    private static SmileMapper SMILE_MAPPER = new SmileMapper();
+
+    public enum Status {
+        OPEN, IN_PROGRESS, CLOSED
+    }
+
+    public static final class Primitives {
+        public int id = 42;
+        public long count = 9876543210L;
+        public double ratio = 0.75;
+        public boolean active = true;
+        public String name = "widget";
+    }
+
+    public static final class Customer {
+        public String firstName;
+        public String lastName;
+        public String email;
+
+        Customer(String firstName, String lastName, String email) {
+            this.firstName = firstName;
+            this.lastName = lastName;
+            this.email = email;
+        }
+    }
+
+    public static final class Address {
+        public String street = "1 Main Street";
+        public String city = "Springfield";
+        public String postalCode = "12345";
+    }
+
+    public static final class Order {
+        public int orderId = 1001;
+        public Customer customer = new Customer("Ada", "Lovelace", "ada@example.com");
+        public Address shippingAddress = new Address();
+    }
+
+    public static final class Ticket {
+        public int ticketId = 7;
+        public Status status = Status.IN_PROGRESS;
+        public List<Status> history = Arrays.asList(Status.OPEN, Status.CLOSED, Status.OPEN, Status.IN_PROGRESS);
+    }
+
+    public static final class Profile {
+        public String userName = "sfackler";
+        public String nickName = null;
+        public Integer age = 30;
+        public String bio = null;
+        public Boolean verified = false;
+    }
+
+    public static final class Config {
+        public String configName = "service";
+        public Map<String, String> properties = new TreeMap<>();
+        public Map<String, Integer> limits = new TreeMap<>();
+
+        Config() {
+            properties.put("host", "localhost");
+            properties.put("logLevel", "debug");
+            limits.put("maxConnections", 512);
+            limits.put("timeoutMs", -1);
+        }
+    }
+
+    public static final class Attachment {
+        public String fileName = "logo.png";
+        public String contentType = "image/png";
+        public byte[] data = new byte[] { (byte) 0x89, 'P', 'N', 'G', '\r', '\n', 0x1a, '\n', 0, 0, 0, 0x0d };
+    }
+
+    public static final class Event {
+        public String eventId = "evt-1";
+        public Date createdAt = new Date(1634567890123L);
+        public Date updatedAt = null;
+        public List<Date> retries = Arrays.asList(new Date(0), new Date(-1000), new Date(1700000000000L));
+    }
+
+    public static final class Invoice {
+        public String invoiceNumber = "INV-2021-0001";
+        public BigDecimal total = new BigDecimal("1234.56");
+        public BigDecimal taxRate = new BigDecimal("0.0825");
+        public List<BigDecimal> lineAmounts = Arrays.asList(new BigDecimal("-10.5"), new BigDecimal("1E+3"),
+                new BigDecimal("0"));
+    }
+
+    public static final class Member {
+        public int memberId;
+        public String displayName;
+
+        Member(int memberId, String displayName) {
+            this.memberId = memberId;
+            this.displayName = displayName;
+        }
+    }
+
+    public static final class Team {
+        public String teamName = "core";
+        public List<Member> members = Arrays.asList(new Member(1, "alice"), new Member(2, "bob"),
+                new Member(3, "carol"));
+    }
+
+    public static final class Message {
+        public String senderName = "Zo\u00eb";
+        public String subject = "";
+        public String body = "The quick brown fox jumps over the lazy dog, and then the dog jumps back "
+                + "\u2014 \ud83e\udd8a\ud83d\udc15!";
+    }
+
+    public static void main(String[] args) throws IOException {
+        encode("primitives", new Primitives());
+        encode("nested", new Order());
+        encode("enum_list", new Ticket());
+        encode("optional_fields", new Profile());
+        encode("string_maps", new Config());
+        encode("binary", new Attachment());
+        encode("timestamps", new Event());
+        encode("big_decimal", new Invoice());
+        encode("list_of_structs", new Team());
+        encode("unicode_strings", new Message());
+    }
+
+    private static void encode(String name, Object value) throws IOException {
+        Path path = Paths.get("../tests/interop", name + ".smile");
+        Files.write(path, SMILE_MAPPER.writeValueAsBytes(value));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::encoding;
+use crate::Error;
+
+/// How byte buffers used as map keys are handled.
+///
+/// Smile map keys must be strings. Byte buffers such as `serde_bytes::ByteBuf` or `[u8; N]` can instead be encoded
+/// as text when serialized and decoded back when deserialized. The serializer and deserializer must be configured with
+/// the same policy.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ByteKeyPolicy {
+    /// Byte buffer keys are rejected by the serializer, and are not decoded by the deserializer.
+    #[default]
+    Reject,
+    /// Byte buffer keys are encoded as lowercase hex strings.
+    Hex,
+    /// Byte buffer keys are encoded as standard, padded base64 strings.
+    Base64,
+}
+
+impl ByteKeyPolicy {
+    pub(crate) fn encode(self, v: &[u8]) -> Result<String, Error> {
+        let mut out = String::new();
+        match self {
+            ByteKeyPolicy::Reject => return Err(Error::key_must_be_a_string()),
+            ByteKeyPolicy::Hex => encoding::encode_hex(&mut out, v),
+            ByteKeyPolicy::Base64 => encoding::encode_base64(&mut out, v),
+        }
+        Ok(out)
+    }
+
+    /// Returns `None` under the `Reject` policy.
+    pub(crate) fn decode(self, s: &str) -> Option<Result<Vec<u8>, Error>> {
+        let (v, name) = match self {
+            ByteKeyPolicy::Reject => return None,
+            ByteKeyPolicy::Hex => (encoding::decode_hex(s), "hex"),
+            ByteKeyPolicy::Base64 => (encoding::decode_base64(s), "base64"),
+        };
+        Some(v.ok_or_else(|| Error::invalid_byte_key(name)))
+    }
+}
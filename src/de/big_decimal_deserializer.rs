@@ -1,10 +1,10 @@
 use crate::de::read::Buf;
 use crate::de::{zigzag_i32, Read};
-use crate::value::BigDecimal;
+use crate::value::synthetic_key::SyntheticKey;
 use crate::{Deserializer, Error};
-use serde::de::value::BorrowedStrDeserializer;
 use serde::de::{DeserializeSeed, MapAccess, Visitor};
 use serde::forward_to_deserialize_any;
+use std::convert::TryFrom;
 
 #[derive(Copy, Clone)]
 pub(crate) enum Stage {
@@ -28,12 +28,8 @@ where
         K: DeserializeSeed<'de>,
     {
         match self.stage {
-            Some(Stage::Scale) => seed
-                .deserialize(BorrowedStrDeserializer::new(BigDecimal::SCALE_FIELD_NAME))
-                .map(Some),
-            Some(Stage::Buf) => seed
-                .deserialize(BorrowedStrDeserializer::new(BigDecimal::VALUE_FIELD_NAME))
-                .map(Some),
+            Some(Stage::Scale) => seed.deserialize(SyntheticKey::DecimalScale).map(Some),
+            Some(Stage::Buf) => seed.deserialize(SyntheticKey::DecimalValue).map(Some),
             None => Ok(None),
         }
     }
@@ -80,11 +76,12 @@ where
         match self.stage {
             Stage::Scale => {
                 let scale = self.de.parse_vint(5)?;
-                let scale = zigzag_i32(scale as u32);
+                let scale = u32::try_from(scale).map_err(|_| Error::scale_out_of_range())?;
+                let scale = zigzag_i32(scale);
                 visitor.visit_i32(scale)
             }
             Stage::Buf => {
-                let buf = self.de.parse_7_bit_binary()?;
+                let buf = self.de.parse_big_integer_bytes()?;
                 match buf {
                     Buf::Short(buf) => visitor.visit_bytes(buf),
                     Buf::Long(buf) => visitor.visit_borrowed_bytes(buf),
@@ -1,7 +1,6 @@
 use crate::de::read::Buf;
-use crate::value::BigInteger;
+use crate::value::synthetic_key::SyntheticKey;
 use crate::Error;
-use serde::de::value::BorrowedStrDeserializer;
 use serde::de::{DeserializeSeed, MapAccess, Visitor};
 use serde::forward_to_deserialize_any;
 
@@ -20,8 +19,7 @@ impl<'de> MapAccess<'de> for BigIntegerDeserializer<'_, 'de> {
             return Ok(None);
         }
 
-        seed.deserialize(BorrowedStrDeserializer::new(BigInteger::FIELD_NAME))
-            .map(Some)
+        seed.deserialize(SyntheticKey::IntegerValue).map(Some)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
@@ -0,0 +1,35 @@
+/// The Smile header read from the start of a document.
+///
+/// Returned by [`Deserializer::header`](crate::Deserializer::header).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Header {
+    info: u8,
+}
+
+impl Header {
+    pub(crate) fn new(info: u8) -> Self {
+        Header { info }
+    }
+
+    /// Returns the version number stored in the high nibble of the header's flags byte.
+    ///
+    /// This is always 0 unless [`Builder::accept_any_version`](crate::de::Builder::accept_any_version) is enabled.
+    pub fn version(&self) -> u8 {
+        self.info >> 4
+    }
+
+    /// Returns whether the document may contain binary data in "raw" form.
+    pub fn raw_binary(&self) -> bool {
+        self.info & 0x04 != 0
+    }
+
+    /// Returns whether the document uses shared value strings.
+    pub fn shared_strings(&self) -> bool {
+        self.info & 0x02 != 0
+    }
+
+    /// Returns whether the document uses shared map key strings.
+    pub fn shared_properties(&self) -> bool {
+        self.info & 0x01 != 0
+    }
+}
@@ -1,14 +1,18 @@
 use crate::de::read::Buf;
+use crate::de::string_cache::check_string_caches;
 use crate::de::{Deserializer, Read};
-use crate::Error;
+use crate::{integer_key, Error};
+use serde::de::value::SeqDeserializer;
 use serde::de::{self, Visitor};
-use serde::{forward_to_deserialize_any, serde_if_integer128};
+use serde::forward_to_deserialize_any;
 use std::borrow::Cow;
 use std::ops::Deref;
 use std::str;
 
 pub(crate) struct KeyDeserializer<'a, 'de, R> {
     pub(crate) de: &'a mut Deserializer<'de, R>,
+    /// The struct fields which identifiers are matched against case-insensitively, if enabled.
+    pub(crate) fields: &'static [&'static str],
 }
 
 impl<'de, R> KeyDeserializer<'_, 'de, R>
@@ -22,6 +26,7 @@ where
             .as_ref()
             .and_then(|c| c.get(reference))
             .ok_or_else(Error::invalid_string_reference)?;
+        self.de.tag_reference(Some(reference));
 
         let s = match cow {
             Cow::Borrowed(s) => Str::Long(s),
@@ -49,6 +54,11 @@ where
                 if s.len() <= 64 {
                     if let Some(shared_properties) = &mut self.de.shared_properties {
                         shared_properties.intern(Cow::Owned(s.to_string()));
+                        check_string_caches(
+                            self.de.max_property_cache_bytes,
+                            &self.de.shared_strings,
+                            &self.de.shared_properties,
+                        )?;
                     }
                 }
 
@@ -59,6 +69,11 @@ where
                 if s.len() <= 64 {
                     if let Some(shared_properties) = &mut self.de.shared_properties {
                         shared_properties.intern(Cow::Borrowed(s));
+                        check_string_caches(
+                            self.de.max_property_cache_bytes,
+                            &self.de.shared_strings,
+                            &self.de.shared_properties,
+                        )?;
                     }
                 }
 
@@ -75,22 +90,35 @@ where
         self.parse_str_inner(|r| r.read(len))
     }
 
+    fn parse_byte_key<'a>(&'a mut self) -> Result<ByteKey<'a, 'de>, Error> {
+        let byte_keys = self.de.byte_keys;
+        let s = self.parse_str()?;
+        match byte_keys.decode(&s) {
+            Some(v) => v.map(ByteKey::Bytes),
+            None => Ok(ByteKey::Str(s)),
+        }
+    }
+
     fn parse_str<'a>(&'a mut self) -> Result<Str<'a, 'de>, Error> {
+        self.de.count_element()?;
+        self.de.tag_reference(None);
         match self.de.parse_u8()? {
-            0x00..=0x1f => Err(Error::reserved_token()),
+            token @ 0x00..=0x1f => Err(self.de.reserved_token(token)),
             0x20 => Ok(Str::Long("")),
-            0x21..=0x2f => Err(Error::reserved_token()),
+            token @ 0x21..=0x2f => Err(self.de.reserved_token(token)),
             token @ 0x30..=0x33 => self.parse_long_shared_str(token - 0x30),
             0x34 => self.parse_long_str(),
-            0x35..=0x39 => Err(Error::reserved_token()),
-            0x3a => Err(Error::unexpected_token()),
-            0x3b..=0x3f => Err(Error::reserved_token()),
+            token @ 0x35..=0x39 => Err(self.de.reserved_token(token)),
+            0x3a => Err(self
+                .de
+                .colon_error(|offset| Error::unexpected_token(0x3a, offset))),
+            token @ 0x3b..=0x3f => Err(self.de.reserved_token(token)),
             token @ 0x40..=0x7f => self.parse_shared_str(token as u16 - 0x40),
             token @ 0x80..=0xbf => self.parse_short_str(token as usize - (0x80 - 1)),
             token @ 0xc0..=0xf7 => self.parse_short_str(token as usize - (0xc0 - 2)),
-            0xf8..=0xfa => Err(Error::reserved_token()),
-            0xfb => Err(Error::unexpected_token()),
-            0xfc..=0xff => Err(Error::reserved_token()),
+            token @ 0xf8..=0xfa => Err(self.de.reserved_token(token)),
+            token @ 0xfb => Err(self.de.unexpected_token(token)),
+            token @ 0xfc..=0xff => Err(self.de.reserved_token(token)),
         }
     }
 }
@@ -102,10 +130,10 @@ macro_rules! deserialize_integer_key {
             V: Visitor<'de>,
         {
             let s = self.parse_str()?;
-            match (s.parse(), s) {
-                (Ok(integer), _) => visitor.$visit(integer),
-                (Err(_), Str::Short(s)) => visitor.visit_str(s),
-                (Err(_), Str::Long(s)) => visitor.visit_borrowed_str(s),
+            match (integer_key::parse(&s), s) {
+                (Some(integer), _) => visitor.$visit(integer?),
+                (None, Str::Short(s)) => visitor.visit_str(s),
+                (None, Str::Long(s)) => visitor.visit_borrowed_str(s),
             }
         }
     };
@@ -132,18 +160,14 @@ where
     deserialize_integer_key!(deserialize_i32 => visit_i32);
     deserialize_integer_key!(deserialize_i64 => visit_i64);
 
-    serde_if_integer128! {
-        deserialize_integer_key!(deserialize_i128 => visit_i128);
-    }
+    deserialize_integer_key!(deserialize_i128 => visit_i128);
 
     deserialize_integer_key!(deserialize_u8 => visit_u8);
     deserialize_integer_key!(deserialize_u16 => visit_u16);
     deserialize_integer_key!(deserialize_u32 => visit_u32);
     deserialize_integer_key!(deserialize_u64 => visit_u64);
 
-    serde_if_integer128! {
-        deserialize_integer_key!(deserialize_u128 => visit_u128);
-    }
+    deserialize_integer_key!(deserialize_u128 => visit_u128);
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
@@ -175,9 +199,81 @@ where
         visitor.visit_enum(EnumAccess { de: self })
     }
 
+    fn deserialize_identifier<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let fields = self.fields;
+        let s = self.parse_str()?;
+        if !fields.contains(&&*s) {
+            let field = fields.iter().find(|f| {
+                f.chars()
+                    .flat_map(char::to_lowercase)
+                    .eq(s.chars().flat_map(char::to_lowercase))
+            });
+            if let Some(field) = field {
+                return visitor.visit_borrowed_str(field);
+            }
+        }
+
+        match s {
+            Str::Short(s) => visitor.visit_str(s),
+            Str::Long(s) => visitor.visit_borrowed_str(s),
+        }
+    }
+
+    fn deserialize_char<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let s = self.parse_str()?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(Error::mismatched_value(de::Unexpected::Str(&s), &visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse_byte_key()? {
+            ByteKey::Bytes(v) => visitor.visit_byte_buf(v),
+            ByteKey::Str(Str::Short(s)) => visitor.visit_str(s),
+            ByteKey::Str(Str::Long(s)) => visitor.visit_borrowed_str(s),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse_byte_key()? {
+            ByteKey::Bytes(v) => {
+                de::Deserializer::deserialize_any(SeqDeserializer::new(v.into_iter()), visitor)
+            }
+            ByteKey::Str(Str::Short(s)) => visitor.visit_str(s),
+            ByteKey::Str(Str::Long(s)) => visitor.visit_borrowed_str(s),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
     forward_to_deserialize_any! {
-        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
-        tuple_struct map struct identifier ignored_any
+        bool f32 f64 str string unit unit_struct tuple_struct map struct ignored_any
     }
 
     #[inline]
@@ -220,7 +316,7 @@ impl<'de> de::VariantAccess<'de> for UnitVariantAccess {
     where
         T: de::DeserializeSeed<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"newtype variant",
         ))
@@ -230,7 +326,7 @@ impl<'de> de::VariantAccess<'de> for UnitVariantAccess {
     where
         V: Visitor<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"tuple variant",
         ))
@@ -244,13 +340,18 @@ impl<'de> de::VariantAccess<'de> for UnitVariantAccess {
     where
         V: Visitor<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"struct variant",
         ))
     }
 }
 
+enum ByteKey<'a, 'de> {
+    Bytes(Vec<u8>),
+    Str(Str<'a, 'de>),
+}
+
 enum Str<'a, 'de> {
     Short(&'a str),
     Long(&'de str),
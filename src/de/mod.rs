@@ -1,15 +1,22 @@
 //! Deserialize Smile data into a Rust data structure.
+pub use crate::byte_key_policy::ByteKeyPolicy;
 use crate::de::big_decimal_deserializer::BigDecimalDeserializer;
 use crate::de::big_integer_deserializer::BigIntegerDeserializer;
+pub use crate::de::header::Header;
 use crate::de::key_deserializer::KeyDeserializer;
 use crate::de::read::{Buf, MutBuf};
-pub use crate::de::read::{IoRead, MutSliceRead, Read, SliceRead};
+pub use crate::de::read::{CowRead, IoRead, MutSliceRead, Read, ReadState, SliceRead};
+pub use crate::de::reference_tags::ReferenceTags;
 pub use crate::de::stream_deserializer::StreamDeserializer;
-use crate::de::string_cache::StringCache;
-use crate::value::{BigDecimal, BigInteger};
+use crate::de::string_cache::{check_string_caches, StringCache};
+use crate::limits::DEFAULT_RECURSION_LIMIT;
+#[cfg(feature = "metrics")]
+use crate::telemetry::Timer;
+use crate::value::big_integer::{self, NativeInteger};
+use crate::value::{BigDecimal, BigInteger, Value};
 use crate::Error;
-use serde::de::{self, DeserializeOwned, Visitor};
-use serde::{serde_if_integer128, Deserialize, Deserializer as _};
+use serde::de::{self, DeserializeOwned, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer as _};
 use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::io::BufRead;
@@ -18,10 +25,12 @@ use std::str;
 
 mod big_decimal_deserializer;
 mod big_integer_deserializer;
+mod header;
 mod key_deserializer;
 mod read;
+mod reference_tags;
 mod stream_deserializer;
-mod string_cache;
+pub(crate) mod string_cache;
 
 /// Deserializes an instance of type `T` from a slice of Smile data.
 ///
@@ -30,10 +39,7 @@ pub fn from_slice<'de, T>(slice: &'de [u8]) -> Result<T, Error>
 where
     T: Deserialize<'de>,
 {
-    let mut de = Deserializer::from_slice(slice);
-    let value = T::deserialize(&mut de)?;
-    de.end()?;
-    Ok(value)
+    deserialize_document(Deserializer::from_slice(slice), "slice")
 }
 
 /// Deserializes an instance of type `T` from a mutable slice of Smile data.
@@ -44,10 +50,18 @@ pub fn from_mut_slice<'de, T>(slice: &'de mut [u8]) -> Result<T, Error>
 where
     T: Deserialize<'de>,
 {
-    let mut de = Deserializer::from_mut_slice(slice);
-    let value = T::deserialize(&mut de)?;
-    de.end()?;
-    Ok(value)
+    deserialize_document(Deserializer::from_mut_slice(slice), "mut_slice")
+}
+
+/// Deserializes an instance of type `T` from a possibly-owned slice of Smile data.
+///
+/// Borrowed input behaves as with [`from_slice`]. Nothing can be borrowed from owned input, but its 7-bit encoded
+/// binary data is decoded in place without copying.
+pub fn from_cow<'de, T>(slice: Cow<'de, [u8]>) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    deserialize_document(Deserializer::from_cow(slice), "cow")
 }
 
 /// Deserializes an instance of type `T` from an IO stream of Smile data.
@@ -58,21 +72,284 @@ where
     T: DeserializeOwned,
     R: BufRead,
 {
-    let mut de = Deserializer::from_reader(reader);
+    deserialize_document(Deserializer::from_reader(reader), "io")
+}
+
+/// Deserializes a complete document using default deserializer settings, recording metrics for the call if enabled.
+#[cfg(feature = "metrics")]
+fn deserialize_document<'de, T, R>(
+    mut de: Deserializer<'de, R>,
+    kind: &'static str,
+) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+    R: Read<'de>,
+{
+    let timer = Timer::start();
+    let result = T::deserialize(&mut de).and_then(|value| de.end().map(|()| value));
+    timer.deserialized(kind, result.as_ref().map(|_| de.reader.byte_offset()));
+    result
+}
+
+#[cfg(not(feature = "metrics"))]
+#[inline]
+fn deserialize_document<'de, T, R>(
+    mut de: Deserializer<'de, R>,
+    _: &'static str,
+) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+    R: Read<'de>,
+{
     let value = T::deserialize(&mut de)?;
     de.end()?;
     Ok(value)
 }
 
+/// A builder to configure a [`Deserializer`].
+#[derive(Clone, Debug)]
+pub struct Builder {
+    byte_keys: ByteKeyPolicy,
+    strict_number_types: bool,
+    accept_any_version: bool,
+    case_insensitive_fields: bool,
+    max_total_elements: u64,
+    reference_tags: bool,
+    skip_stray_string_terminators: bool,
+    empty_containers_as_unit: bool,
+    strict_big_integers: bool,
+    max_property_cache_bytes: usize,
+}
+
+impl Builder {
+    /// Sets the policy used for byte buffer map keys.
+    ///
+    /// When set to [`ByteKeyPolicy::Hex`] or [`ByteKeyPolicy::Base64`], map keys deserialized as bytes or as sequences
+    /// are decoded back into byte buffers. This must match the policy passed to [`crate::ser::Builder::byte_keys`].
+    ///
+    /// Defaults to [`ByteKeyPolicy::Reject`].
+    pub fn byte_keys(&mut self, byte_keys: ByteKeyPolicy) -> &mut Self {
+        self.byte_keys = byte_keys;
+        self
+    }
+
+    /// If enabled, numeric values must be encoded with a Smile type matching the Rust type they're deserialized into.
+    ///
+    /// Rust numeric types will reject `BigInteger` values even if they're small enough to fit, `f32` will reject
+    /// `double` values, and `f64` will reject `float` values. Other conversions, such as an `integer` value into an
+    /// `i64` or `f64`, are unaffected.
+    ///
+    /// Defaults to `false`.
+    pub fn strict_number_types(&mut self, strict_number_types: bool) -> &mut Self {
+        self.strict_number_types = strict_number_types;
+        self
+    }
+
+    /// If enabled, documents with a nonzero version number in their header are accepted rather than rejected.
+    ///
+    /// The version is otherwise ignored, and can be retrieved with [`Deserializer::header`].
+    ///
+    /// Defaults to `false`.
+    pub fn accept_any_version(&mut self, accept_any_version: bool) -> &mut Self {
+        self.accept_any_version = accept_any_version;
+        self
+    }
+
+    /// If enabled, map keys are matched to struct field names case-insensitively.
+    ///
+    /// This mirrors Jackson's `ACCEPT_CASE_INSENSITIVE_PROPERTIES` feature. A key which does not exactly match one of
+    /// the struct's fields is matched to the first field which is equal to it ignoring case, so a `userName` field can
+    /// be read from `username` or `USERNAME` keys. Only struct fields are affected; the keys of maps such as
+    /// `HashMap<String, V>` are always deserialized unchanged.
+    ///
+    /// Defaults to `false`.
+    pub fn case_insensitive_fields(&mut self, case_insensitive_fields: bool) -> &mut Self {
+        self.case_insensitive_fields = case_insensitive_fields;
+        self
+    }
+
+    /// Sets the maximum number of elements the deserializer will decode before returning an error.
+    ///
+    /// Every value, including arrays and objects themselves, and every object key counts as one element. This bounds
+    /// the work done on untrusted input regardless of the type it's deserialized into. The count is reset before each
+    /// value produced by a [`StreamDeserializer`].
+    ///
+    /// Defaults to `u64::MAX`.
+    pub fn max_total_elements(&mut self, max_total_elements: u64) -> &mut Self {
+        self.max_total_elements = max_total_elements;
+        self
+    }
+
+    /// If enabled, the deserializer records whether each string it visits was decoded from a shared string
+    /// back-reference.
+    ///
+    /// The tag can be read with [`Deserializer::last_string_reference`], or from within a `DeserializeSeed` through the
+    /// handle returned by [`Deserializer::reference_tags`].
+    ///
+    /// Defaults to `false`.
+    pub fn reference_tags(&mut self, reference_tags: bool) -> &mut Self {
+        self.reference_tags = reference_tags;
+        self
+    }
+
+    /// If enabled, stray end-of-string `0xFC` tokens are skipped where a value or key is expected.
+    ///
+    /// This is a workaround for documents written by a buggy producer which emitted `0xFC` after every string rather
+    /// than only after long strings. Only whole tokens are skipped, so `0xFC` bytes in other positions, such as within
+    /// a length or the contents of a string, are handled exactly as they would be without this option. Well-formed
+    /// documents never contain a stray `0xFC` token, so this should only be enabled to read data from such producers.
+    ///
+    /// Defaults to `false`.
+    pub fn skip_stray_string_terminators(
+        &mut self,
+        skip_stray_string_terminators: bool,
+    ) -> &mut Self {
+        self.skip_stray_string_terminators = skip_stray_string_terminators;
+        self
+    }
+
+    /// If enabled, empty arrays and objects are accepted where a unit or unit struct is expected, in addition to
+    /// `null`.
+    ///
+    /// Some Java serializers write marker types as an empty object, so this allows values such as the `Present` in a
+    /// `HashMap<String, Present>` to be read from either form. Non-empty arrays and objects are still rejected.
+    ///
+    /// Defaults to `false`.
+    pub fn empty_containers_as_unit(&mut self, empty_containers_as_unit: bool) -> &mut Self {
+        self.empty_containers_as_unit = empty_containers_as_unit;
+        self
+    }
+
+    /// If enabled, the two's complement payloads of `BigInteger` values and of the unscaled values of `BigDecimal`
+    /// values must be minimally encoded.
+    ///
+    /// Payloads with a redundant leading sign extension byte (a `0x00` followed by a byte with a clear high bit, or a
+    /// `0xFF` followed by a byte with a set high bit) and empty payloads are rejected, so every accepted number has
+    /// exactly one encoding. This is the encoding produced by Java's `BigInteger.toByteArray` and by the `serde_smile`
+    /// serializer for normalized values.
+    ///
+    /// When disabled, redundant bytes are passed through unchanged and an empty payload is read as zero, represented
+    /// by a single `0x00` byte.
+    ///
+    /// Defaults to `false`.
+    pub fn strict_big_integers(&mut self, strict_big_integers: bool) -> &mut Self {
+        self.strict_big_integers = strict_big_integers;
+        self
+    }
+
+    /// Sets the maximum total length in bytes of the strings held in the shared property and shared value string
+    /// caches combined.
+    ///
+    /// A document which enables shared strings can make the deserializer hold up to 1024 strings of up to 64 bytes in
+    /// each cache. Since the producer may refer back to any of them, entries can't be dropped early, so the
+    /// deserializer instead returns an error as soon as the limit is exceeded. Every cached string counts towards the
+    /// limit, including those borrowed from the input, so a document is accepted or rejected regardless of the input
+    /// source. The caches are emptied by the producer every 1024 strings and at the start of each document.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_property_cache_bytes(&mut self, max_property_cache_bytes: usize) -> &mut Self {
+        self.max_property_cache_bytes = max_property_cache_bytes;
+        self
+    }
+
+    /// Creates a new [`Deserializer`] from one of the possible `serde_smile` input sources.
+    pub fn build<'de, R>(&self, reader: R) -> Deserializer<'de, R>
+    where
+        R: Read<'de>,
+    {
+        Deserializer {
+            reader,
+            depth: 0,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            byte_keys: self.byte_keys,
+            strict_number_types: self.strict_number_types,
+            accept_any_version: self.accept_any_version,
+            case_insensitive_fields: self.case_insensitive_fields,
+            max_total_elements: self.max_total_elements,
+            elements: 0,
+            reference_tags: if self.reference_tags {
+                Some(ReferenceTags::new())
+            } else {
+                None
+            },
+            skip_stray_string_terminators: self.skip_stray_string_terminators,
+            empty_containers_as_unit: self.empty_containers_as_unit,
+            strict_big_integers: self.strict_big_integers,
+            max_property_cache_bytes: self.max_property_cache_bytes,
+            initialized: false,
+            header: None,
+            shared_strings: None,
+            shared_properties: None,
+        }
+    }
+}
+
 /// A structure that deserializes Smile into Rust values.
 pub struct Deserializer<'de, R> {
     reader: R,
-    remaining_depth: u8,
+    depth: usize,
+    recursion_limit: usize,
+    byte_keys: ByteKeyPolicy,
+    strict_number_types: bool,
+    accept_any_version: bool,
+    case_insensitive_fields: bool,
+    max_total_elements: u64,
+    elements: u64,
+    reference_tags: Option<ReferenceTags>,
+    skip_stray_string_terminators: bool,
+    empty_containers_as_unit: bool,
+    strict_big_integers: bool,
+    max_property_cache_bytes: usize,
     initialized: bool,
+    header: Option<Header>,
     shared_strings: Option<StringCache<'de>>,
     shared_properties: Option<StringCache<'de>>,
 }
 
+/// A `Deserializer` reading from an IO stream.
+///
+/// Nothing can be borrowed from an IO stream, so the `'de` lifetime of a `Deserializer` created by
+/// [`Deserializer::from_reader`] is unconstrained. This alias fixes it to `'static`, which makes the deserializer
+/// easier to store and to use through type-erased interfaces like [`erased_serde`] that expect a single lifetime.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::de::IoDeserializer;
+///
+/// let buf = serde_smile::to_vec(&vec!["hello", "world"]).unwrap();
+///
+/// let mut de: IoDeserializer<&[u8]> = serde_smile::Deserializer::from_reader(&buf[..]);
+/// let mut erased: Box<dyn erased_serde::Deserializer<'static>> =
+///     Box::new(<dyn erased_serde::Deserializer>::erase(&mut de));
+/// let value: Vec<String> = erased_serde::deserialize(&mut *erased).unwrap();
+/// drop(erased);
+/// de.end().unwrap();
+///
+/// assert_eq!(value, ["hello", "world"]);
+/// ```
+///
+/// [`erased_serde`]: https://docs.rs/erased-serde
+pub type IoDeserializer<R> = Deserializer<'static, IoRead<R>>;
+
+impl Deserializer<'static, ()> {
+    /// Returns a builder used to configure a `Deserializer`.
+    pub fn builder() -> Builder {
+        Builder {
+            byte_keys: ByteKeyPolicy::Reject,
+            strict_number_types: false,
+            accept_any_version: false,
+            case_insensitive_fields: false,
+            max_total_elements: u64::MAX,
+            reference_tags: false,
+            skip_stray_string_terminators: false,
+            empty_containers_as_unit: false,
+            strict_big_integers: false,
+            max_property_cache_bytes: usize::MAX,
+        }
+    }
+}
+
 impl<'de> Deserializer<'de, SliceRead<'de>> {
     /// Creates a `Deserializer` from a shared slice.
     ///
@@ -92,6 +369,16 @@ impl<'de> Deserializer<'de, MutSliceRead<'de>> {
     }
 }
 
+impl<'de> Deserializer<'de, CowRead<'de>> {
+    /// Creates a `Deserializer` from a possibly-owned slice.
+    ///
+    /// Borrowed input behaves as with [`Self::from_slice`]. Nothing can be borrowed from owned input, but its 7-bit
+    /// encoded binary data is decoded in place without copying.
+    pub fn from_cow(slice: Cow<'de, [u8]>) -> Self {
+        Deserializer::new(CowRead::new(slice))
+    }
+}
+
 impl<'de, R> Deserializer<'de, IoRead<R>>
 where
     R: BufRead,
@@ -102,6 +389,16 @@ where
     pub fn from_reader(reader: R) -> Self {
         Deserializer::new(IoRead::new(reader))
     }
+
+    /// Consumes the `Deserializer`, returning the inner reader along with any bytes which were read from it but not
+    /// consumed by the deserializer.
+    ///
+    /// The deserializer only peeks into the reader's own buffer when looking ahead, so the reader is positioned
+    /// immediately after the last value deserialized (or the end-of-stream token consumed by [`Self::end`]), and the
+    /// returned bytes are always empty after successful deserialization. The position is unspecified after an error.
+    pub fn into_reader_parts(self) -> (R, Vec<u8>) {
+        (self.reader.into_inner(), vec![])
+    }
 }
 
 impl<'de, R> Deserializer<'de, R>
@@ -110,16 +407,10 @@ where
 {
     /// Creates a new `Deserializer` from one of the possible `serde_smile` input sources.
     ///
-    /// The [`Self::from_slice`], [`Self::from_mut_slice`], and [`Self::from_reader`] constructors should generally be
-    /// preferred to this.
+    /// The [`Self::from_slice`], [`Self::from_mut_slice`], [`Self::from_cow`], and [`Self::from_reader`] constructors
+    /// should generally be preferred to this.
     pub fn new(reader: R) -> Self {
-        Deserializer {
-            reader,
-            remaining_depth: 128,
-            initialized: false,
-            shared_strings: None,
-            shared_properties: None,
-        }
+        Deserializer::builder().build(reader)
     }
 
     /// Returns a shared reference to the inner reader.
@@ -169,9 +460,10 @@ where
         }
 
         let info = header[3];
-        if info & 0xf0 != 0 {
+        if info & 0xf0 != 0 && !self.accept_any_version {
             return Err(Error::unsupported_version());
         }
+        self.header = Some(Header::new(info));
 
         self.shared_strings = if info & 0x02 != 0 {
             Some(StringCache::new())
@@ -187,28 +479,161 @@ where
         Ok(())
     }
 
+    /// Returns the header of the document, if it has been read.
+    pub fn header(&self) -> Option<Header> {
+        self.header
+    }
+
+    /// Returns the shared string back-reference the most recently visited string was decoded from.
+    ///
+    /// This is only tracked if enabled with [`Builder::reference_tags`], and always returns `None` otherwise. Object
+    /// keys refer to the shared property name table and values to the shared value string table, so the same
+    /// reference from a key and from a value identifies different strings. A table is cleared once it holds 1024
+    /// strings, after which its references are reused for new strings.
+    ///
+    /// The tag is overwritten by every value and key the deserializer reads, so it is only meaningful immediately after
+    /// a string has been visited, such as in a custom `DeserializeSeed` directly after deserializing a `String`.
+    /// It is `None` if that string was encoded inline.
+    pub fn last_string_reference(&self) -> Option<u16> {
+        self.reference_tags
+            .as_ref()
+            .and_then(ReferenceTags::last_string_reference)
+    }
+
+    /// Returns a handle observing the same tag as [`Self::last_string_reference`].
+    ///
+    /// The handle can be moved into a `DeserializeSeed` or `Visitor`, which otherwise has no access to the concrete
+    /// deserializer. Returns `None` unless enabled with [`Builder::reference_tags`].
+    pub fn reference_tags(&self) -> Option<ReferenceTags> {
+        self.reference_tags.clone()
+    }
+
     /// Validates that all Smile data has been consumed from the input.
     ///
     /// Both the Smile end-of-stream token and an actual EOF from the input are considered valid ends.
     pub fn end(&mut self) -> Result<(), Error> {
         self.read_header()?;
+        self.skip_stray_string_terminators()?;
         match self.reader.next()? {
             Some(0xff) => Ok(()),
+            Some(b':') => Err(self.colon_error(|_| Error::trailing_data())),
             Some(_) => Err(Error::trailing_data()),
             None => Ok(()),
         }
     }
 
+    /// Deserializes the next value as a [`Value`] and validates that it is the last one in the input.
+    ///
+    /// This is equivalent to calling `Value::deserialize` followed by [`Self::end`], and is useful for protocols that
+    /// decode a typed prefix of a stream before handling the rest of it dynamically. The deserializer is left in place
+    /// so the reader can be recovered afterwards; see [`Self::finish_value`] to consume it instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_smile::value::Value;
+    /// use serde_smile::{smile, Deserializer, Serializer};
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Envelope {
+    ///     kind: String,
+    /// }
+    ///
+    /// let mut ser = Serializer::new(vec![]);
+    /// Envelope { kind: "event".to_string() }.serialize(&mut ser).unwrap();
+    /// smile!({"id": 1}).serialize(&mut ser).unwrap();
+    /// let buf = ser.into_inner();
+    ///
+    /// let mut de = Deserializer::from_slice(&buf);
+    /// let envelope = Envelope::deserialize(&mut de).unwrap();
+    /// assert_eq!(envelope.kind, "event");
+    /// assert_eq!(de.value().unwrap(), smile!({"id": 1}));
+    /// ```
+    pub fn value(&mut self) -> Result<Value, Error> {
+        let value = Value::deserialize(&mut *self)?;
+        self.end()?;
+        Ok(value)
+    }
+
+    /// Consumes the deserializer, deserializing the next value as a [`Value`] and validating that it is the last one in
+    /// the input.
+    ///
+    /// See [`Self::value`] for details.
+    pub fn finish_value(mut self) -> Result<Value, Error> {
+        self.value()
+    }
+
+    /// Returns the error for a `:` token read where it isn't allowed.
+    ///
+    /// The token is the first byte of the Smile header, so finding it mid-document usually means that two documents
+    /// were concatenated without an end-of-stream token between them. Only the full `:)\n` signature is reported as a
+    /// document boundary.
+    fn colon_error<F>(&mut self, default: F) -> Error
+    where
+        F: FnOnce(u64) -> Error,
+    {
+        let offset = self.reader.byte_offset() - 1;
+        match self.reader.read(2) {
+            Ok(Some(buf)) if *buf == *b")\n" => Error::unexpected_document_boundary(),
+            _ => default(offset),
+        }
+    }
+
+    /// Returns the error for a reserved token which was just read.
+    fn reserved_token(&self, token: u8) -> Error {
+        Error::reserved_token(token, self.reader.byte_offset() - 1)
+    }
+
+    /// Returns the error for a token which was just read where it isn't allowed.
+    fn unexpected_token(&self, token: u8) -> Error {
+        Error::unexpected_token(token, self.reader.byte_offset() - 1)
+    }
+
+    /// Consumes any stray end-of-string tokens at the current position if enabled by
+    /// [`Builder::skip_stray_string_terminators`].
+    fn skip_stray_string_terminators(&mut self) -> Result<(), Error> {
+        if self.skip_stray_string_terminators {
+            while let Some(0xfc) = self.reader.peek()? {
+                self.reader.consume();
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the next token without consuming it, skipping stray end-of-string tokens first.
+    fn peek_token(&mut self) -> Result<Option<u8>, Error> {
+        self.skip_stray_string_terminators()?;
+        self.reader.peek()
+    }
+
+    fn tag_reference(&self, reference: Option<u16>) {
+        if let Some(reference_tags) = &self.reference_tags {
+            reference_tags.set(reference);
+        }
+    }
+
+    fn count_element(&mut self) -> Result<(), Error> {
+        if self.elements >= self.max_total_elements {
+            return Err(Error::element_limit_exceeded(self.max_total_elements));
+        }
+        self.elements += 1;
+        Ok(())
+    }
+
     fn recursion_checked<F, T>(&mut self, f: F) -> Result<T, Error>
     where
         F: FnOnce(&mut Deserializer<'de, R>) -> Result<T, Error>,
     {
-        self.remaining_depth -= 1;
-        if self.remaining_depth == 0 {
-            return Err(Error::recursion_limit_exceeded());
+        if self.depth >= self.recursion_limit {
+            return Err(Error::recursion_limit_exceeded(
+                self.recursion_limit,
+                self.depth + 1,
+            ));
         }
+        self.depth += 1;
         let r = f(self);
-        self.remaining_depth += 1;
+        self.depth -= 1;
         r
     }
 
@@ -227,6 +652,7 @@ where
             .as_ref()
             .and_then(|c| c.get(reference))
             .ok_or_else(Error::invalid_string_reference)?;
+        self.tag_reference(Some(reference));
         match s {
             Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
             Cow::Owned(s) => visitor.visit_str(s),
@@ -269,6 +695,8 @@ where
     }
 
     fn parse_7_bit_binary<'a>(&'a mut self) -> Result<Buf<'a, 'de>, Error> {
+        // the length prefix is the length of the raw data, and the length of its encoding is derived from it; see
+        // Serializer::serialize_7_bit_binary
         let raw_len = self.parse_vint(10)?;
         let chunks = raw_len / 7;
         let remainder = raw_len % 7;
@@ -285,29 +713,7 @@ where
             .read_mut(encoded_len)?
             .ok_or_else(Error::eof_while_parsing_value)?;
 
-        let mut in_base = 0;
-        let mut out_base = 0;
-        for _ in 0..chunks {
-            buf[out_base] = buf[in_base] << 1 | buf[in_base + 1] >> 6;
-            buf[out_base + 1] = buf[in_base + 1] << 2 | buf[in_base + 2] >> 5;
-            buf[out_base + 2] = buf[in_base + 2] << 3 | buf[in_base + 3] >> 4;
-            buf[out_base + 3] = buf[in_base + 3] << 4 | buf[in_base + 4] >> 3;
-            buf[out_base + 4] = buf[in_base + 4] << 5 | buf[in_base + 5] >> 2;
-            buf[out_base + 5] = buf[in_base + 5] << 6 | buf[in_base + 6] >> 1;
-            buf[out_base + 6] = buf[in_base + 6] << 7 | buf[in_base + 7];
-
-            in_base += 8;
-            out_base += 7;
-        }
-
-        if remainder > 0 {
-            // the last byte is annoyingly right-aligned
-            buf[in_base + remainder as usize] <<= 7 - remainder as usize;
-
-            for i in 0..(remainder as usize) {
-                buf[out_base + i] = buf[in_base + i] << (i + 1) | buf[in_base + i + 1] >> (6 - i);
-            }
-        }
+        decode_7_bit(&mut buf, chunks as usize, remainder as usize);
 
         let out = match buf {
             MutBuf::Short(buf) => Buf::Short(&buf[..raw_len as usize]),
@@ -316,56 +722,35 @@ where
         Ok(out)
     }
 
-    fn sign_extend(&self, extra: &mut [u8], number: &[u8]) {
-        let extension = (number[0] as i8 >> 7) as u8;
-        extra.fill(extension);
-    }
-
-    fn parse_big_integer<V>(&mut self, visitor: V) -> Result<V::Value, Error>
-    where
-        V: Visitor<'de>,
-    {
+    /// Parses the two's complement payload of a `BigInteger` or of the unscaled value of a `BigDecimal`.
+    fn parse_big_integer_bytes(&mut self) -> Result<Buf<'_, 'de>, Error> {
+        let strict = self.strict_big_integers;
         let buf = self.parse_7_bit_binary()?;
 
         if buf.is_empty() {
-            return visitor.visit_map(BigIntegerDeserializer { buf: Some(buf) });
+            if strict {
+                return Err(Error::empty_big_integer());
+            }
+            return Ok(Buf::Long(&[0]));
         }
 
-        if buf.len() <= 8 {
-            let mut out = [0; 8];
-            let (extra, number) = out.split_at_mut(8 - buf.len());
-            number.copy_from_slice(&buf);
-            self.sign_extend(extra, number);
-            let v = i64::from_be_bytes(out);
-            return visitor.visit_i64(v);
+        if strict && big_integer::strip_sign_extension(&buf).len() != buf.len() {
+            return Err(Error::non_minimal_big_integer());
         }
 
-        if buf.len() == 9 && buf[0] == 0 {
-            let mut out = [0; 8];
-            out.copy_from_slice(&buf[1..]);
-            let v = u64::from_be_bytes(out);
-            return visitor.visit_u64(v);
-        }
+        Ok(buf)
+    }
 
-        serde_if_integer128! {
-            if buf.len() <= 16 {
-                let mut out = [0; 16];
-                let (extra, number) = out.split_at_mut(16 - buf.len());
-                number.copy_from_slice(&buf);
-                self.sign_extend(extra, number);
-                let v = i128::from_be_bytes(out);
-                return visitor.visit_i128(v);
-            }
+    fn parse_big_integer<V>(&mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let buf = self.parse_big_integer_bytes()?;
 
-            if buf.len() == 17 && buf[0] == 0 {
-                let mut out = [0; 16];
-                out.copy_from_slice(&buf[1..]);
-                let v = u128::from_be_bytes(out);
-                return visitor.visit_u128(v);
-            }
+        match NativeInteger::from_be_bytes(&buf) {
+            Some(v) => v.visit(visitor),
+            None => visitor.visit_map(BigIntegerDeserializer { buf: Some(buf) }),
         }
-
-        visitor.visit_map(BigIntegerDeserializer { buf: Some(buf) })
     }
 
     fn parse_f32<V>(&mut self, visitor: V) -> Result<V::Value, Error>
@@ -425,12 +810,19 @@ where
             .reader
             .read(len)?
             .ok_or_else(Error::eof_while_parsing_value)?;
+        // Slice-backed readers always return Long buffers, so the shared string cache can borrow directly from the
+        // input and back-references never allocate. Only IoRead returns Short buffers, which are reused.
         match buf {
             Buf::Short(buf) => {
                 let s = str::from_utf8(buf).map_err(|_| Error::invalid_utf8())?;
                 if let Some(shared_strings) = &mut self.shared_strings {
                     if s.len() <= 64 {
                         shared_strings.intern(Cow::Owned(s.to_string()));
+                        check_string_caches(
+                            self.max_property_cache_bytes,
+                            &self.shared_strings,
+                            &self.shared_properties,
+                        )?;
                     }
                 }
 
@@ -441,6 +833,11 @@ where
                 if let Some(shared_strings) = &mut self.shared_strings {
                     if s.len() <= 64 {
                         shared_strings.intern(Cow::Borrowed(s));
+                        check_string_caches(
+                            self.max_property_cache_bytes,
+                            &self.shared_strings,
+                            &self.shared_properties,
+                        )?;
                     }
                 }
 
@@ -499,6 +896,7 @@ where
     {
         self.recursion_checked(|de| {
             let value = visitor.visit_seq(SeqAccess { de })?;
+            de.skip_stray_string_terminators()?;
             match de.reader.next()? {
                 Some(0xf9) => Ok(value),
                 Some(_) => Err(Error::trailing_data()),
@@ -507,12 +905,48 @@ where
         })
     }
 
+    /// Parses an empty array or object, whose start token has already been peeked, as a unit.
+    fn parse_empty_container<V>(&mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.count_element()?;
+        let (end, unexpected) = match self.parse_u8()? {
+            0xf8 => (0xf9, Unexpected::Seq),
+            _ => (0xfb, Unexpected::Map),
+        };
+
+        match self.peek_token()? {
+            Some(token) if token == end => {
+                self.reader.consume();
+                visitor.visit_unit()
+            }
+            Some(_) => Err(Error::mismatched_type(unexpected, &visitor)),
+            None if end == 0xf9 => Err(Error::eof_while_parsing_array()),
+            None => Err(Error::eof_while_parsing_map()),
+        }
+    }
+
     fn parse_map<V>(&mut self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.parse_struct(&[], visitor)
+    }
+
+    /// Parses a map whose keys may be matched case-insensitively against `fields` if they're deserialized as
+    /// identifiers.
+    fn parse_struct<V>(
+        &mut self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
         self.recursion_checked(|de| {
-            let value = visitor.visit_map(MapAccess { de })?;
+            let value = visitor.visit_map(MapAccess { de, fields })?;
+            de.skip_stray_string_terminators()?;
             match de.reader.next()? {
                 Some(0xfb) => Ok(value),
                 Some(_) => Err(Error::trailing_data()),
@@ -538,12 +972,35 @@ where
         }
     }
 
+    /// Deserializes a number, rejecting the listed Smile types under `strict_number_types`.
+    fn parse_strict_number<V>(
+        &mut self,
+        rejected: &[(u8, &'static str)],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.read_header()?;
+        if self.strict_number_types {
+            if let Some(token) = self.peek_token()? {
+                if let Some((_, name)) = rejected.iter().find(|(t, _)| *t == token) {
+                    return Err(Error::mismatched_type(Unexpected::Other(name), &visitor));
+                }
+            }
+        }
+        self.parse_value(visitor)
+    }
+
     fn parse_value<V>(&mut self, visitor: V) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
+        self.skip_stray_string_terminators()?;
+        self.count_element()?;
+        self.tag_reference(None);
         match self.parse_u8()? {
-            0x00 => Err(Error::reserved_token()),
+            token @ 0x00 => Err(self.reserved_token(token)),
             token @ 0x01..=0x1f => self.parse_shared_string(token as u16 - 1, visitor),
             0x20 => visitor.visit_borrowed_str(""),
             0x21 => visitor.visit_unit(),
@@ -552,38 +1009,55 @@ where
             0x24 => self.parse_i32(visitor),
             0x25 => self.parse_i64(visitor),
             0x26 => self.parse_big_integer(visitor),
-            0x27 => Err(Error::reserved_token()),
+            token @ 0x27 => Err(self.reserved_token(token)),
             0x28 => self.parse_f32(visitor),
             0x29 => self.parse_f64(visitor),
             0x2a => self.parse_big_decimal(visitor),
-            0x2b => Err(Error::reserved_token()),
-            0x2c..=0x3f => Err(Error::reserved_token()),
+            token @ 0x2b => Err(self.reserved_token(token)),
+            token @ 0x2c..=0x39 => Err(self.reserved_token(token)),
+            0x3a => Err(self.colon_error(|offset| Error::reserved_token(0x3a, offset))),
+            token @ 0x3b..=0x3f => Err(self.reserved_token(token)),
             token @ 0x40..=0x5f => self.parse_short_string(token as usize - (0x40 - 1), visitor),
             token @ 0x60..=0x7f => self.parse_short_string(token as usize - (0x60 - 33), visitor),
             token @ 0x80..=0x9f => self.parse_short_string(token as usize - (0x80 - 2), visitor),
             token @ 0xa0..=0xbf => self.parse_short_string(token as usize - (0xa0 - 34), visitor),
             token @ 0xc0..=0xdf => visitor.visit_i32(zigzag_i32(token as u32 - 0xc0)),
             0xe0 => self.parse_long_string(visitor),
-            0xe1..=0xe3 => Err(Error::reserved_token()),
+            token @ 0xe1..=0xe3 => Err(self.reserved_token(token)),
             0xe4 => self.parse_long_string(visitor),
-            0xe5..=0xe7 => Err(Error::reserved_token()),
+            token @ 0xe5..=0xe7 => Err(self.reserved_token(token)),
             0xe8 => self.parse_binary(visitor),
-            0xe9..=0xeb => Err(Error::reserved_token()),
+            token @ 0xe9..=0xeb => Err(self.reserved_token(token)),
             token @ 0xec..=0xef => self.parse_long_shared_string(token - 0xec, visitor),
-            0xf0..=0xf7 => Err(Error::reserved_token()),
+            token @ 0xf0..=0xf7 => Err(self.reserved_token(token)),
             0xf8 => self.parse_array(visitor),
-            0xf9 => Err(Error::unexpected_token()),
+            token @ 0xf9 => Err(self.unexpected_token(token)),
             0xfa => self.parse_map(visitor),
-            0xfb => Err(Error::unexpected_token()),
-            0xfc => Err(Error::unexpected_token()),
+            token @ 0xfb => Err(self.unexpected_token(token)),
+            token @ 0xfc => Err(self.unexpected_token(token)),
             0xfd => self.parse_raw_binary(visitor),
-            0xfe => Err(Error::reserved_token()),
+            token @ 0xfe => Err(self.reserved_token(token)),
             0xff => Err(Error::eof_while_parsing_value()),
         }
     }
 }
 
-impl<'de, 'a, R> serde::Deserializer<'de> for &'a mut Deserializer<'de, R>
+const INTEGER_REJECTED: &[(u8, &str)] = &[(0x26, "big integer")];
+const F32_REJECTED: &[(u8, &str)] = &[(0x26, "big integer"), (0x29, "double")];
+const F64_REJECTED: &[(u8, &str)] = &[(0x26, "big integer"), (0x28, "float")];
+
+macro_rules! deserialize_strict_number {
+    ($method:ident, $rejected:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: Visitor<'de>,
+        {
+            self.parse_strict_number($rejected, visitor)
+        }
+    };
+}
+
+impl<'de, R> serde::Deserializer<'de> for &mut Deserializer<'de, R>
 where
     R: Read<'de>,
 {
@@ -597,16 +1071,45 @@ where
         self.parse_value(visitor)
     }
 
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.read_header()?;
+        if self.empty_containers_as_unit {
+            if let Some(0xf8 | 0xfa) = self.peek_token()? {
+                return self.parse_empty_container(visitor);
+            }
+        }
+        self.parse_value(visitor)
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
         self.read_header()?;
-        match self.reader.peek()? {
+        match self.peek_token()? {
             Some(0x21) => {
+                self.count_element()?;
                 self.reader.consume();
                 visitor.visit_none()
             }
+            // A stream with no values is treated as `None`. The end-of-stream token is only checked outside of
+            // containers, since it isn't consumed here and would otherwise be seen repeatedly by a sequence.
+            None => visitor.visit_none(),
+            Some(0xff) if self.depth == 0 => visitor.visit_none(),
             _ => visitor.visit_some(self),
         }
     }
@@ -632,11 +1135,13 @@ where
         V: Visitor<'de>,
     {
         self.read_header()?;
-        match self.reader.peek()? {
+        match self.peek_token()? {
             Some(0xfa) => {
+                self.count_element()?;
                 self.reader.consume();
                 self.recursion_checked(|de| {
                     let value = visitor.visit_enum(VariantAccess { de })?;
+                    de.skip_stray_string_terminators()?;
                     match de.reader.next()? {
                         Some(0xfb) => Ok(value),
                         Some(_) => Err(Error::trailing_data()),
@@ -660,9 +1165,10 @@ where
     {
         self.read_header()?;
         if name == BigInteger::STRUCT_NAME && fields == [BigInteger::FIELD_NAME] {
-            if let Some(0x26) = self.reader.peek()? {
+            if let Some(0x26) = self.peek_token()? {
+                self.count_element()?;
                 self.reader.consume();
-                let buf = self.parse_7_bit_binary()?;
+                let buf = self.parse_big_integer_bytes()?;
                 return visitor.visit_map(BigIntegerDeserializer { buf: Some(buf) });
             }
         }
@@ -670,7 +1176,8 @@ where
         if name == BigDecimal::STRUCT_NAME
             && fields == [BigDecimal::SCALE_FIELD_NAME, BigDecimal::VALUE_FIELD_NAME]
         {
-            if let Some(0x2a) = self.reader.peek()? {
+            if let Some(0x2a) = self.peek_token()? {
+                self.count_element()?;
                 self.reader.consume();
                 return visitor.visit_map(BigDecimalDeserializer {
                     de: self,
@@ -679,12 +1186,33 @@ where
             }
         }
 
+        if self.case_insensitive_fields {
+            if let Some(0xfa) = self.peek_token()? {
+                self.count_element()?;
+                self.reader.consume();
+                return self.parse_struct(fields, visitor);
+            }
+        }
+
         self.deserialize_any(visitor)
     }
 
+    deserialize_strict_number!(deserialize_i8, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_i16, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_i32, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_i64, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_i128, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_u8, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_u16, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_u32, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_u64, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_u128, INTEGER_REJECTED);
+    deserialize_strict_number!(deserialize_f32, F32_REJECTED);
+    deserialize_strict_number!(deserialize_f64, F64_REJECTED);
+
     serde::forward_to_deserialize_any! {
-        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string unit
-        unit_struct seq tuple tuple_struct map identifier ignored_any
+        bool char str string seq tuple
+        tuple_struct map identifier ignored_any
         bytes byte_buf
     }
 
@@ -694,6 +1222,47 @@ where
     }
 }
 
+/// Decodes 7-bit encoded binary data in place.
+///
+/// The input consists of `chunks` 8 byte groups followed by a partial group encoding `remainder` bytes, and the decoded
+/// bytes are written to the start of the buffer.
+fn decode_7_bit(buf: &mut [u8], chunks: usize, remainder: usize) {
+    // checking the length once up front and working with fixed size groups lets the compiler drop the bounds checks
+    // from the loop
+    let encoded_remainder = if remainder == 0 { 0 } else { remainder + 1 };
+    assert!(remainder < 7);
+    assert!(buf.len() >= chunks * 8 + encoded_remainder);
+
+    for i in 0..chunks {
+        let mut group = [0; 8];
+        group.copy_from_slice(&buf[i * 8..i * 8 + 8]);
+
+        let out = &mut buf[i * 7..i * 7 + 7];
+        out[0] = group[0] << 1 | group[1] >> 6;
+        out[1] = group[1] << 2 | group[2] >> 5;
+        out[2] = group[2] << 3 | group[3] >> 4;
+        out[3] = group[3] << 4 | group[4] >> 3;
+        out[4] = group[4] << 5 | group[5] >> 2;
+        out[5] = group[5] << 6 | group[6] >> 1;
+        out[6] = group[6] << 7 | group[7];
+    }
+
+    if remainder > 0 {
+        let in_base = chunks * 8;
+        let out_base = chunks * 7;
+
+        let mut group = [0; 7];
+        group[..encoded_remainder].copy_from_slice(&buf[in_base..in_base + encoded_remainder]);
+        // the last byte is annoyingly right-aligned
+        group[remainder] <<= 7 - remainder;
+
+        let out = &mut buf[out_base..out_base + remainder];
+        for (i, out) in out.iter_mut().enumerate() {
+            *out = group[i] << (i + 1) | group[i + 1] >> (6 - i);
+        }
+    }
+}
+
 #[inline]
 fn zigzag_i32(v: u32) -> i32 {
     ((v >> 1) as i32) ^ (-((v & 1) as i32))
@@ -718,7 +1287,7 @@ where
     where
         T: de::DeserializeSeed<'de>,
     {
-        match self.de.reader.peek()? {
+        match self.de.peek_token()? {
             Some(0xf9) => return Ok(None),
             Some(_) => {}
             None => return Err(Error::eof_while_parsing_array()),
@@ -730,6 +1299,7 @@ where
 
 struct MapAccess<'a, 'de, R> {
     de: &'a mut Deserializer<'de, R>,
+    fields: &'static [&'static str],
 }
 
 impl<'de, R> de::MapAccess<'de> for MapAccess<'_, 'de, R>
@@ -742,14 +1312,17 @@ where
     where
         K: de::DeserializeSeed<'de>,
     {
-        match self.de.reader.peek()? {
+        match self.de.peek_token()? {
             Some(0xfb) => return Ok(None),
             Some(_) => {}
             None => return Err(Error::eof_while_parsing_map()),
         }
 
-        seed.deserialize(KeyDeserializer { de: &mut *self.de })
-            .map(Some)
+        seed.deserialize(KeyDeserializer {
+            de: &mut *self.de,
+            fields: self.fields,
+        })
+        .map(Some)
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
@@ -776,7 +1349,10 @@ where
     where
         V: de::DeserializeSeed<'de>,
     {
-        let variant = seed.deserialize(KeyDeserializer { de: &mut *self.de })?;
+        let variant = seed.deserialize(KeyDeserializer {
+            de: &mut *self.de,
+            fields: &[],
+        })?;
         Ok((variant, self))
     }
 }
@@ -852,7 +1428,7 @@ where
     where
         T: de::DeserializeSeed<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"newtype variant",
         ))
@@ -862,7 +1438,7 @@ where
     where
         V: Visitor<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"tuple variant",
         ))
@@ -876,7 +1452,7 @@ where
     where
         V: Visitor<'de>,
     {
-        Err(de::Error::invalid_type(
+        Err(Error::mismatched_type(
             de::Unexpected::UnitVariant,
             &"struct variant",
         ))
@@ -1,5 +1,6 @@
 use crate::Error;
 use memchr::memchr;
+use std::borrow::Cow;
 use std::io::BufRead;
 use std::mem;
 use std::ops::{Deref, DerefMut};
@@ -12,6 +13,14 @@ pub(crate) mod private {
 ///
 /// This trait is sealed and cannot be implemented outside of `serde_smile`. The contents of the trait are not
 /// considered part of the crate's public API and are subject to change at any time.
+///
+/// `read` and `read_mut` return `None` only if fewer than `n` bytes remain in the input. A request for zero bytes
+/// therefore always succeeds with an empty buffer, even at the end of the input, and never touches the underlying
+/// stream.
+///
+/// `consume` discards the byte returned by the preceding call to `peek`, and must only be called after `peek` returned
+/// `Some` with no other calls in between. The slice-backed readers treat a call at the end of the input as a no-op
+/// rather than panicking.
 pub trait Read<'de>: private::Sealed {
     #[doc(hidden)]
     fn next(&mut self) -> Result<Option<u8>, Error>;
@@ -30,6 +39,28 @@ pub trait Read<'de>: private::Sealed {
 
     #[doc(hidden)]
     fn read_until<'a>(&'a mut self, end: u8) -> Result<Option<Buf<'a, 'de>>, Error>;
+
+    /// Returns the number of bytes consumed from the input so far.
+    #[doc(hidden)]
+    fn byte_offset(&self) -> u64;
+}
+
+/// A trait exposing the progress of a reader through its input.
+///
+/// This allows code generic over the reader type, like a [`Deserializer`](crate::Deserializer) accessed through
+/// [`get_ref`](crate::Deserializer::get_ref), to report how much of the input has been processed. Bytes which the
+/// deserializer has peeked at but not yet consumed are still considered remaining.
+///
+/// This trait is sealed and cannot be implemented outside of `serde_smile`.
+pub trait ReadState: private::Sealed {
+    /// Returns the number of bytes consumed from the input so far.
+    fn position(&self) -> u64;
+
+    /// Returns the portion of the input which has not yet been consumed, if it is known.
+    ///
+    /// This is always `Some` for slice-backed readers, and always `None` for [`IoRead`], which does not know what
+    /// remains in its stream.
+    fn remaining(&self) -> Option<&[u8]>;
 }
 
 pub enum Buf<'a, 'de> {
@@ -92,10 +123,45 @@ impl<'a> SliceRead<'a> {
             buf: vec![],
         }
     }
+
+    /// Returns the entire input slice, including the portion which has already been consumed.
+    pub fn slice(&self) -> &'a [u8] {
+        self.slice
+    }
+
+    /// Returns the number of bytes consumed from the input so far.
+    ///
+    /// This is the same offset reported by [`ReadState::position`], as a `usize`.
+    pub fn consumed(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the portion of the input which has not yet been consumed.
+    pub fn remaining_slice(&self) -> &'a [u8] {
+        &self.slice[self.index..]
+    }
+
+    /// Consumes the `SliceRead`, returning the portion of the input which has not yet been consumed.
+    ///
+    /// Like [`IoRead::into_inner`], this returns the input positioned immediately after the last byte consumed. Use
+    /// [`Self::slice`] beforehand to recover the entire input.
+    pub fn into_inner(self) -> &'a [u8] {
+        self.remaining_slice()
+    }
 }
 
 impl private::Sealed for SliceRead<'_> {}
 
+impl ReadState for SliceRead<'_> {
+    fn position(&self) -> u64 {
+        self.index as u64
+    }
+
+    fn remaining(&self) -> Option<&[u8]> {
+        Some(self.remaining_slice())
+    }
+}
+
 impl<'de> Read<'de> for SliceRead<'de> {
     #[inline]
     fn next(&mut self) -> Result<Option<u8>, Error> {
@@ -119,7 +185,9 @@ impl<'de> Read<'de> for SliceRead<'de> {
 
     #[inline]
     fn consume(&mut self) {
-        self.index += 1;
+        if self.index < self.slice.len() {
+            self.index += 1;
+        }
     }
 
     #[inline]
@@ -155,22 +223,56 @@ impl<'de> Read<'de> for SliceRead<'de> {
             None => Ok(None),
         }
     }
+
+    #[inline]
+    fn byte_offset(&self) -> u64 {
+        self.index as u64
+    }
 }
 
 /// A [`Read`] implementation for mutable slices.
 pub struct MutSliceRead<'a> {
     slice: &'a mut [u8],
+    consumed: usize,
 }
 
 impl<'a> MutSliceRead<'a> {
     /// Creates a new `MutSliceRead`.
     pub fn new(slice: &'a mut [u8]) -> Self {
-        MutSliceRead { slice }
+        MutSliceRead { slice, consumed: 0 }
+    }
+
+    /// Returns the portion of the input which has not yet been consumed.
+    pub fn remaining_slice(&self) -> &[u8] {
+        self.slice
+    }
+
+    /// Returns the number of bytes consumed from the input so far.
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Consumes the `MutSliceRead`, returning the portion of the input which has not yet been consumed.
+    ///
+    /// Unlike [`SliceRead`], the consumed portion of the input cannot be recovered, since values deserialized from
+    /// the input may hold mutable borrows of it.
+    pub fn into_inner(self) -> &'a mut [u8] {
+        self.slice
     }
 }
 
 impl private::Sealed for MutSliceRead<'_> {}
 
+impl ReadState for MutSliceRead<'_> {
+    fn position(&self) -> u64 {
+        self.consumed as u64
+    }
+
+    fn remaining(&self) -> Option<&[u8]> {
+        Some(self.slice)
+    }
+}
+
 impl<'de> Read<'de> for MutSliceRead<'de> {
     fn next(&mut self) -> Result<Option<u8>, Error> {
         if !self.slice.is_empty() {
@@ -178,6 +280,7 @@ impl<'de> Read<'de> for MutSliceRead<'de> {
             let b = slice[0];
 
             self.slice = &mut slice[1..];
+            self.consumed += 1;
             Ok(Some(b))
         } else {
             Ok(None)
@@ -193,14 +296,18 @@ impl<'de> Read<'de> for MutSliceRead<'de> {
     }
 
     fn consume(&mut self) {
-        let slice = mem::take(&mut self.slice);
-        self.slice = &mut slice[1..];
+        if !self.slice.is_empty() {
+            let slice = mem::take(&mut self.slice);
+            self.slice = &mut slice[1..];
+            self.consumed += 1;
+        }
     }
 
     fn read<'a>(&'a mut self, n: usize) -> Result<Option<Buf<'a, 'de>>, Error> {
         if n <= self.slice.len() {
             let (a, b) = mem::take(&mut self.slice).split_at_mut(n);
             self.slice = b;
+            self.consumed += n;
             Ok(Some(Buf::Long(a)))
         } else {
             Ok(None)
@@ -211,6 +318,7 @@ impl<'de> Read<'de> for MutSliceRead<'de> {
         if n <= self.slice.len() {
             let (a, b) = mem::take(&mut self.slice).split_at_mut(n);
             self.slice = b;
+            self.consumed += n;
             Ok(Some(MutBuf::Long(a)))
         } else {
             Ok(None)
@@ -222,17 +330,169 @@ impl<'de> Read<'de> for MutSliceRead<'de> {
             Some(end) => {
                 let (a, b) = mem::take(&mut self.slice).split_at_mut(end);
                 self.slice = &mut b[1..];
+                self.consumed += end + 1;
                 Ok(Some(Buf::Long(a)))
             }
             None => Ok(None),
         }
     }
+
+    #[inline]
+    fn byte_offset(&self) -> u64 {
+        self.consumed as u64
+    }
+}
+
+/// A [`Read`] implementation for possibly-owned slices.
+///
+/// Borrowed slices are read as with [`SliceRead`]. Owned buffers are held by the reader, so nothing can be borrowed
+/// from them, but 7-bit encoded binary data is decoded in place rather than copied.
+pub struct CowRead<'a> {
+    inner: CowReadInner<'a>,
+}
+
+enum CowReadInner<'a> {
+    Borrowed(SliceRead<'a>),
+    Owned(VecRead),
+}
+
+impl<'a> CowRead<'a> {
+    /// Creates a new `CowRead`.
+    pub fn new(slice: Cow<'a, [u8]>) -> Self {
+        let inner = match slice {
+            Cow::Borrowed(slice) => CowReadInner::Borrowed(SliceRead::new(slice)),
+            Cow::Owned(buf) => CowReadInner::Owned(VecRead { buf, index: 0 }),
+        };
+        CowRead { inner }
+    }
+}
+
+impl private::Sealed for CowRead<'_> {}
+
+impl ReadState for CowRead<'_> {
+    fn position(&self) -> u64 {
+        self.byte_offset()
+    }
+
+    fn remaining(&self) -> Option<&[u8]> {
+        match &self.inner {
+            CowReadInner::Borrowed(r) => r.remaining(),
+            CowReadInner::Owned(r) => Some(&r.buf[r.index..]),
+        }
+    }
+}
+
+impl<'de> Read<'de> for CowRead<'de> {
+    #[inline]
+    fn next(&mut self) -> Result<Option<u8>, Error> {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.next(),
+            CowReadInner::Owned(r) => r.next(),
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.peek(),
+            CowReadInner::Owned(r) => r.peek(),
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self) {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.consume(),
+            CowReadInner::Owned(r) => r.consume(),
+        }
+    }
+
+    #[inline]
+    fn read<'a>(&'a mut self, n: usize) -> Result<Option<Buf<'a, 'de>>, Error> {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.read(n),
+            CowReadInner::Owned(r) => Ok(r.read(n).map(Buf::Short)),
+        }
+    }
+
+    fn read_mut<'a>(&'a mut self, n: usize) -> Result<Option<MutBuf<'a, 'de>>, Error> {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.read_mut(n),
+            CowReadInner::Owned(r) => Ok(r.read_mut(n).map(MutBuf::Short)),
+        }
+    }
+
+    fn read_until<'a>(&'a mut self, end: u8) -> Result<Option<Buf<'a, 'de>>, Error> {
+        match &mut self.inner {
+            CowReadInner::Borrowed(r) => r.read_until(end),
+            CowReadInner::Owned(r) => Ok(r.read_until(end).map(Buf::Short)),
+        }
+    }
+
+    #[inline]
+    fn byte_offset(&self) -> u64 {
+        match &self.inner {
+            CowReadInner::Borrowed(r) => r.byte_offset(),
+            CowReadInner::Owned(r) => r.index as u64,
+        }
+    }
+}
+
+struct VecRead {
+    buf: Vec<u8>,
+    index: usize,
+}
+
+impl VecRead {
+    fn next(&mut self) -> Result<Option<u8>, Error> {
+        let b = self.peek()?;
+        if b.is_some() {
+            self.index += 1;
+        }
+        Ok(b)
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        Ok(self.buf.get(self.index).copied())
+    }
+
+    fn consume(&mut self) {
+        if self.index < self.buf.len() {
+            self.index += 1;
+        }
+    }
+
+    fn read(&mut self, n: usize) -> Option<&[u8]> {
+        self.read_mut(n).map(|buf| &*buf)
+    }
+
+    fn read_mut(&mut self, n: usize) -> Option<&mut [u8]> {
+        let s = &mut self.buf[self.index..];
+        if n <= s.len() {
+            self.index += n;
+            Some(&mut s[..n])
+        } else {
+            None
+        }
+    }
+
+    fn read_until(&mut self, end: u8) -> Option<&[u8]> {
+        let s = &self.buf[self.index..];
+        match memchr(end, s) {
+            Some(end) => {
+                self.index += end + 1;
+                Some(&s[..end])
+            }
+            None => None,
+        }
+    }
 }
 
 /// A [`Read`] implementation for buffered IO streams.
 pub struct IoRead<R> {
     reader: R,
     buf: Vec<u8>,
+    offset: u64,
 }
 
 impl<R> IoRead<R>
@@ -244,6 +504,7 @@ where
         IoRead {
             reader,
             buf: vec![],
+            offset: 0,
         }
     }
 
@@ -263,9 +524,14 @@ where
     }
 
     fn fill_buf(&mut self, n: usize) -> Result<bool, Error> {
+        // the buffer's allocation is reused from one read to the next, so it only needs to grow for reads larger than
+        // any previous one
         self.buf.clear();
         // defend against malicious input pretending to be huge by limiting growth
-        self.buf.reserve(usize::min(n, 16 * 1024));
+        let capacity = usize::min(n, 16 * 1024);
+        if self.buf.capacity() < capacity {
+            self.buf.reserve_exact(capacity);
+        }
 
         let mut remaining = n;
         while remaining > 0 {
@@ -277,6 +543,7 @@ where
             let len = usize::min(remaining, buf.len());
             self.buf.extend_from_slice(&buf[..len]);
             self.reader.consume(len);
+            self.offset += len as u64;
             remaining -= len;
         }
 
@@ -286,6 +553,16 @@ where
 
 impl<R> private::Sealed for IoRead<R> {}
 
+impl<R> ReadState for IoRead<R> {
+    fn position(&self) -> u64 {
+        self.offset
+    }
+
+    fn remaining(&self) -> Option<&[u8]> {
+        None
+    }
+}
+
 impl<'de, R> Read<'de> for IoRead<R>
 where
     R: BufRead,
@@ -309,6 +586,7 @@ where
 
     fn consume(&mut self) {
         self.reader.consume(1);
+        self.offset += 1;
     }
 
     // FIXME ideally we'd be able to avoid a copy by directly referencing the reader's buffer when it has enough data
@@ -342,14 +620,21 @@ where
                 Some(end) => {
                     self.buf.extend_from_slice(&buf[..end]);
                     self.reader.consume(end + 1);
+                    self.offset += (end + 1) as u64;
                     return Ok(Some(Buf::Short(&self.buf)));
                 }
                 None => {
                     self.buf.extend(buf);
                     let len = buf.len();
                     self.reader.consume(len);
+                    self.offset += len as u64;
                 }
             }
         }
     }
+
+    #[inline]
+    fn byte_offset(&self) -> u64 {
+        self.offset
+    }
 }
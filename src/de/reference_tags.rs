@@ -0,0 +1,33 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+const NO_REFERENCE: u32 = u32::MAX;
+
+/// A handle to the shared string reference tag of a [`Deserializer`](crate::Deserializer).
+///
+/// Created by [`Deserializer::reference_tags`](crate::Deserializer::reference_tags). The handle observes the same tag
+/// as [`Deserializer::last_string_reference`](crate::Deserializer::last_string_reference), but can be held by a
+/// `DeserializeSeed` or `Visitor` which only sees the deserializer through serde's generic interfaces.
+#[derive(Clone, Debug)]
+pub struct ReferenceTags(Arc<AtomicU32>);
+
+impl ReferenceTags {
+    pub(crate) fn new() -> Self {
+        ReferenceTags(Arc::new(AtomicU32::new(NO_REFERENCE)))
+    }
+
+    pub(crate) fn set(&self, reference: Option<u16>) {
+        let raw = reference.map_or(NO_REFERENCE, u32::from);
+        self.0.store(raw, Ordering::Relaxed);
+    }
+
+    /// Returns the back-reference the most recently visited string was decoded from.
+    ///
+    /// See [`Deserializer::last_string_reference`](crate::Deserializer::last_string_reference) for details.
+    pub fn last_string_reference(&self) -> Option<u16> {
+        match self.0.load(Ordering::Relaxed) {
+            NO_REFERENCE => None,
+            raw => Some(raw as u16),
+        }
+    }
+}
@@ -31,7 +31,7 @@ where
             return Some(Err(e));
         }
 
-        match self.de.reader.peek() {
+        match self.de.peek_token() {
             Ok(Some(0xff)) => {
                 self.de.reader.consume();
                 self.done = true;
@@ -48,6 +48,7 @@ where
             }
         }
 
+        self.de.elements = 0;
         match T::deserialize(&mut self.de) {
             Ok(value) => Some(Ok(value)),
             Err(e) => {
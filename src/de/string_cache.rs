@@ -1,25 +1,52 @@
+use crate::Error;
 use std::borrow::Cow;
 
 const LIMIT: usize = 1024;
 
 pub struct StringCache<'de> {
     vec: Vec<Cow<'de, str>>,
+    bytes: usize,
 }
 
 impl<'de> StringCache<'de> {
     pub fn new() -> Self {
-        StringCache { vec: vec![] }
+        StringCache {
+            vec: vec![],
+            bytes: 0,
+        }
     }
 
     pub fn intern(&mut self, s: Cow<'de, str>) {
         if self.vec.len() >= LIMIT {
             self.vec.clear();
+            self.bytes = 0;
         }
 
+        self.bytes += s.len();
         self.vec.push(s);
     }
 
+    /// Returns the total length of the strings in the cache.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
     pub fn get(&self, reference: u16) -> Option<&Cow<'de, str>> {
         self.vec.get(reference as usize)
     }
 }
+
+/// Returns an error if the shared value string and shared property caches together hold more than `max_bytes` bytes.
+pub fn check_string_caches(
+    max_bytes: usize,
+    shared_strings: &Option<StringCache<'_>>,
+    shared_properties: &Option<StringCache<'_>>,
+) -> Result<(), Error> {
+    let bytes = shared_strings.as_ref().map_or(0, StringCache::bytes)
+        + shared_properties.as_ref().map_or(0, StringCache::bytes);
+    if bytes > max_bytes {
+        return Err(Error::string_cache_limit_exceeded(max_bytes));
+    }
+
+    Ok(())
+}
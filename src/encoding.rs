@@ -0,0 +1,102 @@
+//! Text encodings of binary data.
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Appends the lowercase hex encoding of `v` to `out`.
+pub(crate) fn encode_hex(out: &mut String, v: &[u8]) {
+    out.reserve(v.len() * 2);
+    for b in v {
+        out.push(HEX[(b >> 4) as usize] as char);
+        out.push(HEX[(b & 0xf) as usize] as char);
+    }
+}
+
+/// Decodes a hex string, accepting either case.
+pub(crate) fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    fn nibble(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            b'A'..=b'F' => Some(b - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    let chunks = s.as_bytes().chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+
+    chunks
+        .map(|c| Some(nibble(c[0])? << 4 | nibble(c[1])?))
+        .collect()
+}
+
+/// Appends the standard, padded base64 encoding of `v` to `out`.
+pub(crate) fn encode_base64(out: &mut String, v: &[u8]) {
+    out.reserve(v.len() / 3 * 4 + 4);
+
+    for chunk in v.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
+
+/// Decodes a standard, padded base64 string.
+pub(crate) fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    fn sextet(b: u8) -> Option<u32> {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        Some(v as u32)
+    }
+
+    let chunks = s.as_bytes().chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+
+    let count = chunks.len();
+    let mut out = Vec::with_capacity(count * 3);
+    for (i, chunk) in chunks.enumerate() {
+        let last = i == count - 1;
+        let padding = if last {
+            chunk.iter().rev().take_while(|b| **b == b'=').count()
+        } else {
+            0
+        };
+        if padding > 2 {
+            return None;
+        }
+
+        let mut n = 0;
+        for &b in &chunk[..4 - padding] {
+            n = n << 6 | sextet(b)?;
+        }
+        n <<= 6 * padding;
+
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        // reject non-canonical encodings with nonzero bits in the padding
+        if bytes[3 - padding..].iter().any(|b| *b != 0) {
+            return None;
+        }
+        out.extend_from_slice(&bytes[..3 - padding]);
+    }
+
+    Some(out)
+}
@@ -1,51 +1,168 @@
+use crate::value::ValueKind;
 use serde::{de, ser};
 use std::{error, fmt, io};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ErrorKind {
-    Io(io::Error),
-    Custom(String),
+    Io(IoError),
+    Serialize(ErrorDetail),
+    Deserialize(ErrorDetail),
+    Format(ErrorDetail),
     KeyMustBeAString,
     EofWhileParsingValue,
-    ReservedToken,
+    ReservedToken {
+        token: u8,
+        offset: u64,
+    },
     InvalidStringReference,
     UnterminatedVint,
     BufferLengthOverflow,
     InvalidUtf8,
-    RecursionLimitExceeded,
+    RecursionLimitExceeded {
+        limit: usize,
+        depth: usize,
+    },
     TrailingData,
     EofWhileParsingArray,
-    UnexpectedToken,
+    UnexpectedToken {
+        token: u8,
+        offset: u64,
+    },
     EofWhileParsingMap,
     InvalidHeader,
     UnsupportedVersion,
     EofWhileParsingHeader,
+    ScaleOutOfRange,
+    InvalidValueType {
+        expected: &'static str,
+        found: ValueKind,
+    },
+    ValueOutOfRange {
+        expected: &'static str,
+    },
+    InvalidByteKey {
+        encoding: &'static str,
+    },
+    UnexpectedDocumentBoundary,
+    HeaderFlagsChanged {
+        old: u8,
+        new: u8,
+    },
+    ElementLimitExceeded {
+        limit: u64,
+    },
+    LimitExceeded {
+        limit: &'static str,
+        max: usize,
+    },
+    InvalidBigInteger,
+    EmptyBigInteger,
+    NonMinimalBigInteger,
+    StringCacheLimitExceeded {
+        limit: usize,
+    },
+}
+
+/// An IO error which can be cloned.
+struct IoError(io::Error);
+
+impl fmt::Debug for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl Clone for IoError {
+    fn clone(&self) -> Self {
+        IoError(io::Error::new(self.0.kind(), self.0.to_string()))
+    }
 }
 
 /// An error encountered when serializing or deserializing to or from Smile.
-#[derive(Debug)]
+///
+/// Errors can be cloned. The clone of an IO error is a new `io::Error` with the same kind and message, but the source
+/// and OS error code of the original are not preserved.
+#[derive(Debug, Clone)]
 pub struct Error(Box<ErrorKind>);
 
+// errors are commonly sent between threads and boxed into `dyn Error + Send + Sync + 'static` trait objects
+const _: () = {
+    fn assert_send_sync<T>()
+    where
+        T: Send + Sync + 'static,
+    {
+    }
+
+    let _ = assert_send_sync::<Error>;
+};
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &*self.0 {
             ErrorKind::Io(_) => f.write_str("IO error"),
-            ErrorKind::Custom(e) => f.write_str(e),
+            ErrorKind::Serialize(e) => write!(f, "{}{}", SERIALIZE_PREFIX, e),
+            ErrorKind::Deserialize(e) => write!(f, "{}{}", DESERIALIZE_PREFIX, e),
+            ErrorKind::Format(e) => fmt::Display::fmt(e, f),
             ErrorKind::KeyMustBeAString => f.write_str("key must be a string"),
             ErrorKind::EofWhileParsingValue => f.write_str("EOF while parsing a value"),
-            ErrorKind::ReservedToken => f.write_str("reserved token"),
+            ErrorKind::ReservedToken { token, offset } => write!(
+                f,
+                "reserved token {:#04x} at offset {}; the document may use a newer revision of the Smile format",
+                token, offset
+            ),
             ErrorKind::InvalidStringReference => f.write_str("invalid string reference"),
             ErrorKind::UnterminatedVint => f.write_str("unterminated vint"),
             ErrorKind::BufferLengthOverflow => f.write_str("buffer length overflow"),
             ErrorKind::InvalidUtf8 => f.write_str("invalid UTF-8"),
-            ErrorKind::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
+            ErrorKind::RecursionLimitExceeded { limit, depth } => write!(
+                f,
+                "recursion limit of {} exceeded at depth {}",
+                limit, depth
+            ),
             ErrorKind::TrailingData => f.write_str("trailing data"),
             ErrorKind::EofWhileParsingArray => f.write_str("EOF while parsing array"),
-            ErrorKind::UnexpectedToken => f.write_str("unexpected token"),
+            ErrorKind::UnexpectedToken { token, offset } => {
+                write!(f, "unexpected token {:#04x} at offset {}", token, offset)
+            }
             ErrorKind::EofWhileParsingMap => f.write_str("EOF while parsing map"),
             ErrorKind::InvalidHeader => f.write_str("invalid header"),
             ErrorKind::UnsupportedVersion => f.write_str("unsupported version"),
             ErrorKind::EofWhileParsingHeader => f.write_str("EOF while parsing header"),
+            ErrorKind::ScaleOutOfRange => f.write_str("BigDecimal scale out of range"),
+            ErrorKind::InvalidValueType { expected, found } => {
+                write!(
+                    f,
+                    "invalid value type: expected {}, found {}",
+                    expected, found
+                )
+            }
+            ErrorKind::ValueOutOfRange { expected } => {
+                write!(f, "value out of range: expected {}", expected)
+            }
+            ErrorKind::InvalidByteKey { encoding } => write!(f, "invalid {} byte key", encoding),
+            ErrorKind::UnexpectedDocumentBoundary => f.write_str(
+                "unexpected Smile header inside a document; \
+                 concatenated documents must be separated by the end-of-stream token",
+            ),
+            ErrorKind::HeaderFlagsChanged { old, new } => write!(
+                f,
+                "header flags changed from {:#04x} to {:#04x} after the header was written",
+                old, new
+            ),
+            ErrorKind::ElementLimitExceeded { limit } => {
+                write!(f, "element limit of {} exceeded", limit)
+            }
+            ErrorKind::LimitExceeded { limit, max } => write!(f, "{} limit of {} exceeded", limit, max),
+            ErrorKind::InvalidBigInteger => f.write_str("invalid big integer string"),
+            ErrorKind::EmptyBigInteger => f.write_str("empty big integer"),
+            ErrorKind::NonMinimalBigInteger => {
+                f.write_str("big integer has redundant sign extension bytes")
+            }
+            ErrorKind::StringCacheLimitExceeded { limit } => write!(
+                f,
+                "shared string cache limit of {} bytes exceeded; the document may be hostile",
+                limit
+            ),
         }
     }
 }
@@ -53,7 +170,7 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match &*self.0 {
-            ErrorKind::Io(e) => Some(e),
+            ErrorKind::Io(e) => Some(&e.0),
             _ => None,
         }
     }
@@ -64,7 +181,9 @@ impl ser::Error for Error {
     where
         T: fmt::Display,
     {
-        Error(Box::new(ErrorKind::Custom(msg.to_string())))
+        let msg = msg.to_string();
+        let msg = strip_prefix(msg, SERIALIZE_PREFIX);
+        Error(Box::new(ErrorKind::Serialize(ErrorDetail::Message(msg))))
     }
 }
 
@@ -73,13 +192,343 @@ impl de::Error for Error {
     where
         T: fmt::Display,
     {
-        Error(Box::new(ErrorKind::Custom(msg.to_string())))
+        let msg = msg.to_string();
+        let msg = strip_prefix(msg, DESERIALIZE_PREFIX);
+        Error::deserialize(ErrorDetail::Message(msg))
+    }
+
+    fn invalid_type(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Error::deserialize(ErrorDetail::invalid_type(unexp, exp))
+    }
+
+    fn invalid_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Error::deserialize(ErrorDetail::invalid_value(unexp, exp))
+    }
+
+    fn invalid_length(len: usize, exp: &dyn de::Expected) -> Self {
+        Error::deserialize(ErrorDetail::invalid_length(len, exp))
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        Error::deserialize(ErrorDetail::UnknownVariant {
+            variant: variant.to_string(),
+            expected,
+        })
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Error::deserialize(ErrorDetail::UnknownField {
+            field: field.to_string(),
+            expected,
+        })
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::deserialize(ErrorDetail::MissingField { field })
+    }
+
+    fn duplicate_field(field: &'static str) -> Self {
+        Error::deserialize(ErrorDetail::DuplicateField { field })
+    }
+}
+
+const SERIALIZE_PREFIX: &str = "error in Serialize impl: ";
+const DESERIALIZE_PREFIX: &str = "error in Deserialize impl: ";
+
+// Adapters like erased-serde convert errors by passing them to `custom`, so avoid nesting the origin prefix.
+fn strip_prefix(msg: String, prefix: &str) -> String {
+    match msg.strip_prefix(prefix) {
+        Some(rest) => rest.to_string(),
+        None => msg,
+    }
+}
+
+/// The source of an [`Error`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ErrorOrigin {
+    /// The error was raised by a `Serialize` implementation through `serde::ser::Error`.
+    Serialize,
+    /// The error was raised by a `Deserialize` implementation through `serde::de::Error`.
+    ///
+    /// This includes errors raised by the implementations generated by `serde_derive` and those provided by `serde`
+    /// and this crate, such as a `Vec`'s rejection of a string.
+    Deserialize,
+    /// The error was raised by `serde_smile` itself, for example because the input was malformed or didn't match the
+    /// type being deserialized.
+    Internal,
+}
+
+/// Structured information about an error raised through serde's error constructors.
+///
+/// Returned by [`Error::detail`]. Its `Display` implementation produces the same messages as serde's default
+/// implementations of the `serde::de::Error` constructors.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum ErrorDetail {
+    /// A free-form message passed to `custom`.
+    Message(String),
+    /// A value of the wrong type was found.
+    InvalidType {
+        /// A description of the value which was found.
+        unexpected: String,
+        /// A description of what was expected.
+        expected: String,
+    },
+    /// A value of the right type but the wrong value was found.
+    InvalidValue {
+        /// A description of the value which was found.
+        unexpected: String,
+        /// A description of what was expected.
+        expected: String,
+    },
+    /// A sequence or map had the wrong number of elements.
+    InvalidLength {
+        /// The number of elements found.
+        len: usize,
+        /// A description of what was expected.
+        expected: String,
+    },
+    /// An enum variant with an unrecognized name was found.
+    UnknownVariant {
+        /// The name of the variant.
+        variant: String,
+        /// The names of the variants which were expected.
+        expected: &'static [&'static str],
+    },
+    /// A struct field with an unrecognized name was found.
+    UnknownField {
+        /// The name of the field.
+        field: String,
+        /// The names of the fields which were expected.
+        expected: &'static [&'static str],
+    },
+    /// A required struct field was missing.
+    MissingField {
+        /// The name of the field.
+        field: &'static str,
+    },
+    /// A struct field appeared more than once.
+    DuplicateField {
+        /// The name of the field.
+        field: &'static str,
+    },
+}
+
+impl ErrorDetail {
+    pub(crate) fn invalid_type(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        ErrorDetail::InvalidType {
+            unexpected: unexp.to_string(),
+            expected: exp.to_string(),
+        }
+    }
+
+    pub(crate) fn invalid_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        ErrorDetail::InvalidValue {
+            unexpected: unexp.to_string(),
+            expected: exp.to_string(),
+        }
+    }
+
+    pub(crate) fn invalid_length(len: usize, exp: &dyn de::Expected) -> Self {
+        ErrorDetail::InvalidLength {
+            len,
+            expected: exp.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorDetail::Message(msg) => f.write_str(msg),
+            ErrorDetail::InvalidType {
+                unexpected,
+                expected,
+            } => write!(f, "invalid type: {}, expected {}", unexpected, expected),
+            ErrorDetail::InvalidValue {
+                unexpected,
+                expected,
+            } => write!(f, "invalid value: {}, expected {}", unexpected, expected),
+            ErrorDetail::InvalidLength { len, expected } => {
+                write!(f, "invalid length {}, expected {}", len, expected)
+            }
+            ErrorDetail::UnknownVariant { variant, expected } => {
+                if expected.is_empty() {
+                    write!(f, "unknown variant `{}`, there are no variants", variant)
+                } else {
+                    write!(
+                        f,
+                        "unknown variant `{}`, expected {}",
+                        variant,
+                        OneOf(expected)
+                    )
+                }
+            }
+            ErrorDetail::UnknownField { field, expected } => {
+                if expected.is_empty() {
+                    write!(f, "unknown field `{}`, there are no fields", field)
+                } else {
+                    write!(f, "unknown field `{}`, expected {}", field, OneOf(expected))
+                }
+            }
+            ErrorDetail::MissingField { field } => write!(f, "missing field `{}`", field),
+            ErrorDetail::DuplicateField { field } => write!(f, "duplicate field `{}`", field),
+        }
+    }
+}
+
+/// Formats a list of names the same way as serde.
+struct OneOf(&'static [&'static str]);
+
+impl fmt::Display for OneOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            [] => Ok(()),
+            [a] => write!(f, "`{}`", a),
+            [a, b] => write!(f, "`{}` or `{}`", a, b),
+            names => {
+                f.write_str("one of ")?;
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "`{}`", name)?;
+                }
+                Ok(())
+            }
+        }
     }
 }
 
 impl Error {
+    /// Returns the source of the error.
+    ///
+    /// Errors raised through `serde::ser::Error` and `serde::de::Error` are attributed to the `Serialize` or
+    /// `Deserialize` implementation which raised them, and all other errors to `serde_smile` itself.
+    pub fn origin(&self) -> ErrorOrigin {
+        match &*self.0 {
+            ErrorKind::Serialize(_) => ErrorOrigin::Serialize,
+            ErrorKind::Deserialize(_) => ErrorOrigin::Deserialize,
+            _ => ErrorOrigin::Internal,
+        }
+    }
+
+    /// Returns structured information about the error, if it was raised through one of serde's error constructors.
+    ///
+    /// This is the case for errors with a `Serialize` or `Deserialize` [origin](Self::origin), and for errors raised
+    /// by `serde_smile` when the input doesn't match the type being deserialized.
+    pub fn detail(&self) -> Option<&ErrorDetail> {
+        match &*self.0 {
+            ErrorKind::Serialize(e) | ErrorKind::Deserialize(e) | ErrorKind::Format(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns the token byte responsible for the error, if the error was caused by a reserved or unexpected token.
+    ///
+    /// Reserved tokens are not assigned a meaning by the Smile specification, so they may indicate that the document
+    /// was produced by an implementation of a newer revision of the format.
+    pub fn token(&self) -> Option<u8> {
+        match &*self.0 {
+            ErrorKind::ReservedToken { token, .. } | ErrorKind::UnexpectedToken { token, .. } => {
+                Some(*token)
+            }
+            _ => None,
+        }
+    }
+
+    /// Determines if the operation which produced the error may succeed if retried.
+    ///
+    /// This is the case for IO errors and for errors caused by the input ending early, which may be resolved by reading
+    /// the document again once it has been completely written. Errors caused by malformed input or by data which
+    /// can't be represented are permanent.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            *self.0,
+            ErrorKind::Io(_)
+                | ErrorKind::EofWhileParsingValue
+                | ErrorKind::EofWhileParsingArray
+                | ErrorKind::EofWhileParsingMap
+                | ErrorKind::EofWhileParsingHeader
+        )
+    }
+
+    /// Returns the category of the error reported in metrics.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn category(&self) -> &'static str {
+        match &*self.0 {
+            ErrorKind::Io(_) => "io",
+            ErrorKind::EofWhileParsingValue
+            | ErrorKind::EofWhileParsingArray
+            | ErrorKind::EofWhileParsingMap
+            | ErrorKind::EofWhileParsingHeader => "eof",
+            ErrorKind::ReservedToken { .. }
+            | ErrorKind::InvalidStringReference
+            | ErrorKind::UnterminatedVint
+            | ErrorKind::BufferLengthOverflow
+            | ErrorKind::InvalidUtf8
+            | ErrorKind::TrailingData
+            | ErrorKind::UnexpectedToken { .. }
+            | ErrorKind::InvalidHeader
+            | ErrorKind::UnsupportedVersion
+            | ErrorKind::UnexpectedDocumentBoundary
+            | ErrorKind::EmptyBigInteger
+            | ErrorKind::NonMinimalBigInteger => "syntax",
+            ErrorKind::RecursionLimitExceeded { .. }
+            | ErrorKind::ElementLimitExceeded { .. }
+            | ErrorKind::LimitExceeded { .. }
+            | ErrorKind::StringCacheLimitExceeded { .. } => "limit",
+            ErrorKind::Serialize(_)
+            | ErrorKind::Deserialize(_)
+            | ErrorKind::Format(_)
+            | ErrorKind::KeyMustBeAString
+            | ErrorKind::ScaleOutOfRange
+            | ErrorKind::InvalidValueType { .. }
+            | ErrorKind::ValueOutOfRange { .. }
+            | ErrorKind::InvalidByteKey { .. }
+            | ErrorKind::HeaderFlagsChanged { .. }
+            | ErrorKind::InvalidBigInteger => "data",
+        }
+    }
+
+    fn deserialize(detail: ErrorDetail) -> Self {
+        Error(Box::new(ErrorKind::Deserialize(detail)))
+    }
+
+    /// Like `de::Error::custom`, but attributed to `serde_smile` rather than a `Deserialize` implementation.
+    #[cfg(any(test, feature = "testsuite", feature = "tools"))]
+    pub(crate) fn message<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error(Box::new(ErrorKind::Format(ErrorDetail::Message(
+            msg.to_string(),
+        ))))
+    }
+
+    /// Like `de::Error::invalid_type`, but attributed to `serde_smile` rather than a `Deserialize` implementation.
+    pub(crate) fn mismatched_type(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Error(Box::new(ErrorKind::Format(ErrorDetail::invalid_type(
+            unexp, exp,
+        ))))
+    }
+
+    /// Like `de::Error::invalid_value`, but attributed to `serde_smile` rather than a `Deserialize` implementation.
+    pub(crate) fn mismatched_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Error(Box::new(ErrorKind::Format(ErrorDetail::invalid_value(
+            unexp, exp,
+        ))))
+    }
+
+    /// Like `de::Error::invalid_length`, but attributed to `serde_smile` rather than a `Deserialize` implementation.
+    pub(crate) fn mismatched_length(len: usize, exp: &dyn de::Expected) -> Self {
+        Error(Box::new(ErrorKind::Format(ErrorDetail::invalid_length(
+            len, exp,
+        ))))
+    }
+
     pub(crate) fn io(e: io::Error) -> Self {
-        Error(Box::new(ErrorKind::Io(e)))
+        Error(Box::new(ErrorKind::Io(IoError(e))))
     }
 
     pub(crate) fn key_must_be_a_string() -> Self {
@@ -90,8 +539,8 @@ impl Error {
         Error(Box::new(ErrorKind::EofWhileParsingValue))
     }
 
-    pub(crate) fn reserved_token() -> Self {
-        Error(Box::new(ErrorKind::ReservedToken))
+    pub(crate) fn reserved_token(token: u8, offset: u64) -> Self {
+        Error(Box::new(ErrorKind::ReservedToken { token, offset }))
     }
 
     pub(crate) fn invalid_string_reference() -> Self {
@@ -110,8 +559,8 @@ impl Error {
         Error(Box::new(ErrorKind::InvalidUtf8))
     }
 
-    pub(crate) fn recursion_limit_exceeded() -> Self {
-        Error(Box::new(ErrorKind::RecursionLimitExceeded))
+    pub(crate) fn recursion_limit_exceeded(limit: usize, depth: usize) -> Self {
+        Error(Box::new(ErrorKind::RecursionLimitExceeded { limit, depth }))
     }
 
     pub(crate) fn trailing_data() -> Self {
@@ -122,8 +571,8 @@ impl Error {
         Error(Box::new(ErrorKind::EofWhileParsingArray))
     }
 
-    pub(crate) fn unexpected_token() -> Self {
-        Error(Box::new(ErrorKind::UnexpectedToken))
+    pub(crate) fn unexpected_token(token: u8, offset: u64) -> Self {
+        Error(Box::new(ErrorKind::UnexpectedToken { token, offset }))
     }
 
     pub(crate) fn eof_while_parsing_map() -> Self {
@@ -141,4 +590,52 @@ impl Error {
     pub(crate) fn eof_while_parsing_header() -> Self {
         Error(Box::new(ErrorKind::EofWhileParsingHeader))
     }
+
+    pub(crate) fn scale_out_of_range() -> Self {
+        Error(Box::new(ErrorKind::ScaleOutOfRange))
+    }
+
+    pub(crate) fn invalid_value_type(expected: &'static str, found: ValueKind) -> Self {
+        Error(Box::new(ErrorKind::InvalidValueType { expected, found }))
+    }
+
+    pub(crate) fn value_out_of_range(expected: &'static str) -> Self {
+        Error(Box::new(ErrorKind::ValueOutOfRange { expected }))
+    }
+
+    pub(crate) fn invalid_byte_key(encoding: &'static str) -> Self {
+        Error(Box::new(ErrorKind::InvalidByteKey { encoding }))
+    }
+
+    pub(crate) fn unexpected_document_boundary() -> Self {
+        Error(Box::new(ErrorKind::UnexpectedDocumentBoundary))
+    }
+
+    pub(crate) fn header_flags_changed(old: u8, new: u8) -> Self {
+        Error(Box::new(ErrorKind::HeaderFlagsChanged { old, new }))
+    }
+
+    pub(crate) fn element_limit_exceeded(limit: u64) -> Self {
+        Error(Box::new(ErrorKind::ElementLimitExceeded { limit }))
+    }
+
+    pub(crate) fn limit_exceeded(limit: &'static str, max: usize) -> Self {
+        Error(Box::new(ErrorKind::LimitExceeded { limit, max }))
+    }
+
+    pub(crate) fn invalid_big_integer() -> Self {
+        Error(Box::new(ErrorKind::InvalidBigInteger))
+    }
+
+    pub(crate) fn empty_big_integer() -> Self {
+        Error(Box::new(ErrorKind::EmptyBigInteger))
+    }
+
+    pub(crate) fn non_minimal_big_integer() -> Self {
+        Error(Box::new(ErrorKind::NonMinimalBigInteger))
+    }
+
+    pub(crate) fn string_cache_limit_exceeded(limit: usize) -> Self {
+        Error(Box::new(ErrorKind::StringCacheLimitExceeded { limit }))
+    }
 }
@@ -0,0 +1,56 @@
+use crate::Error;
+use serde::de::{self, Unexpected};
+use std::any;
+use std::fmt;
+use std::str::FromStr;
+
+/// Parses a map key as an integer.
+///
+/// Returns `None` if the key isn't numeric at all, in which case it should be passed to the visitor as a string.
+/// Numeric keys must be in the canonical form produced by the serializer: an optional `-` followed by decimal digits,
+/// with no `+` or surrounding whitespace. Negative zero is accepted as zero by all integer types.
+pub(crate) fn parse<T>(key: &str) -> Option<Result<T, Error>>
+where
+    T: FromStr,
+{
+    let trimmed = key.trim_matches(|c: char| c.is_ascii_whitespace());
+    let digits = trimmed.strip_prefix(&['+', '-'][..]).unwrap_or(trimmed);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    if trimmed.len() != key.len() || key.starts_with('+') {
+        return Some(Err(error::<T>(key, "a decimal")));
+    }
+
+    let canonical = if digits.bytes().all(|b| b == b'0') {
+        "0"
+    } else {
+        key
+    };
+    match canonical.parse() {
+        Ok(v) => Some(Ok(v)),
+        Err(_) => Some(Err(error::<T>(key, "an in-range"))),
+    }
+}
+
+fn error<T>(key: &str, kind: &'static str) -> Error {
+    Error::mismatched_value(
+        Unexpected::Str(key),
+        &Expected {
+            kind,
+            ty: any::type_name::<T>(),
+        },
+    )
+}
+
+struct Expected {
+    kind: &'static str,
+    ty: &'static str,
+}
+
+impl de::Expected for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} map key", self.kind, self.ty)
+    }
+}
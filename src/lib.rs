@@ -8,17 +8,20 @@
 //! Smile defines several optional features that can be enabled or disabled during serialization:
 //!
 //! * [`Builder::raw_binary`]: If enabled, binary data will be encoded directly as "raw" bytes, rather than using
-//!     Smile's 7-bit "safe" encoding. The raw format is 14% smaller and faster to serialize and deserialize, but usage
-//!     means that encoded values may contain Smile control characters such as the end-of-stream token `0xff`. Disabled
-//!     by default.
+//!   Smile's 7-bit "safe" encoding. The raw format is 14% smaller and faster to serialize and deserialize, but usage
+//!   means that encoded values may contain Smile control characters such as the end-of-stream token `0xff`. Disabled
+//!   by default. Individual values can be forced to the raw encoding with the [`RawBytes`] and [`RawByteBuf`] types.
 //! * [`Builder::shared_strings`]: If enabled, string values 64 bytes and smaller will be deduplicated in the encoded
-//!     format. This increases the memory overhead of serialization and deserialization, but can significantly shrink
-//!     the size of the encoded value when strings are repeated. Disabled by default.
+//!   format. This increases the memory overhead of serialization and deserialization, but can significantly shrink
+//!   the size of the encoded value when strings are repeated. Disabled by default.
 //! * [`Builder::shared_properties`]: If enabled, map keys 64 bytes and smaller will be deduplicated in the encoded
-//!     format. This increases the memory overhead of serialization and deserialization, but can significantly shrink
-//!     the size of the encoded value when keys are repeated (particularly struct field names). Enabled by default.
+//!   format. This increases the memory overhead of serialization and deserialization, but can significantly shrink
+//!   the size of the encoded value when keys are repeated (particularly struct field names). Enabled by default.
 //! * [`Serializer::end`]: A sequence of Smile values can optionally be terminated by the end-of-stream token `0xff`.
-//!     Calling this method will write the token into the output stream.
+//!   Calling this method will write the token into the output stream. [`Builder::end_marker`] does the same for
+//!   values serialized with [`Builder::to_vec`] and [`Builder::to_writer`].
+//!
+//! The options are set on a [`Builder`], which can serialize values directly or create a [`Serializer`].
 //!
 //! # Special Types
 //!
@@ -32,6 +35,64 @@
 //! Rust integer values that cannot be stored in an `i64` will be serialized as Smile `BigInteger` values. In the other
 //! direction, `BigInteger` values will be deserialized to Rust integer types if the value is small enough.
 //!
+//! A stream containing no values (only a header, optionally followed by the end-of-stream token) deserializes to `None`
+//! when the target is an `Option`, and is an error for all other types.
+//!
+//! # Cargo Features
+//!
+//! * `preserve_order`: Backs [`value::Map`] with an `IndexMap` so objects keep the order in which their entries were
+//!   inserted or decoded. When disabled, a `BTreeMap` is used instead and entries are sorted by key. Enabled by
+//!   default.
+//! * `json`: Enables conversions between [`value::Value`] and `serde_json::Value`. See `Value::to_json_value`
+//!   for details. Disabled by default.
+//! * `tools`: Enables the `tools` module of helpers for command line tools which inspect and convert Smile files.
+//!   Implies `json`. Disabled by default.
+//! * `testsuite`: Enables the `testsuite` module, which runs the crate's fixture corpus of JSON values and their
+//!   expected Smile encodings so other Smile implementations can validate themselves. Implies `json`. Disabled by
+//!   default.
+//! * `metrics`: Records [metrics](#metrics) for calls to the top-level serialization and deserialization functions.
+//!   Disabled by default.
+//! * `bytes`: Makes [`value::BinaryBuf`], the buffer of `Value::Binary`, a reference counted `bytes::Bytes` rather
+//!   than a `Vec<u8>`, and adds `value::from_bytes`, which decodes raw binary values as slices of the input buffer
+//!   without copying them. Disabled by default.
+//! * `num-bigint`: Enables conversions between [`value::BigInteger`] and `num_bigint::BigInt` and `BigUint`. Disabled
+//!   by default.
+//! * `bigdecimal`: Enables conversions between [`value::BigDecimal`] and `bigdecimal::BigDecimal`. Implies
+//!   `num-bigint`. Disabled by default.
+//! * `arc-strings`: Makes [`value::StringBuf`], the string of `Value::String`, an `Arc<str>` rather than a `String`,
+//!   so cloning a string value doesn't copy it, and adds `value::from_slice_with_shared_strings` and
+//!   `value::from_reader_with_shared_strings`, which decode repeated shared strings into a single allocation.
+//!   Disabled by default.
+//! * `rust_decimal`: Enables conversions between [`value::BigDecimal`] and `rust_decimal::Decimal`, and the
+//!   `rust_decimal` module for encoding `Decimal` fields as Smile `BigDecimal` values. Disabled by default.
+//! * `schemars`: Implements `schemars::JsonSchema` for [`value::Value`], [`value::Map`], [`value::BigInteger`], and
+//!   [`value::BigDecimal`], describing the JSON forms produced by `Value::to_json_value`. Disabled by default.
+//!
+//! # Metrics
+//!
+//! With the `metrics` Cargo feature enabled, the functions [`to_vec`], [`to_vec_with_capacity`], [`to_writer`],
+//! [`from_slice`], [`from_mut_slice`], [`from_cow`], and [`from_reader`] report to the recorder installed with the
+//! [`metrics`](https://docs.rs/metrics) crate. Each call takes one pair of timestamps; [`Serializer`]s and
+//! [`Deserializer`]s used directly are not instrumented.
+//!
+//! | Name | Type | Labels | Description |
+//! |------|------|--------|-------------|
+//! | `serde_smile_serialize_duration_seconds` | histogram | `writer` | The duration of each serialization call. |
+//! | `serde_smile_serialize_bytes` | histogram | `writer` | The number of bytes written by each successful call. |
+//! | `serde_smile_serialize_errors_total` | counter | `writer`, `category` | The number of failed calls. |
+//! | `serde_smile_deserialize_duration_seconds` | histogram | `reader` | The duration of each deserialization call. |
+//! | `serde_smile_deserialize_bytes` | histogram | `reader` | The number of bytes read by each successful call. |
+//! | `serde_smile_deserialize_errors_total` | counter | `reader`, `category` | The number of failed calls. |
+//!
+//! Labels only take a fixed set of values:
+//!
+//! * `writer`: `vec` for `to_vec` and `to_vec_with_capacity`, and `io` for `to_writer`.
+//! * `reader`: `slice`, `mut_slice`, `cow`, and `io` for `from_slice`, `from_mut_slice`, `from_cow`, and
+//!   `from_reader` respectively.
+//! * `category`: `io` for IO errors, `eof` for input which ended early, `syntax` for malformed input, `limit` for
+//!   input exceeding a configured limit, and `data` for values which can't be represented in the target format or
+//!   type, including errors raised by `Serialize` and `Deserialize` implementations.
+//!
 //! # Examples
 //!
 //! Serialize a Rust object into a Smile value:
@@ -57,6 +118,41 @@
 //! }
 //! ```
 //!
+//! Serialize a Rust object with non-default options:
+//! ```rust
+//! use serde::Serialize;
+//! use serde_smile::{Error, Serializer};
+//!
+//! #[derive(Serialize)]
+//! struct Address {
+//!     number: u32,
+//!     street: String,
+//! }
+//!
+//! fn main() -> Result<(), Error> {
+//!     let addresses = vec![
+//!         Address {
+//!             number: 1600,
+//!             street: "Pennsylvania Avenue".to_string(),
+//!         },
+//!         Address {
+//!             number: 1500,
+//!             street: "Pennsylvania Avenue".to_string(),
+//!         },
+//!     ];
+//!
+//!     let value = Serializer::builder()
+//!         .shared_strings(true)
+//!         .end_marker(true)
+//!         .to_vec(&addresses)?;
+//!
+//!     let mut buf = vec![];
+//!     Serializer::builder().to_writer(&mut buf, &addresses)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! Deserialize a Smile value into a Rust object:
 //! ```rust
 //! use serde::Deserialize;
@@ -83,20 +179,40 @@
 //! [`Builder::raw_binary`]: ser::Builder::raw_binary
 //! [`Builder::shared_strings`]: ser::Builder::shared_strings
 //! [`Builder::shared_properties`]: ser::Builder::shared_properties
+//! [`Builder::end_marker`]: ser::Builder::end_marker
+//! [`Builder::to_vec`]: ser::Builder::to_vec
+//! [`Builder::to_writer`]: ser::Builder::to_writer
+//! [`Builder`]: ser::Builder
 //! [`BigInteger`]: value::BigInteger
 //! [`BigDecimal`]: value::BigDecimal
 #![warn(missing_docs)]
 
 #[doc(inline)]
-pub use de::{from_mut_slice, from_reader, from_slice, Deserializer};
+pub use de::{from_cow, from_mut_slice, from_reader, from_slice, Deserializer};
+#[doc(inline)]
+pub use error::{Error, ErrorDetail, ErrorOrigin};
 #[doc(inline)]
-pub use error::Error;
+pub use raw_bytes::{RawByteBuf, RawBytes};
 #[doc(inline)]
-pub use ser::{to_vec, to_writer, Serializer};
+pub use ser::{to_vec, to_vec_with_capacity, to_writer, Serializer};
 
+mod byte_key_policy;
 pub mod de;
+mod encoding;
 mod error;
+mod integer_key;
+pub mod limits;
+pub mod net;
+mod raw_bytes;
+#[cfg(feature = "rust_decimal")]
+pub mod rust_decimal;
 pub mod ser;
+#[cfg(feature = "metrics")]
+mod telemetry;
 #[cfg(test)]
 mod test;
+#[cfg(any(test, feature = "testsuite"))]
+pub mod testsuite;
+#[cfg(feature = "tools")]
+pub mod tools;
 pub mod value;
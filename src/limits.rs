@@ -0,0 +1,9 @@
+//! Limits shared by serialization and deserialization.
+
+/// The default maximum nesting depth of arrays and objects.
+///
+/// A top-level array or object has a depth of 1. Enum variants that are encoded as single-entry objects count
+/// towards the depth in the same way as any other object. The same default is used by both the
+/// [`Serializer`](crate::Serializer) and the [`Deserializer`](crate::Deserializer) so that any value which can be
+/// serialized can also be deserialized.
+pub const DEFAULT_RECURSION_LIMIT: usize = 128;
@@ -0,0 +1,417 @@
+//! Compact encodings of `std::net` types.
+//!
+//! Serde's implementations for IP and socket addresses encode addresses as sequences of integers when used with a
+//! binary format like Smile. An `Ipv4Addr` is written as a 4 element array, which costs up to 10 bytes, where a binary
+//! token would cost as few as 6. The modules here are intended for use with `#[serde(with = "...")]`, and encode
+//! addresses more efficiently:
+//!
+//! * [`ipv4`] encodes an `Ipv4Addr` as 4 bytes of binary data.
+//! * [`ipv6`] encodes an `Ipv6Addr` as 16 bytes of binary data.
+//! * [`ip_addr`] encodes an `IpAddr` as either 4 or 16 bytes of binary data, depending on the address family.
+//! * [`socket_addr`] encodes a `SocketAddr` as a struct with an `ip` field holding the address in the format of
+//!   [`ip_addr`], and a `port` field.
+//!
+//! To support migrating existing data, deserialization accepts both the compact encoding and the encoding produced by
+//! Serde's own implementations, as well as the string representation used by human readable formats.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::net::{Ipv4Addr, SocketAddr};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Connection {
+//!     #[serde(with = "serde_smile::net::ipv4")]
+//!     gateway: Ipv4Addr,
+//!     #[serde(with = "serde_smile::net::socket_addr")]
+//!     peer: SocketAddr,
+//! }
+//!
+//! let connection = Connection {
+//!     gateway: Ipv4Addr::new(10, 0, 0, 1),
+//!     peer: "[::1]:8080".parse().unwrap(),
+//! };
+//!
+//! let buf = serde_smile::to_vec(&connection).unwrap();
+//! let decoded = serde_smile::from_slice::<Connection>(&buf).unwrap();
+//! assert_eq!(decoded, connection);
+//! ```
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+/// Compact encoding of `Ipv4Addr` as 4 bytes of binary data.
+pub mod ipv4 {
+    use super::*;
+
+    /// Serializes an `Ipv4Addr` as 4 bytes of binary data.
+    pub fn serialize<S>(addr: &Ipv4Addr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&addr.octets())
+    }
+
+    /// Deserializes an `Ipv4Addr` from either its compact or default encoding.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ipv4Addr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(OctetsVisitor::<4>)
+            .map(Ipv4Addr::from)
+    }
+}
+
+/// Compact encoding of `Ipv6Addr` as 16 bytes of binary data.
+pub mod ipv6 {
+    use super::*;
+
+    /// Serializes an `Ipv6Addr` as 16 bytes of binary data.
+    pub fn serialize<S>(addr: &Ipv6Addr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&addr.octets())
+    }
+
+    /// Deserializes an `Ipv6Addr` from either its compact or default encoding.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ipv6Addr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(OctetsVisitor::<16>)
+            .map(Ipv6Addr::from)
+    }
+}
+
+/// Compact encoding of `IpAddr` as 4 or 16 bytes of binary data.
+pub mod ip_addr {
+    use super::*;
+
+    /// Serializes an `IpAddr` as 4 bytes of binary data for IPv4 addresses or 16 bytes for IPv6 addresses.
+    pub fn serialize<S>(addr: &IpAddr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match addr {
+            IpAddr::V4(addr) => ipv4::serialize(addr, serializer),
+            IpAddr::V6(addr) => ipv6::serialize(addr, serializer),
+        }
+    }
+
+    /// Deserializes an `IpAddr` from either its compact or default encoding.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<IpAddr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(IpAddrVisitor)
+    }
+}
+
+/// Compact encoding of `SocketAddr` as a struct of an address and a port.
+pub mod socket_addr {
+    use super::*;
+
+    /// Serializes a `SocketAddr` as a struct with an `ip` field containing the compact encoding of its address and a
+    /// `port` field.
+    ///
+    /// The flow info and scope ID of IPv6 socket addresses are not preserved.
+    pub fn serialize<S>(addr: &SocketAddr, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("SocketAddr", 2)?;
+        s.serialize_field("ip", &CompactIpAddr(addr.ip()))?;
+        s.serialize_field("port", &addr.port())?;
+        s.end()
+    }
+
+    /// Deserializes a `SocketAddr` from either its compact or default encoding.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SocketAddr, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SocketAddrVisitor)
+    }
+}
+
+struct CompactIpv4(Ipv4Addr);
+
+impl<'de> Deserialize<'de> for CompactIpv4 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ipv4::deserialize(deserializer).map(CompactIpv4)
+    }
+}
+
+struct CompactIpv6(Ipv6Addr);
+
+impl<'de> Deserialize<'de> for CompactIpv6 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ipv6::deserialize(deserializer).map(CompactIpv6)
+    }
+}
+
+struct CompactIpAddr(IpAddr);
+
+impl Serialize for CompactIpAddr {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        ip_addr::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompactIpAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ip_addr::deserialize(deserializer).map(CompactIpAddr)
+    }
+}
+
+struct OctetsVisitor<const N: usize>;
+
+impl<'de, const N: usize> Visitor<'de> for OctetsVisitor<N> {
+    type Value = [u8; N];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "an IPv{} address", if N == 4 { 4 } else { 6 })
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        <[u8; N]>::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let addr = v
+            .parse::<IpAddr>()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+        match (addr, N) {
+            (IpAddr::V4(addr), 4) => Ok(<[u8; N]>::try_from(&addr.octets()[..]).unwrap()),
+            (IpAddr::V6(addr), 16) => Ok(<[u8; N]>::try_from(&addr.octets()[..]).unwrap()),
+            _ => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut octets = [0; N];
+        for (i, octet) in octets.iter_mut().enumerate() {
+            *octet = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(N + 1, &self));
+        }
+        Ok(octets)
+    }
+}
+
+struct IpAddrVisitor;
+
+impl<'de> Visitor<'de> for IpAddrVisitor {
+    type Value = IpAddr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an IP address")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match v.len() {
+            4 => OctetsVisitor::<4>.visit_bytes(v).map(IpAddr::from),
+            16 => OctetsVisitor::<16>.visit_bytes(v).map(IpAddr::from),
+            len => Err(E::invalid_length(len, &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    // the default encoding is an externally tagged enum
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let addr = match map.next_key::<IpFamily>()? {
+            Some(IpFamily::V4) => IpAddr::V4(map.next_value::<CompactIpv4>()?.0),
+            Some(IpFamily::V6) => IpAddr::V6(map.next_value::<CompactIpv6>()?.0),
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        finish_variant(map, addr)
+    }
+}
+
+struct SocketAddrVisitor;
+
+impl<'de> Visitor<'de> for SocketAddrVisitor {
+    type Value = SocketAddr;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a socket address")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    // both the compact encoding and the default externally tagged enum encoding are maps, so they're distinguished by
+    // their first key
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut ip = None;
+        let mut port = None;
+
+        while let Some(key) = map.next_key::<SocketAddrKey>()? {
+            match key {
+                SocketAddrKey::V4 if ip.is_none() && port.is_none() => {
+                    let addr = map.next_value::<SocketAddrV4>()?;
+                    return finish_variant(map, SocketAddr::V4(addr));
+                }
+                SocketAddrKey::V6 if ip.is_none() && port.is_none() => {
+                    let addr = map.next_value::<SocketAddrV6>()?;
+                    return finish_variant(map, SocketAddr::V6(addr));
+                }
+                SocketAddrKey::V4 | SocketAddrKey::V6 => {
+                    return Err(de::Error::custom("unexpected socket address variant"))
+                }
+                SocketAddrKey::Ip => {
+                    if ip.is_some() {
+                        return Err(de::Error::duplicate_field("ip"));
+                    }
+                    ip = Some(map.next_value::<CompactIpAddr>()?.0);
+                }
+                SocketAddrKey::Port => {
+                    if port.is_some() {
+                        return Err(de::Error::duplicate_field("port"));
+                    }
+                    port = Some(map.next_value()?);
+                }
+            }
+        }
+
+        let ip = ip.ok_or_else(|| de::Error::missing_field("ip"))?;
+        let port = port.ok_or_else(|| de::Error::missing_field("port"))?;
+        Ok(SocketAddr::new(ip, port))
+    }
+}
+
+fn finish_variant<'de, A, T>(mut map: A, value: T) -> Result<T, A::Error>
+where
+    A: MapAccess<'de>,
+{
+    match map.next_key::<de::IgnoredAny>()? {
+        Some(_) => Err(de::Error::invalid_length(2, &"a map with a single key")),
+        None => Ok(value),
+    }
+}
+
+enum SocketAddrKey {
+    Ip,
+    Port,
+    V4,
+    V6,
+}
+
+impl<'de> Deserialize<'de> for SocketAddrKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = SocketAddrKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`ip`, `port`, `V4`, or `V6`")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match v {
+                    "ip" => Ok(SocketAddrKey::Ip),
+                    "port" => Ok(SocketAddrKey::Port),
+                    "V4" => Ok(SocketAddrKey::V4),
+                    "V6" => Ok(SocketAddrKey::V6),
+                    _ => Err(E::unknown_field(v, &["ip", "port", "V4", "V6"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+enum IpFamily {
+    V4,
+    V6,
+}
+
+impl<'de> Deserialize<'de> for IpFamily {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FamilyVisitor;
+
+        impl Visitor<'_> for FamilyVisitor {
+            type Value = IpFamily;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`V4` or `V6`")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match v {
+                    "V4" => Ok(IpFamily::V4),
+                    "V6" => Ok(IpFamily::V6),
+                    _ => Err(E::unknown_variant(v, &["V4", "V6"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FamilyVisitor)
+    }
+}
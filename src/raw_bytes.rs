@@ -0,0 +1,100 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_bytes::{ByteBuf, Bytes};
+use std::fmt;
+
+pub(crate) const STRUCT_NAME: &str = "\0SmileRawBytes";
+
+/// A borrowed byte slice which is always serialized as Smile raw binary.
+///
+/// The crate's serializer normally encodes binary data with either the 7-bit "safe" encoding or the raw encoding,
+/// depending on [`Builder::raw_binary`](crate::ser::Builder::raw_binary). Wrapping a field in `RawBytes` forces the
+/// raw encoding for that value only, leaving the rest of the document unchanged.
+///
+/// # Warning
+///
+/// Raw binary data may contain any byte, including Smile control characters like the end-of-stream token `0xff` and
+/// the bytes of a Smile header. Anything which frames or splits Smile streams by scanning for those bytes rather than
+/// fully parsing them will misbehave. In addition, the Smile header will only advertise raw binary content if
+/// `Builder::raw_binary` is enabled, so other implementations which enforce that header flag will reject the output.
+///
+/// When deserializing, either binary encoding is accepted, but 7-bit encoded data cannot be borrowed. Use
+/// [`RawByteBuf`] to deserialize from readers or 7-bit encoded input.
+///
+/// Other Serde serializers will treat this as an ordinary byte slice.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RawBytes<'a>(pub &'a [u8]);
+
+impl Serialize for RawBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(STRUCT_NAME, Bytes::new(self.0))
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RawBytes<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        <&Bytes>::deserialize(deserializer).map(|b| RawBytes(b))
+    }
+}
+
+/// An owned byte buffer which is always serialized as Smile raw binary.
+///
+/// This is the owned equivalent of [`RawBytes`], and the same warnings apply. When deserializing, either binary
+/// encoding is accepted.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct RawByteBuf(pub Vec<u8>);
+
+impl Serialize for RawByteBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(STRUCT_NAME, Bytes::new(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for RawByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(STRUCT_NAME, RawByteBufVisitor)
+    }
+}
+
+struct RawByteBufVisitor;
+
+impl<'de> Visitor<'de> for RawByteBufVisitor {
+    type Value = RawByteBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("raw binary data")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ByteBuf::deserialize(deserializer).map(|b| RawByteBuf(b.into_vec()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(RawByteBuf(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(RawByteBuf(v))
+    }
+}
@@ -0,0 +1,49 @@
+//! Serialization of `rust_decimal::Decimal` values as Smile `BigDecimal` values.
+//!
+//! `Decimal`'s own `Serialize` implementation writes a string or a floating point number depending on its features.
+//! This module can be used with `#[serde(with = "serde_smile::rust_decimal")]` to instead encode the value as a Smile
+//! `BigDecimal`, as Jackson does for `java.math.BigDecimal`. Deserialization fails if the `BigDecimal` doesn't fit in a
+//! `Decimal`, and values with a negative scale, which `Decimal` can't represent, are decoded with a scale of 0.
+//!
+//! Requires the `rust_decimal` Cargo feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rust_decimal::Decimal;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Payment {
+//!     #[serde(with = "serde_smile::rust_decimal")]
+//!     amount: Decimal,
+//! }
+//!
+//! let payment = Payment {
+//!     amount: Decimal::new(12345, 2),
+//! };
+//! let buf = serde_smile::to_vec(&payment).unwrap();
+//! assert_eq!(serde_smile::from_slice::<Payment>(&buf).unwrap(), payment);
+//! ```
+use crate::value::BigDecimal;
+use ::rust_decimal::Decimal;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+
+/// Serializes a `Decimal` as a Smile `BigDecimal`.
+pub fn serialize<S>(v: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    BigDecimal::from(*v).serialize(serializer)
+}
+
+/// Deserializes a `Decimal` from a Smile `BigDecimal`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let v = BigDecimal::deserialize(deserializer)?;
+    Decimal::try_from(&v).map_err(D::Error::custom)
+}
@@ -0,0 +1,227 @@
+//! Output buffer capacity estimation.
+use crate::ser::Builder;
+use crate::Error;
+use serde::ser::{self, Impossible, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+
+/// The default number of bytes an exact size pass will count before giving up.
+pub(crate) const DEFAULT_ESTIMATE_LIMIT: usize = 64 * 1024;
+
+/// The estimated encoded size of each top-level element of a value too large to size exactly.
+pub(crate) const BYTES_PER_ELEMENT: usize = 32;
+
+/// Estimates the encoded size of `value`.
+///
+/// Values encoding to at most `limit` bytes are sized exactly by serializing them into a byte-counting writer. Larger
+/// values are estimated from the number of top-level elements, and are never estimated smaller than `limit`. Returns 0
+/// if the value fails to serialize.
+pub(crate) fn estimate<T>(builder: &Builder, value: &T) -> usize
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = builder.build(CountingWriter {
+        count: 0,
+        limit: builder.estimate_capacity_limit,
+    });
+    if value.serialize(&mut serializer).is_ok() {
+        return serializer.into_inner().count;
+    }
+    if serializer.get_ref().count <= builder.estimate_capacity_limit {
+        return 0;
+    }
+
+    let elements = match value.serialize(LenProbe) {
+        Err(Probe::Len(len)) => len,
+        _ => 0,
+    };
+    usize::max(
+        builder.estimate_capacity_limit,
+        elements.saturating_mul(BYTES_PER_ELEMENT),
+    )
+}
+
+/// A writer which counts bytes, refusing to write once more than `limit` bytes have been written.
+struct CountingWriter {
+    count: usize,
+    limit: usize,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.count = self.count.saturating_add(buf.len());
+        if self.count > self.limit {
+            // causes write_all to fail with WriteZero
+            return Ok(0);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The "error" produced by `LenProbe`.
+#[derive(Debug)]
+enum Probe {
+    Len(usize),
+    Other,
+}
+
+impl fmt::Display for Probe {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("length probe")
+    }
+}
+
+impl std::error::Error for Probe {}
+
+impl ser::Error for Probe {
+    fn custom<T>(_: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Probe::Other
+    }
+}
+
+/// A serializer which aborts as soon as it learns the number of elements in the value.
+struct LenProbe;
+
+macro_rules! probe_scalar {
+    ($($method:ident($($arg:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<(), Probe> {
+                Err(Probe::Other)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for LenProbe {
+    type Ok = ();
+    type Error = Probe;
+    type SerializeSeq = Impossible<(), Probe>;
+    type SerializeTuple = Impossible<(), Probe>;
+    type SerializeTupleStruct = Impossible<(), Probe>;
+    type SerializeTupleVariant = Impossible<(), Probe>;
+    type SerializeMap = Impossible<(), Probe>;
+    type SerializeStruct = Impossible<(), Probe>;
+    type SerializeStructVariant = Impossible<(), Probe>;
+
+    probe_scalar! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Probe>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<(), Probe>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Probe>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Probe> {
+        Err(len.map_or(Probe::Other, Probe::Len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Probe> {
+        Err(Probe::Len(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Probe> {
+        Err(Probe::Len(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Probe> {
+        Err(Probe::Len(len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Probe> {
+        Err(len.map_or(Probe::Other, Probe::Len))
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self::SerializeStruct, Probe> {
+        Err(Probe::Len(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Probe> {
+        Err(Probe::Len(len))
+    }
+}
+
+impl Builder {
+    /// Serializes the given data structure to a Smile byte vector using this builder's settings.
+    ///
+    /// If [`Builder::estimate_capacity`] is enabled, the vector is preallocated based on an estimate of the encoded
+    /// size.
+    pub fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let capacity = if self.estimate_capacity {
+            estimate(self, value)
+        } else {
+            0
+        };
+
+        let mut buf = Vec::with_capacity(capacity);
+        self.to_writer(&mut buf, value)?;
+        Ok(buf)
+    }
+}
@@ -36,6 +36,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.ser.depth -= 1;
         self.ser.writer.write_all(&[0xf9]).map_err(Error::io)
     }
 }
@@ -96,6 +97,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.ser.depth -= 2;
         self.ser.writer.write_all(&[0xf9, 0xfb]).map_err(Error::io)
     }
 }
@@ -125,6 +127,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.ser.depth -= 1;
         self.ser.writer.write_all(&[0xfb]).map_err(Error::io)
     }
 }
@@ -179,6 +182,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.ser.depth -= 2;
         self.ser.writer.write_all(&[0xfb, 0xfb]).map_err(Error::io)
     }
 }
@@ -1,10 +1,12 @@
-use crate::ser::Serializer;
+use crate::ser::string_cache::{CachedStr, KeyMemo, MemoEntry, StringCache};
+use crate::ser::{ByteKeyPolicy, Serializer};
 use crate::Error;
-use serde::ser::Impossible;
-use serde::{serde_if_integer128, Serialize, Serializer as _};
+use serde::ser::{Impossible, SerializeSeq, SerializeTuple};
+use serde::{Serialize, Serializer as _};
 use std::borrow::Cow;
 use std::io::Write;
 use std::ops::Deref;
+use std::sync::Arc;
 
 pub(crate) struct KeySerializer<'a, W> {
     pub(crate) ser: &'a mut Serializer<W>,
@@ -24,7 +26,8 @@ where
     }
 
     fn serialize_shared_property(&mut self, v: MaybeStatic<'_, str>) -> Result<bool, Error> {
-        let shared_properties = match &mut self.ser.shared_properties {
+        let ser = &mut *self.ser;
+        let shared_properties = match &mut ser.shared_properties {
             Some(shared_properties) => shared_properties,
             None => return Ok(false),
         };
@@ -33,27 +36,30 @@ where
             return Ok(false);
         }
 
-        match shared_properties.get(&v) {
+        let backref = match &mut ser.key_memo {
+            Some(key_memo) => get_or_intern_memoized(shared_properties, key_memo, v),
+            None => match shared_properties.get(&v) {
+                Some(backref) => Some(backref),
+                None => {
+                    shared_properties.intern(v.to_cached());
+                    None
+                }
+            },
+        };
+
+        match backref {
             Some(backref) => {
                 if backref <= 63 {
-                    self.ser
-                        .writer
+                    ser.writer
                         .write_all(&[0x40 + backref as u8])
                         .map_err(Error::io)?;
                 } else {
                     let buf = [0x30 | (backref >> 8) as u8, backref as u8];
-                    self.ser.writer.write_all(&buf).map_err(Error::io)?;
+                    ser.writer.write_all(&buf).map_err(Error::io)?;
                 }
                 Ok(true)
             }
-            None => {
-                let cow = match v {
-                    MaybeStatic::Static(v) => Cow::Borrowed(v),
-                    MaybeStatic::Nonstatic(v) => Cow::Owned(v.to_string()),
-                };
-                shared_properties.intern(cow);
-                Ok(false)
-            }
+            None => Ok(false),
         }
     }
 
@@ -61,6 +67,37 @@ where
         &mut self,
         v: MaybeStatic<'_, str>,
     ) -> Result<(), Error> {
+        if self.ser.key_transform.is_none() {
+            return self.serialize_transformed_str(v);
+        }
+
+        match v {
+            MaybeStatic::Static(v) => {
+                let key_transform = self.ser.key_transform;
+                let transformed = self
+                    .ser
+                    .static_keys
+                    .entry((v.as_ptr() as usize, v.len()))
+                    .or_insert_with(|| match key_transform.apply(v) {
+                        Cow::Borrowed(v) => CachedStr::Static(v),
+                        Cow::Owned(v) => CachedStr::Shared(Arc::from(v)),
+                    })
+                    .clone();
+                match transformed {
+                    CachedStr::Static(v) => self.serialize_transformed_str(MaybeStatic::Static(v)),
+                    CachedStr::Shared(v) => {
+                        self.serialize_transformed_str(MaybeStatic::Nonstatic(&v))
+                    }
+                }
+            }
+            MaybeStatic::Nonstatic(v) => {
+                let transformed = self.ser.key_transform.apply(v);
+                self.serialize_transformed_str(MaybeStatic::Nonstatic(&transformed))
+            }
+        }
+    }
+
+    fn serialize_transformed_str(&mut self, v: MaybeStatic<'_, str>) -> Result<(), Error> {
         if v.is_empty() {
             return self.ser.writer.write_all(&[0x20]).map_err(Error::io);
         }
@@ -99,9 +136,9 @@ where
 
     type Error = Error;
 
-    type SerializeSeq = Impossible<(), Error>;
+    type SerializeSeq = ByteKeySerializer<'a, W>;
 
-    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTuple = ByteKeySerializer<'a, W>;
 
     type SerializeTupleStruct = Impossible<(), Error>;
 
@@ -133,10 +170,8 @@ where
         self.serialize_int(v)
     }
 
-    serde_if_integer128! {
-        fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-            self.serialize_int(v)
-        }
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_int(v)
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
@@ -155,10 +190,8 @@ where
         self.serialize_int(v)
     }
 
-    serde_if_integer128! {
-        fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-            self.serialize_int(v)
-        }
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_int(v)
     }
 
     fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
@@ -177,8 +210,9 @@ where
         self.serialize_maybe_static_str(MaybeStatic::Nonstatic(v))
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(Error::key_must_be_a_string())
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        let s = self.ser.byte_keys.encode(v)?;
+        self.serialize_str(&s)
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -233,6 +267,244 @@ where
         Err(Error::key_must_be_a_string())
     }
 
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        if self.ser.byte_keys == ByteKeyPolicy::Reject {
+            return Err(Error::key_must_be_a_string());
+        }
+
+        Ok(ByteKeySerializer {
+            key: self,
+            buf: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Collects a sequence of `u8`s used as a map key into a byte buffer.
+pub(crate) struct ByteKeySerializer<'a, W> {
+    key: KeySerializer<'a, W>,
+    buf: Vec<u8>,
+}
+
+impl<'a, W> SerializeSeq for ByteKeySerializer<'a, W>
+where
+    W: Write,
+{
+    type Ok = ();
+
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let b = value.serialize(ByteSerializer)?;
+        self.buf.push(b);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.key.serialize_bytes(&self.buf)
+    }
+}
+
+impl<'a, W> SerializeTuple for ByteKeySerializer<'a, W>
+where
+    W: Write,
+{
+    type Ok = ();
+
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        SerializeSeq::end(self)
+    }
+}
+
+/// Serializes an element of a byte buffer map key, which must be a `u8`.
+struct ByteSerializer;
+
+impl serde::Serializer for ByteSerializer {
+    type Ok = u8;
+
+    type Error = Error;
+
+    type SerializeSeq = Impossible<u8, Error>;
+
+    type SerializeTuple = Impossible<u8, Error>;
+
+    type SerializeTupleStruct = Impossible<u8, Error>;
+
+    type SerializeTupleVariant = Impossible<u8, Error>;
+
+    type SerializeMap = Impossible<u8, Error>;
+
+    type SerializeStruct = Impossible<u8, Error>;
+
+    type SerializeStructVariant = Impossible<u8, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(v)
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(Error::key_must_be_a_string())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(Error::key_must_be_a_string())
+    }
+
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         Err(Error::key_must_be_a_string())
     }
@@ -280,10 +552,49 @@ where
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         Err(Error::key_must_be_a_string())
     }
+}
 
-    fn is_human_readable(&self) -> bool {
-        true
+/// Looks up a property in the cache, consulting the memo first. Returns the backreference if the property was
+/// already present, and interns it otherwise.
+fn get_or_intern_memoized(
+    cache: &mut StringCache,
+    memo: &mut KeyMemo,
+    v: MaybeStatic<'_, str>,
+) -> Option<u16> {
+    if let Some(entry) = memo.get(&v) {
+        if entry.generation == cache.generation() {
+            return Some(entry.id);
+        }
+
+        // the cache has been reset since the entry was created, so the ID is stale
+        let backref = cache.get(&v);
+        // if the cache is full, the entry stays stale and is rechecked the next time the key is seen
+        if let Some(id) = backref.or_else(|| cache.intern(entry.key.clone())) {
+            entry.id = id;
+            entry.generation = cache.generation();
+        }
+        return backref;
     }
+
+    let (key, id, backref) = match cache.get_entry(&v) {
+        Some((key, id)) => (key.clone(), id, Some(id)),
+        None => {
+            let key = v.to_cached();
+            match cache.intern(key.clone()) {
+                Some(id) => (key, id, None),
+                None => return None,
+            }
+        }
+    };
+    memo.insert(
+        &v,
+        MemoEntry {
+            key,
+            generation: cache.generation(),
+            id,
+        },
+    );
+    backref
 }
 
 pub(crate) enum MaybeStatic<'a, T>
@@ -294,6 +605,15 @@ where
     Nonstatic(&'a T),
 }
 
+impl MaybeStatic<'_, str> {
+    pub(crate) fn to_cached(self) -> CachedStr {
+        match self {
+            MaybeStatic::Static(v) => CachedStr::Static(v),
+            MaybeStatic::Nonstatic(v) => CachedStr::Shared(Arc::from(v)),
+        }
+    }
+}
+
 impl<T> Copy for MaybeStatic<'_, T> where T: ?Sized {}
 
 impl<T> Clone for MaybeStatic<'_, T>
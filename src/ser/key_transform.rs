@@ -0,0 +1,37 @@
+use std::borrow::Cow;
+
+/// A transformation applied to map keys and struct field names as they are serialized.
+///
+/// Keys are transformed before they are added to the shared property cache, so keys which transform to the same
+/// string share a single cache entry. Distinct keys of a single map which transform to the same string will be
+/// written as duplicate keys.
+#[derive(Copy, Clone, Debug, Default)]
+pub enum KeyTransform {
+    /// Keys are written unchanged.
+    #[default]
+    None,
+    /// Keys are converted to lowercase, as defined by [`str::to_lowercase`].
+    Lowercase,
+    /// Keys are transformed by a custom function.
+    Custom(fn(&str) -> Cow<'_, str>),
+}
+
+impl KeyTransform {
+    pub(crate) fn apply(self, key: &str) -> Cow<'_, str> {
+        match self {
+            KeyTransform::None => Cow::Borrowed(key),
+            KeyTransform::Lowercase => {
+                if key.chars().any(char::is_uppercase) {
+                    Cow::Owned(key.to_lowercase())
+                } else {
+                    Cow::Borrowed(key)
+                }
+            }
+            KeyTransform::Custom(f) => f(key),
+        }
+    }
+
+    pub(crate) fn is_none(self) -> bool {
+        matches!(self, KeyTransform::None)
+    }
+}
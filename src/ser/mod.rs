@@ -1,20 +1,33 @@
 //! Serialize a Rust data structure into Smile data.
+pub use crate::byte_key_policy::ByteKeyPolicy;
+use crate::limits::DEFAULT_RECURSION_LIMIT;
+use crate::raw_bytes;
+use crate::ser::capacity::DEFAULT_ESTIMATE_LIMIT;
 use crate::ser::compound::{Compound, Mode};
 use crate::ser::key_serializer::{KeySerializer, MaybeStatic};
-use crate::ser::string_cache::StringCache;
+pub use crate::ser::key_transform::KeyTransform;
+use crate::ser::string_cache::{CachedStr, KeyMemo, StringCache};
+pub use crate::ser::writer::{ArrayWriter, ObjectWriter};
+#[cfg(feature = "metrics")]
+use crate::telemetry::{CountingWriter, Timer};
+use crate::value::big_integer::strip_sign_extension;
 use crate::value::{BigDecimal, BigInteger};
 use crate::Error;
 use serde::ser::SerializeStruct;
-use serde::{serde_if_integer128, Serialize};
-use std::borrow::Cow;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::io::Write;
+use std::io::{self, Write};
+use std::mem;
 
 mod big_decimal_serializer;
 mod big_integer_serializer;
+pub(crate) mod capacity;
 mod compound;
 mod key_serializer;
-mod string_cache;
+mod key_transform;
+pub(crate) mod string_cache;
+mod writer;
 
 /// Serializes the given data structure to a Smile byte vector using default serializer settings.
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
@@ -22,7 +35,18 @@ where
     T: Serialize + ?Sized,
 {
     let mut buf = vec![];
-    to_writer(&mut buf, value)?;
+    serialize_document(&mut buf, value, "vec")?;
+    Ok(buf)
+}
+
+/// Serializes the given data structure to a Smile byte vector with the given initial capacity using default serializer
+/// settings.
+pub fn to_vec_with_capacity<T>(value: &T, capacity: usize) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + ?Sized,
+{
+    let mut buf = Vec::with_capacity(capacity);
+    serialize_document(&mut buf, value, "vec")?;
     Ok(buf)
 }
 
@@ -32,18 +56,102 @@ where
     W: Write,
     T: ?Sized + Serialize,
 {
-    let mut serializer = Serializer::new(writer);
-    value.serialize(&mut serializer)
+    serialize_document(writer, value, "io")
+}
+
+/// Serializes a value using default serializer settings, recording metrics for the call if enabled.
+#[cfg(feature = "metrics")]
+fn serialize_document<W, T>(writer: W, value: &T, kind: &'static str) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let timer = Timer::start();
+    let mut writer = CountingWriter::new(writer);
+    let result = value.serialize(&mut Serializer::new(&mut writer));
+    timer.serialized(kind, result.as_ref().map(|_| writer.count()));
+    result
+}
+
+#[cfg(not(feature = "metrics"))]
+#[inline]
+fn serialize_document<W, T>(writer: W, value: &T, _: &'static str) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    value.serialize(&mut Serializer::new(writer))
 }
 
 /// A builder to configure a [`Serializer`].
+#[derive(Clone, Debug)]
 pub struct Builder {
     raw_binary: bool,
     shared_strings: bool,
     shared_properties: bool,
+    memoize_keys: bool,
+    byte_keys: ByteKeyPolicy,
+    key_transform: KeyTransform,
+    estimate_capacity: bool,
+    estimate_capacity_limit: usize,
+    version: u8,
+    recursion_limit: usize,
+    write_header: bool,
+    end_marker: bool,
+    shared_cache_capacity: usize,
 }
 
 impl Builder {
+    /// Returns a builder matching the default configuration of Jackson's `SmileFactory`.
+    ///
+    /// Shared properties are enabled, and shared strings and raw binary are disabled. Output is byte-for-byte
+    /// identical to what Jackson produces with its default settings. This is also the configuration returned by
+    /// [`Serializer::builder`].
+    pub fn jackson_defaults() -> Self {
+        Builder {
+            raw_binary: false,
+            shared_strings: false,
+            shared_properties: true,
+            memoize_keys: false,
+            byte_keys: ByteKeyPolicy::Reject,
+            key_transform: KeyTransform::None,
+            estimate_capacity: false,
+            estimate_capacity_limit: DEFAULT_ESTIMATE_LIMIT,
+            version: 0,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            write_header: true,
+            end_marker: false,
+            shared_cache_capacity: string_cache::LIMIT,
+        }
+    }
+
+    /// Returns a builder configured to produce the smallest possible output.
+    ///
+    /// Shared strings, shared properties, and raw binary are all enabled. Deduplication increases the memory and CPU
+    /// overhead of both serialization and deserialization, and raw binary data may contain Smile framing tokens.
+    pub fn smallest_output() -> Self {
+        Builder {
+            raw_binary: true,
+            shared_strings: true,
+            shared_properties: true,
+            ..Builder::jackson_defaults()
+        }
+    }
+
+    /// Returns a builder configured for the fastest serialization and deserialization.
+    ///
+    /// Shared strings and shared properties are disabled to avoid the overhead of maintaining string caches, and raw
+    /// binary is enabled. Output will be larger than the other presets when strings or map keys are repeated, and raw
+    /// binary data may contain Smile framing tokens.
+    pub fn fastest() -> Self {
+        Builder {
+            raw_binary: true,
+            shared_strings: false,
+            shared_properties: false,
+            ..Builder::jackson_defaults()
+        }
+    }
+
     /// Enables the transmission of binary data in "raw" form.
     ///
     /// This format is more performant and space efficient, but Smile framing tokens may be present in the encoded
@@ -71,58 +179,242 @@ impl Builder {
         self
     }
 
-    /// Creates a new [`Serializer`].
-    pub fn build<W>(&self, writer: W) -> Serializer<W>
+    /// Sets the maximum number of strings retained by each of the shared string and shared property caches.
+    ///
+    /// Once a cache is full, further strings are written out in full without being retained, until the cache is reset
+    /// after the 1024th shareable string per the Smile specification. Lower values reduce the memory used by the
+    /// serializer. The capacity is clamped to between 1 and 1024; disable [`Builder::shared_strings`] and
+    /// [`Builder::shared_properties`] to turn sharing off entirely.
+    ///
+    /// Defaults to 1024.
+    pub fn shared_cache_capacity(&mut self, shared_cache_capacity: usize) -> &mut Self {
+        self.shared_cache_capacity = shared_cache_capacity;
+        self
+    }
+
+    /// Enables memoization of map keys by address when shared properties are enabled.
+    ///
+    /// When the same key string is serialized repeatedly, for example when a single map is serialized many times
+    /// with one `Serializer`, this avoids rehashing the key and copying it into the shared property cache again after
+    /// the cache has been reset. It has no effect on the encoded output, but adds a small amount of overhead for keys
+    /// that are only serialized once.
+    ///
+    /// Defaults to `false`.
+    pub fn memoize_keys(&mut self, memoize_keys: bool) -> &mut Self {
+        self.memoize_keys = memoize_keys;
+        self
+    }
+
+    /// Sets the policy used for byte buffer map keys.
+    ///
+    /// Byte buffers include both values serialized as bytes, like `serde_bytes::ByteBuf`, and sequences of `u8`s,
+    /// like `[u8; 20]`. The deserializer must be configured with the same policy via [`crate::de::Builder::byte_keys`].
+    ///
+    /// Defaults to [`ByteKeyPolicy::Reject`].
+    pub fn byte_keys(&mut self, byte_keys: ByteKeyPolicy) -> &mut Self {
+        self.byte_keys = byte_keys;
+        self
+    }
+
+    /// Sets the transformation applied to map keys and struct field names.
+    ///
+    /// Keys are transformed before being deduplicated by [`Builder::shared_properties`], so differently cased keys
+    /// which lowercase to the same string share a single back reference. The transformed forms of struct field names
+    /// and other `&'static str` keys are computed once and reused.
+    ///
+    /// The serializer does not check for duplicate keys, so a map or struct containing multiple keys which transform
+    /// to the same string will be written with that key repeated.
+    ///
+    /// Defaults to [`KeyTransform::None`].
+    pub fn key_transform(&mut self, key_transform: KeyTransform) -> &mut Self {
+        self.key_transform = key_transform;
+        self
+    }
+
+    /// Preallocates the output of [`Builder::to_vec`] based on an estimate of the encoded size of the value.
+    ///
+    /// Values which encode to at most [`Builder::estimate_capacity_limit`] bytes are sized exactly by an extra
+    /// serialization pass into a counting writer, so the output is allocated once. Larger values are assumed to take
+    /// 32 bytes per top-level element, and at least the limit.
+    ///
+    /// The extra pass costs about as much as serializing the value, so this trades CPU time for an output buffer which
+    /// isn't overallocated. See the `estimate_capacity` benchmark.
+    ///
+    /// Defaults to `false`.
+    pub fn estimate_capacity(&mut self, estimate_capacity: bool) -> &mut Self {
+        self.estimate_capacity = estimate_capacity;
+        self
+    }
+
+    /// Sets the maximum encoded size of values which [`Builder::estimate_capacity`] will size exactly.
+    ///
+    /// Defaults to 64 KiB.
+    pub fn estimate_capacity_limit(&mut self, estimate_capacity_limit: usize) -> &mut Self {
+        self.estimate_capacity_limit = estimate_capacity_limit;
+        self
+    }
+
+    /// Sets the version number written into the high nibble of the header's flags byte.
+    ///
+    /// Version 0 is the only version defined by the Smile specification, so other parsers (including this crate's
+    /// [`Deserializer`](crate::Deserializer), unless [`crate::de::Builder::accept_any_version`] is enabled) will reject
+    /// output with a nonzero version.
+    ///
+    /// Defaults to 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the version is greater than 15, since it must fit in 4 bits.
+    pub fn version(&mut self, version: u8) -> &mut Self {
+        assert!(
+            version <= 0x0f,
+            "invalid version {}: expected a value from 0 to 15",
+            version
+        );
+        self.version = version;
+        self
+    }
+
+    /// Sets the maximum nesting depth of arrays and objects.
+    ///
+    /// Serializing a value nested more deeply returns an error rather than overflowing the stack. The
+    /// [`Deserializer`](crate::Deserializer) always uses the default limit, so output nested more deeply than that
+    /// can't be read back by this crate.
+    ///
+    /// Defaults to [`DEFAULT_RECURSION_LIMIT`].
+    pub fn recursion_limit(&mut self, recursion_limit: usize) -> &mut Self {
+        self.recursion_limit = recursion_limit;
+        self
+    }
+
+    /// Determines if the Smile header is written at the start of the output.
+    ///
+    /// When disabled, the output starts directly with the first value, but is otherwise encoded according to the
+    /// configured raw binary, shared string, and shared property options. This saves 4 bytes per document when the
+    /// consumer knows the configuration out of band.
+    ///
+    /// Smile parsers, including this crate's [`Deserializer`](crate::Deserializer), require the header, so the output
+    /// can only be decoded by a consumer which restores it, for example by prepending the header written by a
+    /// serializer with the same configuration.
+    ///
+    /// Defaults to `true`.
+    pub fn write_header(&mut self, write_header: bool) -> &mut Self {
+        self.write_header = write_header;
+        self
+    }
+
+    /// Determines if [`Builder::to_vec`] and [`Builder::to_writer`] terminate the output with the end-of-stream token
+    /// `0xff`.
+    ///
+    /// This has no effect on [`Serializer`]s created with [`Builder::build`]; use [`Serializer::end`] instead.
+    ///
+    /// Defaults to `false`.
+    pub fn end_marker(&mut self, end_marker: bool) -> &mut Self {
+        self.end_marker = end_marker;
+        self
+    }
+
+    /// Serializes the given data structure as Smile into the IO stream using this builder's settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::Serializer;
+    ///
+    /// let mut buf = vec![];
+    /// Serializer::builder()
+    ///     .shared_strings(true)
+    ///     .end_marker(true)
+    ///     .to_writer(&mut buf, &["hello", "hello"])
+    ///     .unwrap();
+    /// assert_eq!(buf, b":)\n\x03\xf8\x44hello\x01\xf9\xff");
+    /// ```
+    pub fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<(), Error>
     where
         W: Write,
+        T: ?Sized + Serialize,
     {
-        let mut flags = 0;
-        if self.raw_binary {
-            flags |= 0x04;
+        let mut serializer = self.build(writer);
+        value.serialize(&mut serializer)?;
+        if self.end_marker {
+            serializer.end()?;
         }
-        if self.shared_strings {
-            flags |= 0x02;
-        }
-        if self.shared_properties {
-            flags |= 0x01;
-        }
-        let header = [b':', b')', b'\n', flags];
+        Ok(())
+    }
 
+    /// Creates a new [`Serializer`].
+    pub fn build<W>(&self, writer: W) -> Serializer<W>
+    where
+        W: Write,
+    {
         Serializer {
             writer,
-            header: Some(header),
+            flags: self.flags(),
+            header_written: false,
+            emit_header: self.write_header,
             raw_binary: self.raw_binary,
             shared_strings: if self.shared_strings {
-                Some(StringCache::new())
+                Some(StringCache::new(self.shared_cache_capacity))
             } else {
                 None
             },
             shared_properties: if self.shared_properties {
-                Some(StringCache::new())
+                Some(StringCache::new(self.shared_cache_capacity))
+            } else {
+                None
+            },
+            key_memo: if self.shared_properties && self.memoize_keys {
+                Some(KeyMemo::new())
             } else {
                 None
             },
+            byte_keys: self.byte_keys,
+            key_transform: self.key_transform,
+            static_keys: HashMap::new(),
+            depth: 0,
+            recursion_limit: self.recursion_limit,
         }
     }
+
+    // The flags byte of the header.
+    fn flags(&self) -> u8 {
+        let mut flags = self.version << 4;
+        if self.raw_binary {
+            flags |= 0x04;
+        }
+        if self.shared_strings {
+            flags |= 0x02;
+        }
+        if self.shared_properties {
+            flags |= 0x01;
+        }
+        flags
+    }
 }
 
 /// A structure for serializing Rust values into Smile.
 pub struct Serializer<W> {
     writer: W,
-    header: Option<[u8; 4]>,
+    flags: u8,
+    header_written: bool,
+    emit_header: bool,
     raw_binary: bool,
     shared_strings: Option<StringCache>,
     shared_properties: Option<StringCache>,
+    key_memo: Option<KeyMemo>,
+    byte_keys: ByteKeyPolicy,
+    key_transform: KeyTransform,
+    static_keys: HashMap<(usize, usize), CachedStr>,
+    depth: usize,
+    recursion_limit: usize,
 }
 
 impl Serializer<()> {
     /// Returns a builder used to configure a `Serializer`.
+    ///
+    /// The builder starts with the [`Builder::jackson_defaults`] configuration.
     pub fn builder() -> Builder {
-        Builder {
-            raw_binary: false,
-            shared_strings: false,
-            shared_properties: true,
-        }
+        Builder::jackson_defaults()
     }
 }
 
@@ -138,15 +430,72 @@ where
     /// Writes the Smile header to the writer, if not already written.
     ///
     /// This will happen automatically when the first value is serialized, but this method can be
-    /// used to explicitly write it if desired.
+    /// used to explicitly write it if desired. It does nothing if the header is disabled with
+    /// [`Builder::write_header`].
     pub fn write_header(&mut self) -> Result<(), Error> {
-        let Some(header) = self.header.take() else {
+        if self.header_written {
             return Ok(());
-        };
-        self.writer.write_all(&header).map_err(Error::io)?;
+        }
+        self.header_written = true;
+        if self.emit_header {
+            self.writer
+                .write_all(&[b':', b')', b'\n', self.flags])
+                .map_err(Error::io)?;
+        }
         Ok(())
     }
 
+    /// Reconfigures the serializer from a builder, arranging for a new header to be written before the next value.
+    ///
+    /// This allows a single `Serializer` to write a sequence of documents. The string caches are reset, since each
+    /// header starts a new document. Documents should be separated by calling [`Serializer::end`] before rearming.
+    ///
+    /// Before anything has been written, any configuration is accepted. Once the header has been written, the header
+    /// flags configured by the builder (the version, raw binary, shared strings, and shared properties) must match
+    /// those of the existing header; an error is returned and the serializer is left unchanged otherwise.
+    pub fn rearm_header(&mut self, builder: &Builder) -> Result<(), Error> {
+        let flags = builder.flags();
+        if self.header_written && self.flags != flags {
+            return Err(Error::header_flags_changed(self.flags, flags));
+        }
+
+        // every field is listed so that new settings can't be skipped here by accident
+        let Serializer {
+            writer: _,
+            flags,
+            header_written,
+            emit_header,
+            raw_binary,
+            shared_strings,
+            shared_properties,
+            key_memo,
+            byte_keys,
+            key_transform,
+            static_keys,
+            depth,
+            recursion_limit,
+        } = builder.build(io::sink());
+        self.flags = flags;
+        self.header_written = header_written;
+        self.emit_header = emit_header;
+        self.raw_binary = raw_binary;
+        self.shared_strings = shared_strings;
+        self.shared_properties = shared_properties;
+        self.key_memo = key_memo;
+        self.byte_keys = byte_keys;
+        self.key_transform = key_transform;
+        self.static_keys = static_keys;
+        self.depth = depth;
+        self.recursion_limit = recursion_limit;
+        Ok(())
+    }
+
+    /// Returns the number of property names found in the key memo.
+    #[cfg(test)]
+    pub(crate) fn key_memo_hits(&self) -> u64 {
+        self.key_memo.as_ref().map_or(0, KeyMemo::hits)
+    }
+
     /// Writes the Smile end of stream token to the writer.
     ///
     /// The end of stream indicator is not required in a Smile encoding, but can help with framing
@@ -158,6 +507,39 @@ where
         self.writer.write_all(&[0xff]).map_err(Error::io)
     }
 
+    /// Begins an object whose fields are serialized one at a time.
+    ///
+    /// This allows an object to be written incrementally, for example when some of its fields are expensive to
+    /// compute, without collecting it into a value first. The output is identical to that of serializing a map with
+    /// the same entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::Serializer;
+    ///
+    /// let mut ser = Serializer::new(vec![]);
+    /// let mut object = ser.begin_object().unwrap();
+    /// object.field("id", &1).unwrap();
+    /// let mut tags = object.nested_array("tags").unwrap();
+    /// tags.element("a").unwrap();
+    /// tags.end().unwrap();
+    /// object.end().unwrap();
+    ///
+    /// let value = serde_smile::from_slice::<serde_smile::value::Value>(&ser.into_inner()).unwrap();
+    /// assert_eq!(value, serde_smile::smile!({"id": 1, "tags": ["a"]}));
+    /// ```
+    pub fn begin_object(&mut self) -> Result<ObjectWriter<'_, W>, Error> {
+        ObjectWriter::new(self)
+    }
+
+    /// Begins an array whose elements are serialized one at a time.
+    ///
+    /// See [`Serializer::begin_object`] for details.
+    pub fn begin_array(&mut self) -> Result<ArrayWriter<'_, W>, Error> {
+        ArrayWriter::new(self)
+    }
+
     /// Returns a shared reference to the inner writer.
     pub fn get_ref(&self) -> &W {
         &self.writer
@@ -190,7 +572,7 @@ where
         self.writer.write_all(&buf[i..]).map_err(Error::io)
     }
 
-    fn serialize_shared_str(&mut self, v: &str) -> Result<bool, Error> {
+    fn serialize_shared_str(&mut self, v: MaybeStatic<'_, str>) -> Result<bool, Error> {
         let shared_strings = match &mut self.shared_strings {
             Some(shared_strings) => shared_strings,
             None => return Ok(false),
@@ -200,7 +582,7 @@ where
             return Ok(false);
         }
 
-        match shared_strings.get(v) {
+        match shared_strings.get(&v) {
             Some(backref) => {
                 if backref <= 30 {
                     self.writer
@@ -213,12 +595,17 @@ where
                 Ok(true)
             }
             None => {
-                shared_strings.intern(Cow::Owned(v.to_string()));
+                shared_strings.intern(v.to_cached());
                 Ok(false)
             }
         }
     }
 
+    /// Writes the length-prefixed 7-bit encoding of binary data, as used by the `0xe8` binary token and the `0x26` and
+    /// `0x2a` big number tokens.
+    ///
+    /// The length prefix is the length of the raw data, not of its encoding. See the "Tokens: misc; binary / text /
+    /// structure markers" section of the [Smile specification](https://github.com/FasterXML/smile-format-specification).
     fn serialize_7_bit_binary(&mut self, v: &[u8]) -> Result<(), Error> {
         self.serialize_vint(v.len() as u64)?;
 
@@ -259,6 +646,65 @@ where
         self.serialize_7_bit_binary(v)
     }
 
+    fn enter_container(&mut self) -> Result<(), Error> {
+        if self.depth >= self.recursion_limit {
+            return Err(Error::recursion_limit_exceeded(
+                self.recursion_limit,
+                self.depth + 1,
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn serialize_maybe_static_str(&mut self, v: MaybeStatic<'_, str>) -> Result<(), Error> {
+        self.write_header()?;
+        if v.is_empty() {
+            return self.writer.write_all(&[0x20]).map_err(Error::io);
+        }
+
+        if self.serialize_shared_str(v)? {
+            return Ok(());
+        }
+
+        #[allow(clippy::collapsible_else_if)]
+        if v.is_ascii() {
+            if v.len() <= 32 {
+                self.writer
+                    .write_all(&[0x40 + v.len() as u8 - 1])
+                    .map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+            } else if v.len() <= 64 {
+                self.writer
+                    .write_all(&[0x60 + v.len() as u8 - 33])
+                    .map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+            } else {
+                self.writer.write_all(&[0xe0]).map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+                self.writer.write_all(&[0xfc]).map_err(Error::io)?;
+            }
+        } else {
+            if v.len() <= 33 {
+                self.writer
+                    .write_all(&[0x80 + v.len() as u8 - 2])
+                    .map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+            } else if v.len() <= 64 {
+                self.writer
+                    .write_all(&[0xa0 + v.len() as u8 - 34])
+                    .map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+            } else {
+                self.writer.write_all(&[0xe4]).map_err(Error::io)?;
+                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
+                self.writer.write_all(&[0xfc]).map_err(Error::io)?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn serialize_static_key(&mut self, v: &'static str) -> Result<(), Error> {
         KeySerializer { ser: self }.serialize_maybe_static_str(MaybeStatic::Static(v))
     }
@@ -326,12 +772,10 @@ where
         }
     }
 
-    serde_if_integer128! {
-        fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-            match i64::try_from(v) {
-                Ok(v) => self.serialize_i64(v),
-                Err(_) => self.serialize_big_integer(&v.to_be_bytes()),
-            }
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        match i64::try_from(v) {
+            Ok(v) => self.serialize_i64(v),
+            Err(_) => self.serialize_big_integer(strip_sign_extension(&v.to_be_bytes())),
         }
     }
 
@@ -359,16 +803,14 @@ where
         }
     }
 
-    serde_if_integer128! {
-        fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-            match i128::try_from(v) {
-                Ok(v) => self.serialize_i128(v),
-                Err(_) => {
-                    // we need an extra byte for the sign bit
-                    let mut buf = [0; 17];
-                    buf[1..].copy_from_slice(&v.to_be_bytes());
-                    self.serialize_big_integer(&buf)
-                }
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        match i128::try_from(v) {
+            Ok(v) => self.serialize_i128(v),
+            Err(_) => {
+                // we need an extra byte for the sign bit
+                let mut buf = [0; 17];
+                buf[1..].copy_from_slice(&v.to_be_bytes());
+                self.serialize_big_integer(&buf)
             }
         }
     }
@@ -413,51 +855,7 @@ where
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.write_header()?;
-        if v.is_empty() {
-            return self.writer.write_all(&[0x20]).map_err(Error::io);
-        }
-
-        if self.serialize_shared_str(v)? {
-            return Ok(());
-        }
-
-        #[allow(clippy::collapsible_else_if)]
-        if v.is_ascii() {
-            if v.len() <= 32 {
-                self.writer
-                    .write_all(&[0x40 + v.len() as u8 - 1])
-                    .map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-            } else if v.len() <= 64 {
-                self.writer
-                    .write_all(&[0x60 + v.len() as u8 - 33])
-                    .map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-            } else {
-                self.writer.write_all(&[0xe0]).map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-                self.writer.write_all(&[0xfc]).map_err(Error::io)?;
-            }
-        } else {
-            if v.len() <= 33 {
-                self.writer
-                    .write_all(&[0x80 + v.len() as u8 - 2])
-                    .map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-            } else if v.len() <= 64 {
-                self.writer
-                    .write_all(&[0xa0 + v.len() as u8 - 34])
-                    .map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-            } else {
-                self.writer.write_all(&[0xe4]).map_err(Error::io)?;
-                self.writer.write_all(v.as_bytes()).map_err(Error::io)?;
-                self.writer.write_all(&[0xfc]).map_err(Error::io)?;
-            }
-        }
-
-        Ok(())
+        self.serialize_maybe_static_str(MaybeStatic::Nonstatic(v))
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
@@ -476,9 +874,9 @@ where
         self.serialize_unit()
     }
 
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        T: Serialize,
+        T: Serialize + ?Sized,
     {
         value.serialize(self)
     }
@@ -498,17 +896,24 @@ where
         _variant_index: u32,
         variant: &'static str,
     ) -> Result<Self::Ok, Self::Error> {
-        self.serialize_str(variant)
+        self.serialize_maybe_static_str(MaybeStatic::Static(variant))
     }
 
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error>
     where
         T: Serialize + ?Sized,
     {
+        if name == raw_bytes::STRUCT_NAME {
+            let raw_binary = mem::replace(&mut self.raw_binary, true);
+            let r = value.serialize(&mut *self);
+            self.raw_binary = raw_binary;
+            return r;
+        }
+
         value.serialize(self)
     }
 
@@ -528,6 +933,7 @@ where
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.enter_container()?;
         self.write_header()?;
         self.writer.write_all(&[0xf8]).map_err(Error::io)?;
         Ok(Compound {
@@ -555,6 +961,8 @@ where
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.enter_container()?;
+        self.enter_container()?;
         self.write_header()?;
         self.writer.write_all(&[0xfa]).map_err(Error::io)?;
         self.serialize_static_key(variant)?;
@@ -566,6 +974,7 @@ where
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.enter_container()?;
         self.write_header()?;
         self.writer.write_all(&[0xfa]).map_err(Error::io)?;
         Ok(Compound {
@@ -603,6 +1012,8 @@ where
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.enter_container()?;
+        self.enter_container()?;
         self.write_header()?;
         self.writer.write_all(&[0xfa]).map_err(Error::io)?;
         self.serialize_static_key(variant)?;
@@ -619,11 +1030,11 @@ where
 }
 
 #[inline]
-fn zigzag_i32(v: i32) -> u64 {
+pub(crate) fn zigzag_i32(v: i32) -> u64 {
     ((v << 1) ^ (v >> 31)) as u32 as u64
 }
 
 #[inline]
-fn zigzag_i64(v: i64) -> u64 {
+pub(crate) fn zigzag_i64(v: i64) -> u64 {
     ((v << 1) ^ (v >> 63)) as u64
 }
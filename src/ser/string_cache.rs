@@ -1,29 +1,193 @@
-use std::borrow::Cow;
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
 
-const LIMIT: usize = 1024;
+/// The number of shared strings after which readers reset their tables, per the Smile specification.
+pub const LIMIT: usize = 1024;
+
+#[derive(Clone)]
+pub enum CachedStr {
+    Static(&'static str),
+    Shared(Arc<str>),
+}
+
+impl Deref for CachedStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        match self {
+            CachedStr::Static(s) => s,
+            CachedStr::Shared(s) => s,
+        }
+    }
+}
+
+impl Borrow<str> for CachedStr {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl PartialEq for CachedStr {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for CachedStr {}
+
+impl Hash for CachedStr {
+    #[inline]
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        (**self).hash(state)
+    }
+}
 
 pub struct StringCache {
-    map: HashMap<Cow<'static, str>, u16>,
+    map: HashMap<CachedStr, u16>,
+    capacity: usize,
+    next_id: usize,
+    generation: u64,
 }
 
 impl StringCache {
-    pub fn new() -> Self {
+    /// Creates a cache which retains at most `capacity` strings between resets.
+    ///
+    /// The capacity is clamped to between 1 and [`LIMIT`].
+    pub fn new(capacity: usize) -> Self {
         StringCache {
             map: HashMap::new(),
+            capacity: capacity.clamp(1, LIMIT),
+            next_id: 0,
+            generation: 0,
         }
     }
 
-    pub fn intern(&mut self, s: Cow<'static, str>) {
-        if self.map.len() >= LIMIT {
+    /// Records a string which has been written out in full, returning its ID if it was retained.
+    ///
+    /// Readers assign an ID to every shareable string regardless of the writer's capacity, so IDs are counted and the
+    /// cache is reset at the spec's limit even if the string isn't retained.
+    pub fn intern(&mut self, s: CachedStr) -> Option<u16> {
+        if self.next_id >= LIMIT {
             self.map.clear();
+            self.next_id = 0;
+            self.generation += 1;
+        }
+
+        let id = self.next_id as u16;
+        self.next_id += 1;
+        if self.map.len() >= self.capacity {
+            return None;
         }
 
-        let id = self.map.len() as u16;
         self.map.insert(s, id);
+        Some(id)
     }
 
     pub fn get(&mut self, s: &str) -> Option<u16> {
         self.map.get(s).copied()
     }
+
+    pub fn get_entry(&mut self, s: &str) -> Option<(&CachedStr, u16)> {
+        self.map.get_key_value(s).map(|(k, v)| (k, *v))
+    }
+
+    /// Returns a counter which is incremented every time the cache is reset, invalidating previously returned IDs.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// A memo of recently serialized property names, keyed by the address and length of the string.
+///
+/// Looking up a key by address avoids rehashing its contents, and entries retain their string so that it can be
+/// reinterned after a cache reset without allocating. Since an address may be reused for a different string, the
+/// contents of an entry are always compared before it is used.
+pub struct KeyMemo {
+    map: HashMap<(usize, usize), MemoEntry, BuildHasherDefault<AddressHasher>>,
+    #[cfg(test)]
+    hits: u64,
+}
+
+pub struct MemoEntry {
+    pub key: CachedStr,
+    pub generation: u64,
+    pub id: u16,
+}
+
+impl KeyMemo {
+    pub fn new() -> Self {
+        KeyMemo {
+            map: HashMap::default(),
+            #[cfg(test)]
+            hits: 0,
+        }
+    }
+
+    pub fn get(&mut self, s: &str) -> Option<&mut MemoEntry> {
+        let entry = self
+            .map
+            .get_mut(&(s.as_ptr() as usize, s.len()))
+            .filter(|e| *e.key == *s);
+        #[cfg(test)]
+        if entry.is_some() {
+            self.hits += 1;
+        }
+        entry
+    }
+
+    /// Returns the number of lookups which found an entry.
+    #[cfg(test)]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn insert(&mut self, s: &str, entry: MemoEntry) {
+        // keys from freshly allocated strings will never be seen again, so bound the memo's size
+        if self.map.len() >= LIMIT {
+            self.map.clear();
+        }
+
+        self.map.insert((s.as_ptr() as usize, s.len()), entry);
+    }
+}
+
+/// A hasher for the memo's address keys.
+///
+/// The keys aren't attacker controlled, so there's no need for SipHash's DoS resistance, and its cost would otherwise
+/// eat most of the memo's savings over hashing the string itself.
+#[derive(Default)]
+pub struct AddressHasher(u64);
+
+impl Hasher for AddressHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.write_u64(u64::from(b));
+        }
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        // FxHash's mixing step
+        self.0 = (self.0.rotate_left(5) ^ i).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
 }
@@ -0,0 +1,153 @@
+use crate::ser::compound::{Compound, Mode};
+use crate::{Error, Serializer};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::Serialize;
+use std::io::Write;
+
+/// A writer for an object whose fields are serialized one at a time.
+///
+/// Created by [`Serializer::begin_object`], [`ObjectWriter::nested_object`], and [`ArrayWriter::nested_object`].
+///
+/// The object is closed by [`ObjectWriter::end`]. If the writer is dropped without calling `end`, the object is still
+/// closed but any IO error is ignored.
+#[must_use = "the object should be closed with `end`"]
+pub struct ObjectWriter<'a, W>
+where
+    W: Write,
+{
+    ser: &'a mut Serializer<W>,
+    ended: bool,
+}
+
+impl<'a, W> ObjectWriter<'a, W>
+where
+    W: Write,
+{
+    pub(crate) fn new(ser: &'a mut Serializer<W>) -> Result<Self, Error> {
+        ser.enter_container()?;
+        ser.write_header()?;
+        ser.writer.write_all(&[0xfa]).map_err(Error::io)?;
+        Ok(ObjectWriter { ser, ended: false })
+    }
+
+    fn compound(&mut self) -> Compound<'_, W> {
+        Compound {
+            ser: &mut *self.ser,
+            mode: Mode::Normal,
+        }
+    }
+
+    /// Serializes a field of the object.
+    pub fn field<T>(&mut self, key: &str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.compound().serialize_entry(key, value)
+    }
+
+    /// Begins an object-valued field of the object.
+    ///
+    /// The nested object must be closed before more fields can be written to this one.
+    pub fn nested_object(&mut self, key: &str) -> Result<ObjectWriter<'_, W>, Error> {
+        self.compound().serialize_key(key)?;
+        ObjectWriter::new(&mut *self.ser)
+    }
+
+    /// Begins an array-valued field of the object.
+    ///
+    /// The nested array must be closed before more fields can be written to this object.
+    pub fn nested_array(&mut self, key: &str) -> Result<ArrayWriter<'_, W>, Error> {
+        self.compound().serialize_key(key)?;
+        ArrayWriter::new(&mut *self.ser)
+    }
+
+    /// Closes the object.
+    pub fn end(mut self) -> Result<(), Error> {
+        self.ended = true;
+        SerializeMap::end(self.compound())
+    }
+}
+
+impl<W> Drop for ObjectWriter<'_, W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if !self.ended {
+            let _ = SerializeMap::end(self.compound());
+        }
+    }
+}
+
+/// A writer for an array whose elements are serialized one at a time.
+///
+/// Created by [`Serializer::begin_array`], [`ObjectWriter::nested_array`], and [`ArrayWriter::nested_array`].
+///
+/// The array is closed by [`ArrayWriter::end`]. If the writer is dropped without calling `end`, the array is still
+/// closed but any IO error is ignored.
+#[must_use = "the array should be closed with `end`"]
+pub struct ArrayWriter<'a, W>
+where
+    W: Write,
+{
+    ser: &'a mut Serializer<W>,
+    ended: bool,
+}
+
+impl<'a, W> ArrayWriter<'a, W>
+where
+    W: Write,
+{
+    pub(crate) fn new(ser: &'a mut Serializer<W>) -> Result<Self, Error> {
+        ser.enter_container()?;
+        ser.write_header()?;
+        ser.writer.write_all(&[0xf8]).map_err(Error::io)?;
+        Ok(ArrayWriter { ser, ended: false })
+    }
+
+    fn compound(&mut self) -> Compound<'_, W> {
+        Compound {
+            ser: &mut *self.ser,
+            mode: Mode::Normal,
+        }
+    }
+
+    /// Serializes an element of the array.
+    pub fn element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.compound().serialize_element(value)
+    }
+
+    /// Begins an object element of the array.
+    ///
+    /// The nested object must be closed before more elements can be written to this array.
+    pub fn nested_object(&mut self) -> Result<ObjectWriter<'_, W>, Error> {
+        ObjectWriter::new(&mut *self.ser)
+    }
+
+    /// Begins an array element of the array.
+    ///
+    /// The nested array must be closed before more elements can be written to this one.
+    pub fn nested_array(&mut self) -> Result<ArrayWriter<'_, W>, Error> {
+        ArrayWriter::new(&mut *self.ser)
+    }
+
+    /// Closes the array.
+    pub fn end(mut self) -> Result<(), Error> {
+        self.ended = true;
+        SerializeSeq::end(self.compound())
+    }
+}
+
+impl<W> Drop for ArrayWriter<'_, W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if !self.ended {
+            let _ = SerializeSeq::end(self.compound());
+        }
+    }
+}
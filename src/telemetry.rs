@@ -0,0 +1,97 @@
+//! Metrics for the top-level serialization and deserialization functions.
+use crate::Error;
+use std::io::{self, Write};
+use std::time::Instant;
+
+pub(crate) const SERIALIZE_DURATION: &str = "serde_smile_serialize_duration_seconds";
+pub(crate) const SERIALIZE_BYTES: &str = "serde_smile_serialize_bytes";
+pub(crate) const SERIALIZE_ERRORS: &str = "serde_smile_serialize_errors_total";
+pub(crate) const DESERIALIZE_DURATION: &str = "serde_smile_deserialize_duration_seconds";
+pub(crate) const DESERIALIZE_BYTES: &str = "serde_smile_deserialize_bytes";
+pub(crate) const DESERIALIZE_ERRORS: &str = "serde_smile_deserialize_errors_total";
+
+/// Times a single top-level call.
+pub(crate) struct Timer(Instant);
+
+impl Timer {
+    #[inline]
+    pub(crate) fn start() -> Self {
+        Timer(Instant::now())
+    }
+
+    /// Records a call serializing into the `writer` kind of output, and the number of bytes written if it succeeded.
+    pub(crate) fn serialized(self, writer: &'static str, result: Result<u64, &Error>) {
+        let elapsed = self.0.elapsed().as_secs_f64();
+        metrics::histogram!(SERIALIZE_DURATION, "writer" => writer).record(elapsed);
+        match result {
+            Ok(bytes) => {
+                metrics::histogram!(SERIALIZE_BYTES, "writer" => writer).record(bytes as f64)
+            }
+            Err(e) => metrics::counter!(
+                SERIALIZE_ERRORS,
+                "writer" => writer,
+                "category" => e.category(),
+            )
+            .increment(1),
+        }
+    }
+
+    /// Records a call deserializing from the `reader` kind of input, and the number of bytes read if it succeeded.
+    pub(crate) fn deserialized(self, reader: &'static str, result: Result<u64, &Error>) {
+        let elapsed = self.0.elapsed().as_secs_f64();
+        metrics::histogram!(DESERIALIZE_DURATION, "reader" => reader).record(elapsed);
+        match result {
+            Ok(bytes) => {
+                metrics::histogram!(DESERIALIZE_BYTES, "reader" => reader).record(bytes as f64)
+            }
+            Err(e) => metrics::counter!(
+                DESERIALIZE_ERRORS,
+                "reader" => reader,
+                "category" => e.category(),
+            )
+            .increment(1),
+        }
+    }
+}
+
+/// A writer which counts the bytes written through it.
+pub(crate) struct CountingWriter<W> {
+    writer: W,
+    count: u64,
+}
+
+impl<W> CountingWriter<W> {
+    #[inline]
+    pub(crate) fn new(writer: W) -> Self {
+        CountingWriter { writer, count: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+impl<W> Write for CountingWriter<W>
+where
+    W: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.writer.write_all(buf)?;
+        self.count += buf.len() as u64;
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
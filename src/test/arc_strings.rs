@@ -0,0 +1,154 @@
+#![cfg(feature = "arc-strings")]
+
+use crate::ser::Builder;
+use crate::smile;
+use crate::value::{self, Value};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+
+fn shared<T>(value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    Builder::smallest_output().to_vec(value).unwrap()
+}
+
+fn strings(value: &Value) -> Vec<&Arc<str>> {
+    let mut out = vec![];
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::String(s) => out.push(s),
+            Value::Array(v) => stack.extend(v.iter().rev()),
+            Value::Object(v) => stack.extend(v.values().rev()),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[test]
+fn back_references_share_allocation() {
+    let buf = shared(&["info", "warn", "info", "info", "warn"]);
+
+    let value = value::from_slice_with_shared_strings(&buf).unwrap();
+    assert_eq!(value, smile!(["info", "warn", "info", "info", "warn"]));
+
+    let s = strings(&value);
+    // the first occurrence of each string is written inline
+    assert!(!Arc::ptr_eq(s[0], s[2]));
+    assert!(Arc::ptr_eq(s[2], s[3]));
+    assert!(!Arc::ptr_eq(s[1], s[4]));
+
+    let value = value::from_reader_with_shared_strings(&buf[..]).unwrap();
+    let s = strings(&value);
+    assert!(Arc::ptr_eq(s[2], s[3]));
+}
+
+#[test]
+fn plain_decoding_does_not_share() {
+    let buf = shared(&["info", "info", "info"]);
+
+    let value = crate::from_slice::<Value>(&buf).unwrap();
+    let s = strings(&value);
+    assert!(!Arc::ptr_eq(s[1], s[2]));
+}
+
+#[test]
+fn without_shared_strings() {
+    let buf = crate::to_vec(&["info", "info", "info"]).unwrap();
+
+    let value = value::from_slice_with_shared_strings(&buf).unwrap();
+    assert_eq!(value, smile!(["info", "info", "info"]));
+    let s = strings(&value);
+    assert!(!Arc::ptr_eq(s[1], s[2]));
+}
+
+#[test]
+fn keys_are_not_values() {
+    let buf = shared(&smile!([{"info": "info"}, {"info": "info"}]));
+
+    let value = value::from_slice_with_shared_strings(&buf).unwrap();
+    assert_eq!(value, smile!([{"info": "info"}, {"info": "info"}]));
+}
+
+#[test]
+fn reused_references() {
+    // the decoder's table is reset after 1024 strings, so references are reused for different strings
+    let mut elements = vec![];
+    for round in 0..3 {
+        for i in 0..1500 {
+            elements.push(format!("{}-{}", round, i % 700));
+        }
+    }
+    let buf = shared(&elements);
+
+    let value = value::from_slice_with_shared_strings(&buf).unwrap();
+    assert_eq!(value, crate::from_slice::<Value>(&buf).unwrap());
+}
+
+#[derive(Serialize)]
+struct LogRecord {
+    timestamp: u64,
+    level: &'static str,
+    logger: &'static str,
+    thread: String,
+    message: &'static str,
+}
+
+fn log_corpus() -> Vec<LogRecord> {
+    let levels = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+    let loggers = [
+        "com.example.http.RequestHandler",
+        "com.example.db.ConnectionPool",
+        "com.example.cache.Evictor",
+    ];
+    let messages = [
+        "request completed successfully",
+        "connection returned to pool",
+        "evicted expired entries",
+        "slow response from upstream",
+    ];
+
+    (0..10_000)
+        .map(|i| LogRecord {
+            timestamp: 1_634_567_890_000 + i,
+            level: levels[i as usize * 7 % levels.len()],
+            logger: loggers[i as usize % loggers.len()],
+            thread: format!("worker-{}", i % 16),
+            message: messages[i as usize * 3 % messages.len()],
+        })
+        .collect()
+}
+
+/// Returns the heap memory used by the strings of the value, counting each allocation once.
+fn string_memory(value: &Value) -> usize {
+    let mut allocations = HashMap::new();
+    for s in strings(value) {
+        allocations.insert(Arc::as_ptr(s), 2 * mem::size_of::<usize>() + s.len());
+    }
+    allocations.values().sum()
+}
+
+#[test]
+fn log_corpus_memory() {
+    let buf = shared(&log_corpus());
+
+    let plain = crate::from_slice::<Value>(&buf).unwrap();
+    let value = value::from_slice_with_shared_strings(&buf).unwrap();
+    assert_eq!(value, plain);
+
+    // 40,000 string values with 28 distinct contents decode into one allocation per inline occurrence and per
+    // back-reference table entry rather than one per value
+    assert_eq!(strings(&value).len(), 40_000);
+    let plain_memory = string_memory(&plain);
+    let shared_memory = string_memory(&value);
+    assert!(
+        shared_memory * 100 < plain_memory,
+        "{} vs {}",
+        shared_memory,
+        plain_memory
+    );
+}
@@ -0,0 +1,322 @@
+use crate::value::{BigDecimal, BigInteger, Value};
+
+fn run_test(unscaled: &[u8], scale: i32, expected: &[u8]) {
+    let value = BigDecimal::new(BigInteger::from_be_bytes(unscaled.to_vec()), scale);
+
+    let actual = crate::to_vec(&value).unwrap();
+    assert_eq!(&actual[4..], expected);
+
+    let actual = crate::from_slice::<BigDecimal>(&actual).unwrap();
+    assert_eq!(actual, value);
+}
+
+// The scale is written as a zigzag vint immediately after the 0x2a token, with no token of its own, followed by the
+// unscaled value as 7-bit encoded binary.
+#[test]
+fn raw_bytes() {
+    run_test(&[1], -5, b"\x2a\x89\x81\x00\x01");
+    run_test(&[0xcf, 0xc7], 0, b"\x2a\x80\x82\x67\x71\x03");
+    run_test(&[0x01, 0xe2, 0x40], 5, b"\x2a\x8a\x83\x00\x78\x48\x00");
+    run_test(&[1], 1_000_000, b"\x2a\x01\x74\x12\x80\x81\x00\x01");
+    run_test(&[1], i32::MAX, b"\x2a\x1f\x7f\x7f\x7f\xbe\x81\x00\x01");
+    run_test(&[1], i32::MIN, b"\x2a\x1f\x7f\x7f\x7f\xbf\x81\x00\x01");
+}
+
+#[test]
+fn scale_in_value() {
+    let buf = b":)\n\x00\xf8\x2a\x89\x81\x00\x01\x21\xf9";
+    let value = crate::from_slice::<Value>(buf).unwrap();
+    assert_eq!(
+        value,
+        Value::Array(vec![
+            Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), -5)),
+            Value::Null,
+        ])
+    );
+}
+
+#[test]
+fn scale_out_of_range() {
+    // a 5 byte vint can hold 34 bits
+    let buf = b":)\n\x00\x2a\x7f\x7f\x7f\x7f\xbf\x81\x00\x01";
+    let err = crate::from_slice::<BigDecimal>(buf).unwrap_err();
+    assert_eq!(err.to_string(), "BigDecimal scale out of range");
+}
+
+fn decimal(unscaled: i64, scale: i32) -> BigDecimal {
+    BigDecimal::new(BigInteger::from(unscaled), scale)
+}
+
+fn raw_decimal(unscaled: &[u8], scale: i32) -> BigDecimal {
+    BigDecimal::new(BigInteger::from_be_bytes(unscaled.to_vec()), scale)
+}
+
+#[test]
+fn normalized() {
+    let cases = [
+        (decimal(150, 2), decimal(15, 1)),
+        (decimal(-1500, 0), decimal(-15, -2)),
+        (decimal(15, 1), decimal(15, 1)),
+        (decimal(0, 7), decimal(0, 0)),
+        (decimal(0, -7), decimal(0, 0)),
+        (raw_decimal(&[0x00, 0x00, 0x64], 2), decimal(1, 0)),
+        (raw_decimal(&[0xff, 0x9c], 0), decimal(-1, -2)),
+        // the scale can't be reduced below i32::MIN
+        (decimal(1000, i32::MIN + 1), decimal(100, i32::MIN)),
+        (decimal(1000, i32::MAX), decimal(1, i32::MAX - 3)),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(value.normalized(), expected, "{:?}", value);
+        assert_eq!(value.normalized().normalized(), expected, "{:?}", value);
+    }
+
+    // 10^30 * 10^-30
+    let mut unscaled = "1".to_string();
+    unscaled.push_str(&"0".repeat(30));
+    let value = BigDecimal::new(unscaled.parse().unwrap(), 30);
+    assert_eq!(value.normalized(), decimal(1, 0));
+}
+
+#[test]
+fn numeric_eq() {
+    let equal = [
+        (decimal(150, 2), decimal(15, 1)),
+        (decimal(-150, 2), decimal(-15, 1)),
+        (decimal(0, 5), decimal(0, -5)),
+        (decimal(1, -3), decimal(1000, 0)),
+        (raw_decimal(&[0x00, 0x00, 0x01], 0), decimal(1, 0)),
+        (raw_decimal(&[0xff, 0xff], 1), decimal(-10, 2)),
+        (raw_decimal(&[], 3), decimal(0, 0)),
+        (decimal(10, i32::MIN + 1), decimal(1, i32::MIN)),
+    ];
+    for (a, b) in &equal {
+        assert!(a.numeric_eq(b), "{:?} {:?}", a, b);
+        assert!(b.numeric_eq(a), "{:?} {:?}", a, b);
+    }
+
+    let unequal = [
+        (decimal(15, 1), decimal(15, 2)),
+        (decimal(15, 1), decimal(-15, 1)),
+        (decimal(1, i32::MAX), decimal(0, 0)),
+        (decimal(1, i32::MAX), decimal(1, i32::MIN)),
+        (decimal(151, 2), decimal(15, 1)),
+    ];
+    for (a, b) in &unequal {
+        assert!(!a.numeric_eq(b), "{:?} {:?}", a, b);
+        assert!(!b.numeric_eq(a), "{:?} {:?}", a, b);
+    }
+}
+
+#[test]
+fn ordering() {
+    let sorted = [
+        decimal(-1, i32::MIN),
+        decimal(-1000, 0),
+        decimal(-151, 2),
+        decimal(-15, 1),
+        decimal(-150, 2),
+        decimal(-1, i32::MAX),
+        decimal(0, -3),
+        decimal(0, 0),
+        raw_decimal(&[0x00, 0x00], 0),
+        decimal(0, 3),
+        decimal(1, i32::MAX),
+        decimal(1, 2),
+        decimal(1, 1),
+        decimal(15, 1),
+        decimal(150, 2),
+        raw_decimal(&[0x00, 0x00, 0x96], 2),
+        decimal(151, 2),
+        decimal(1, -3),
+        decimal(99, -2),
+        decimal(1, i32::MIN),
+    ];
+
+    for (i, a) in sorted.iter().enumerate() {
+        for (j, b) in sorted.iter().enumerate() {
+            assert_eq!(a.cmp(b), i.cmp(&j), "{:?} {:?}", a, b);
+            assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)), "{:?} {:?}", a, b);
+        }
+    }
+
+    let mut shuffled = sorted.to_vec();
+    shuffled.reverse();
+    shuffled.sort();
+    assert_eq!(shuffled, sorted);
+}
+
+#[test]
+fn ordering_large_values() {
+    // 2^200 at scale 60 is roughly 1.6 and 10^61 at scale 60 is exactly 10
+    let mut magnitude = vec![1];
+    magnitude.extend_from_slice(&[0; 25]);
+    let a = raw_decimal(&magnitude, 60);
+    let b = BigDecimal::new(format!("1{}", "0".repeat(61)).parse().unwrap(), 60);
+    assert!(a < b);
+    assert!(a > decimal(16, 1));
+    assert!(a < decimal(17, 1));
+    assert!(b.numeric_eq(&decimal(10, 0)));
+    assert_eq!(b.normalized(), decimal(1, -1));
+}
+
+#[test]
+fn to_parts() {
+    assert_eq!(decimal(-150, 2).to_parts().unwrap(), (-150, 2));
+    assert_eq!(
+        raw_decimal(&[0xff, 0xff, 0xff], i32::MIN)
+            .to_parts()
+            .unwrap(),
+        (-1, i32::MIN)
+    );
+    assert_eq!(
+        BigDecimal::new(BigInteger::from(i128::MAX), 7)
+            .to_parts()
+            .unwrap(),
+        (i128::MAX, 7)
+    );
+
+    let e = BigDecimal::new(BigInteger::from(u128::MAX), 0)
+        .to_parts()
+        .unwrap_err();
+    assert_eq!(e.to_string(), "value out of range: expected i128");
+}
+
+/// Checks the exact expansion of an `f64`, abbreviating the digits of long unscaled values.
+fn check_from_f64(v: f64, len: usize, start: &str, end: &str, scale: i32) {
+    let decimal = BigDecimal::from_f64(v).unwrap();
+    let digits = decimal.unscaled_value().to_string();
+    assert_eq!(digits.trim_start_matches('-').len(), len, "{}", v);
+    assert!(digits.starts_with(start), "{} {}", v, digits);
+    assert!(digits.ends_with(end), "{} {}", v, digits);
+    assert_eq!(decimal.scale(), scale, "{}", v);
+    assert_eq!(decimal.to_f64().to_bits(), v.to_bits(), "{}", v);
+}
+
+#[test]
+fn from_f64() {
+    check_from_f64(
+        0.1,
+        55,
+        "1000000000000000055511",
+        "21181583404541015625",
+        55,
+    );
+    check_from_f64(
+        -123.456,
+        49,
+        "-123456000000000003069",
+        "36328029632568359375",
+        46,
+    );
+    check_from_f64(
+        1e308,
+        309,
+        "1000000000000000010979",
+        "74885715430223118336",
+        0,
+    );
+    check_from_f64(
+        f64::MAX,
+        309,
+        "1797693134862315708145",
+        "50404026184124858368",
+        0,
+    );
+    check_from_f64(
+        f64::MIN_POSITIVE,
+        715,
+        "2225073858507201383090",
+        "10924625396728515625",
+        1022,
+    );
+    // the smallest subnormal
+    check_from_f64(
+        5e-324,
+        751,
+        "4940656458412465441765",
+        "19718265533447265625",
+        1074,
+    );
+
+    assert_eq!(BigDecimal::from_f64(2.5).unwrap(), decimal(25, 1));
+    assert_eq!(BigDecimal::from_f64(-100.25).unwrap(), decimal(-10025, 2));
+    assert_eq!(
+        BigDecimal::from_f64(1e15).unwrap(),
+        decimal(1_000_000_000_000_000, 0)
+    );
+    assert_eq!(BigDecimal::from_f64(0.0).unwrap(), decimal(0, 0));
+    assert_eq!(BigDecimal::from_f64(-0.0).unwrap(), decimal(0, 0));
+
+    assert_eq!(BigDecimal::from_f64(f64::INFINITY), None);
+    assert_eq!(BigDecimal::from_f64(f64::NEG_INFINITY), None);
+    assert_eq!(BigDecimal::from_f64(f64::NAN), None);
+}
+
+#[test]
+fn f64_round_trip() {
+    // xorshift, to cover many bit patterns without a property testing dependency
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for i in 0..2000 {
+        let mut bits = next();
+        // make sure subnormals are covered
+        if i % 4 == 0 {
+            bits &= !(0x7ff << 52);
+        }
+        let v = f64::from_bits(bits);
+        if !v.is_finite() {
+            continue;
+        }
+
+        let decimal = BigDecimal::from_f64(v).unwrap();
+        assert_eq!(decimal.to_f64().to_bits(), v.to_bits(), "{:e}", v);
+        assert!(decimal.scale() >= 0 && decimal.scale() <= 1074, "{:e}", v);
+        // the scale of fractional values is minimal
+        if decimal.scale() > 0 {
+            assert_eq!(decimal.normalized(), decimal, "{:e}", v);
+        }
+    }
+}
+
+#[test]
+fn to_f64() {
+    assert_eq!(decimal(1, 1).to_f64(), 0.1);
+    assert_eq!(decimal(-25, 7).to_f64(), -2.5e-6);
+    assert_eq!(decimal(15, -300).to_f64(), 1.5e301);
+    assert_eq!(decimal(0, i32::MAX).to_f64(), 0.0);
+    assert_eq!(decimal(0, i32::MIN).to_f64(), 0.0);
+
+    // 2^53 + 1 is halfway between two f64s and rounds to even, while 2^53 + 3 rounds up
+    assert_eq!(
+        decimal(9_007_199_254_740_993, 0).to_f64(),
+        9_007_199_254_740_992.0
+    );
+    assert_eq!(
+        decimal(9_007_199_254_740_995, 0).to_f64(),
+        9_007_199_254_740_996.0
+    );
+    // digits beyond the 17th still affect rounding
+    let above_half = BigDecimal::new(
+        "90071992547409930000000000000000000000001".parse().unwrap(),
+        25,
+    );
+    assert_eq!(above_half.to_f64(), 9_007_199_254_740_994.0);
+
+    // out of range values saturate
+    assert_eq!(decimal(1, -400).to_f64(), f64::INFINITY);
+    assert_eq!(decimal(-1, -400).to_f64(), f64::NEG_INFINITY);
+    assert_eq!(decimal(1, i32::MIN).to_f64(), f64::INFINITY);
+    assert_eq!(decimal(1, 400).to_f64(), 0.0);
+    assert_eq!(decimal(-1, i32::MAX).to_f64(), -0.0);
+    assert!(decimal(-1, i32::MAX).to_f64().is_sign_negative());
+    // below half of the smallest subnormal
+    assert_eq!(decimal(24, 325).to_f64(), 0.0);
+    assert_eq!(decimal(25, 325).to_f64(), 5e-324);
+}
@@ -0,0 +1,490 @@
+use crate::value::BigInteger;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+fn serialized<T>(value: &T) -> Vec<u8>
+where
+    T: Serialize + ?Sized,
+{
+    crate::to_vec(value).unwrap()
+}
+
+#[test]
+fn minimal_encodings() {
+    assert_eq!(BigInteger::from(0i64).as_be_bytes(), [0x00]);
+    assert_eq!(BigInteger::from(1i64).as_be_bytes(), [0x01]);
+    assert_eq!(BigInteger::from(-1i64).as_be_bytes(), [0xff]);
+    assert_eq!(BigInteger::from(127i64).as_be_bytes(), [0x7f]);
+    assert_eq!(BigInteger::from(128i64).as_be_bytes(), [0x00, 0x80]);
+    assert_eq!(BigInteger::from(-128i64).as_be_bytes(), [0x80]);
+    assert_eq!(BigInteger::from(-129i64).as_be_bytes(), [0xff, 0x7f]);
+    assert_eq!(BigInteger::from(255u64).as_be_bytes(), [0x00, 0xff]);
+    assert_eq!(BigInteger::from(256u64).as_be_bytes(), [0x01, 0x00]);
+    assert_eq!(
+        BigInteger::from(i64::MIN).as_be_bytes(),
+        [0x80, 0, 0, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        BigInteger::from(u64::MAX).as_be_bytes(),
+        [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_eq!(BigInteger::from(0u128).as_be_bytes(), [0x00]);
+    assert_eq!(BigInteger::from(-128i128).as_be_bytes(), [0x80]);
+    assert_eq!(
+        BigInteger::from(i128::MIN).into_be_bytes(),
+        i128::MIN.to_be_bytes()
+    );
+
+    let mut expected = vec![0x00];
+    expected.extend_from_slice(&u128::MAX.to_be_bytes());
+    assert_eq!(BigInteger::from(u128::MAX).into_be_bytes(), expected);
+}
+
+#[test]
+fn same_width_types_agree() {
+    for v in [0, 1, 127, 128, 255, 256, i64::MAX as u64, u64::MAX] {
+        assert_eq!(
+            BigInteger::from(v),
+            BigInteger::from(u128::from(v)),
+            "{}",
+            v
+        );
+    }
+    for v in [0, -1, -128, -129, 128, i64::MIN, i64::MAX] {
+        assert_eq!(
+            BigInteger::from(v),
+            BigInteger::from(i128::from(v)),
+            "{}",
+            v
+        );
+    }
+}
+
+// The serializer only falls back to a BigInteger for values outside of the range of an i64.
+#[test]
+fn matches_serializer() {
+    assert_eq!(
+        serialized(&BigInteger::from(u64::MAX)),
+        serialized(&u64::MAX)
+    );
+
+    for v in [
+        i128::from(i64::MAX) + 1,
+        i128::from(i64::MIN) - 1,
+        i128::from(u64::MAX),
+        1 << 100,
+        -(1 << 100),
+        i128::MIN,
+        i128::MAX,
+    ] {
+        assert_eq!(serialized(&BigInteger::from(v)), serialized(&v), "{}", v);
+    }
+
+    for v in [u128::from(u64::MAX) + 1, u128::MAX] {
+        assert_eq!(serialized(&BigInteger::from(v)), serialized(&v), "{}", v);
+    }
+}
+
+#[test]
+fn round_trip() {
+    for v in [0, -1, -128, 128, i128::from(u64::MAX), i128::MIN, i128::MAX] {
+        let buf = serialized(&BigInteger::from(v));
+        assert_eq!(crate::from_slice::<i128>(&buf).unwrap(), v);
+    }
+}
+
+#[test]
+fn native_round_trip() {
+    for v in [0, 1, -1, 127, 128, -128, -129, i64::MIN, i64::MAX] {
+        let big = BigInteger::from(v);
+        assert_eq!(big.to_i64(), Some(v));
+        assert_eq!(big.to_i128(), Some(i128::from(v)));
+        assert_eq!(i64::try_from(&big).unwrap(), v);
+    }
+    for v in [0, 255, u64::from(u32::MAX) + 1, u64::MAX] {
+        let big = BigInteger::from(v);
+        assert_eq!(big.to_u64(), Some(v));
+        assert_eq!(big.to_u128(), Some(u128::from(v)));
+        assert_eq!(u64::try_from(big).unwrap(), v);
+    }
+    for v in [i128::MIN, i128::MAX, -(1 << 100)] {
+        assert_eq!(BigInteger::from(v).to_i128(), Some(v));
+    }
+    assert_eq!(BigInteger::from(u128::MAX).to_u128(), Some(u128::MAX));
+}
+
+#[test]
+fn native_out_of_range() {
+    assert_eq!(BigInteger::from(u64::MAX).to_i64(), None);
+    assert_eq!(BigInteger::from(-1i64).to_u64(), None);
+    assert_eq!(BigInteger::from(-1i64).to_u128(), None);
+    assert_eq!(BigInteger::from(i128::from(i64::MIN) - 1).to_i64(), None);
+    assert_eq!(BigInteger::from(u128::from(u64::MAX) + 1).to_u64(), None);
+    assert_eq!(BigInteger::from(u128::MAX).to_i128(), None);
+    assert_eq!(BigInteger::from(i128::MIN).to_u128(), None);
+
+    let mut buf = vec![0x01];
+    buf.extend_from_slice(&[0; 16]);
+    let big = BigInteger::from_be_bytes(buf);
+    assert_eq!(big.to_u128(), None);
+    assert_eq!(big.to_i128(), None);
+
+    assert_eq!(BigInteger::from_be_bytes(vec![]).to_i64(), None);
+
+    let e = i64::try_from(&BigInteger::from(u64::MAX)).unwrap_err();
+    assert_eq!(e.to_string(), "value out of range: expected i64");
+    let e = u128::try_from(BigInteger::from(-1i64)).unwrap_err();
+    assert_eq!(e.to_string(), "value out of range: expected u128");
+}
+
+#[test]
+fn native_non_minimal() {
+    let big = BigInteger::from_be_bytes(vec![0x00, 0x00, 0x00, 0x01]);
+    assert_eq!(big.to_i64(), Some(1));
+    assert_eq!(big.to_u64(), Some(1));
+
+    let big = BigInteger::from_be_bytes(vec![0xff; 20]);
+    assert_eq!(big.to_i64(), Some(-1));
+    assert_eq!(big.to_i128(), Some(-1));
+    assert_eq!(big.to_u64(), None);
+
+    let mut buf = vec![0x00; 4];
+    buf.extend_from_slice(&u64::MAX.to_be_bytes());
+    let big = BigInteger::from_be_bytes(buf);
+    assert_eq!(big.to_u64(), Some(u64::MAX));
+    assert_eq!(big.to_i64(), None);
+
+    let mut buf = vec![0x00; 3];
+    buf.extend_from_slice(&u128::MAX.to_be_bytes());
+    assert_eq!(BigInteger::from_be_bytes(buf).to_u128(), Some(u128::MAX));
+
+    let mut buf = vec![0xff; 3];
+    buf.extend_from_slice(&i128::MIN.to_be_bytes());
+    assert_eq!(BigInteger::from_be_bytes(buf).to_i128(), Some(i128::MIN));
+}
+
+#[test]
+fn native_from_deserializer() {
+    for v in [u128::from(u64::MAX), u128::MAX] {
+        let buf = serialized(&v);
+        let big = crate::from_slice::<BigInteger>(&buf).unwrap();
+        assert_eq!(big.to_u128(), Some(v));
+    }
+}
+
+const TWO_POW_1000: &str = "10715086071862673209484250490600018105614048117055336074437503883703510511249361224931983788156958581275946729175531468251871452856923140435984577574698574803934567774824230985421074605062371141877954182153046474983581941267398767559165543946077062914571196477686542167660429831652624386837205668069376";
+const NEG_THREE_POW_500: &str = "-36360291795869936842385267079543319118023385026001623040346035832580600191583895484198508262979388783308179702534403855752855931517013066142992430916562025780021771247847643450125342836565813209972590371590152578728008385990139795377610001";
+const NEG_THREE_POW_500_BYTES: &str = "fe9aa2d31fa9c089fb1bc7ac915742e78e8572be45bb4c9ff8038e401120722559e97433ad09ef937df93ed6c08a9fd78a5d7428ef9821389707a848778960182f8a7f1a1d6503026e604658fca8267cf1ef9573a5fc35adc68b839830f08e24c82622ef";
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn display() {
+    assert_eq!(BigInteger::from(0i64).to_string(), "0");
+    assert_eq!(BigInteger::from(-1i64).to_string(), "-1");
+    assert_eq!(
+        BigInteger::from(i128::MIN).to_string(),
+        i128::MIN.to_string()
+    );
+    assert_eq!(
+        BigInteger::from(u128::MAX).to_string(),
+        u128::MAX.to_string()
+    );
+    assert_eq!(BigInteger::from_be_bytes(vec![]).to_string(), "0");
+    assert_eq!(
+        BigInteger::from_be_bytes(vec![0xff, 0xff, 0x80]).to_string(),
+        "-128"
+    );
+    assert_eq!(format!("{:>6}", BigInteger::from(-42i64)), "   -42");
+    assert_eq!(format!("{:+}", BigInteger::from(42i64)), "+42");
+
+    let mut buf = vec![0x01];
+    buf.extend_from_slice(&[0; 125]);
+    assert_eq!(BigInteger::from_be_bytes(buf).to_string(), TWO_POW_1000);
+
+    let v = BigInteger::from_be_bytes(hex(NEG_THREE_POW_500_BYTES));
+    assert_eq!(v.to_string(), NEG_THREE_POW_500);
+}
+
+#[test]
+fn from_str() {
+    let mut buf = vec![0x01];
+    buf.extend_from_slice(&[0; 125]);
+    assert_eq!(
+        BigInteger::from_str(TWO_POW_1000).unwrap().into_be_bytes(),
+        buf
+    );
+    assert_eq!(
+        BigInteger::from_str(NEG_THREE_POW_500)
+            .unwrap()
+            .into_be_bytes(),
+        hex(NEG_THREE_POW_500_BYTES)
+    );
+
+    assert_eq!(BigInteger::from_str("0").unwrap(), BigInteger::from(0i64));
+    assert_eq!(BigInteger::from_str("-0").unwrap(), BigInteger::from(0i64));
+    assert_eq!(
+        BigInteger::from_str("000128").unwrap(),
+        BigInteger::from(128i64)
+    );
+    assert_eq!(
+        BigInteger::from_str("-128").unwrap(),
+        BigInteger::from(-128i64)
+    );
+    assert_eq!(
+        BigInteger::from_str("-1000000000").unwrap(),
+        BigInteger::from(-1_000_000_000i64)
+    );
+
+    for s in ["", "-", "+1", "1.0", " 1", "1 ", "--1", "1e3", "\u{0661}"] {
+        let e = BigInteger::from_str(s).unwrap_err();
+        assert_eq!(e.to_string(), "invalid big integer string", "{:?}", s);
+    }
+}
+
+#[test]
+fn powers_of_two() {
+    // doubles a decimal string independently of BigInteger's arithmetic
+    fn double(digits: &str) -> String {
+        let mut out = vec![];
+        let mut carry = 0;
+        for b in digits.bytes().rev() {
+            let cur = (b - b'0') * 2 + carry;
+            out.push(b'0' + cur % 10);
+            carry = cur / 10;
+        }
+        if carry != 0 {
+            out.push(b'0' + carry);
+        }
+        out.reverse();
+        String::from_utf8(out).unwrap()
+    }
+
+    let mut expected = "1".to_string();
+    for i in 0..1000 {
+        let mut buf = vec![0; i / 8 + 2];
+        buf[1] = 1 << (i % 8);
+        let v = BigInteger::from_be_bytes(buf);
+        assert_eq!(v.to_string(), expected, "2^{}", i);
+        assert_eq!(
+            BigInteger::from_str(&format!("-{}", expected))
+                .unwrap()
+                .to_string(),
+            format!("-{}", expected),
+            "-2^{}",
+            i
+        );
+
+        expected = double(&expected);
+    }
+    assert_eq!(expected, TWO_POW_1000);
+}
+
+#[test]
+fn decimal_round_trip() {
+    // xorshift, to cover many buffers without a property testing dependency
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..2000 {
+        let len = next() % 64 + 1;
+        let buf = (0..len).map(|_| next() as u8).collect::<Vec<_>>();
+        let v = BigInteger::from_be_bytes(buf);
+
+        let parsed = BigInteger::from_str(&v.to_string()).unwrap();
+        assert_eq!(parsed.to_string(), v.to_string());
+        assert_eq!(
+            parsed.as_be_bytes(),
+            crate::value::big_integer::strip_sign_extension(v.as_be_bytes())
+        );
+
+        let n = i128::from(next() as i64) << (next() % 64);
+        assert_eq!(BigInteger::from(n).to_string(), n.to_string());
+        assert_eq!(
+            BigInteger::from_str(&n.to_string()).unwrap(),
+            BigInteger::from(n)
+        );
+    }
+}
+
+fn padded(v: i128, extra: usize) -> BigInteger {
+    let minimal = BigInteger::from(v).into_be_bytes();
+    let extension = if v < 0 { 0xff } else { 0x00 };
+    let mut buf = vec![extension; extra];
+    buf.extend_from_slice(&minimal);
+    BigInteger::from_be_bytes(buf)
+}
+
+#[test]
+fn numeric_eq() {
+    for v in [0, 1, -1, 127, 128, -128, -129, i128::MIN, i128::MAX] {
+        for extra in 0..4 {
+            let a = padded(v, extra);
+            assert!(a.numeric_eq(&BigInteger::from(v)), "{} {}", v, extra);
+            assert_eq!(a == BigInteger::from(v), extra == 0, "{} {}", v, extra);
+        }
+        assert!(!padded(v, 2).numeric_eq(&padded(v.wrapping_add(1), 1)));
+    }
+
+    assert!(BigInteger::from_be_bytes(vec![]).numeric_eq(&BigInteger::from(0i64)));
+    // 0x80 is -128, not 128
+    assert!(!BigInteger::from_be_bytes(vec![0x80]).numeric_eq(&BigInteger::from(128i64)));
+}
+
+#[test]
+fn ordering() {
+    let values = [
+        i128::MIN,
+        i128::from(i64::MIN) - 1,
+        -65536,
+        -256,
+        -129,
+        -128,
+        -1,
+        0,
+        1,
+        127,
+        128,
+        255,
+        256,
+        65536,
+        i128::from(u64::MAX),
+        i128::MAX,
+    ];
+
+    for (i, &a) in values.iter().enumerate() {
+        for (j, &b) in values.iter().enumerate() {
+            for (ea, eb) in [(0, 0), (0, 3), (3, 0), (1, 2)] {
+                let x = padded(a, ea);
+                let y = padded(b, eb);
+                let expected = i.cmp(&j).then(ea.cmp(&eb));
+                assert_eq!(x.cmp(&y), expected, "{}+{} vs {}+{}", a, ea, b, eb);
+                assert_eq!(x.partial_cmp(&y), Some(expected));
+            }
+        }
+    }
+
+    let mut sorted = vec![
+        padded(256, 2),
+        BigInteger::from(u128::MAX),
+        padded(-1, 5),
+        BigInteger::from(-1i64),
+        padded(0, 1),
+        BigInteger::from(i128::MIN),
+    ];
+    sorted.sort();
+    assert_eq!(
+        sorted,
+        [
+            BigInteger::from(i128::MIN),
+            BigInteger::from(-1i64),
+            padded(-1, 5),
+            padded(0, 1),
+            padded(256, 2),
+            BigInteger::from(u128::MAX),
+        ]
+    );
+}
+
+#[test]
+fn normalize() {
+    let cases: &[(&[u8], &[u8])] = &[
+        (&[], &[0x00]),
+        (&[0x00], &[0x00]),
+        (&[0x00, 0x00, 0x00], &[0x00]),
+        (&[0x00, 0x01], &[0x01]),
+        (&[0x00, 0x7f], &[0x7f]),
+        // positive values with the high bit set need a leading zero byte
+        (&[0x00, 0x80], &[0x00, 0x80]),
+        (&[0x00, 0x00, 0x00, 0xff], &[0x00, 0xff]),
+        (
+            &[0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            &[0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+        (&[0xff], &[0xff]),
+        (&[0xff, 0xff, 0xff], &[0xff]),
+        (&[0xff, 0x80], &[0x80]),
+        // negative values with the high bit clear need a leading 0xff byte
+        (&[0xff, 0x7f], &[0xff, 0x7f]),
+        (&[0xff, 0xff, 0x7f, 0xff], &[0xff, 0x7f, 0xff]),
+        (
+            &[0xff, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            &[0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        ),
+    ];
+
+    for (buf, expected) in cases {
+        let v = BigInteger::from_be_bytes(buf.to_vec());
+        assert_eq!(v.normalized().as_be_bytes(), *expected, "{:?}", buf);
+
+        let mut normalized = v.clone();
+        normalized.normalize();
+        assert_eq!(normalized.as_be_bytes(), *expected, "{:?}", buf);
+        assert!(normalized.numeric_eq(&v), "{:?}", buf);
+    }
+}
+
+#[test]
+fn normalize_matches_native() {
+    for v in [0, 1, -1, 127, 128, 255, -128, -129, i128::MIN, i128::MAX] {
+        for extra in 0..4 {
+            let padded = padded(v, extra);
+            let normalized = padded.normalized();
+            assert_eq!(normalized, BigInteger::from(v), "{} {}", v, extra);
+            assert_eq!(
+                serialized(&normalized),
+                serialized(&BigInteger::from(v)),
+                "{} {}",
+                v,
+                extra
+            );
+            if extra > 0 {
+                assert!(serialized(&normalized).len() < serialized(&padded).len());
+            }
+        }
+    }
+}
+
+#[test]
+fn sign() {
+    for (v, sign) in [
+        (0, Ordering::Equal),
+        (1, Ordering::Greater),
+        (128, Ordering::Greater),
+        (i128::MAX, Ordering::Greater),
+        (-1, Ordering::Less),
+        (-129, Ordering::Less),
+        (i128::MIN, Ordering::Less),
+    ] {
+        for extra in 0..3 {
+            let v = padded(v, extra);
+            assert_eq!(v.sign(), sign, "{:?}", v);
+            assert_eq!(v.is_zero(), sign == Ordering::Equal, "{:?}", v);
+            assert_eq!(v.is_negative(), sign == Ordering::Less, "{:?}", v);
+        }
+    }
+
+    let empty = BigInteger::from_be_bytes(vec![]);
+    assert_eq!(empty.sign(), Ordering::Equal);
+    assert!(empty.is_zero());
+    assert!(!empty.is_negative());
+
+    // 0x80 is -128, not 128
+    assert_eq!(BigInteger::from_be_bytes(vec![0x80]).sign(), Ordering::Less);
+    assert_eq!(
+        BigInteger::from_be_bytes(vec![0x00, 0x00, 0x80]).sign(),
+        Ordering::Greater
+    );
+}
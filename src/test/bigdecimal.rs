@@ -0,0 +1,71 @@
+#![cfg(feature = "bigdecimal")]
+
+use crate::value::{BigDecimal, BigInteger};
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+fn check(s: &str, unscaled: i128, scale: i32) {
+    let v = bigdecimal::BigDecimal::from_str(s).unwrap();
+
+    let big_decimal = BigDecimal::try_from(&v).unwrap();
+    assert_eq!(
+        big_decimal,
+        BigDecimal::new(BigInteger::from(unscaled), scale),
+        "{}",
+        s
+    );
+
+    let buf = crate::to_vec(&big_decimal).unwrap();
+    let decoded = crate::from_slice::<BigDecimal>(&buf).unwrap();
+    let round_tripped = bigdecimal::BigDecimal::from(decoded);
+    assert_eq!(round_tripped, v, "{}", s);
+    assert_eq!(
+        round_tripped.as_bigint_and_exponent(),
+        v.as_bigint_and_exponent(),
+        "{}",
+        s
+    );
+}
+
+#[test]
+fn decimal_strings() {
+    check("0", 0, 0);
+    check("1", 1, 0);
+    check("-1", -1, 0);
+    check("1234.56", 123_456, 2);
+    check("-0.000123", -123, 6);
+    check("0.10", 10, 2);
+    check("1e+40", 1, -40);
+    check("-2.5E-3", -25, 4);
+    check("170141183460469231731687303715884105727", i128::MAX, 0);
+}
+
+#[test]
+fn large_unscaled_value() {
+    let unscaled = BigInt::from(u128::MAX) * BigInt::from(u128::MAX);
+    let v = bigdecimal::BigDecimal::new(unscaled.clone(), 17);
+
+    let big_decimal = BigDecimal::try_from(v.clone()).unwrap();
+    assert_eq!(BigInt::from(big_decimal.unscaled_value()), unscaled);
+    assert_eq!(big_decimal.scale(), 17);
+    assert_eq!(bigdecimal::BigDecimal::from(big_decimal), v);
+}
+
+#[test]
+fn scale_limits() {
+    let v = bigdecimal::BigDecimal::new(BigInt::from(1), i64::from(i32::MAX));
+    assert_eq!(BigDecimal::try_from(&v).unwrap().scale(), i32::MAX);
+
+    let v = bigdecimal::BigDecimal::new(BigInt::from(1), i64::from(i32::MIN));
+    assert_eq!(BigDecimal::try_from(&v).unwrap().scale(), i32::MIN);
+}
+
+#[test]
+fn scale_overflow() {
+    for scale in [i64::from(i32::MAX) + 1, i64::from(i32::MIN) - 1] {
+        let v = bigdecimal::BigDecimal::new(BigInt::from(1), scale);
+        let e = BigDecimal::try_from(v).unwrap_err();
+        assert_eq!(e.to_string(), "value out of range: expected i32 scale");
+    }
+}
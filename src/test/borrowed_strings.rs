@@ -0,0 +1,68 @@
+use crate::ser::Builder;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+fn log_corpus() -> Vec<u8> {
+    #[derive(Serialize)]
+    struct Entry {
+        level: &'static str,
+        target: &'static str,
+        message: String,
+    }
+
+    let levels = ["INFO", "WARN", "ERROR", "DEBUG"];
+    let targets = ["server::http", "server::db", "worker::queue"];
+    let entries = (0..500)
+        .map(|i| Entry {
+            level: levels[i % levels.len()],
+            target: targets[i % targets.len()],
+            message: format!("request {} completed", i % 10),
+        })
+        .collect::<Vec<_>>();
+
+    let mut ser = Builder::jackson_defaults()
+        .shared_strings(true)
+        .build(vec![]);
+    entries.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn assert_borrowed(range: &Range<*const u8>, s: &str) {
+    let ptr = s.as_ptr();
+    assert!(
+        range.start <= ptr && ptr < range.end,
+        "{:?} was not borrowed from the input",
+        s,
+    );
+}
+
+type Entries<'a> = Vec<BTreeMap<&'a str, &'a str>>;
+
+fn check(entries: &Entries<'_>, range: Range<*const u8>) {
+    assert_eq!(entries.len(), 500);
+    for entry in entries {
+        assert_eq!(entry.len(), 3);
+        for (key, value) in entry {
+            assert_borrowed(&range, key);
+            assert_borrowed(&range, value);
+        }
+    }
+}
+
+// Both the first occurrence of a string and any later back-references to it are borrowed directly from the input
+// when deserializing from a slice, so no strings are allocated.
+#[test]
+fn slice() {
+    let buf = log_corpus();
+    let entries = crate::from_slice::<Entries<'_>>(&buf).unwrap();
+    check(&entries, buf.as_ptr_range());
+}
+
+#[test]
+fn mut_slice() {
+    let mut buf = log_corpus();
+    let range = buf.as_ptr_range();
+    let entries = crate::from_mut_slice::<Entries<'_>>(&mut buf).unwrap();
+    check(&entries, range);
+}
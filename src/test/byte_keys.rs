@@ -0,0 +1,170 @@
+use crate::de::{ByteKeyPolicy, SliceRead};
+use crate::value::Value;
+use crate::{Deserializer, Serializer};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+fn serialize<T>(policy: ByteKeyPolicy, value: &T) -> Result<Vec<u8>, crate::Error>
+where
+    T: Serialize,
+{
+    let mut ser = Serializer::builder().byte_keys(policy).build(vec![]);
+    value.serialize(&mut ser)?;
+    Ok(ser.into_inner())
+}
+
+fn deserialize<T>(policy: ByteKeyPolicy, buf: &[u8]) -> Result<T, crate::Error>
+where
+    T: DeserializeOwned,
+{
+    let mut de = Deserializer::builder()
+        .byte_keys(policy)
+        .build(SliceRead::new(buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+fn keys(buf: &[u8]) -> Vec<String> {
+    match crate::from_slice(buf).unwrap() {
+        Value::Object(map) => map.into_iter().map(|(k, _)| k).collect(),
+        v => panic!("unexpected value {:?}", v),
+    }
+}
+
+fn round_trip<T>(policy: ByteKeyPolicy, value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let buf = serialize(policy, value).unwrap();
+    let actual = deserialize::<T>(policy, &buf).unwrap();
+    assert_eq!(*value, actual);
+}
+
+fn array_map() -> HashMap<[u8; 20], i32> {
+    (0..10u8)
+        .map(|i| ([i.wrapping_mul(37); 20], i32::from(i)))
+        .collect()
+}
+
+fn byte_buf_map() -> HashMap<ByteBuf, i32> {
+    (0..10u8)
+        .map(|i| (ByteBuf::from((0..i).collect::<Vec<_>>()), i32::from(i)))
+        .collect()
+}
+
+#[test]
+fn hex_round_trip() {
+    round_trip(ByteKeyPolicy::Hex, &array_map());
+    round_trip(ByteKeyPolicy::Hex, &byte_buf_map());
+}
+
+#[test]
+fn base64_round_trip() {
+    round_trip(ByteKeyPolicy::Base64, &array_map());
+    round_trip(ByteKeyPolicy::Base64, &byte_buf_map());
+}
+
+#[test]
+fn hex_encoding() {
+    let map = HashMap::from([(ByteBuf::from(vec![0x01, 0xab, 0xff]), 0)]);
+    let buf = serialize(ByteKeyPolicy::Hex, &map).unwrap();
+    assert_eq!(keys(&buf), ["01abff"]);
+
+    let map = HashMap::from([([0xfe_u8, 0x10], 0)]);
+    let buf = serialize(ByteKeyPolicy::Hex, &map).unwrap();
+    assert_eq!(keys(&buf), ["fe10"]);
+}
+
+#[test]
+fn base64_encoding() {
+    let map = HashMap::from([(ByteBuf::from(b"hello".to_vec()), 0)]);
+    let buf = serialize(ByteKeyPolicy::Base64, &map).unwrap();
+    assert_eq!(keys(&buf), ["aGVsbG8="]);
+
+    let map = HashMap::from([([0xfb_u8, 0xff], 0)]);
+    let buf = serialize(ByteKeyPolicy::Base64, &map).unwrap();
+    assert_eq!(keys(&buf), ["+/8="]);
+}
+
+#[test]
+fn reject_serialize() {
+    let err = serialize(ByteKeyPolicy::Reject, &array_map()).unwrap_err();
+    assert_eq!(err.to_string(), "key must be a string");
+
+    let err = serialize(ByteKeyPolicy::Reject, &byte_buf_map()).unwrap_err();
+    assert_eq!(err.to_string(), "key must be a string");
+}
+
+#[test]
+fn non_byte_sequence_key() {
+    let map = HashMap::from([(vec![1u16, 2], 0)]);
+    let err = serialize(ByteKeyPolicy::Hex, &map).unwrap_err();
+    assert_eq!(err.to_string(), "key must be a string");
+
+    let map = HashMap::from([(vec![String::new()], 0)]);
+    let err = serialize(ByteKeyPolicy::Base64, &map).unwrap_err();
+    assert_eq!(err.to_string(), "key must be a string");
+}
+
+#[test]
+fn reject_deserialize_array() {
+    let buf = serialize(ByteKeyPolicy::Hex, &array_map()).unwrap();
+    deserialize::<HashMap<[u8; 20], i32>>(ByteKeyPolicy::Reject, &buf).unwrap_err();
+}
+
+#[test]
+fn reject_deserialize_byte_buf() {
+    // without a policy, keys are passed through as strings
+    let map = HashMap::from([("abc".to_string(), 0)]);
+    let buf = crate::to_vec(&map).unwrap();
+    let actual = deserialize::<HashMap<ByteBuf, i32>>(ByteKeyPolicy::Reject, &buf).unwrap();
+    assert_eq!(actual, HashMap::from([(ByteBuf::from(b"abc".to_vec()), 0)]));
+}
+
+#[test]
+fn hex_to_base64_mismatch() {
+    // 40 hex characters decode as 30 bytes of base64
+    let buf = serialize(ByteKeyPolicy::Hex, &array_map()).unwrap();
+    deserialize::<HashMap<[u8; 20], i32>>(ByteKeyPolicy::Base64, &buf).unwrap_err();
+}
+
+#[test]
+fn base64_to_hex_mismatch() {
+    let buf = serialize(ByteKeyPolicy::Base64, &array_map()).unwrap();
+    let err = deserialize::<HashMap<[u8; 20], i32>>(ByteKeyPolicy::Hex, &buf).unwrap_err();
+    assert_eq!(err.to_string(), "invalid hex byte key");
+
+    let buf = serialize(ByteKeyPolicy::Base64, &byte_buf_map()).unwrap();
+    let err = deserialize::<HashMap<ByteBuf, i32>>(ByteKeyPolicy::Hex, &buf).unwrap_err();
+    assert_eq!(err.to_string(), "invalid hex byte key");
+}
+
+#[test]
+fn invalid_base64() {
+    for key in ["a", "ab=c", "a===", "YQ", "YR==", "Y*=="] {
+        let map = HashMap::from([(key, 0)]);
+        let buf = crate::to_vec(&map).unwrap();
+        let err = deserialize::<HashMap<ByteBuf, i32>>(ByteKeyPolicy::Base64, &buf).unwrap_err();
+        assert_eq!(err.to_string(), "invalid base64 byte key", "{}", key);
+    }
+}
+
+#[test]
+fn string_keys_unaffected() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Foo {
+        a: HashMap<String, i32>,
+        b: HashMap<i32, i32>,
+    }
+
+    let value = Foo {
+        a: HashMap::from([("xyz".to_string(), 1)]),
+        b: HashMap::from([(5, 6)]),
+    };
+    round_trip(ByteKeyPolicy::Hex, &value);
+    round_trip(ByteKeyPolicy::Base64, &value);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "bytes")]
+
+use crate::value::{self, Value};
+use crate::{smile, Serializer};
+use bytes::Bytes;
+
+fn serialize(raw_binary: bool, value: &Value) -> Bytes {
+    let buf = Serializer::builder()
+        .raw_binary(raw_binary)
+        .to_vec(value)
+        .unwrap();
+    Bytes::from(buf)
+}
+
+fn within(outer: &[u8], inner: &[u8]) -> bool {
+    let outer = outer.as_ptr_range();
+    let inner = inner.as_ptr_range();
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+fn blob(value: &Value) -> &Bytes {
+    match value {
+        Value::Binary(v) => v,
+        _ => panic!("expected binary: {:?}", value),
+    }
+}
+
+#[test]
+fn clone_shares_storage() {
+    let value = Value::binary(vec![1; 1024]);
+    let clone = value.clone();
+    assert_eq!(blob(&value).as_ptr(), blob(&clone).as_ptr());
+}
+
+#[test]
+fn raw_binary_is_shared() {
+    let value = smile!({
+        "blob": (Value::binary(vec![0xfd; 1024])),
+        "nested": [(Value::binary(vec![1, 2, 3]))],
+    });
+    let buf = serialize(true, &value);
+
+    let decoded = value::from_bytes(&buf).unwrap();
+    assert_eq!(decoded, value);
+    assert!(within(&buf, blob(&decoded["blob"])));
+    assert!(within(&buf, blob(&decoded["nested"][0])));
+}
+
+#[test]
+fn seven_bit_binary_is_copied() {
+    let value = smile!([(Value::binary(vec![0xfd; 1024]))]);
+    let buf = serialize(false, &value);
+
+    let decoded = value::from_bytes(&buf).unwrap();
+    assert_eq!(decoded, value);
+    assert!(!within(&buf, blob(&decoded[0])));
+}
+
+#[test]
+fn other_sources_copy() {
+    let value = smile!([(Value::binary(vec![1, 2, 3]))]);
+    let buf = serialize(true, &value);
+
+    let decoded = crate::from_slice::<Value>(&buf).unwrap();
+    assert_eq!(decoded, value);
+    assert!(!within(&buf, blob(&decoded[0])));
+}
+
+#[test]
+fn errors() {
+    let buf = serialize(true, &Value::binary(vec![1, 2, 3]));
+    value::from_bytes(&buf.slice(..buf.len() - 1)).unwrap_err();
+
+    let mut trailing = buf.to_vec();
+    trailing.push(0x21);
+    value::from_bytes(&Bytes::from(trailing)).unwrap_err();
+}
+
+#[test]
+fn value_conversions() {
+    let value = Value::binary(vec![1, 2, 3]);
+    assert_eq!(value::to_value(&value).unwrap(), value);
+    assert_eq!(
+        value::from_value::<serde_bytes::ByteBuf>(value).unwrap(),
+        [1, 2, 3]
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    let value = smile!([(Value::binary(vec![1, 2, 3]))]);
+    assert_eq!(
+        value.to_json_value(value::JsonBinary::Base64),
+        serde_json::json!(["AQID"]),
+    );
+    assert_eq!(
+        value.to_json_value(value::JsonBinary::Array),
+        serde_json::json!([[1, 2, 3]]),
+    );
+    assert_eq!(value.to_json_string(), r#"["AQID"]"#);
+}
@@ -0,0 +1,181 @@
+use crate::smile;
+use crate::value::{BigDecimal, BigInteger, HashValue, Value};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+
+fn big(buf: &[u8]) -> BigInteger {
+    BigInteger::from_be_bytes(buf.to_vec())
+}
+
+// The canonical order is persisted by users, so this list must never be reordered.
+fn golden() -> Vec<Value> {
+    vec![
+        Value::Null,
+        Value::Boolean(false),
+        Value::Boolean(true),
+        Value::Integer(i32::MIN),
+        Value::Integer(-1),
+        Value::Integer(0),
+        Value::Integer(i32::MAX),
+        Value::Long(i64::MIN),
+        Value::Long(0),
+        Value::Long(i64::MAX),
+        Value::BigInteger(big(&[0x80, 0, 0, 0, 0, 0, 0, 0, 0])),
+        Value::BigInteger(big(&[0xff, 0xfe])),
+        Value::BigInteger(big(&[0xff])),
+        Value::BigInteger(big(&[0xff, 0xff])),
+        Value::BigInteger(big(&[])),
+        Value::BigInteger(big(&[0])),
+        Value::BigInteger(big(&[0, 0])),
+        Value::BigInteger(big(&[1])),
+        Value::BigInteger(big(&[0, 0x80])),
+        Value::BigInteger(big(&[1, 0, 0, 0, 0, 0, 0, 0, 0])),
+        Value::Float(-f32::NAN),
+        Value::Float(f32::NEG_INFINITY),
+        Value::Float(-1.5),
+        Value::Float(-0.),
+        Value::Float(0.),
+        Value::Float(1.5),
+        Value::Float(f32::INFINITY),
+        Value::Float(f32::NAN),
+        Value::Double(-f64::NAN),
+        Value::Double(f64::NEG_INFINITY),
+        Value::Double(-0.),
+        Value::Double(0.),
+        Value::Double(f64::MIN_POSITIVE),
+        Value::Double(f64::INFINITY),
+        Value::Double(f64::NAN),
+        Value::BigDecimal(BigDecimal::new(big(&[0xff]), 0)),
+        Value::BigDecimal(BigDecimal::new(big(&[1]), -1)),
+        Value::BigDecimal(BigDecimal::new(big(&[1]), 0)),
+        Value::BigDecimal(BigDecimal::new(big(&[1]), 2)),
+        Value::BigDecimal(BigDecimal::new(big(&[10]), 0)),
+        Value::String("".into()),
+        Value::String("A".into()),
+        Value::String("a".into()),
+        Value::String("ab".into()),
+        Value::String("é".into()),
+        Value::binary(vec![]),
+        Value::binary(vec![0]),
+        Value::binary(vec![0, 0]),
+        Value::binary(vec![0xff]),
+        smile!([]),
+        smile!([null]),
+        smile!([null, 1]),
+        smile!([false]),
+        smile!([[]]),
+        smile!({}),
+        smile!({"a": null}),
+        smile!({"a": 1}),
+        smile!({"a": 1, "b": null}),
+        smile!({"b": null}),
+    ]
+}
+
+#[test]
+fn golden_order() {
+    let golden = golden();
+    for pair in golden.windows(2) {
+        assert_eq!(
+            pair[0].canonical_cmp(&pair[1]),
+            Ordering::Less,
+            "{:?} < {:?}",
+            pair[0],
+            pair[1],
+        );
+        assert_eq!(pair[1].canonical_cmp(&pair[0]), Ordering::Greater);
+    }
+
+    // a deterministic shuffle
+    let mut shuffled = golden.clone();
+    let len = shuffled.len();
+    for i in 0..len {
+        shuffled.swap(i, i * 31 % len);
+    }
+    shuffled.reverse();
+
+    shuffled.sort_by(Value::canonical_cmp);
+    for (actual, expected) in shuffled.iter().zip(&golden) {
+        assert_eq!(actual.canonical_cmp(expected), Ordering::Equal);
+    }
+
+    let set = golden
+        .iter()
+        .cloned()
+        .map(HashValue)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(set.len(), golden.len());
+    let set = golden
+        .iter()
+        .cloned()
+        .map(HashValue)
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), golden.len());
+}
+
+#[test]
+fn float_semantics() {
+    assert_eq!(
+        HashValue(Value::Double(f64::NAN)),
+        HashValue(Value::Double(f64::NAN))
+    );
+    assert_ne!(HashValue(Value::Double(0.)), HashValue(Value::Double(-0.)));
+    assert_ne!(HashValue(Value::Float(1.)), HashValue(Value::Double(1.)));
+
+    let mut set = HashSet::new();
+    assert!(set.insert(HashValue(smile!([(Value::Double(f64::NAN))]))));
+    assert!(!set.insert(HashValue(smile!([(Value::Double(f64::NAN))]))));
+}
+
+#[test]
+fn numbers_differ_by_variant() {
+    assert_ne!(HashValue(Value::Integer(1)), HashValue(Value::Long(1)));
+    assert_ne!(
+        HashValue(Value::Long(1)),
+        HashValue(Value::BigInteger(big(&[1])))
+    );
+    assert_ne!(
+        HashValue(Value::BigInteger(big(&[1]))),
+        HashValue(Value::BigInteger(big(&[0, 1])))
+    );
+}
+
+#[test]
+fn object_entry_order_ignored() {
+    let mut a = smile!({"a": 1, "b": [1.5, null]});
+    let b = smile!({"b": [1.5, null], "a": 1});
+    assert_eq!(a.canonical_cmp(&b), Ordering::Equal);
+
+    let set = vec![HashValue(a.clone()), HashValue(b)]
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 1);
+
+    a["b"][0] = Value::Float(-1.5);
+    assert_eq!(
+        a.canonical_cmp(&smile!({"a": 1, "b": [1.5]})),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn dedup_documents() {
+    let documents = vec![
+        smile!({"id": 1, "tags": ["a"]}),
+        smile!({"tags": ["a"], "id": 1}),
+        smile!({"id": 2}),
+        smile!({"id": 1, "tags": ["a"]}),
+    ];
+
+    let unique = documents
+        .into_iter()
+        .map(HashValue::from)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(HashValue::into_inner)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        unique,
+        vec![smile!({"id": 1, "tags": ["a"]}), smile!({"id": 2})]
+    );
+}
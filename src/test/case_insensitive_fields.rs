@@ -0,0 +1,164 @@
+use crate::de::{Deserializer, SliceRead};
+use crate::smile;
+use crate::value::Value;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    user_name: String,
+    user_id: i32,
+}
+
+fn user() -> User {
+    User {
+        user_name: "bob".to_string(),
+        user_id: 1,
+    }
+}
+
+fn deserialize<T>(case_insensitive_fields: bool, value: &Value) -> Result<T, crate::Error>
+where
+    T: DeserializeOwned,
+{
+    let buf = crate::to_vec(value).unwrap();
+    let mut de = Deserializer::builder()
+        .case_insensitive_fields(case_insensitive_fields)
+        .build(SliceRead::new(&buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+fn check<T>(value: &Value, expected: &T)
+where
+    T: DeserializeOwned + PartialEq + Debug,
+{
+    assert_eq!(deserialize::<T>(true, value).unwrap(), *expected);
+}
+
+#[test]
+fn disabled_by_default() {
+    let err = deserialize::<User>(false, &smile!({"username": "bob", "userid": 1})).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: missing field `userName`"
+    );
+}
+
+#[test]
+fn exact() {
+    check(&smile!({"userName": "bob", "userId": 1}), &user());
+}
+
+#[test]
+fn lowercase_and_uppercase() {
+    check(&smile!({"username": "bob", "userid": 1}), &user());
+    check(&smile!({"USERNAME": "bob", "USERID": 1}), &user());
+    check(&smile!({"UserName": "bob", "uSeRiD": 1}), &user());
+}
+
+#[test]
+fn shared_keys() {
+    // keys read back from the shared property cache are matched as well
+    let value = smile!([
+        {"USERNAME": "bob", "userid": 1},
+        {"USERNAME": "bob", "userid": 1},
+    ]);
+    check(&value, &vec![user(), user()]);
+}
+
+#[test]
+fn unicode() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Summer {
+        #[serde(rename = "été")]
+        summer: i32,
+    }
+
+    check(&smile!({"ÉTÉ": 1}), &Summer { summer: 1 });
+}
+
+#[test]
+fn exact_match_preferred() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Ambiguous {
+        #[serde(rename = "key")]
+        lower: i32,
+        #[serde(rename = "KEY")]
+        upper: i32,
+    }
+
+    check(
+        &smile!({"KEY": 2, "key": 1}),
+        &Ambiguous { lower: 1, upper: 2 },
+    );
+}
+
+#[test]
+fn unknown_fields() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Strict {
+        name: i32,
+    }
+
+    check(&smile!({"NAME": 1}), &Strict { name: 1 });
+    let err = deserialize::<Strict>(true, &smile!({"NAME": 1, "Other": 2})).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: unknown field `Other`, expected `name`"
+    );
+
+    // ignored keys are skipped as usual
+    check(
+        &smile!({"OTHER": [1, 2], "userID": 1, "USERNAME": "bob"}),
+        &user(),
+    );
+}
+
+#[test]
+fn map_keys_unchanged() {
+    let value = smile!({"UserName": 1, "userId": 2});
+    let expected = vec![("UserName".to_string(), 1), ("userId".to_string(), 2)];
+    check(&value, &expected.iter().cloned().collect::<HashMap<_, _>>());
+    check(&value, &expected.into_iter().collect::<BTreeMap<_, _>>());
+    check(&value, &value);
+}
+
+#[test]
+fn nested() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(rename_all = "camelCase")]
+    struct Outer {
+        owner: User,
+        extra_data: HashMap<String, i32>,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Wrapper {
+        Variant {
+            #[serde(rename = "innerValue")]
+            inner_value: i32,
+        },
+    }
+
+    check(
+        &smile!({
+            "OWNER": {"username": "bob", "USERID": 1},
+            "extradata": {"MixedCase": 3},
+        }),
+        &Outer {
+            owner: user(),
+            extra_data: vec![("MixedCase".to_string(), 3)].into_iter().collect(),
+        },
+    );
+
+    check(
+        &smile!({"Variant": {"INNERVALUE": 4}}),
+        &Wrapper::Variant { inner_value: 4 },
+    );
+}
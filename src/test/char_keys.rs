@@ -0,0 +1,128 @@
+use crate::ser::Builder;
+use crate::value::{Map, Value};
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+fn serialize<T>(builder: &Builder, value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = builder.build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn check_all_readers<T>(buf: &[u8], expected: &T)
+where
+    T: DeserializeOwned + PartialEq + Debug,
+{
+    let actual = crate::from_slice::<T>(buf).unwrap();
+    assert_eq!(*expected, actual, "from_slice");
+
+    let actual = crate::from_mut_slice::<T>(&mut buf.to_vec()).unwrap();
+    assert_eq!(*expected, actual, "from_mut_slice");
+
+    let actual = crate::from_reader::<T, _>(buf).unwrap();
+    assert_eq!(*expected, actual, "from_reader");
+}
+
+fn check_round_trip<T>(value: &T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    for builder in &[
+        Builder::jackson_defaults(),
+        Builder::smallest_output(),
+        Builder::fastest(),
+    ] {
+        let buf = serialize(builder, value);
+        check_all_readers(&buf, value);
+    }
+}
+
+#[test]
+fn ascii() {
+    let map = ('a'..='z')
+        .enumerate()
+        .map(|(i, c)| (c, i as i32))
+        .collect::<BTreeMap<_, _>>();
+    check_round_trip(&map);
+}
+
+#[test]
+fn multi_byte() {
+    let map = ['é', 'ß', '€', 'あ', '𝄞', '🦀']
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (*c, i as i32))
+        .collect::<IndexMap<_, _>>();
+    check_round_trip(&map);
+}
+
+#[test]
+fn repeated_multi_byte_shared() {
+    // each key after the first map is written as a back reference into the property cache
+    let map = ['é', '€', '𝄞', 'x']
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (*c, i as i32))
+        .collect::<IndexMap<_, _>>();
+    let maps = vec![map; 10];
+
+    let buf = serialize(&Builder::jackson_defaults(), &maps);
+    assert!(buf.windows(2).any(|w| w == [0xfa, 0x40]));
+    check_all_readers(&buf, &maps);
+
+    check_round_trip(&maps);
+}
+
+#[test]
+fn multi_char_key() {
+    let mut map = IndexMap::new();
+    map.insert("ab", 1);
+    let buf = crate::to_vec(&map).unwrap();
+
+    let err = crate::from_slice::<IndexMap<char, i32>>(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"ab\", expected a character"
+    );
+    crate::from_mut_slice::<IndexMap<char, i32>>(&mut buf.clone()).unwrap_err();
+    crate::from_reader::<IndexMap<char, i32>, _>(&*buf).unwrap_err();
+
+    // the second key is read back from the property cache
+    let buf = crate::to_vec(&(map.clone(), map)).unwrap();
+    type Pair = (IndexMap<String, i32>, IndexMap<char, i32>);
+    let err = crate::from_slice::<Pair>(&buf).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: string \"ab\", expected a character"
+    );
+    crate::from_mut_slice::<Pair>(&mut buf.clone()).unwrap_err();
+    crate::from_reader::<Pair, _>(&*buf).unwrap_err();
+}
+
+#[test]
+fn empty_key() {
+    let mut map = IndexMap::new();
+    map.insert("", 1);
+    let buf = crate::to_vec(&map).unwrap();
+    crate::from_slice::<IndexMap<char, i32>>(&buf).unwrap_err();
+}
+
+#[test]
+fn value_round_trip() {
+    let mut map = IndexMap::new();
+    map.insert('€', 1);
+    let value = crate::value::to_value(&map).unwrap();
+
+    let mut expected = Map::new();
+    expected.insert("€".to_string(), Value::Integer(1));
+    assert_eq!(value, Value::Object(expected));
+
+    let actual = crate::value::from_value::<IndexMap<char, i32>>(value).unwrap();
+    assert_eq!(actual, map);
+}
@@ -0,0 +1,131 @@
+use crate::value::Value;
+use crate::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_bytes::{ByteBuf, Bytes};
+use std::borrow::Cow;
+
+fn encode<T>(value: &T, raw_binary: bool) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::builder()
+        .raw_binary(raw_binary)
+        .shared_strings(true)
+        .build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+#[test]
+fn borrowed_str() {
+    let buf = encode("hello world", false);
+
+    let s = crate::from_cow::<&str>(Cow::Borrowed(&buf)).unwrap();
+    assert_eq!(s, "hello world");
+    assert!(buf.as_ptr_range().contains(&s.as_ptr()));
+}
+
+#[test]
+fn owned_str() {
+    let buf = encode("hello world", false);
+
+    let expected = crate::from_reader::<String, _>(&buf[..]).unwrap();
+    assert_eq!(
+        crate::from_cow::<String>(Cow::Owned(buf.clone())).unwrap(),
+        expected
+    );
+
+    let expected = crate::from_slice::<Box<str>>(&buf).unwrap();
+    assert_eq!(
+        crate::from_cow::<Box<str>>(Cow::Owned(buf.clone())).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn borrowed_raw_binary() {
+    let data = [1, 2, 3, 0xff];
+    let buf = encode(Bytes::new(&data), true);
+
+    let bytes = crate::from_cow::<&Bytes>(Cow::Borrowed(&buf)).unwrap();
+    assert_eq!(bytes, Bytes::new(&data));
+    assert!(buf.as_ptr_range().contains(&bytes.as_ptr()));
+}
+
+#[test]
+fn borrowed_7_bit_binary() {
+    let data = [1, 2, 3, 0xff];
+    let buf = encode(Bytes::new(&data), false);
+
+    assert_eq!(
+        crate::from_cow::<&Bytes>(Cow::Borrowed(&buf))
+            .unwrap_err()
+            .to_string(),
+        crate::from_slice::<&Bytes>(&buf).unwrap_err().to_string(),
+    );
+    assert_eq!(
+        crate::from_cow::<ByteBuf>(Cow::Borrowed(&buf)).unwrap(),
+        data
+    );
+}
+
+#[test]
+fn owned_binary() {
+    let data = (0..=255).collect::<Vec<u8>>();
+    for &raw_binary in &[false, true] {
+        let buf = encode(Bytes::new(&data), raw_binary);
+        assert_eq!(
+            crate::from_cow::<ByteBuf>(Cow::Owned(buf.clone())).unwrap(),
+            data
+        );
+        assert_eq!(
+            crate::from_cow::<&Bytes>(Cow::Owned(buf.clone()))
+                .unwrap_err()
+                .to_string(),
+            <&Bytes>::deserialize(&mut Deserializer::from_reader(&buf[..]))
+                .unwrap_err()
+                .to_string(),
+        );
+    }
+}
+
+#[test]
+fn values_match_slice() {
+    let value = crate::smile!({
+        "name": "widget",
+        "tags": ["name", "widget", "widget"],
+        "data": (Value::binary(vec![0x80; 100])),
+        "nested": [{"name": "widget"}, {"name": "gadget"}],
+    });
+
+    for &raw_binary in &[false, true] {
+        let buf = encode(&value, raw_binary);
+        assert_eq!(
+            crate::from_cow::<Value>(Cow::Borrowed(&buf)).unwrap(),
+            value
+        );
+        assert_eq!(crate::from_cow::<Value>(Cow::Owned(buf)).unwrap(), value);
+    }
+}
+
+#[test]
+fn truncated() {
+    let buf = encode(&["hello", "world"], false);
+    let truncated = &buf[..buf.len() - 3];
+
+    let expected = crate::from_slice::<Vec<String>>(truncated)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        crate::from_cow::<Vec<String>>(Cow::Borrowed(truncated))
+            .unwrap_err()
+            .to_string(),
+        expected
+    );
+    assert_eq!(
+        crate::from_cow::<Vec<String>>(Cow::Owned(truncated.to_vec()))
+            .unwrap_err()
+            .to_string(),
+        expected
+    );
+}
@@ -0,0 +1,122 @@
+use crate::smile;
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+use std::mem;
+
+const VALUE: usize = mem::size_of::<Value>();
+
+// the reference counts stored alongside the contents of an `Arc<str>`
+#[cfg(not(feature = "arc-strings"))]
+const STRING_OVERHEAD: usize = 0;
+#[cfg(feature = "arc-strings")]
+const STRING_OVERHEAD: usize = 2 * mem::size_of::<usize>();
+
+#[test]
+fn scalars() {
+    for value in &[
+        smile!(null),
+        smile!(true),
+        smile!(1),
+        smile!(10000000000),
+        smile!(1.5),
+        Value::Float(1.5),
+    ] {
+        assert_eq!(value.deep_size_of(), VALUE);
+    }
+}
+
+#[test]
+fn buffers_use_capacity() {
+    let mut s = String::with_capacity(100);
+    s.push_str("hello");
+    #[cfg(not(feature = "arc-strings"))]
+    assert_eq!(Value::String(s).deep_size_of(), VALUE + 100);
+    // converting to an `Arc<str>` copies the string into an allocation of its exact length
+    #[cfg(feature = "arc-strings")]
+    assert_eq!(
+        Value::String(s.into()).deep_size_of(),
+        VALUE + STRING_OVERHEAD + 5
+    );
+
+    let mut b = Vec::with_capacity(50);
+    b.push(1);
+    #[cfg(not(feature = "bytes"))]
+    assert_eq!(Value::binary(b).deep_size_of(), VALUE + 50);
+    // the storage of `Bytes` may be shared, so only the visible portion is counted
+    #[cfg(feature = "bytes")]
+    assert_eq!(Value::binary(b).deep_size_of(), VALUE + 1);
+
+    let big = || {
+        let mut buf = Vec::with_capacity(20);
+        buf.push(1);
+        BigInteger::from_be_bytes(buf)
+    };
+    assert_eq!(Value::BigInteger(big()).deep_size_of(), VALUE + 20);
+    assert_eq!(
+        Value::BigDecimal(BigDecimal::new(big(), 2)).deep_size_of(),
+        VALUE + 20
+    );
+}
+
+#[test]
+fn arrays() {
+    let mut elements = Vec::with_capacity(10);
+    elements.push(Value::String("abc".into()));
+    elements.push(Value::Null);
+    let value = Value::Array(elements);
+    assert_eq!(
+        value.deep_size_of(),
+        VALUE + 10 * VALUE + STRING_OVERHEAD + 3
+    );
+
+    let nested = smile!([[(Value::String("abc".into()))]]);
+    let inner = match &nested {
+        Value::Array(v) => v.capacity(),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        nested.deep_size_of(),
+        VALUE + inner * VALUE + VALUE + STRING_OVERHEAD + 3
+    );
+}
+
+#[test]
+fn objects() {
+    let empty = Value::Object(Map::new());
+    assert_eq!(empty.deep_size_of(), VALUE);
+
+    let mut map = Map::new();
+    map.insert("key".to_string(), Value::String("value".into()));
+    let small = Value::Object(map);
+    // the table, the key, and the value's string are all accounted for
+    assert!(
+        small.deep_size_of() >= VALUE + mem::size_of::<String>() + VALUE + 3 + 5,
+        "{}",
+        small.deep_size_of(),
+    );
+
+    let mut map = Map::new();
+    for i in 0..1000 {
+        map.insert(i.to_string(), Value::Null);
+    }
+    let large = Value::Object(map);
+    assert!(large.deep_size_of() >= 1000 * (mem::size_of::<String>() + VALUE));
+    assert!(large.deep_size_of() > small.deep_size_of());
+}
+
+#[test]
+fn deeply_nested() {
+    let mut value = Value::Null;
+    for _ in 0..10_000 {
+        value = Value::Array(vec![value]);
+    }
+
+    assert_eq!(value.deep_size_of(), VALUE + 10_000 * VALUE);
+
+    // dismantle the value iteratively so that dropping it doesn't overflow the stack either
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        if let Value::Array(elements) = value {
+            stack.extend(elements);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::smile;
+use crate::value::{self, DifferenceKind, Value};
+
+fn summary(left: &Value, right: &Value) -> Vec<(String, DifferenceKind)> {
+    value::diff(left, right)
+        .into_iter()
+        .map(|d| (d.path().to_string(), d.kind()))
+        .collect()
+}
+
+#[test]
+fn equal() {
+    let value = smile!({"a": [1, 2.5, null], "b": {"c": "d"}});
+    assert!(value::diff(&value, &value.clone()).is_empty());
+
+    let nan = Value::Double(f64::NAN);
+    assert!(value::diff(&nan, &nan).is_empty());
+}
+
+#[test]
+fn scalars() {
+    let diff = value::diff(&smile!(1), &smile!(2));
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff[0].path(), "");
+    assert_eq!(diff[0].kind(), DifferenceKind::Changed);
+    assert_eq!(diff[0].left(), Some(&smile!(1)));
+    assert_eq!(diff[0].right(), Some(&smile!(2)));
+
+    assert_eq!(
+        summary(&Value::Integer(1), &Value::Long(1)),
+        [("".to_string(), DifferenceKind::TypeMismatch)],
+    );
+    assert_eq!(
+        summary(&Value::Double(0.), &Value::Double(-0.)),
+        [("".to_string(), DifferenceKind::Changed)],
+    );
+    assert_eq!(
+        summary(&smile!([]), &smile!({})),
+        [("".to_string(), DifferenceKind::TypeMismatch)],
+    );
+}
+
+#[test]
+fn arrays() {
+    assert_eq!(
+        summary(&smile!([1, 2, 3]), &smile!([1, 5])),
+        [
+            ("/1".to_string(), DifferenceKind::Changed),
+            ("/2".to_string(), DifferenceKind::Removed),
+        ],
+    );
+
+    let diff = value::diff(&smile!([1]), &smile!([1, "x", "y"]));
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff[0].path(), "/1");
+    assert_eq!(diff[0].kind(), DifferenceKind::Added);
+    assert_eq!(diff[0].left(), None);
+    assert_eq!(diff[0].right(), Some(&smile!("x")));
+    assert_eq!(diff[1].path(), "/2");
+}
+
+#[test]
+fn objects() {
+    let left = smile!({"z": 1, "a": {"x": true, "y": [null]}, "removed": 1});
+    let right = smile!({"added": 2, "a": {"y": [1], "x": true}, "z": 1});
+    assert_eq!(
+        summary(&left, &right),
+        [
+            ("/a/y/0".to_string(), DifferenceKind::TypeMismatch),
+            ("/added".to_string(), DifferenceKind::Added),
+            ("/removed".to_string(), DifferenceKind::Removed),
+        ],
+    );
+
+    // entry order doesn't affect the result
+    let reordered = smile!({"z": 1, "added": 2, "a": {"x": true, "y": [1]}});
+    assert_eq!(value::diff(&left, &right), value::diff(&left, &reordered));
+}
+
+#[test]
+fn escaped_paths() {
+    let left = smile!({"a/b": {"c~d": 1}, "": 1});
+    let right = smile!({"a/b": {"c~d": 2}, "": 2});
+    let diff = value::diff(&left, &right);
+    let paths = diff.iter().map(|d| d.path()).collect::<Vec<_>>();
+    assert_eq!(paths, ["/", "/a~1b/c~0d"]);
+
+    // paths resolve with Value::pointer
+    for d in &diff {
+        assert_eq!(left.pointer(d.path()), d.left());
+        assert_eq!(right.pointer(d.path()), d.right());
+    }
+}
+
+#[test]
+fn display() {
+    let diff = value::diff(
+        &smile!({"a": 1, "b": [true]}),
+        &smile!({"a": 2, "b": [], "c": null}),
+    );
+    let lines = diff.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [
+            "/a: Integer(1) != Integer(2)",
+            "/b/0: removed Boolean(true)",
+            "/c: added Null",
+        ],
+    );
+    assert_eq!(
+        value::diff(&smile!(1), &smile!("1"))[0].to_string(),
+        r#"root: Integer(1) != String("1")"#,
+    );
+}
@@ -0,0 +1,134 @@
+use crate::value::Value;
+use crate::{Deserializer, Error, Serializer};
+use serde::Serialize;
+use std::fs;
+
+fn fixture(path: &str) -> Vec<u8> {
+    let mut buf = fs::read(format!("tests/{}.smile", path)).unwrap();
+    if buf.last() == Some(&0xff) {
+        buf.pop();
+    }
+    buf
+}
+
+fn is_boundary(e: &Error) -> bool {
+    e.to_string().starts_with("unexpected Smile header")
+}
+
+#[test]
+fn concatenated_fixtures() {
+    let fixtures = [
+        "integer/-1",
+        "string/a",
+        "list/1",
+        "map/aa",
+        "shared_property/ab",
+        "shared_string/ab",
+        "binary/1pattern-raw",
+    ];
+
+    for a in fixtures {
+        for b in fixtures {
+            let mut buf = fixture(a);
+            buf.extend_from_slice(&fixture(b));
+
+            let e = crate::from_slice::<Value>(&buf).unwrap_err();
+            assert!(is_boundary(&e), "{} + {}: {}", a, b, e);
+
+            let mut it = Deserializer::from_slice(&buf).into_iter::<Value>();
+            it.next().unwrap().unwrap();
+            let e = it.next().unwrap().unwrap_err();
+            assert!(is_boundary(&e), "{} + {}: {}", a, b, e);
+            assert!(it.next().is_none());
+        }
+    }
+}
+
+#[test]
+fn key_position() {
+    // an object missing its end token
+    let mut buf = fixture("map/aa");
+    assert_eq!(buf.pop(), Some(0xfb));
+    buf.extend_from_slice(&fixture("map/a"));
+
+    let e = crate::from_slice::<Value>(&buf).unwrap_err();
+    assert!(is_boundary(&e), "{}", e);
+}
+
+#[test]
+fn value_position() {
+    // an array missing its end token
+    let mut buf = fixture("list/1");
+    assert_eq!(buf.pop(), Some(0xf9));
+    buf.extend_from_slice(&fixture("list/1"));
+
+    let e = crate::from_slice::<Value>(&buf).unwrap_err();
+    assert!(is_boundary(&e), "{}", e);
+}
+
+#[test]
+fn colon_strings() {
+    let value = crate::smile!({":": ":", ":)": [":)\n", ":"]});
+    let buf = crate::to_vec(&value).unwrap();
+    assert_eq!(crate::from_slice::<Value>(&buf).unwrap(), value);
+
+    let mut ser = Serializer::new(vec![]);
+    value.serialize(&mut ser).unwrap();
+    value.serialize(&mut ser).unwrap();
+    let buf = ser.into_inner();
+    let values = Deserializer::from_slice(&buf)
+        .into_iter::<Value>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [value.clone(), value]);
+}
+
+#[test]
+fn partial_signature() {
+    // value position
+    for buf in [
+        &b":)\n\x00\xf8:"[..],
+        b":)\n\x00\xf8:)",
+        b":)\n\x00\xf8:)\r",
+        b":)\n\x00\xf8:(\n",
+    ] {
+        let e = crate::from_slice::<Value>(buf).unwrap_err();
+        assert_eq!(e.token(), Some(b':'));
+        assert_eq!(
+            e.to_string(),
+            "reserved token 0x3a at offset 5; the document may use a newer revision of the Smile format",
+        );
+    }
+
+    // key position
+    for buf in [
+        &b":)\n\x00\xfa:"[..],
+        b":)\n\x00\xfa:)",
+        b":)\n\x00\xfa::\n",
+    ] {
+        let e = crate::from_slice::<Value>(buf).unwrap_err();
+        assert_eq!(e.token(), Some(b':'));
+        assert_eq!(e.to_string(), "unexpected token 0x3a at offset 5");
+    }
+
+    // after the value
+    for buf in [&b":)\n\x00\x21:"[..], b":)\n\x00\x21:)\x00"] {
+        let e = crate::from_slice::<Value>(buf).unwrap_err();
+        assert_eq!(e.to_string(), "trailing data");
+    }
+}
+
+#[cfg(feature = "tools")]
+#[test]
+fn dump_tokens() {
+    use crate::tools::{self, DumpFormat};
+
+    let mut buf = fixture("list/1");
+    buf.extend_from_slice(&fixture("list/1"));
+
+    let e = tools::dump(&*buf, vec![], DumpFormat::Tokens).unwrap_err();
+    assert!(is_boundary(&e), "{}", e);
+
+    let e = tools::dump(&b":)\n\x00\xfa:)\n\x00"[..], vec![], DumpFormat::Tokens).unwrap_err();
+    assert!(is_boundary(&e), "{}", e);
+}
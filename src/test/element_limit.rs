@@ -0,0 +1,76 @@
+use crate::de::{Deserializer, SliceRead};
+use crate::smile;
+use crate::value::Value;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Doc {
+    a: Vec<i32>,
+    b: Option<i32>,
+}
+
+// the object, 2 keys, the array, 2 integers, and null
+const ELEMENTS: u64 = 7;
+
+fn deserialize<T>(max_total_elements: u64) -> Result<T, crate::Error>
+where
+    T: DeserializeOwned,
+{
+    let buf = crate::to_vec(&smile!({"a": [1, 2], "b": null})).unwrap();
+    let mut de = Deserializer::builder()
+        .max_total_elements(max_total_elements)
+        .build(SliceRead::new(&buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+#[test]
+fn value_at_limit() {
+    let value = deserialize::<Value>(ELEMENTS).unwrap();
+    assert_eq!(value, smile!({"a": [1, 2], "b": null}));
+}
+
+#[test]
+fn value_over_limit() {
+    let e = deserialize::<Value>(ELEMENTS - 1).unwrap_err();
+    assert_eq!(e.to_string(), "element limit of 6 exceeded");
+}
+
+#[test]
+fn typed_at_limit() {
+    let value = deserialize::<Doc>(ELEMENTS).unwrap();
+    assert_eq!(
+        value,
+        Doc {
+            a: vec![1, 2],
+            b: None,
+        }
+    );
+}
+
+#[test]
+fn typed_over_limit() {
+    let e = deserialize::<Doc>(ELEMENTS - 1).unwrap_err();
+    assert_eq!(e.to_string(), "element limit of 6 exceeded");
+
+    let e = deserialize::<HashMap<String, Value>>(ELEMENTS - 1).unwrap_err();
+    assert_eq!(e.to_string(), "element limit of 6 exceeded");
+}
+
+#[test]
+fn stream_resets_per_value() {
+    let mut buf = crate::to_vec(&vec![1, 2]).unwrap();
+    buf.extend_from_slice(&crate::to_vec(&vec![3, 4]).unwrap()[4..]);
+
+    let de = Deserializer::builder()
+        .max_total_elements(3)
+        .build(SliceRead::new(&buf));
+    let values = de
+        .into_iter::<Vec<i32>>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [vec![1, 2], vec![3, 4]]);
+}
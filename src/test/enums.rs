@@ -82,3 +82,88 @@ fn struct_variant() {
     let actual = crate::from_slice::<TestEnum>(&expected_bytes).unwrap();
     assert_eq!(expected, actual);
 }
+
+fn serialize_with<T>(builder: &crate::ser::Builder, value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = builder.build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+#[test]
+fn repeated_newtype_variant_shares_key() {
+    let values = (0..1000).map(|_| TestEnum::Newtype(1)).collect::<Vec<_>>();
+    let equivalent = (0..1000)
+        .map(|_| NewtypeEquivalent { newtype: 1 })
+        .collect::<Vec<_>>();
+
+    let builder = crate::ser::Builder::jackson_defaults();
+    let actual = serialize_with(&builder, &values);
+    assert_eq!(actual, serialize_with(&builder, &equivalent));
+
+    // header, array start, the first entry with the full key, 999 entries with a back reference, and array end
+    let mut expected = vec![b':', b')', b'\n', 0x01, 0xf8];
+    expected.extend_from_slice(&[
+        0xfa, 0x86, b'N', b'e', b'w', b't', b'y', b'p', b'e', 0xc2, 0xfb,
+    ]);
+    for _ in 1..1000 {
+        expected.extend_from_slice(&[0xfa, 0x40, 0xc2, 0xfb]);
+    }
+    expected.push(0xf9);
+    assert_eq!(actual, expected);
+
+    let deserialized = crate::from_slice::<Vec<TestEnum>>(&actual).unwrap();
+    assert_eq!(deserialized, values);
+}
+
+#[test]
+fn repeated_tuple_and_struct_variants_share_keys() {
+    let builder = crate::ser::Builder::jackson_defaults();
+
+    let values = (0..1000)
+        .map(|_| TestEnum::Tuple(1, true))
+        .collect::<Vec<_>>();
+    let equivalent = (0..1000)
+        .map(|_| TupleEquivalent { tuple: (1, true) })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        serialize_with(&builder, &values),
+        serialize_with(&builder, &equivalent),
+    );
+
+    let values = (0..1000)
+        .map(|_| TestEnum::Struct { a: 1, b: true })
+        .collect::<Vec<_>>();
+    let equivalent = (0..1000)
+        .map(|_| StructEquivalent {
+            struct_: StructEquivalentInner { a: 1, b: true },
+        })
+        .collect::<Vec<_>>();
+    let actual = serialize_with(&builder, &values);
+    assert_eq!(actual, serialize_with(&builder, &equivalent));
+
+    let deserialized = crate::from_slice::<Vec<TestEnum>>(&actual).unwrap();
+    assert_eq!(deserialized, values);
+}
+
+#[test]
+fn repeated_unit_variant_shares_string() {
+    let values = (0..1000).map(|_| TestEnum::Unit).collect::<Vec<_>>();
+    let equivalent = (0..1000).map(|_| "Unit").collect::<Vec<_>>();
+
+    let mut builder = crate::ser::Builder::jackson_defaults();
+    builder.shared_strings(true);
+    let actual = serialize_with(&builder, &values);
+    assert_eq!(actual, serialize_with(&builder, &equivalent));
+
+    // header, array start, the first string in full, 999 back references, and array end
+    let mut expected = vec![b':', b')', b'\n', 0x03, 0xf8, 0x43, b'U', b'n', b'i', b't'];
+    expected.extend_from_slice(&[0x01; 999]);
+    expected.push(0xf9);
+    assert_eq!(actual, expected);
+
+    let deserialized = crate::from_slice::<Vec<TestEnum>>(&actual).unwrap();
+    assert_eq!(deserialized, values);
+}
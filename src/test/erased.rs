@@ -0,0 +1,139 @@
+use crate::de::{Deserializer, IoDeserializer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io::BufReader;
+
+trait Plugin: Debug {
+    fn describe(&self) -> String;
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Greeter {
+    name: String,
+}
+
+impl Plugin for Greeter {
+    fn describe(&self) -> String {
+        format!("hello, {}", self.name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Counter(Vec<u32>);
+
+impl Plugin for Counter {
+    fn describe(&self) -> String {
+        format!("{} items", self.0.len())
+    }
+}
+
+type Factory =
+    for<'de> fn(&mut dyn erased_serde::Deserializer<'de>) -> erased_serde::Result<Box<dyn Plugin>>;
+
+fn factory<T>(de: &mut dyn erased_serde::Deserializer<'_>) -> erased_serde::Result<Box<dyn Plugin>>
+where
+    T: Plugin + for<'de> Deserialize<'de> + 'static,
+{
+    erased_serde::deserialize::<T>(de).map(|p| Box::new(p) as Box<dyn Plugin>)
+}
+
+fn registry() -> HashMap<&'static str, Factory> {
+    let mut registry = HashMap::new();
+    registry.insert("greeter", factory::<Greeter> as Factory);
+    registry.insert("counter", factory::<Counter> as Factory);
+    registry
+}
+
+fn load(name: &str, de: &mut dyn erased_serde::Deserializer<'_>) -> Box<dyn Plugin> {
+    registry()[name](de).unwrap()
+}
+
+fn greeter() -> Vec<u8> {
+    crate::to_vec(&Greeter {
+        name: "world".to_string(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn slice() {
+    let buf = greeter();
+    let mut de = Deserializer::from_slice(&buf);
+    let plugin = load(
+        "greeter",
+        &mut <dyn erased_serde::Deserializer>::erase(&mut de),
+    );
+    de.end().unwrap();
+    assert_eq!(plugin.describe(), "hello, world");
+}
+
+#[test]
+fn mut_slice() {
+    let mut buf = crate::to_vec(&Counter(vec![1, 2, 3])).unwrap();
+    let mut de = Deserializer::from_mut_slice(&mut buf);
+    let plugin = load(
+        "counter",
+        &mut <dyn erased_serde::Deserializer>::erase(&mut de),
+    );
+    de.end().unwrap();
+    assert_eq!(plugin.describe(), "3 items");
+}
+
+#[test]
+fn reader() {
+    let buf = greeter();
+    let mut de: IoDeserializer<_> = Deserializer::from_reader(BufReader::new(&buf[..]));
+    let plugin = load(
+        "greeter",
+        &mut <dyn erased_serde::Deserializer>::erase(&mut de),
+    );
+    de.end().unwrap();
+    assert_eq!(plugin.describe(), "hello, world");
+}
+
+#[test]
+fn boxed() {
+    // a type-erased deserializer can be stored and driven later
+    struct Source<'a> {
+        de: Box<dyn erased_serde::Deserializer<'static> + 'a>,
+    }
+
+    let buf = crate::to_vec(&Counter(vec![1, 2])).unwrap();
+    let mut de: IoDeserializer<_> = Deserializer::from_reader(std::io::Cursor::new(buf));
+    let mut source = Source {
+        de: Box::new(<dyn erased_serde::Deserializer>::erase(&mut de)),
+    };
+    let plugin = load("counter", &mut *source.de);
+    drop(source);
+    de.end().unwrap();
+    assert_eq!(plugin.describe(), "2 items");
+}
+
+#[test]
+fn borrowed_strings() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+    }
+
+    let buf = greeter();
+    let mut de = Deserializer::from_slice(&buf);
+    let value = erased_serde::deserialize::<Borrowed<'_>>(
+        &mut <dyn erased_serde::Deserializer>::erase(&mut de),
+    )
+    .unwrap();
+    assert_eq!(value.name, "world");
+}
+
+#[test]
+fn errors() {
+    let buf = greeter();
+    let mut de = Deserializer::from_slice(&buf);
+    let err =
+        registry()["counter"](&mut <dyn erased_serde::Deserializer>::erase(&mut de)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: invalid type: map, expected a sequence"
+    );
+}
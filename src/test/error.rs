@@ -0,0 +1,92 @@
+use crate::value::Value;
+use crate::Error;
+use serde::de::Error as _;
+use std::error::Error as _;
+use std::io::{self, Write};
+use std::thread;
+
+struct FailingWriter(fn() -> io::Error);
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err((self.0)())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn io_error(f: fn() -> io::Error) -> Error {
+    crate::to_writer(FailingWriter(f), &"hello").unwrap_err()
+}
+
+#[test]
+fn trait_objects() {
+    let e = crate::from_slice::<Value>(b":)\n\x00\xf8").unwrap_err();
+    let boxed: Box<dyn std::error::Error + Send + Sync + 'static> = Box::new(e);
+    let boxed = thread::spawn(move || boxed).join().unwrap();
+    assert_eq!(boxed.to_string(), "EOF while parsing array");
+    assert!(boxed.downcast::<Error>().is_ok());
+}
+
+#[test]
+fn clone() {
+    let e = crate::from_slice::<Value>(b":)\n\x00\xf8\xfb").unwrap_err();
+    let clone = e.clone();
+    assert_eq!(clone.to_string(), e.to_string());
+    assert_eq!(format!("{:?}", clone), format!("{:?}", e));
+    assert_eq!(clone.token(), Some(0xfb));
+
+    let e = Error::custom("custom message");
+    assert_eq!(
+        e.clone().to_string(),
+        "error in Deserialize impl: custom message"
+    );
+}
+
+#[test]
+fn clone_io() {
+    let e = io_error(|| io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"));
+    let clone = e.clone();
+    assert_eq!(clone.to_string(), e.to_string());
+
+    let source = clone.source().unwrap().downcast_ref::<io::Error>().unwrap();
+    assert_eq!(source.kind(), io::ErrorKind::BrokenPipe);
+    assert_eq!(source.to_string(), "pipe closed");
+
+    let e = io_error(|| io::Error::from_raw_os_error(32));
+    let original = e.source().unwrap().downcast_ref::<io::Error>().unwrap();
+    let clone = e.clone();
+    let source = clone.source().unwrap().downcast_ref::<io::Error>().unwrap();
+    assert_eq!(source.kind(), original.kind());
+    assert_eq!(source.to_string(), original.to_string());
+    assert_eq!(original.raw_os_error(), Some(32));
+    assert_eq!(source.raw_os_error(), None);
+}
+
+#[test]
+fn retryable() {
+    let e = io_error(|| io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+    assert!(e.is_retryable());
+
+    for buf in [
+        &b":)"[..],
+        b":)\n\x00",
+        b":)\n\x00\xf8",
+        b":)\n\x00\xfa\x80a",
+    ] {
+        let e = crate::from_slice::<Value>(buf).unwrap_err();
+        assert!(e.is_retryable(), "{}", e);
+    }
+
+    for buf in [&b":)\n\x00\xf8\xfb"[..], b"{}\n\x00", b":)\n\x00\x41a\xfc"] {
+        let e = crate::from_slice::<Value>(buf).unwrap_err();
+        assert!(!e.is_retryable(), "{}", e);
+    }
+
+    assert!(!crate::from_slice::<bool>(b":)\n\x00\xc2")
+        .unwrap_err()
+        .is_retryable());
+    assert!(!Error::custom("custom message").is_retryable());
+}
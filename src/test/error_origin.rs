@@ -0,0 +1,256 @@
+use crate::value::Value;
+use crate::{Error, ErrorDetail, ErrorOrigin};
+use serde::de::{self, Error as _, Unexpected};
+use serde::ser::{self, Serialize};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+enum Shape {
+    Circle,
+    Square,
+    Triangle,
+}
+
+fn point_error(value: Value) -> Error {
+    let buf = crate::to_vec(&value).unwrap();
+    crate::from_slice::<Point>(&buf).unwrap_err()
+}
+
+#[test]
+fn missing_field() {
+    let mut map = HashMap::new();
+    map.insert("x", 1);
+    let buf = crate::to_vec(&map).unwrap();
+    let e = crate::from_slice::<Point>(&buf).unwrap_err();
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(e.detail(), Some(&ErrorDetail::MissingField { field: "y" }));
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: missing field `y`"
+    );
+}
+
+#[test]
+fn unknown_field() {
+    let e = point_error(crate::smile!({"x": 1, "y": 2, "z": 3}));
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::UnknownField {
+            field: "z".to_string(),
+            expected: &["x", "y"],
+        })
+    );
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: unknown field `z`, expected `x` or `y`"
+    );
+}
+
+#[test]
+fn duplicate_field() {
+    let buf = b":)\n\x00\xfa\x80x\xc2\x80y\xc4\x80x\xc6\xfb";
+    let e = crate::from_slice::<Point>(buf).unwrap_err();
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::DuplicateField { field: "x" })
+    );
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: duplicate field `x`"
+    );
+}
+
+#[test]
+fn unknown_variant() {
+    let buf = crate::to_vec(&"Hexagon").unwrap();
+    let e = crate::from_slice::<Shape>(&buf).unwrap_err();
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::UnknownVariant {
+            variant: "Hexagon".to_string(),
+            expected: &["Circle", "Square", "Triangle"],
+        })
+    );
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: unknown variant `Hexagon`, expected one of `Circle`, `Square`, `Triangle`"
+    );
+}
+
+#[test]
+fn invalid_length_and_type() {
+    let e = point_error(crate::smile!([1]));
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::InvalidLength {
+            len: 1,
+            expected: "struct Point with 2 elements".to_string(),
+        })
+    );
+
+    let e = point_error(crate::smile!("point"));
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::InvalidType {
+            unexpected: "string \"point\"".to_string(),
+            expected: "struct Point".to_string(),
+        })
+    );
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: invalid type: string \"point\", expected struct Point"
+    );
+}
+
+#[test]
+fn invalid_value() {
+    let buf = crate::to_vec(&300).unwrap();
+    let e = crate::from_slice::<u8>(&buf).unwrap_err();
+
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::InvalidValue {
+            unexpected: "integer `300`".to_string(),
+            expected: "u8".to_string(),
+        })
+    );
+}
+
+#[test]
+fn custom() {
+    let e = Error::custom("bad input");
+    assert_eq!(e.origin(), ErrorOrigin::Deserialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::Message("bad input".to_string()))
+    );
+    assert_eq!(e.to_string(), "error in Deserialize impl: bad input");
+
+    // re-wrapping an error doesn't repeat the prefix
+    let e = Error::custom(e);
+    assert_eq!(e.to_string(), "error in Deserialize impl: bad input");
+}
+
+#[test]
+fn serialize() {
+    struct Failing;
+
+    impl Serialize for Failing {
+        fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+        where
+            S: ser::Serializer,
+        {
+            Err(ser::Error::custom("not today"))
+        }
+    }
+
+    let e = crate::to_vec(&Failing).unwrap_err();
+    assert_eq!(e.origin(), ErrorOrigin::Serialize);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::Message("not today".to_string()))
+    );
+    assert_eq!(e.to_string(), "error in Serialize impl: not today");
+}
+
+#[test]
+fn internal() {
+    let e = crate::from_slice::<Value>(b":)\n\x00\xf8").unwrap_err();
+    assert_eq!(e.origin(), ErrorOrigin::Internal);
+    assert_eq!(e.detail(), None);
+    assert_eq!(e.to_string(), "EOF while parsing array");
+
+    // mismatches detected by the deserializer itself carry details but no prefix
+    let buf = crate::to_vec(&crate::smile!({"ab": 1})).unwrap();
+    let e = crate::from_slice::<HashMap<char, i32>>(&buf).unwrap_err();
+    assert_eq!(e.origin(), ErrorOrigin::Internal);
+    assert_eq!(
+        e.detail(),
+        Some(&ErrorDetail::InvalidValue {
+            unexpected: "string \"ab\"".to_string(),
+            expected: "a character".to_string(),
+        })
+    );
+    assert_eq!(
+        e.to_string(),
+        "invalid value: string \"ab\", expected a character"
+    );
+}
+
+#[test]
+fn detail_display() {
+    let cases = [
+        (
+            ErrorDetail::UnknownField {
+                field: "a".to_string(),
+                expected: &[],
+            },
+            "unknown field `a`, there are no fields",
+        ),
+        (
+            ErrorDetail::UnknownField {
+                field: "a".to_string(),
+                expected: &["b"],
+            },
+            "unknown field `a`, expected `b`",
+        ),
+        (
+            ErrorDetail::UnknownVariant {
+                variant: "a".to_string(),
+                expected: &[],
+            },
+            "unknown variant `a`, there are no variants",
+        ),
+        (
+            ErrorDetail::UnknownVariant {
+                variant: "a".to_string(),
+                expected: &["b", "c"],
+            },
+            "unknown variant `a`, expected `b` or `c`",
+        ),
+    ];
+
+    for (detail, expected) in cases {
+        assert_eq!(detail.to_string(), expected);
+    }
+
+    // the messages match serde's own formatting
+    let e = de::value::Error::invalid_length(2, &"3 elements");
+    assert_eq!(
+        ErrorDetail::InvalidLength {
+            len: 2,
+            expected: "3 elements".to_string(),
+        }
+        .to_string(),
+        e.to_string()
+    );
+    let e = de::value::Error::invalid_type(Unexpected::Bool(true), &"a string");
+    assert_eq!(
+        Error::invalid_type(Unexpected::Bool(true), &"a string")
+            .detail()
+            .unwrap()
+            .to_string(),
+        e.to_string()
+    );
+}
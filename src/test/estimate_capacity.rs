@@ -0,0 +1,138 @@
+use crate::ser::capacity::{self, BYTES_PER_ELEMENT, DEFAULT_ESTIMATE_LIMIT};
+use crate::ser::Serializer;
+use crate::value::Value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Document {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+}
+
+fn documents(n: usize) -> Vec<Document> {
+    (0..n)
+        .map(|i| Document {
+            id: i as u64,
+            name: format!("document {}", i),
+            tags: vec!["a".to_string(), format!("tag{}", i % 7)],
+        })
+        .collect()
+}
+
+#[test]
+fn to_vec_with_capacity() {
+    let value = documents(10);
+    let out = crate::to_vec_with_capacity(&value, 4096).unwrap();
+    assert_eq!(out, crate::to_vec(&value).unwrap());
+    assert_eq!(out.capacity(), 4096);
+}
+
+#[test]
+fn disabled_by_default() {
+    let value = documents(10);
+    let out = Serializer::builder().to_vec(&value).unwrap();
+    assert_eq!(out, crate::to_vec(&value).unwrap());
+    assert_ne!(out.len(), out.capacity());
+}
+
+#[test]
+fn exact_below_limit() {
+    for builder in [
+        Serializer::builder(),
+        crate::ser::Builder::smallest_output(),
+        crate::ser::Builder::fastest(),
+    ] {
+        let mut builder = builder;
+        builder.estimate_capacity(true);
+
+        for value in [documents(0), documents(1), documents(100)] {
+            let out = builder.to_vec(&value).unwrap();
+            assert_eq!(out.len(), out.capacity());
+            assert_eq!(capacity::estimate(&builder, &value), out.len());
+
+            let mut serializer = builder.build(vec![]);
+            value.serialize(&mut serializer).unwrap();
+            assert_eq!(out, serializer.into_inner());
+        }
+    }
+}
+
+#[test]
+fn tiny_values() {
+    let mut builder = Serializer::builder();
+    builder.estimate_capacity(true);
+
+    let out = builder.to_vec(&()).unwrap();
+    assert_eq!(out, b":)\n\x01\x21");
+    assert_eq!(out.capacity(), out.len());
+
+    let out = builder.to_vec(&1).unwrap();
+    assert_eq!(out.capacity(), out.len());
+}
+
+#[test]
+fn exactly_at_limit() {
+    let value = documents(20);
+    let len = crate::to_vec(&value).unwrap().len();
+
+    let mut builder = Serializer::builder();
+    builder.estimate_capacity(true).estimate_capacity_limit(len);
+    assert_eq!(capacity::estimate(&builder, &value), len);
+
+    builder.estimate_capacity_limit(len - 1);
+    assert_eq!(
+        capacity::estimate(&builder, &value),
+        usize::max(len - 1, 20 * BYTES_PER_ELEMENT),
+    );
+}
+
+#[test]
+fn shallow_above_limit() {
+    let mut builder = Serializer::builder();
+    builder.estimate_capacity(true).estimate_capacity_limit(16);
+
+    // sequences, structs, and maps are estimated by their top-level length
+    let value = documents(100);
+    assert_eq!(
+        capacity::estimate(&builder, &value),
+        100 * BYTES_PER_ELEMENT
+    );
+    let out = builder.to_vec(&value).unwrap();
+    assert_eq!(out, crate::to_vec(&value).unwrap());
+
+    let value = (0..50).map(|i| (i, "value")).collect::<BTreeMap<_, _>>();
+    assert_eq!(capacity::estimate(&builder, &value), 50 * BYTES_PER_ELEMENT);
+
+    let value = Some(Value::Array(vec![Value::Null; 40]));
+    assert_eq!(capacity::estimate(&builder, &value), 40 * BYTES_PER_ELEMENT);
+
+    // the estimate never drops below the limit
+    builder.estimate_capacity_limit(100);
+    let value = vec!["x".repeat(60), "y".repeat(60)];
+    assert_eq!(capacity::estimate(&builder, &value), 100);
+
+    // scalars have no elements
+    let value = "x".repeat(200);
+    assert_eq!(capacity::estimate(&builder, &value), 100);
+}
+
+#[test]
+fn default_limit() {
+    let mut builder = Serializer::builder();
+    builder.estimate_capacity(true);
+
+    let value = vec![Value::binary(vec![0; DEFAULT_ESTIMATE_LIMIT])];
+    assert_eq!(capacity::estimate(&builder, &value), DEFAULT_ESTIMATE_LIMIT);
+}
+
+#[test]
+fn serialization_errors() {
+    let mut builder = Serializer::builder();
+    builder.estimate_capacity(true);
+
+    let value = BTreeMap::from([(vec![1u8], 1)]);
+    assert_eq!(capacity::estimate(&builder, &value), 0);
+    builder.to_vec(&value).unwrap_err();
+}
@@ -0,0 +1,211 @@
+use crate::value::{from_value, to_value, BigDecimal, BigInteger, Map, Value};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_bytes::{ByteBuf, Bytes};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::iter::FromIterator;
+
+fn run_test<T>(expected: T)
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let value = to_value(&expected).unwrap();
+    let actual = from_value::<T>(value).unwrap();
+    assert_eq!(expected, actual);
+
+    let bytes = crate::to_vec(&expected).unwrap();
+    let value = crate::from_slice::<Value>(&bytes).unwrap();
+    let actual = T::deserialize(&value).unwrap();
+    assert_eq!(expected, actual);
+    let actual = from_value::<T>(value).unwrap();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn primitives() {
+    run_test(());
+    run_test(true);
+    run_test(-5i8);
+    run_test(i32::MAX);
+    run_test(i64::MIN);
+    run_test(u32::MAX);
+    run_test(1.5f32);
+    run_test(1.5f64);
+    run_test('x');
+    run_test("hello".to_string());
+    run_test(Some(1));
+    run_test(None::<i32>);
+}
+
+#[test]
+fn long_into_unsigned() {
+    assert_eq!(from_value::<u64>(Value::Long(1 << 40)).unwrap(), 1 << 40);
+    assert_eq!(
+        from_value::<i64>(Value::Long(-(1 << 40))).unwrap(),
+        -(1 << 40)
+    );
+    from_value::<u64>(Value::Long(-1)).unwrap_err();
+}
+
+#[test]
+fn big_integer_into_integers() {
+    run_test(u64::MAX);
+    run_test(i128::MIN);
+    run_test(u128::MAX);
+}
+
+#[test]
+fn binary() {
+    assert_eq!(
+        from_value::<ByteBuf>(Value::binary(vec![1, 2, 3])).unwrap(),
+        ByteBuf::from(vec![1, 2, 3]),
+    );
+}
+
+#[test]
+fn big_number_types() {
+    run_test(BigInteger::from_be_bytes(vec![0xff; 30]));
+    run_test(BigInteger::from_be_bytes(vec![1]));
+    run_test(BigDecimal::new(
+        BigInteger::from_be_bytes(vec![0x12, 0x34]),
+        -10,
+    ));
+}
+
+#[test]
+fn value() {
+    let value = || {
+        Value::Array(vec![
+            Value::Null,
+            Value::Long(1 << 40),
+            Value::BigInteger(BigInteger::from_be_bytes(vec![1])),
+            Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 3)),
+            Value::binary(vec![1]),
+            Value::Object(Map::from_iter(vec![(
+                "a".to_string(),
+                Value::String("b".into()),
+            )])),
+        ])
+    };
+    let actual = Value::deserialize(&value()).unwrap();
+    assert_eq!(value(), actual);
+    let actual = from_value::<Value>(value()).unwrap();
+    assert_eq!(value(), actual);
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Inner {
+    a: i32,
+    b: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Outer {
+    inner: Inner,
+    number: BigInteger,
+    decimal: BigDecimal,
+    map: BTreeMap<u32, bool>,
+    bytes: ByteBuf,
+}
+
+#[test]
+fn nested_structs() {
+    run_test(Outer {
+        inner: Inner {
+            a: 1,
+            b: vec!["x".to_string()],
+        },
+        number: BigInteger::from_be_bytes(vec![1]),
+        decimal: BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 1),
+        map: BTreeMap::from_iter([(1, true), (2, false)]),
+        bytes: ByteBuf::from(vec![1, 2, 3]),
+    });
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum TestEnum {
+    Unit,
+    Newtype(i32),
+    Tuple(i32, bool),
+    Struct { a: i32, b: bool },
+}
+
+#[test]
+fn enums() {
+    run_test(TestEnum::Unit);
+    run_test(TestEnum::Newtype(42));
+    run_test(TestEnum::Tuple(42, true));
+    run_test(TestEnum::Struct { a: 42, b: true });
+}
+
+#[test]
+fn type_mismatch() {
+    let err = from_value::<String>(Value::Boolean(true)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: invalid type: boolean `true`, expected a string"
+    );
+
+    let err = from_value::<Inner>(Value::binary(vec![1])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: invalid type: byte array, expected struct Inner",
+    );
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[serde(borrow)]
+    data: &'a Bytes,
+    tags: Vec<&'a str>,
+    lookup: BTreeMap<&'a str, u32>,
+}
+
+#[test]
+fn borrowed() {
+    let value = crate::smile!({
+        "name": "foo",
+        "data": (Value::binary(vec![1, 2, 3])),
+        "tags": ["a", "b"],
+        "lookup": {"x": 1},
+    });
+
+    let actual = Borrowed::deserialize(&value).unwrap();
+    assert_eq!(
+        actual,
+        Borrowed {
+            name: "foo",
+            data: Bytes::new(&[1, 2, 3]),
+            tags: vec!["a", "b"],
+            lookup: BTreeMap::from_iter([("x", 1)]),
+        }
+    );
+    assert!(std::ptr::eq(actual.name, value["name"].as_str().unwrap()));
+}
+
+#[test]
+fn route_then_decode() {
+    #[derive(Deserialize)]
+    struct Envelope<'a> {
+        kind: &'a str,
+    }
+
+    let value = to_value(&TestEnum::Struct { a: 1, b: false }).unwrap();
+    let value = crate::smile!({"kind": "test", "payload": value});
+
+    let envelope = Envelope::deserialize(&value).unwrap();
+    assert_eq!(envelope.kind, "test");
+    let payload = TestEnum::deserialize(&value["payload"]).unwrap();
+    assert_eq!(payload, TestEnum::Struct { a: 1, b: false });
+}
+
+#[test]
+fn borrowed_type_mismatch() {
+    let err = String::deserialize(&Value::Boolean(true)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "error in Deserialize impl: invalid type: boolean `true`, expected a string"
+    );
+}
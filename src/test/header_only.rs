@@ -0,0 +1,84 @@
+use crate::value::Value;
+use crate::Deserializer;
+use std::collections::BTreeMap;
+
+const HEADER_ONLY: &[u8] = b":)\n\x00";
+const HEADER_EOS: &[u8] = b":)\n\x00\xff";
+
+#[test]
+fn value() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        crate::from_slice::<Value>(buf).unwrap_err();
+        crate::from_reader::<Value, _>(buf).unwrap_err();
+    }
+}
+
+#[test]
+fn option_value() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        assert_eq!(crate::from_slice::<Option<Value>>(buf).unwrap(), None);
+        assert_eq!(crate::from_reader::<Option<Value>, _>(buf).unwrap(), None);
+        assert_eq!(
+            crate::from_mut_slice::<Option<Value>>(&mut buf.to_vec()).unwrap(),
+            None
+        );
+    }
+}
+
+#[test]
+fn unit() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        crate::from_slice::<()>(buf).unwrap_err();
+    }
+}
+
+#[test]
+fn option_unit() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        assert_eq!(crate::from_slice::<Option<()>>(buf).unwrap(), None);
+    }
+}
+
+#[test]
+fn nested_option() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        assert_eq!(crate::from_slice::<Option<Option<i32>>>(buf).unwrap(), None);
+    }
+
+    // an explicit null is still distinguishable from an empty stream
+    let buf = b":)\n\x00\x21";
+    assert_eq!(crate::from_slice::<Option<Option<i32>>>(buf).unwrap(), None);
+    let buf = b":)\n\x00\x21";
+    assert_eq!(crate::from_slice::<Option<()>>(buf).unwrap(), None);
+}
+
+#[test]
+fn missing_header() {
+    crate::from_slice::<Option<Value>>(b"").unwrap_err();
+    crate::from_slice::<Option<Value>>(b":)").unwrap_err();
+}
+
+#[test]
+fn trailing_data() {
+    crate::from_slice::<Option<Value>>(b":)\n\x00\x21\x21").unwrap_err();
+}
+
+#[test]
+fn truncated_containers() {
+    // [null, <eof>
+    crate::from_slice::<Vec<Option<i32>>>(b":)\n\x00\xf8\x21").unwrap_err();
+    // [null, <eos>
+    crate::from_slice::<Vec<Option<i32>>>(b":)\n\x00\xf8\x21\xff").unwrap_err();
+    // {"a": <eof>
+    crate::from_slice::<BTreeMap<String, Option<i32>>>(b":)\n\x00\xfa\x80a").unwrap_err();
+    // {"a": <eos>
+    crate::from_slice::<BTreeMap<String, Option<i32>>>(b":)\n\x00\xfa\x80a\xff").unwrap_err();
+}
+
+#[test]
+fn stream_deserializer() {
+    for buf in [HEADER_ONLY, HEADER_EOS] {
+        let mut it = Deserializer::from_slice(buf).into_iter::<Option<Value>>();
+        assert!(it.next().is_none());
+    }
+}
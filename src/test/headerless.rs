@@ -0,0 +1,98 @@
+use crate::ser::Builder;
+use crate::smile;
+use crate::value::Value;
+use serde::Serialize;
+
+fn headerless(mut builder: Builder) -> Builder {
+    builder.write_header(false);
+    builder
+}
+
+fn header(builder: &Builder) -> Vec<u8> {
+    let mut ser = builder.build(vec![]);
+    ser.write_header().unwrap();
+    ser.into_inner()
+}
+
+#[test]
+fn bytes() {
+    let builder = headerless(Builder::jackson_defaults());
+
+    assert_eq!(crate::to_vec(&1).unwrap(), b":)\n\x01\xc2");
+    assert_eq!(builder.to_vec(&1).unwrap(), b"\xc2");
+
+    assert_eq!(crate::to_vec(&"hi").unwrap(), b":)\n\x01\x41hi");
+    assert_eq!(builder.to_vec(&"hi").unwrap(), b"\x41hi");
+
+    assert_eq!(
+        crate::to_vec(&smile!({"a": [null]})).unwrap(),
+        b":)\n\x01\xfa\x80a\xf8\x21\xf9\xfb"
+    );
+    assert_eq!(
+        builder.to_vec(&smile!({"a": [null]})).unwrap(),
+        b"\xfa\x80a\xf8\x21\xf9\xfb"
+    );
+}
+
+#[test]
+fn end() {
+    let builder = headerless(Builder::jackson_defaults());
+
+    let mut ser = builder.build(vec![]);
+    ser.end().unwrap();
+    assert_eq!(ser.into_inner(), b"\xff");
+
+    let mut ser = builder.build(vec![]);
+    ser.write_header().unwrap();
+    1.serialize(&mut ser).unwrap();
+    ser.end().unwrap();
+    assert_eq!(ser.into_inner(), b"\xc2\xff");
+}
+
+#[test]
+fn options_are_honored() {
+    // shared strings and keys produce back references, and raw binary is written unencoded
+    let value = smile!([
+        {"key": "value"},
+        {"key": "value"},
+        Value::binary(vec![0xff, 0x00]),
+    ]);
+
+    for mut builder in [
+        Builder::jackson_defaults(),
+        Builder::smallest_output(),
+        Builder::fastest(),
+    ] {
+        let expected = builder.to_vec(&value).unwrap();
+        let header = header(&builder);
+        assert!(expected.starts_with(&header));
+
+        builder.write_header(false);
+        let buf = builder.to_vec(&value).unwrap();
+        assert_eq!(buf, expected[header.len()..], "{:?}", builder);
+
+        // the output can be decoded once the header is restored
+        let restored = [&header[..], &buf].concat();
+        assert_eq!(crate::from_slice::<Value>(&restored).unwrap(), value);
+
+        builder.estimate_capacity(true);
+        assert_eq!(builder.to_vec(&value).unwrap(), buf, "{:?}", builder);
+    }
+}
+
+#[test]
+fn rearm_header() {
+    let mut ser = headerless(Builder::jackson_defaults()).build(vec![]);
+    1.serialize(&mut ser).unwrap();
+    ser.end().unwrap();
+
+    ser.rearm_header(&Builder::jackson_defaults()).unwrap();
+    2.serialize(&mut ser).unwrap();
+    ser.end().unwrap();
+
+    ser.rearm_header(&headerless(Builder::jackson_defaults()))
+        .unwrap();
+    3.serialize(&mut ser).unwrap();
+
+    assert_eq!(ser.into_inner(), b"\xc2\xff:)\n\x01\xc4\xff\xc6");
+}
@@ -0,0 +1,136 @@
+use crate::value::{Map, Value, ValueKind};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Results {
+    results: Vec<Entry>,
+}
+
+#[derive(Serialize)]
+struct Entry {
+    name: &'static str,
+    id: i32,
+}
+
+fn results() -> Value {
+    crate::value::to_value(&Results {
+        results: vec![Entry { name: "foo", id: 1 }, Entry { name: "bar", id: 2 }],
+    })
+    .unwrap()
+}
+
+#[test]
+fn chained() {
+    let value = results();
+
+    assert_eq!(value["results"][0]["name"], Value::String("foo".into()));
+    assert_eq!(value["results"][1]["id"], Value::Integer(2));
+    assert_eq!(
+        value[String::from("results")][1][&"name".to_string()],
+        Value::String("bar".into())
+    );
+}
+
+#[test]
+fn missing() {
+    let value = results();
+
+    assert_eq!(value["missing"], Value::Null);
+    assert_eq!(value["missing"]["nested"][3], Value::Null);
+    assert_eq!(value["results"][2], Value::Null);
+    assert_eq!(value["results"]["name"], Value::Null);
+    assert_eq!(value[0], Value::Null);
+    assert_eq!(Value::Boolean(true)["key"], Value::Null);
+}
+
+#[test]
+fn index_mut() {
+    let mut value = results();
+
+    value["results"][0]["name"] = Value::String("baz".into());
+    assert_eq!(value["results"][0]["name"], Value::String("baz".into()));
+
+    value["results"][1]["extra"] = Value::Boolean(true);
+    assert_eq!(value["results"][1]["extra"], Value::Boolean(true));
+}
+
+#[test]
+fn auto_vivify() {
+    let mut value = Value::Null;
+    value["a"]["b"] = Value::Integer(1);
+
+    let mut inner = Map::new();
+    inner.insert("b".to_string(), Value::Integer(1));
+    let mut outer = Map::new();
+    outer.insert("a".to_string(), Value::Object(inner));
+    assert_eq!(value, Value::Object(outer));
+}
+
+#[test]
+#[should_panic(expected = "cannot access key \"a\" in Smile array")]
+fn index_mut_type_mismatch() {
+    let mut value = Value::Array(vec![]);
+    value["a"] = Value::Null;
+}
+
+#[test]
+#[should_panic(expected = "cannot access index 0 of Smile object")]
+fn index_mut_array_type_mismatch() {
+    let mut value = Value::Object(Map::new());
+    value[0] = Value::Null;
+}
+
+#[test]
+#[should_panic(expected = "cannot access index 2 of Smile array of length 2")]
+fn index_mut_out_of_bounds() {
+    let mut value = results();
+    value["results"][2] = Value::Null;
+}
+
+#[test]
+fn get() {
+    let value = results();
+    assert_eq!(
+        value.get("results").and_then(|v| v.get(1)),
+        Some(&value["results"][1])
+    );
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(value.get(0), None);
+    assert_eq!(value["results"].get(2), None);
+    assert_eq!(value["results"].get("a"), None);
+    assert_eq!(Value::Integer(1).get("a"), None);
+    assert_eq!(Value::Null.get(0), None);
+    let key = "results".to_string();
+    assert_eq!(value.get(key).map(Value::kind), Some(ValueKind::Array));
+}
+
+#[test]
+fn get_mut() {
+    let mut value = results();
+    *value.get_mut("results").unwrap().get_mut(0).unwrap() = Value::Integer(5);
+    assert_eq!(value["results"][0], Value::Integer(5));
+
+    assert_eq!(value.get_mut("missing"), None);
+    assert_eq!(value.get_mut(0), None);
+    assert_eq!(Value::Null.get_mut("a"), None);
+}
+
+#[test]
+fn take() {
+    let mut value = results();
+    let first = value["results"][0].clone();
+
+    let taken = value["results"][0].take();
+    assert_eq!(taken, first);
+    assert_eq!(value["results"][0], Value::Null);
+    assert_eq!(value["results"].as_array().unwrap().len(), 2);
+
+    let results = value.get_mut("results").unwrap().take();
+    assert_eq!(results.as_array().unwrap().len(), 2);
+    assert_eq!(value["results"], Value::Null);
+    assert!(value.is_object());
+
+    let mut scalar = Value::Integer(1);
+    assert_eq!(scalar.take(), Value::Integer(1));
+    assert_eq!(scalar, Value::Null);
+}
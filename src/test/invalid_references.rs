@@ -0,0 +1,254 @@
+use crate::de::{Deserializer, IoRead};
+use crate::value::Value;
+use crate::{smile, Error};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::io::BufReader;
+
+const SHARED_STRINGS: u8 = 0x02;
+const SHARED_PROPERTIES: u8 = 0x01;
+
+fn document(flags: u8, body: &[u8]) -> Vec<u8> {
+    let mut buf = vec![b':', b')', b'\n', flags];
+    buf.extend_from_slice(body);
+    buf
+}
+
+fn from_buf_reader(buf: &[u8], capacity: usize) -> Result<Value, Error> {
+    let mut de = Deserializer::new(IoRead::new(BufReader::with_capacity(capacity, buf)));
+    let value = Value::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+// Every reader must produce the same result, whether borrowing from the input or copying out of it.
+fn check(flags: u8, body: &[u8], expected: Result<Value, &str>) {
+    let buf = document(flags, body);
+
+    let results = vec![
+        ("from_slice", crate::from_slice::<Value>(&buf)),
+        (
+            "from_mut_slice",
+            crate::from_mut_slice::<Value>(&mut buf.clone()),
+        ),
+        (
+            "from_cow",
+            crate::from_cow::<Value>(Cow::Owned(buf.clone())),
+        ),
+        ("from_reader", crate::from_reader::<Value, _>(&buf[..])),
+        ("BufReader", from_buf_reader(&buf, 1)),
+    ];
+
+    for (name, result) in results {
+        match (&expected, result) {
+            (Ok(expected), Ok(actual)) => assert_eq!(*expected, actual, "{}", name),
+            (Err(expected), Err(actual)) => assert_eq!(*expected, actual.to_string(), "{}", name),
+            (expected, actual) => panic!("{}: expected {:?}, got {:?}", name, expected, actual),
+        }
+    }
+}
+
+const INVALID: Result<Value, &str> = Err("invalid string reference");
+
+/// An array of tiny ASCII string values "a", "b", ..., followed by `tail` and the end of the array.
+fn values(count: u8, tail: &[u8]) -> Vec<u8> {
+    let mut body = vec![0xf8];
+    for i in 0..count {
+        body.extend_from_slice(&[0x40, b'a' + i]);
+    }
+    body.extend_from_slice(tail);
+    body.push(0xf9);
+    body
+}
+
+/// An array of objects with the short ASCII keys "a", "b", ..., followed by an object with the key `tail`.
+fn keys(count: u8, tail: &[u8]) -> Vec<u8> {
+    let mut body = vec![0xf8];
+    for i in 0..count {
+        body.extend_from_slice(&[0xfa, 0x80, b'a' + i, 0x21, 0xfb]);
+    }
+    body.push(0xfa);
+    body.extend_from_slice(tail);
+    body.extend_from_slice(&[0x21, 0xfb, 0xf9]);
+    body
+}
+
+#[test]
+fn value_before_any_string() {
+    check(SHARED_STRINGS, &[0x01], INVALID);
+    check(SHARED_STRINGS, &[0xec, 0x00], INVALID);
+    check(SHARED_STRINGS, &values(0, &[0x01]), INVALID);
+    check(SHARED_STRINGS, &values(0, &[0xec, 0x00]), INVALID);
+}
+
+#[test]
+fn key_before_any_string() {
+    check(SHARED_PROPERTIES, &[0xfa, 0x40, 0x21, 0xfb], INVALID);
+    check(SHARED_PROPERTIES, &[0xfa, 0x30, 0x00, 0x21, 0xfb], INVALID);
+    check(SHARED_PROPERTIES, &keys(0, &[0x40]), INVALID);
+    check(SHARED_PROPERTIES, &keys(0, &[0x30, 0x00]), INVALID);
+}
+
+#[test]
+fn value_reference_bounds() {
+    // the cache holds "a", "b", and "c"
+    let valid = smile!(["a", "b", "c", "c"]);
+    check(SHARED_STRINGS, &values(3, &[0x03]), Ok(valid.clone()));
+    check(SHARED_STRINGS, &values(3, &[0xec, 0x02]), Ok(valid));
+    check(SHARED_STRINGS, &values(3, &[0x04]), INVALID);
+    check(SHARED_STRINGS, &values(3, &[0xec, 0x03]), INVALID);
+    check(SHARED_STRINGS, &values(3, &[0xed, 0x02]), INVALID);
+
+    check(
+        SHARED_STRINGS,
+        &values(3, &[0x01]),
+        Ok(smile!(["a", "b", "c", "a"])),
+    );
+}
+
+#[test]
+fn key_reference_bounds() {
+    // the cache holds "a", "b", and "c"
+    let valid = smile!([{"a": null}, {"b": null}, {"c": null}, {"c": null}]);
+    check(SHARED_PROPERTIES, &keys(3, &[0x42]), Ok(valid.clone()));
+    check(SHARED_PROPERTIES, &keys(3, &[0x30, 0x02]), Ok(valid));
+    check(SHARED_PROPERTIES, &keys(3, &[0x43]), INVALID);
+    check(SHARED_PROPERTIES, &keys(3, &[0x30, 0x03]), INVALID);
+    check(SHARED_PROPERTIES, &keys(3, &[0x31, 0x02]), INVALID);
+}
+
+#[test]
+fn sharing_disabled() {
+    check(0, &values(3, &[0x01]), INVALID);
+    check(0, &values(3, &[0xec, 0x00]), INVALID);
+    check(0, &keys(3, &[0x40]), INVALID);
+    check(0, &keys(3, &[0x30, 0x00]), INVALID);
+
+    // each kind of sharing only enables its own cache
+    check(SHARED_PROPERTIES, &values(3, &[0x01]), INVALID);
+    check(SHARED_STRINGS, &keys(3, &[0x40]), INVALID);
+}
+
+#[test]
+fn caches_are_separate() {
+    // keys and values are interned separately, so a value reference can't see a key and vice versa
+    let body = [0xfa, 0x80, b'k', 0x01, 0xfb];
+    check(SHARED_STRINGS | SHARED_PROPERTIES, &body, INVALID);
+
+    let body = [0xf8, 0x40, b'v', 0xfa, 0x40, 0x21, 0xfb, 0xf9];
+    check(SHARED_STRINGS | SHARED_PROPERTIES, &body, INVALID);
+
+    let body = [0xfa, 0x80, b'k', 0x40, b'v', 0x80, b'j', 0x01, 0xfb];
+    check(
+        SHARED_STRINGS | SHARED_PROPERTIES,
+        &body,
+        Ok(smile!({"k": "v", "j": "v"})),
+    );
+}
+
+#[test]
+fn uninterned_strings() {
+    // empty strings are never added to the cache
+    check(SHARED_STRINGS, &[0xf8, 0x20, 0x01, 0xf9], INVALID);
+
+    // nor are strings longer than 64 bytes
+    let mut body = vec![0xf8, 0xe0];
+    body.extend_from_slice(&[b'x'; 65]);
+    body.extend_from_slice(&[0xfc, 0x01, 0xf9]);
+    check(SHARED_STRINGS, &body, INVALID);
+
+    let mut body = vec![0xf8, 0x60 + 31];
+    body.extend_from_slice(&[b'x'; 64]);
+    body.extend_from_slice(&[0x01, 0xf9]);
+    check(
+        SHARED_STRINGS,
+        &body,
+        Ok(smile!([
+            (Value::from("x".repeat(64))),
+            (Value::from("x".repeat(64)))
+        ])),
+    );
+}
+
+fn numbered(i: usize) -> String {
+    format!("{:04}", i)
+}
+
+/// An array of `count` distinct 4 byte string values, followed by `tail`.
+fn many_values(count: usize, tail: &[u8]) -> (Vec<u8>, Vec<Value>) {
+    let mut body = vec![0xf8];
+    let mut elements = vec![];
+    for i in 0..count {
+        body.push(0x43);
+        body.extend_from_slice(numbered(i).as_bytes());
+        elements.push(Value::from(numbered(i)));
+    }
+    body.extend_from_slice(tail);
+    body.push(0xf9);
+    (body, elements)
+}
+
+/// An object with `count` distinct 4 byte keys, followed by the key `tail`.
+fn many_keys(count: usize, tail: &[u8]) -> (Vec<u8>, Vec<String>) {
+    let mut body = vec![0xfa];
+    let mut keys = vec![];
+    for i in 0..count {
+        body.push(0x83);
+        body.extend_from_slice(numbered(i).as_bytes());
+        body.push(0x21);
+        keys.push(numbered(i));
+    }
+    body.extend_from_slice(tail);
+    body.extend_from_slice(&[0x21, 0xfb]);
+    (body, keys)
+}
+
+#[test]
+fn full_value_cache() {
+    // the largest encodable reference points at the last entry of a full cache
+    let (body, mut elements) = many_values(1024, &[0xef, 0xff]);
+    elements.push(Value::from(numbered(1023)));
+    check(SHARED_STRINGS, &body, Ok(Value::Array(elements)));
+
+    let (body, _) = many_values(1023, &[0xef, 0xff]);
+    check(SHARED_STRINGS, &body, INVALID);
+}
+
+#[test]
+fn wrapped_value_cache() {
+    // the cache is cleared when a string is interned into a full cache, leaving only that string
+    let (body, mut elements) = many_values(1025, &[0x01]);
+    elements.push(Value::from(numbered(1024)));
+    check(SHARED_STRINGS, &body, Ok(Value::Array(elements)));
+
+    let (body, mut elements) = many_values(1025, &[0xec, 0x00]);
+    elements.push(Value::from(numbered(1024)));
+    check(SHARED_STRINGS, &body, Ok(Value::Array(elements)));
+
+    for tail in &[&[0x02][..], &[0xec, 0x01], &[0xef, 0xff], &[0x1f]] {
+        let (body, _) = many_values(1025, tail);
+        check(SHARED_STRINGS, &body, INVALID);
+    }
+}
+
+#[test]
+fn full_key_cache() {
+    let (body, keys) = many_keys(1024, &[0x33, 0xff]);
+    let expected = keys.into_iter().map(|k| (k, Value::Null)).collect();
+    check(SHARED_PROPERTIES, &body, Ok(Value::Object(expected)));
+
+    let (body, _) = many_keys(1023, &[0x33, 0xff]);
+    check(SHARED_PROPERTIES, &body, INVALID);
+}
+
+#[test]
+fn wrapped_key_cache() {
+    let (body, keys) = many_keys(1025, &[0x40]);
+    let expected = keys.into_iter().map(|k| (k, Value::Null)).collect();
+    check(SHARED_PROPERTIES, &body, Ok(Value::Object(expected)));
+
+    for tail in &[&[0x41][..], &[0x30, 0x01], &[0x33, 0xff], &[0x7f]] {
+        let (body, _) = many_keys(1025, tail);
+        check(SHARED_PROPERTIES, &body, INVALID);
+    }
+}
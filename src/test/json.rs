@@ -0,0 +1,151 @@
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+use base64::Engine;
+
+fn big_integer(v: i128) -> BigInteger {
+    BigInteger::from_be_bytes(v.to_be_bytes().to_vec())
+}
+
+fn run_test(value: Value, expected: &str) {
+    let actual = value.to_json_string();
+    assert_eq!(actual, expected);
+    serde_json::from_str::<serde_json::Value>(&actual).unwrap();
+}
+
+#[test]
+fn scalars() {
+    run_test(Value::Null, "null");
+    run_test(Value::Boolean(true), "true");
+    run_test(Value::Boolean(false), "false");
+    run_test(Value::Integer(i32::MIN), "-2147483648");
+    run_test(Value::Long(i64::MAX), "9223372036854775807");
+    run_test(Value::Float(0.1), "0.1");
+    run_test(Value::Double(1.), "1.0");
+    run_test(Value::Double(-1.5e300), "-1.5e300");
+}
+
+#[test]
+fn non_finite() {
+    run_test(Value::Float(f32::NAN), "\"NaN\"");
+    run_test(Value::Double(f64::INFINITY), "\"Infinity\"");
+    run_test(Value::Double(f64::NEG_INFINITY), "\"-Infinity\"");
+}
+
+#[test]
+fn big_integers() {
+    for v in [
+        0,
+        1,
+        -1,
+        127,
+        128,
+        -128,
+        -129,
+        999_999_999,
+        1_000_000_000,
+        -1_000_000_000,
+        i64::MIN as i128,
+        u64::MAX as i128,
+        i128::MAX,
+        i128::MIN,
+    ]
+    .iter()
+    {
+        run_test(Value::BigInteger(big_integer(*v)), &v.to_string());
+    }
+
+    run_test(Value::BigInteger(BigInteger::from_be_bytes(vec![])), "0");
+    run_test(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![0xff; 3])),
+        "-1",
+    );
+    // 2^128
+    let mut buf = vec![0; 17];
+    buf[0] = 1;
+    run_test(
+        Value::BigInteger(BigInteger::from_be_bytes(buf)),
+        "340282366920938463463374607431768211456",
+    );
+}
+
+#[test]
+fn big_decimals() {
+    let cases = [
+        (123, 0, "123"),
+        (-123, 0, "-123"),
+        (123, -1, "1.23E+3"),
+        (123, -3, "1.23E+5"),
+        (123, 1, "12.3"),
+        (123, 3, "0.123"),
+        (123, 5, "0.00123"),
+        (123, 10, "1.23E-8"),
+        (-123, 12, "-1.23E-10"),
+        (0, 0, "0"),
+        (0, 2, "0.00"),
+        (0, -2, "0E+2"),
+        (5, -9, "5E+9"),
+        (1, 6, "0.000001"),
+        (1, 7, "1E-7"),
+    ];
+
+    for (unscaled, scale, expected) in cases.iter() {
+        run_test(
+            Value::BigDecimal(BigDecimal::new(big_integer(*unscaled), *scale)),
+            expected,
+        );
+    }
+
+    // too large to be parsed as an f64, but still valid JSON
+    let value = Value::BigDecimal(BigDecimal::new(big_integer(i128::MIN), i32::MIN));
+    assert_eq!(
+        value.to_json_string(),
+        "-1.70141183460469231731687303715884105728E+2147483686"
+    );
+}
+
+#[test]
+fn binary() {
+    for len in 0..10 {
+        let data = (0..len)
+            .map(|i: u8| i.wrapping_mul(37).wrapping_add(200))
+            .collect::<Vec<u8>>();
+        let expected = format!(
+            "\"{}\"",
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        );
+        run_test(Value::binary(data), &expected);
+    }
+}
+
+#[test]
+fn string_escapes() {
+    run_test(Value::String("".into()), r#""""#);
+    run_test(Value::String("foo".into()), r#""foo""#);
+    run_test(Value::String("a\"b\\c/".into()), r#""a\"b\\c/""#);
+    run_test(Value::String("\n\r\t\u{8}\u{c}".into()), r#""\n\r\t\b\f""#);
+    run_test(
+        Value::String("\u{0}\u{1}\u{1f}\u{7f}".into()),
+        "\"\\u0000\\u0001\\u001f\u{7f}\"",
+    );
+    run_test(Value::String("héllo ☃ 😀".into()), "\"héllo ☃ 😀\"");
+
+    let all = (0..0x80u8).map(char::from).collect::<String>();
+    let json = Value::from(all.clone()).to_json_string();
+    assert_eq!(serde_json::from_str::<String>(&json).unwrap(), all);
+}
+
+#[test]
+fn containers() {
+    run_test(Value::Array(vec![]), "[]");
+    run_test(Value::Object(Map::new()), "{}");
+
+    let mut inner = Map::new();
+    inner.insert("b\n".to_string(), Value::Array(vec![Value::Null]));
+    inner.insert("c".to_string(), Value::binary(vec![1, 2, 3]));
+    let mut outer = Map::new();
+    outer.insert("a".to_string(), Value::Object(inner));
+    outer.insert("d".to_string(), Value::Integer(1));
+    run_test(
+        Value::Array(vec![Value::Object(outer), Value::Boolean(true)]),
+        r#"[{"a":{"b\n":[null],"c":"AQID"},"d":1},true]"#,
+    );
+}
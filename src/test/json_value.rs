@@ -0,0 +1,243 @@
+#![cfg(feature = "json")]
+
+use crate::ser::Serializer;
+use crate::value::{BigDecimal, BigInteger, JsonBinary, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serde_json::value::RawValue;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCase {
+    #[serde(default)]
+    raw_binary: bool,
+    #[serde(default)]
+    shared_strings: bool,
+    #[serde(default)]
+    shared_properties: bool,
+    #[serde(default)]
+    write_end_marker: bool,
+    value: Box<RawValue>,
+}
+
+fn for_each_case(category: &str, mut f: impl FnMut(&Path, TestCase, Vec<u8>)) {
+    for r in fs::read_dir(format!("tests/{}", category)).unwrap() {
+        let path = r.unwrap().path();
+        if path.extension() != Some(OsStr::new("json")) {
+            continue;
+        }
+
+        println!("testing {}", path.display());
+        let test_case = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let smile = fs::read(path.with_extension("smile")).unwrap();
+        f(&path, test_case, smile);
+    }
+}
+
+// Big numbers are compared as text since serde_json parses them into lossy floats.
+fn decimal_text(value: &serde_json::Value) -> f64 {
+    match value {
+        serde_json::Value::Number(v) => v.as_f64().unwrap(),
+        serde_json::Value::String(v) => v.parse().unwrap(),
+        v => panic!("unexpected value {}", v),
+    }
+}
+
+#[test]
+fn fixtures_to_json() {
+    for category in [
+        "integer",
+        "long",
+        "string",
+        "float",
+        "double",
+        "boolean",
+        "binary",
+        "null",
+        "list",
+        "map",
+        "shared_property",
+        "shared_string",
+    ] {
+        for_each_case(category, |_, test_case, smile| {
+            let expected =
+                serde_json::from_str::<serde_json::Value>(test_case.value.get()).unwrap();
+            let value = crate::from_slice::<Value>(&smile).unwrap();
+            assert_eq!(serde_json::Value::from(value), expected);
+        });
+    }
+}
+
+#[test]
+fn big_integer_fixtures_to_json() {
+    for_each_case("big_integer", |_, test_case, smile| {
+        let value = crate::from_slice::<Value>(&smile).unwrap();
+        let actual = serde_json::Value::from(value);
+        match &actual {
+            serde_json::Value::Number(v) => assert_eq!(v.to_string(), test_case.value.get()),
+            serde_json::Value::String(v) => assert_eq!(v, test_case.value.get()),
+            v => panic!("unexpected value {}", v),
+        }
+    });
+}
+
+#[test]
+fn big_decimal_fixtures_to_json() {
+    for_each_case("big_decimal", |path, test_case, smile| {
+        let expected = serde_json::from_str::<serde_json::Value>(test_case.value.get()).unwrap();
+        let value = crate::from_slice::<Value>(&smile).unwrap();
+        let actual = serde_json::Value::from(value);
+
+        if path.file_stem() == Some(OsStr::new("1E-1000000")) {
+            assert_eq!(actual, json!("1E-1000000"));
+        } else {
+            assert!(actual.is_number(), "{}", actual);
+            assert_eq!(decimal_text(&actual), decimal_text(&expected));
+        }
+    });
+}
+
+#[test]
+fn fixtures_from_json() {
+    for category in [
+        "integer",
+        "long",
+        "string",
+        "double",
+        "boolean",
+        "null",
+        "list",
+        "map",
+        "shared_property",
+        "shared_string",
+    ] {
+        for_each_case(category, |_, test_case, expected| {
+            let json = serde_json::from_str::<serde_json::Value>(test_case.value.get()).unwrap();
+            let value = Value::from(json.clone());
+
+            let mut serializer = Serializer::builder()
+                .raw_binary(test_case.raw_binary)
+                .shared_strings(test_case.shared_strings)
+                .shared_properties(test_case.shared_properties)
+                .build(vec![]);
+            value.serialize(&mut serializer).unwrap();
+            if test_case.write_end_marker {
+                serializer.end().unwrap();
+            }
+            let actual = serializer.into_inner();
+
+            // without preserve_order, object keys are re-sorted so the encodings only match as values
+            if cfg!(feature = "preserve_order") {
+                assert_eq!(actual, expected);
+            } else {
+                assert_eq!(
+                    crate::from_slice::<Value>(&actual).unwrap(),
+                    crate::from_slice::<Value>(&expected).unwrap()
+                );
+            }
+
+            assert_eq!(serde_json::Value::from(value), json);
+        });
+    }
+}
+
+#[test]
+fn binary_representation() {
+    let value = Value::binary(vec![0, 1, 255]);
+    assert_eq!(value.to_json_value(JsonBinary::Base64), json!("AAH/"));
+    assert_eq!(value.to_json_value(JsonBinary::Array), json!([0, 1, 255]));
+
+    let value = Value::Array(vec![Value::binary(vec![7])]);
+    assert_eq!(value.to_json_value(JsonBinary::Array), json!([[7]]));
+
+    for_each_case("binary", |_, test_case, smile| {
+        let expected = serde_json::from_str::<String>(test_case.value.get()).unwrap();
+        let expected = crate::encoding::decode_base64(&expected).unwrap();
+        let value = crate::from_slice::<Value>(&smile).unwrap();
+        assert_eq!(value.to_json_value(JsonBinary::Array), json!(expected));
+    });
+}
+
+#[test]
+fn big_integer_policy() {
+    let value = Value::BigInteger(BigInteger::from_be_bytes(vec![
+        0, 255, 255, 255, 255, 255, 255, 255, 255,
+    ]));
+    assert_eq!(serde_json::Value::from(value), json!(u64::MAX));
+
+    let value = Value::BigInteger(BigInteger::from_be_bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]));
+    assert_eq!(
+        serde_json::Value::from(value),
+        json!("18446744073709551616")
+    );
+
+    let value = Value::BigInteger(BigInteger::from_be_bytes(vec![
+        0xff, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]));
+    assert_eq!(
+        serde_json::Value::from(value),
+        json!("-18446744073709551616")
+    );
+
+    let value = Value::BigInteger(BigInteger::from_be_bytes(vec![0xff]));
+    assert_eq!(serde_json::Value::from(value), json!(-1));
+}
+
+#[test]
+fn big_decimal_policy() {
+    let decimal = |unscaled: i64, scale: i32| {
+        let unscaled = Value::from(unscaled);
+        let unscaled = match unscaled {
+            Value::Integer(v) => i64::from(v),
+            Value::Long(v) => v,
+            _ => unreachable!(),
+        };
+        Value::BigDecimal(BigDecimal::new(
+            BigInteger::from_be_bytes(unscaled.to_be_bytes().to_vec()),
+            scale,
+        ))
+    };
+
+    assert_eq!(serde_json::Value::from(decimal(10025, 2)), json!(100.25));
+    assert_eq!(serde_json::Value::from(decimal(1, 1)), json!(0.1));
+    assert_eq!(serde_json::Value::from(decimal(-5, 0)), json!(-5.0));
+    assert_eq!(serde_json::Value::from(decimal(1, -5)), json!(100000.0));
+    assert_eq!(serde_json::Value::from(decimal(0, 3)), json!(0.0));
+    assert_eq!(
+        serde_json::Value::from(decimal(1234567890123456789, 18)),
+        json!("1.234567890123456789"),
+    );
+    assert_eq!(serde_json::Value::from(decimal(1, -400)), json!("1E+400"));
+    assert_eq!(serde_json::Value::from(decimal(1, 400)), json!("1E-400"));
+}
+
+#[test]
+fn float_policy() {
+    assert_eq!(
+        serde_json::Value::from(Value::Float(0.1)),
+        json!(f64::from(0.1f32))
+    );
+    assert_eq!(
+        serde_json::Value::from(Value::Float(f32::NAN)),
+        json!("NaN")
+    );
+    assert_eq!(
+        serde_json::Value::from(Value::Double(f64::INFINITY)),
+        json!("Infinity")
+    );
+    assert_eq!(
+        serde_json::Value::from(Value::Double(f64::NEG_INFINITY)),
+        json!("-Infinity")
+    );
+}
+
+#[test]
+fn from_json_numbers() {
+    assert_eq!(Value::from(json!(1)), Value::Integer(1));
+    assert_eq!(Value::from(json!(i64::MIN)), Value::Long(i64::MIN));
+    assert_eq!(Value::from(json!(u64::MAX)), Value::from(u64::MAX));
+    assert_eq!(Value::from(json!(1.5)), Value::Double(1.5));
+}
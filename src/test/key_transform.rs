@@ -0,0 +1,241 @@
+use crate::ser::{Builder, KeyTransform};
+use crate::value::{Value, ValueRef};
+use crate::{smile, Serializer};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::borrow::Cow;
+
+fn serialize<T>(builder: &Builder, value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    let mut ser = builder.build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn lowercase() -> Builder {
+    Serializer::builder()
+        .key_transform(KeyTransform::Lowercase)
+        .clone()
+}
+
+fn map(entries: &[(&str, i32)]) -> IndexMap<String, i32> {
+    entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+#[test]
+fn default_unchanged() {
+    let value = map(&[("Name", 1)]);
+    assert_eq!(
+        serialize(&Serializer::builder(), &value),
+        crate::to_vec(&value).unwrap()
+    );
+    assert_eq!(
+        crate::from_slice::<Value>(&crate::to_vec(&value).unwrap()).unwrap(),
+        smile!({"Name": 1})
+    );
+}
+
+#[test]
+fn lowercase_map_keys() {
+    let value = map(&[("Name", 1), ("ÉTÉ", 2), ("already", 3)]);
+    let buf = serialize(&lowercase(), &value);
+    assert_eq!(
+        crate::from_slice::<Value>(&buf).unwrap(),
+        smile!({"name": 1, "été": 2, "already": 3}),
+    );
+}
+
+#[test]
+fn lowercase_struct_fields() {
+    #[derive(Serialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Upper {
+        user_name: &'static str,
+        user_id: i32,
+    }
+
+    #[derive(Serialize)]
+    enum Variant {
+        Struct {
+            #[serde(rename = "Field")]
+            field: i32,
+        },
+        Newtype(i32),
+    }
+
+    let buf = serialize(
+        &lowercase(),
+        &Upper {
+            user_name: "Bob",
+            user_id: 1,
+        },
+    );
+    assert_eq!(
+        crate::from_slice::<Value>(&buf).unwrap(),
+        smile!({"username": "Bob", "userid": 1}),
+    );
+
+    let buf = serialize(
+        &lowercase(),
+        &[Variant::Struct { field: 1 }, Variant::Newtype(2)],
+    );
+    assert_eq!(
+        crate::from_slice::<Value>(&buf).unwrap(),
+        smile!([{"struct": {"field": 1}}, {"newtype": 2}]),
+    );
+}
+
+#[test]
+fn shared_across_casings() {
+    // every key after the first is a back reference to the same cache entry
+    let values = vec![
+        map(&[("Id", 1)]),
+        map(&[("ID", 2)]),
+        map(&[("id", 3)]),
+        map(&[("iD", 4)]),
+    ];
+    let lowercased = vec![
+        map(&[("id", 1)]),
+        map(&[("id", 2)]),
+        map(&[("id", 3)]),
+        map(&[("id", 4)]),
+    ];
+
+    let buf = serialize(&lowercase(), &values);
+    assert_eq!(buf, crate::to_vec(&lowercased).unwrap());
+    assert_eq!(buf.iter().filter(|&&b| b == 0x40).count(), 3, "{:x?}", buf);
+}
+
+#[test]
+fn shared_between_static_and_dynamic_keys() {
+    #[derive(Serialize)]
+    struct Record {
+        #[serde(rename = "Key")]
+        key: i32,
+    }
+
+    #[derive(Serialize)]
+    struct Both {
+        a: Record,
+        b: IndexMap<String, i32>,
+        c: Record,
+    }
+
+    let value = Both {
+        a: Record { key: 1 },
+        b: map(&[("KEY", 2)]),
+        c: Record { key: 3 },
+    };
+    let buf = serialize(&lowercase(), &value);
+    assert_eq!(
+        buf,
+        crate::to_vec(&smile!({"a": {"key": 1}, "b": {"key": 2}, "c": {"key": 3}})).unwrap(),
+    );
+
+    // memoization of static keys doesn't change the output
+    let buf_memoized = serialize(lowercase().memoize_keys(true), &value);
+    assert_eq!(buf, buf_memoized);
+}
+
+#[test]
+fn without_shared_properties() {
+    let builder = lowercase().shared_properties(false).clone();
+    let buf = serialize(&builder, &vec![map(&[("A", 1)]), map(&[("A", 2)])]);
+    assert_eq!(
+        buf,
+        serialize(
+            Serializer::builder().shared_properties(false),
+            &smile!([{"a": 1}, {"a": 2}])
+        ),
+    );
+}
+
+fn kebab_to_snake(key: &str) -> Cow<'_, str> {
+    if key.contains('-') {
+        Cow::Owned(key.replace('-', "_"))
+    } else {
+        Cow::Borrowed(key)
+    }
+}
+
+#[test]
+fn custom() {
+    #[derive(Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct Kebab {
+        first_name: &'static str,
+        age: i32,
+    }
+
+    let builder = Serializer::builder()
+        .key_transform(KeyTransform::Custom(kebab_to_snake))
+        .clone();
+    let buf = serialize(
+        &builder,
+        &[
+            Kebab {
+                first_name: "a",
+                age: 1,
+            },
+            Kebab {
+                first_name: "b",
+                age: 2,
+            },
+        ],
+    );
+    #[derive(Serialize)]
+    struct Snake {
+        first_name: &'static str,
+        age: i32,
+    }
+
+    let expected = [
+        Snake {
+            first_name: "a",
+            age: 1,
+        },
+        Snake {
+            first_name: "b",
+            age: 2,
+        },
+    ];
+    assert_eq!(buf, crate::to_vec(&expected).unwrap());
+}
+
+#[test]
+fn colliding_keys() {
+    // keys which collide after transformation are written as duplicates
+    let buf = serialize(&lowercase(), &map(&[("A", 1), ("a", 2)]));
+    assert_eq!(
+        crate::from_slice::<ValueRef<'_>>(&buf).unwrap(),
+        ValueRef::Object(vec![
+            (Cow::Borrowed("a"), ValueRef::Integer(1)),
+            (Cow::Borrowed("a"), ValueRef::Integer(2)),
+        ]),
+    );
+    // and the last value wins when deserialized into a map
+    assert_eq!(crate::from_slice::<Value>(&buf).unwrap(), smile!({"a": 2}));
+}
+
+#[test]
+fn rearm_resets_static_keys() {
+    #[derive(Serialize)]
+    struct Record {
+        #[serde(rename = "Key")]
+        key: i32,
+    }
+
+    let mut ser = lowercase().build(vec![]);
+    Record { key: 1 }.serialize(&mut ser).unwrap();
+    ser.end().unwrap();
+    ser.rearm_header(&Serializer::builder()).unwrap();
+    Record { key: 2 }.serialize(&mut ser).unwrap();
+    let buf = ser.into_inner();
+
+    let mut expected = crate::to_vec(&smile!({"key": 1})).unwrap();
+    expected.push(0xff);
+    expected.extend_from_slice(&crate::to_vec(&smile!({"Key": 2})).unwrap());
+    assert_eq!(buf, expected);
+}
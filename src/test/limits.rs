@@ -0,0 +1,131 @@
+use crate::smile;
+use crate::value::{self, BigInteger, Limits, Value};
+
+const HEADER: &[u8] = b":)\n\x00";
+
+fn document(body: &[u8]) -> Vec<u8> {
+    let mut buf = HEADER.to_vec();
+    buf.extend_from_slice(body);
+    buf
+}
+
+fn check_err(buf: &[u8], limits: &Limits, expected: &str) {
+    let e = value::from_slice_with_limits(buf, limits).unwrap_err();
+    assert_eq!(e.to_string(), expected);
+
+    let e = value::from_reader_with_limits(buf, limits).unwrap_err();
+    assert_eq!(e.to_string(), expected);
+}
+
+#[test]
+fn within_limits() {
+    let value = smile!({
+        "a": [1, 2, "hello"],
+        "b": Value::binary(vec![1, 2, 3]),
+        "c": Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20])),
+    });
+    let buf = crate::to_vec(&value).unwrap();
+
+    let mut limits = Limits::new();
+    limits
+        .max_string_bytes(5)
+        .max_binary_bytes(3)
+        .max_array_elements(3)
+        .max_object_entries(3)
+        .max_total_nodes(7);
+
+    assert_eq!(value::from_slice_with_limits(&buf, &limits).unwrap(), value);
+    assert_eq!(
+        value::from_reader_with_limits(&buf[..], &limits).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn string_bytes() {
+    let buf = crate::to_vec(&"hello world").unwrap();
+    let mut limits = Limits::new();
+    limits.max_string_bytes(10);
+    check_err(&buf, &limits, "max_string_bytes limit of 10 exceeded");
+}
+
+#[test]
+fn key_bytes() {
+    let buf = crate::to_vec(&smile!({"hello world": 1})).unwrap();
+    let mut limits = Limits::new();
+    limits.max_string_bytes(10);
+    check_err(&buf, &limits, "max_string_bytes limit of 10 exceeded");
+}
+
+#[test]
+fn binary_bytes() {
+    let buf = crate::to_vec(&Value::binary(vec![0; 100])).unwrap();
+    let mut limits = Limits::new();
+    limits.max_binary_bytes(99);
+    check_err(&buf, &limits, "max_binary_bytes limit of 99 exceeded");
+
+    // raw binary of 100 bytes
+    let mut body = vec![0xfd, 0x01, 0xa4];
+    body.extend_from_slice(&[0; 100]);
+    check_err(
+        &document(&body),
+        &limits,
+        "max_binary_bytes limit of 99 exceeded",
+    );
+}
+
+#[test]
+fn huge_claimed_binary_length() {
+    // a 7-bit binary value claiming to be 2^60 bytes long, with no data following it
+    let buf = document(&[0xe8, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0x7f, 0xbf]);
+    let mut limits = Limits::new();
+    limits.max_binary_bytes(1024);
+    check_err(&buf, &limits, "EOF while parsing a value");
+}
+
+#[test]
+fn array_elements_unterminated() {
+    // the array is never closed, so the limit must be enforced before reaching the end of the input
+    let mut body = vec![0xf8];
+    body.extend_from_slice(&[0xc2; 10_000]);
+    let mut limits = Limits::new();
+    limits.max_array_elements(100);
+    check_err(
+        &document(&body),
+        &limits,
+        "max_array_elements limit of 100 exceeded",
+    );
+}
+
+#[test]
+fn object_entries_unterminated() {
+    // the same single-character key repeated, with the object never closed
+    let mut body = vec![0xfa];
+    for _ in 0..10_000 {
+        body.extend_from_slice(&[0x80, b'a', 0xc2]);
+    }
+    let mut limits = Limits::new();
+    limits.max_object_entries(100);
+    check_err(
+        &document(&body),
+        &limits,
+        "max_object_entries limit of 100 exceeded",
+    );
+}
+
+#[test]
+fn total_nodes() {
+    let buf = crate::to_vec(&smile!([[1, 2], [3, 4], {"a": 5}])).unwrap();
+    let mut limits = Limits::new();
+    limits.max_total_nodes(9);
+    assert!(value::from_slice_with_limits(&buf, &limits).is_ok());
+
+    limits.max_total_nodes(8);
+    check_err(&buf, &limits, "max_total_nodes limit of 8 exceeded");
+}
+
+#[test]
+fn other_errors_unchanged() {
+    let buf = document(&[0xf8, 0xc2]);
+    check_err(&buf, &Limits::new(), "EOF while parsing array");
+}
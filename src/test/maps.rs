@@ -101,3 +101,104 @@ impl Display for TestNewtype {
 fn newtype_keys() {
     run_test(TestNewtype("hello".to_string()))
 }
+
+fn key_result<T>(key: &str) -> Result<T, String>
+where
+    T: DeserializeOwned + Eq + Hash + Debug,
+{
+    let bytes = crate::to_vec(&HashMap::<_, _>::from_iter([(key, 0)])).unwrap();
+    let direct = crate::from_slice::<HashMap<T, i32>>(&bytes);
+
+    let value = crate::from_slice::<crate::value::Value>(&bytes).unwrap();
+    let from_value = crate::value::from_value::<HashMap<T, i32>>(value.clone());
+    let from_ref = HashMap::<T, i32>::deserialize(&value);
+
+    let results = [direct, from_value, from_ref].map(|r| {
+        r.map(|m| m.into_iter().next().unwrap().0)
+            .map_err(|e| e.to_string())
+    });
+    let [a, b, c] = results;
+    assert_eq!(a, b);
+    assert_eq!(a, c);
+    a
+}
+
+macro_rules! integer_key_tests {
+    ($($name:ident: $ty:ident,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                run_test($ty::MIN);
+                run_test($ty::MAX);
+                assert_eq!(key_result::<$ty>(&$ty::MIN.to_string()), Ok($ty::MIN));
+                assert_eq!(key_result::<$ty>(&$ty::MAX.to_string()), Ok($ty::MAX));
+                assert_eq!(key_result::<$ty>("-0"), Ok(0));
+                assert_eq!(key_result::<$ty>("007"), Ok(7));
+
+                let overflow = format!("{}0", $ty::MAX);
+                assert_eq!(
+                    key_result::<$ty>(&overflow),
+                    Err(format!(
+                        "invalid value: string {:?}, expected an in-range {} map key",
+                        overflow,
+                        stringify!($ty),
+                    )),
+                );
+                let underflow = format!("-{}0", $ty::MAX);
+                assert_eq!(
+                    key_result::<$ty>(&underflow),
+                    Err(format!(
+                        "invalid value: string {:?}, expected an in-range {} map key",
+                        underflow,
+                        stringify!($ty),
+                    )),
+                );
+
+                for malformed in ["+5", " 5", "5 ", "\t-5", "+0"] {
+                    assert_eq!(
+                        key_result::<$ty>(malformed),
+                        Err(format!(
+                            "invalid value: string {:?}, expected a decimal {} map key",
+                            malformed,
+                            stringify!($ty),
+                        )),
+                    );
+                }
+
+                for non_numeric in ["", "-", "+", "five", "5a", "--5", "1.5"] {
+                    assert_eq!(
+                        key_result::<$ty>(non_numeric),
+                        Err(format!(
+                            "error in Deserialize impl: invalid type: string {:?}, expected {}",
+                            non_numeric,
+                            stringify!($ty),
+                        )),
+                    );
+                }
+            }
+        )*
+    };
+}
+
+integer_key_tests! {
+    u8_key_boundaries: u8,
+    u16_key_boundaries: u16,
+    u32_key_boundaries: u32,
+    u64_key_boundaries: u64,
+    u128_key_boundaries: u128,
+    i8_key_boundaries: i8,
+    i16_key_boundaries: i16,
+    i32_key_boundaries: i32,
+    i64_key_boundaries: i64,
+    i128_key_boundaries: i128,
+}
+
+#[test]
+fn negative_keys() {
+    run_test(-5i32);
+    assert_eq!(key_result::<i32>("-5"), Ok(-5));
+    assert_eq!(
+        key_result::<u32>("-5"),
+        Err("invalid value: string \"-5\", expected an in-range u32 map key".to_string()),
+    );
+}
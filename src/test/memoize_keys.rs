@@ -0,0 +1,100 @@
+use crate::ser::Builder;
+use crate::{Deserializer, Serializer};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+fn serialize_all<T>(builder: &Builder, values: &[T]) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = builder.build(vec![]);
+    for value in values {
+        value.serialize(&mut ser).unwrap();
+    }
+    ser.into_inner()
+}
+
+fn check<T>(values: &[T])
+where
+    T: Serialize,
+{
+    let expected = serialize_all(&Builder::jackson_defaults(), values);
+    let actual = serialize_all(Builder::jackson_defaults().memoize_keys(true), values);
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn repeated_map() {
+    let map = (0..100)
+        .map(|i| (format!("key{}", i), i))
+        .collect::<IndexMap<_, _>>();
+    check(&[&map; 100]);
+}
+
+#[test]
+fn cache_reset() {
+    // more distinct keys than fit in the shared property cache
+    let map = (0..1500)
+        .map(|i| (format!("key{}", i), i))
+        .collect::<IndexMap<_, _>>();
+    check(&[&map; 5]);
+}
+
+#[test]
+fn struct_fields() {
+    #[derive(Serialize)]
+    struct Test {
+        foo: i32,
+        bar: i32,
+    }
+
+    check(&[&Test { foo: 1, bar: 2 }; 10]);
+}
+
+#[test]
+fn reused_allocations() {
+    fn map(i: i32) -> BTreeMap<String, i32> {
+        let mut map = BTreeMap::new();
+        map.insert(format!("key{:04}", i % 1200), i);
+        map
+    }
+
+    // keys with the same length that are allocated and freed in turn are likely to share an address
+    let mut ser = Builder::jackson_defaults().memoize_keys(true).build(vec![]);
+    for i in 0..2000 {
+        map(i).serialize(&mut ser).unwrap();
+    }
+    let buf = ser.into_inner();
+
+    let mut ser = Serializer::new(vec![]);
+    for i in 0..2000 {
+        map(i).serialize(&mut ser).unwrap();
+    }
+    assert_eq!(buf, ser.into_inner());
+
+    let actual = Deserializer::from_slice(&buf)
+        .into_iter::<BTreeMap<String, i32>>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let expected = (0..2000).map(map).collect::<Vec<_>>();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn memo_hits() {
+    let map = (0..100)
+        .map(|i| (format!("key{}", i), i))
+        .collect::<IndexMap<_, _>>();
+
+    let mut ser = Builder::jackson_defaults().memoize_keys(true).build(vec![]);
+    for _ in 0..10 {
+        map.serialize(&mut ser).unwrap();
+    }
+    // every key is memoized the first time it's seen and found in the memo after that
+    assert_eq!(ser.key_memo_hits(), 900);
+
+    let mut ser = Builder::jackson_defaults().build(vec![]);
+    map.serialize(&mut ser).unwrap();
+    assert_eq!(ser.key_memo_hits(), 0);
+}
@@ -0,0 +1,126 @@
+use crate::smile;
+use crate::value::{ArrayMerge, MergeOptions, Value};
+
+#[test]
+fn deep_merge() {
+    let mut base = smile!({
+        "name": "service",
+        "limits": {"cpu": 1, "memory": 512},
+        "tags": ["a"],
+        "debug": false
+    });
+    base.merge(smile!({
+        "limits": {"memory": 1024, "disk": 10},
+        "tags": ["b"],
+        "debug": true,
+        "region": "us"
+    }));
+
+    assert_eq!(
+        base,
+        smile!({
+            "name": "service",
+            "limits": {"cpu": 1, "memory": 1024, "disk": 10},
+            "tags": ["b"],
+            "debug": true,
+            "region": "us"
+        }),
+    );
+}
+
+#[test]
+fn key_order() {
+    let mut base = smile!({"c": 1, "a": 2, "b": 3});
+    base.merge(smile!({"z": 4, "a": 5, "d": 6}));
+
+    let keys = base.as_object().unwrap().keys().collect::<Vec<_>>();
+    if cfg!(feature = "preserve_order") {
+        assert_eq!(keys, ["c", "a", "b", "z", "d"]);
+    } else {
+        assert_eq!(keys, ["a", "b", "c", "d", "z"]);
+    }
+    assert_eq!(base, smile!({"c": 1, "a": 5, "b": 3, "z": 4, "d": 6}));
+}
+
+#[test]
+fn null_removes() {
+    let mut base = smile!({"a": 1, "b": {"c": 2, "d": 3}, "e": 4});
+    base.merge(smile!({"a": null, "b": {"c": null}, "missing": null}));
+    assert_eq!(base, smile!({"b": {"d": 3}, "e": 4}));
+    assert_eq!(
+        base.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["b", "e"]
+    );
+}
+
+#[test]
+fn keep_nulls() {
+    let mut options = MergeOptions::new();
+    options.keep_nulls(true);
+
+    let mut base = smile!({"a": 1, "b": {"c": 2}});
+    base.merge_with(smile!({"a": null, "b": {"c": null}, "d": null}), &options);
+    assert_eq!(base, smile!({"a": null, "b": {"c": null}, "d": null}));
+}
+
+#[test]
+fn arrays() {
+    let mut base = smile!({"a": [1, 2], "b": [{"c": 1}]});
+    base.merge(smile!({"a": [3], "b": [{"d": 2}]}));
+    assert_eq!(base, smile!({"a": [3], "b": [{"d": 2}]}));
+
+    let mut options = MergeOptions::new();
+    options.arrays(ArrayMerge::Concatenate);
+
+    let mut base = smile!({"a": [1, 2], "b": [{"c": 1}]});
+    base.merge_with(smile!({"a": [3], "b": [{"d": 2}]}), &options);
+    assert_eq!(base, smile!({"a": [1, 2, 3], "b": [{"c": 1}, {"d": 2}]}));
+
+    let mut base = smile!([1]);
+    base.merge_with(smile!([]), &options);
+    assert_eq!(base, smile!([1]));
+
+    // arrays replace other kinds even when concatenating
+    let mut base = smile!({"a": 1});
+    base.merge_with(smile!({"a": [2]}), &options);
+    assert_eq!(base, smile!({"a": [2]}));
+}
+
+#[test]
+fn non_objects() {
+    let mut base = smile!(1);
+    base.merge(smile!({"a": 1}));
+    assert_eq!(base, smile!({"a": 1}));
+
+    let mut base = smile!({"a": 1});
+    base.merge(smile!("replacement"));
+    assert_eq!(base, smile!("replacement"));
+
+    let mut base = smile!({"a": {"b": 1}});
+    base.merge(smile!({"a": [1]}));
+    assert_eq!(base, smile!({"a": [1]}));
+
+    let mut base = smile!({"a": [1]});
+    base.merge(smile!({"a": {"b": 1}}));
+    assert_eq!(base, smile!({"a": {"b": 1}}));
+
+    // a top-level null has no key to remove, so it replaces the value
+    let mut base = smile!({"a": 1});
+    base.merge(Value::Null);
+    assert_eq!(base, Value::Null);
+
+    let mut base = Value::Null;
+    base.merge(smile!({"a": null}));
+    assert_eq!(base, smile!({"a": null}));
+}
+
+#[test]
+fn empty_objects() {
+    let mut base = smile!({"a": 1});
+    base.merge(smile!({}));
+    assert_eq!(base, smile!({"a": 1}));
+
+    let mut base = smile!({});
+    base.merge(smile!({"a": {"b": null}}));
+    assert_eq!(base, smile!({"a": {"b": null}}));
+}
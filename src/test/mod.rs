@@ -1,6 +1,84 @@
+mod arc_strings;
+mod big_decimal;
+mod big_integer;
+mod bigdecimal;
+mod borrowed_strings;
+mod byte_keys;
+mod bytes_binary;
+mod canonical;
+mod case_insensitive_fields;
+mod char_keys;
+mod cow_read;
+mod deep_size;
+mod diff;
+mod document_boundary;
+mod element_limit;
 mod enums;
+mod erased;
+mod error;
+mod error_origin;
+mod estimate_capacity;
+mod from_value;
+mod header_only;
+mod headerless;
+mod index;
+mod interop;
 mod ints;
+mod invalid_references;
+mod json;
+mod json_value;
+mod key_transform;
+mod limits;
 mod maps;
+mod memoize_keys;
+mod merge;
+mod net;
+mod normalize;
+mod num_bigint;
+mod number;
+mod object_writer;
+mod output_equivalence;
+mod pointer;
+mod presets;
+mod pretty;
+mod preview;
+mod property_cache_limit;
+mod raw_bytes;
+mod read_state;
+mod reader_eof;
+mod reader_parts;
+mod rearm_header;
+mod recursion_limit;
 mod reference;
+mod reference_tags;
+mod remaining_value;
+mod rust_decimal;
+mod schemars;
+mod semantic_eq;
+mod sentinel_keys;
+mod serialized_size;
+mod shared_cache_capacity;
+mod shared_chars;
+mod shared_wrappers;
+mod size_corpus;
+mod skip_deserializing;
+mod smile_macro;
+mod stray_string_terminators;
 mod stream_deserializer;
+mod strict_big_integers;
+mod strict_number_types;
+mod telemetry;
+mod to_value;
+mod tools;
+mod unit_structs;
+mod unknown_tokens;
 mod value;
+mod value_constructors;
+mod value_from;
+mod value_iter;
+mod value_map;
+mod value_ref;
+mod value_try_from;
+mod version;
+mod walk;
+mod zero_length;
@@ -0,0 +1,208 @@
+use crate::value::{to_value, Value};
+use crate::{smile, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ipv4(#[serde(with = "crate::net::ipv4")] Ipv4Addr);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ipv6(#[serde(with = "crate::net::ipv6")] Ipv6Addr);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Ip(#[serde(with = "crate::net::ip_addr")] IpAddr);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Socket(#[serde(with = "crate::net::socket_addr")] SocketAddr);
+
+fn serialize<T>(raw_binary: bool, value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = Serializer::builder().raw_binary(raw_binary).build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    // strip the header
+    ser.into_inner().split_off(4)
+}
+
+fn round_trip<T>(value: T)
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    for &raw_binary in &[false, true] {
+        let mut ser = Serializer::builder().raw_binary(raw_binary).build(vec![]);
+        value.serialize(&mut ser).unwrap();
+        let buf = ser.into_inner();
+
+        assert_eq!(crate::from_slice::<T>(&buf).unwrap(), value);
+        assert_eq!(crate::from_mut_slice::<T>(&mut buf.clone()).unwrap(), value);
+        assert_eq!(crate::from_reader::<T, _>(&*buf).unwrap(), value);
+    }
+}
+
+#[test]
+fn default_encoding() {
+    // serde's own implementation writes an array of integers
+    assert_eq!(
+        serialize(false, &Ipv4Addr::new(192, 168, 0, 1)),
+        [0xf8, 0x24, 0x06, 0x80, 0x24, 0x05, 0x90, 0xc0, 0xc2, 0xf9],
+    );
+}
+
+#[test]
+fn ipv4_layout() {
+    let addr = Ipv4(Ipv4Addr::new(192, 168, 0, 1));
+    assert_eq!(
+        serialize(false, &addr),
+        [0xe8, 0x84, 0x60, 0x2a, 0x00, 0x00, 0x01]
+    );
+    assert_eq!(serialize(true, &addr), [0xfd, 0x84, 0xc0, 0xa8, 0x00, 0x01]);
+}
+
+#[test]
+fn ipv6_layout() {
+    let addr = Ipv6("2001:db8::1".parse().unwrap());
+    let mut expected = vec![0xfd, 0x90];
+    expected.extend_from_slice(&addr.0.octets());
+    assert_eq!(serialize(true, &addr), expected);
+
+    let buf = serialize(false, &addr);
+    assert_eq!(buf[..2], [0xe8, 0x90]);
+    // 16 bytes are encoded as 2 full 7-bit groups and a partial group of 2 bytes
+    assert_eq!(buf.len(), 2 + 8 + 8 + 3);
+}
+
+#[test]
+fn ip_addr_layout() {
+    assert_eq!(
+        serialize(true, &Ip(IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)))),
+        [0xfd, 0x84, 0xc0, 0xa8, 0x00, 0x01]
+    );
+
+    let mut expected = vec![0xfd, 0x90];
+    expected.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+    assert_eq!(
+        serialize(true, &Ip(IpAddr::V6(Ipv6Addr::LOCALHOST))),
+        expected
+    );
+}
+
+#[test]
+fn socket_addr_layout() {
+    let addr = Socket("192.168.0.1:8080".parse().unwrap());
+    assert_eq!(
+        serialize(true, &addr),
+        [
+            0xfa, // object start
+            0x81, b'i', b'p', // key "ip"
+            0xfd, 0x84, 0xc0, 0xa8, 0x00, 0x01, // 4 bytes of raw binary
+            0x83, b'p', b'o', b'r', b't', // key "port"
+            0x24, 0x01, 0x7c, 0xa0, // 8080
+            0xfb, // object end
+        ],
+    );
+}
+
+#[test]
+fn round_trips() {
+    round_trip(Ipv4(Ipv4Addr::new(192, 168, 0, 1)));
+    round_trip(Ipv4(Ipv4Addr::UNSPECIFIED));
+    round_trip(Ipv4(Ipv4Addr::BROADCAST));
+    round_trip(Ipv6(Ipv6Addr::LOCALHOST));
+    round_trip(Ipv6("2001:db8::ff00:42:8329".parse().unwrap()));
+    round_trip(Ip(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    round_trip(Ip(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    round_trip(Socket("127.0.0.1:80".parse().unwrap()));
+    round_trip(Socket("[2001:db8::1]:65535".parse().unwrap()));
+}
+
+// The same structs as above, but using serde's own implementations.
+mod legacy {
+    use serde::Serialize;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    #[derive(Serialize)]
+    pub struct Ipv4(pub Ipv4Addr);
+
+    #[derive(Serialize)]
+    pub struct Ipv6(pub Ipv6Addr);
+
+    #[derive(Serialize)]
+    pub struct Ip(pub IpAddr);
+
+    #[derive(Serialize)]
+    pub struct Socket(pub SocketAddr);
+}
+
+fn migrate<L, T>(legacy: L, expected: T)
+where
+    L: Serialize,
+    T: for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    let buf = crate::to_vec(&legacy).unwrap();
+    assert_eq!(crate::from_slice::<T>(&buf).unwrap(), expected);
+    assert_eq!(crate::from_reader::<T, _>(&*buf).unwrap(), expected);
+}
+
+#[test]
+fn accepts_default_encoding() {
+    let v4 = Ipv4Addr::new(192, 168, 0, 1);
+    let v6 = "2001:db8::1".parse::<Ipv6Addr>().unwrap();
+
+    migrate(legacy::Ipv4(v4), Ipv4(v4));
+    migrate(legacy::Ipv6(v6), Ipv6(v6));
+    migrate(legacy::Ip(IpAddr::V4(v4)), Ip(IpAddr::V4(v4)));
+    migrate(legacy::Ip(IpAddr::V6(v6)), Ip(IpAddr::V6(v6)));
+
+    let addr = "192.168.0.1:8080".parse().unwrap();
+    migrate(legacy::Socket(addr), Socket(addr));
+    let addr = "[2001:db8::1]:8080".parse().unwrap();
+    migrate(legacy::Socket(addr), Socket(addr));
+}
+
+#[test]
+fn accepts_strings() {
+    assert_eq!(
+        crate::value::from_value::<Ipv4>(smile!("10.0.0.1")).unwrap(),
+        Ipv4(Ipv4Addr::new(10, 0, 0, 1)),
+    );
+    assert_eq!(
+        crate::value::from_value::<Ip>(smile!("::1")).unwrap(),
+        Ip(IpAddr::V6(Ipv6Addr::LOCALHOST)),
+    );
+    assert_eq!(
+        crate::value::from_value::<Socket>(smile!("10.0.0.1:22")).unwrap(),
+        Socket("10.0.0.1:22".parse().unwrap()),
+    );
+    crate::value::from_value::<Ipv4>(smile!("::1")).unwrap_err();
+}
+
+#[test]
+fn value_round_trip() {
+    let addr = Socket("[::1]:8080".parse().unwrap());
+    let value = to_value(&addr).unwrap();
+    assert_eq!(
+        value,
+        smile!({
+            "ip": (Value::binary(Ipv6Addr::LOCALHOST.octets().to_vec())),
+            "port": 8080,
+        }),
+    );
+    assert_eq!(crate::value::from_value::<Socket>(value).unwrap(), addr);
+}
+
+#[test]
+fn invalid() {
+    crate::value::from_value::<Ipv4>(Value::binary(vec![1, 2, 3])).unwrap_err();
+    crate::value::from_value::<Ipv6>(Value::binary(vec![0; 4])).unwrap_err();
+    crate::value::from_value::<Ip>(Value::binary(vec![0; 5])).unwrap_err();
+    crate::value::from_value::<Ipv4>(smile!([1, 2, 3])).unwrap_err();
+    crate::value::from_value::<Ipv4>(smile!([1, 2, 3, 4, 5])).unwrap_err();
+    crate::value::from_value::<Ipv4>(smile!([1, 2, 3, 256])).unwrap_err();
+    crate::value::from_value::<Socket>(smile!({"port": 80})).unwrap_err();
+    crate::value::from_value::<Socket>(
+        smile!({"ip": (Value::binary(vec![1, 2, 3, 4])), "port": 80, "V4": null}),
+    )
+    .unwrap_err();
+}
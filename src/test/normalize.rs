@@ -0,0 +1,217 @@
+use crate::smile;
+use crate::value::{BigDecimal, BigInteger, NormalizeOptions, Value};
+use serde::Serialize;
+
+fn big(v: i128) -> Value {
+    Value::BigInteger(BigInteger::from_be_bytes(v.to_be_bytes().to_vec()))
+}
+
+fn normalize(mut value: Value) -> Value {
+    value.normalize_numbers();
+    value
+}
+
+fn narrow(mut value: Value) -> Value {
+    value.normalize_numbers_with(NormalizeOptions::new().narrow_doubles(true));
+    value
+}
+
+#[test]
+fn integer_boundaries() {
+    let i32_boundaries = [0, 1, -1, i64::from(i32::MIN), i64::from(i32::MAX)];
+    for &v in &i32_boundaries {
+        let expected = Value::Integer(v as i32);
+        assert_eq!(normalize(Value::Integer(v as i32)), expected);
+        assert_eq!(normalize(Value::Long(v)), expected);
+        assert_eq!(normalize(big(i128::from(v))), expected);
+    }
+
+    let i64_boundaries = [
+        i64::from(i32::MIN) - 1,
+        i64::from(i32::MAX) + 1,
+        i64::MIN,
+        i64::MAX,
+    ];
+    for &v in &i64_boundaries {
+        let expected = Value::Long(v);
+        assert_eq!(normalize(Value::Long(v)), expected);
+        assert_eq!(normalize(big(i128::from(v))), expected);
+    }
+
+    for &v in &[i128::from(i64::MIN) - 1, i128::from(i64::MAX) + 1] {
+        assert_eq!(normalize(big(v)), big(v));
+    }
+}
+
+#[test]
+fn padded_big_integer() {
+    let padded = Value::BigInteger(BigInteger::from_be_bytes(vec![
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 5,
+    ]));
+    assert_eq!(normalize(padded), Value::Integer(5));
+
+    let padded = Value::BigInteger(BigInteger::from_be_bytes(vec![0xff, 0xff, 0xff, 0xfb]));
+    assert_eq!(normalize(padded), Value::Integer(-5));
+
+    let mut buf = vec![0xff; 20];
+    buf.extend_from_slice(&i64::MIN.to_be_bytes());
+    let padded = Value::BigInteger(BigInteger::from_be_bytes(buf));
+    assert_eq!(normalize(padded), Value::Long(i64::MIN));
+}
+
+#[test]
+fn doubles_kept_by_default() {
+    assert_eq!(normalize(Value::Double(1.5)), Value::Double(1.5));
+    assert_eq!(normalize(Value::Float(1.5)), Value::Float(1.5));
+}
+
+#[test]
+fn narrow_doubles() {
+    assert_eq!(narrow(Value::Double(1.5)), Value::Float(1.5));
+    assert_eq!(narrow(Value::Double(-0.)), Value::Float(-0.));
+    assert_eq!(
+        narrow(Value::Double(f64::INFINITY)),
+        Value::Float(f32::INFINITY)
+    );
+    assert_eq!(
+        narrow(Value::Double(f64::from(f32::MAX))),
+        Value::Float(f32::MAX)
+    );
+
+    assert_eq!(narrow(Value::Double(0.1)), Value::Double(0.1));
+    assert_eq!(narrow(Value::Double(1e300)), Value::Double(1e300));
+    assert_eq!(narrow(Value::Double(1e-300)), Value::Double(1e-300));
+}
+
+#[test]
+fn big_decimal_untouched() {
+    let value = Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![5]), 0));
+    assert_eq!(normalize(value.clone()), value);
+    assert_eq!(narrow(value.clone()), value);
+}
+
+#[test]
+fn nested() {
+    let value = smile!({
+        "a": [(Value::Long(1)), (big(2)), {"b": (Value::Long(1 << 40))}],
+        "c": (Value::Double(2.0)),
+        "d": "text",
+    });
+
+    assert_eq!(
+        normalize(value.clone()),
+        smile!({
+            "a": [1, 2, {"b": (Value::Long(1 << 40))}],
+            "c": (Value::Double(2.0)),
+            "d": "text",
+        })
+    );
+    assert_eq!(
+        narrow(value),
+        smile!({
+            "a": [1, 2, {"b": (Value::Long(1 << 40))}],
+            "c": (Value::Float(2.0)),
+            "d": "text",
+        })
+    );
+}
+
+fn canonicalize(mut value: Value) -> Value {
+    value.canonicalize();
+    value
+}
+
+fn decimal(unscaled: &[u8], scale: i32) -> Value {
+    Value::BigDecimal(BigDecimal::new(
+        BigInteger::from_be_bytes(unscaled.to_vec()),
+        scale,
+    ))
+}
+
+#[test]
+fn canonical_integers() {
+    assert_eq!(canonicalize(Value::Long(5)), Value::Integer(5));
+    assert_eq!(canonicalize(big(5)), Value::Integer(5));
+    assert_eq!(canonicalize(big(1 << 40)), Value::Long(1 << 40));
+
+    let mut padded = vec![0xff; 4];
+    padded.extend_from_slice(&i128::MIN.to_be_bytes());
+    assert_eq!(
+        canonicalize(Value::BigInteger(BigInteger::from_be_bytes(padded))),
+        big(i128::MIN)
+    );
+    assert_eq!(
+        canonicalize(big(i128::from(u64::MAX))),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ]))
+    );
+
+    assert_eq!(canonicalize(Value::Double(2.)), Value::Double(2.));
+}
+
+#[test]
+fn canonical_decimals() {
+    // 1.50 => 1.5
+    assert_eq!(canonicalize(decimal(&[0, 150], 2)), decimal(&[15], 1));
+    // -150.0 => -1.5E+2
+    assert_eq!(
+        canonicalize(decimal(&(-1500i16).to_be_bytes(), 1)),
+        decimal(&[0xf1], -1)
+    );
+    // 100 => 1E+2
+    assert_eq!(canonicalize(decimal(&[100], 0)), decimal(&[1], -2));
+    // 0.000 => 0
+    assert_eq!(canonicalize(decimal(&[0, 0], 3)), decimal(&[0], 0));
+    assert_eq!(canonicalize(decimal(&[], -7)), decimal(&[0], 0));
+    // 0.15 is already canonical but carries a padding byte
+    assert_eq!(canonicalize(decimal(&[0, 0, 15], 2)), decimal(&[15], 2));
+
+    // the scale can't underflow
+    assert_eq!(
+        canonicalize(decimal(&[100], i32::MIN + 1)),
+        decimal(&[10], i32::MIN)
+    );
+    assert_eq!(
+        canonicalize(decimal(&[10], i32::MIN)),
+        decimal(&[10], i32::MIN)
+    );
+
+    // 10^30 with a large magnitude
+    let mut unscaled = vec![0x0c, 0x9f, 0x2c, 0x9c, 0xd0, 0x46, 0x74, 0xed, 0xea, 0x40];
+    unscaled.extend_from_slice(&[0; 3]);
+    assert_eq!(canonicalize(decimal(&unscaled, 0)), decimal(&[1], -30));
+}
+
+#[test]
+fn canonical_encodings_match() {
+    #[derive(Serialize)]
+    struct Narrow {
+        id: i32,
+        count: u64,
+        price: BigDecimal,
+    }
+
+    let narrow = crate::to_vec(&Narrow {
+        id: 7,
+        count: 1 << 40,
+        price: BigDecimal::new(BigInteger::from_be_bytes(vec![15]), 1),
+    })
+    .unwrap();
+
+    let wide = crate::to_vec(&smile!({
+        "id": (Value::BigInteger(BigInteger::from_be_bytes(vec![0, 0, 0, 7]))),
+        "count": (big(1 << 40)),
+        "price": (decimal(&[0, 0, 150], 2)),
+    }))
+    .unwrap();
+    assert_ne!(narrow, wide);
+
+    let narrow = crate::from_slice::<Value>(&narrow).unwrap();
+    let wide = crate::from_slice::<Value>(&wide).unwrap();
+    assert_ne!(narrow, wide);
+    assert_eq!(
+        canonicalize(smile!([(narrow)])),
+        canonicalize(smile!([(wide)]))
+    );
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "num-bigint")]
+
+use crate::value::{BigInteger, Value};
+use num_bigint::{BigInt, BigUint};
+use std::convert::TryFrom;
+
+fn check(v: BigInt) {
+    let big_integer = BigInteger::from(&v);
+    assert_eq!(BigInt::from(&big_integer), v, "{}", v);
+
+    let buf = crate::to_vec(&big_integer).unwrap();
+    let decoded = crate::from_slice::<BigInteger>(&buf).unwrap();
+    assert_eq!(BigInt::from(decoded), v, "{}", v);
+}
+
+#[test]
+fn matches_native() {
+    let values = [
+        0,
+        1,
+        -1,
+        127,
+        128,
+        -128,
+        -129,
+        255,
+        256,
+        -255,
+        -256,
+        -257,
+        32767,
+        32768,
+        -32768,
+        -32769,
+        i64::MAX as i128,
+        i64::MIN as i128,
+        i128::MAX,
+        i128::MIN,
+    ];
+
+    for v in values {
+        let big_integer = BigInteger::from(BigInt::from(v));
+        assert_eq!(big_integer, BigInteger::from(v), "{}", v);
+        assert_eq!(BigInt::from(&BigInteger::from(v)), BigInt::from(v));
+    }
+}
+
+#[test]
+fn powers_of_two() {
+    for shift in 0..2048usize {
+        let v = BigInt::from(1u8) << shift;
+        check(v.clone());
+        check(-v.clone());
+        check(v.clone() - 1);
+        check(-v - 1);
+    }
+}
+
+#[test]
+fn multi_hundred_byte() {
+    let v = BigInt::parse_bytes(&[b'7'; 700], 10).unwrap();
+    assert!(v.to_signed_bytes_be().len() > 250);
+    check(v.clone());
+    check(-v.clone());
+    check(v.clone() * &v);
+    check(-(v.clone() * &v));
+}
+
+#[test]
+fn sign_extended() {
+    // non-minimal encodings from other writers are still decoded correctly
+    let v = BigInteger::from_be_bytes(vec![0xff, 0xff, 0x80]);
+    assert_eq!(BigInt::from(v), BigInt::from(-128));
+
+    let v = BigInteger::from_be_bytes(vec![0x00, 0x00, 0x80]);
+    assert_eq!(BigInt::from(v), BigInt::from(128));
+
+    let v = BigInteger::from_be_bytes(vec![]);
+    assert_eq!(BigInt::from(v), BigInt::from(0));
+}
+
+#[test]
+fn unsigned() {
+    for v in [0u128, 1, 127, 128, 255, 256, u64::MAX as u128, u128::MAX] {
+        let big_integer = BigInteger::from(BigUint::from(v));
+        assert_eq!(big_integer, BigInteger::from(v), "{}", v);
+        assert_eq!(BigUint::try_from(&big_integer).unwrap(), BigUint::from(v));
+    }
+
+    let v = BigUint::from(1u8) << 1000usize;
+    assert_eq!(BigUint::try_from(BigInteger::from(&v)).unwrap(), v);
+
+    let e = BigUint::try_from(BigInteger::from(BigInt::from(-128))).unwrap_err();
+    assert_eq!(e.to_string(), "value out of range: expected BigUint");
+}
+
+#[test]
+fn value() {
+    let v = -(BigInt::from(1u8) << 200usize);
+    let value = Value::BigInteger(BigInteger::from(&v));
+    let buf = crate::to_vec(&value).unwrap();
+    match crate::from_slice::<Value>(&buf).unwrap() {
+        Value::BigInteger(big_integer) => assert_eq!(BigInt::from(big_integer), v),
+        value => panic!("unexpected value {:?}", value),
+    }
+}
@@ -0,0 +1,163 @@
+use crate::value::{BigDecimal, BigInteger, Number, Value};
+use std::convert::TryFrom;
+
+fn big_integer(buf: &[u8]) -> Number {
+    Number::from(BigInteger::from_be_bytes(buf.to_vec()))
+}
+
+#[test]
+fn constructors() {
+    assert_eq!(Number::from(1u8).as_value(), &Value::Integer(1));
+    assert_eq!(Number::from(-1i16).as_value(), &Value::Integer(-1));
+    assert_eq!(Number::from(i32::MIN).as_value(), &Value::Integer(i32::MIN));
+    assert_eq!(
+        Number::from(u32::MAX).as_value(),
+        &Value::Long(u32::MAX as i64)
+    );
+    assert_eq!(Number::from(i64::MIN).as_value(), &Value::Long(i64::MIN));
+    assert_eq!(Number::from(1i64).as_value(), &Value::Integer(1));
+    assert_eq!(
+        Number::from(u64::MAX).as_value(),
+        &Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ])),
+    );
+    assert_eq!(Number::from(1.5f32).as_value(), &Value::Float(1.5));
+    assert_eq!(Number::from(1.5f64).as_value(), &Value::Double(1.5));
+    assert_eq!(
+        Value::from(Number::from(BigDecimal::new(
+            BigInteger::from_be_bytes(vec![1]),
+            1
+        ))),
+        Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 1)),
+    );
+}
+
+#[test]
+fn as_number() {
+    assert_eq!(Value::Integer(1).as_number(), Some(Number::from(1)));
+    assert_eq!(Value::Double(1.).as_number(), Some(Number::from(1.)));
+    assert_eq!(Value::Null.as_number(), None);
+    assert_eq!(Value::String("1".into()).as_number(), None);
+
+    assert_eq!(
+        Number::try_from(Value::Long(i64::MAX)),
+        Ok(Number::from(i64::MAX))
+    );
+    assert_eq!(
+        Number::try_from(Value::Boolean(true)),
+        Err(Value::Boolean(true))
+    );
+}
+
+#[test]
+fn kinds() {
+    for n in [
+        Number::from(1),
+        Number::from(i64::MAX),
+        big_integer(&[1; 17]),
+    ] {
+        assert!(n.is_integer());
+        assert!(!n.is_float());
+    }
+
+    for n in [
+        Number::from(1f32),
+        Number::from(f64::NAN),
+        Number::from(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 0)),
+    ] {
+        assert!(!n.is_integer());
+        assert!(n.is_float());
+    }
+}
+
+#[test]
+fn as_i64() {
+    assert_eq!(Number::from(i32::MIN).as_i64(), Some(i32::MIN as i64));
+    assert_eq!(Number::from(i64::MIN).as_i64(), Some(i64::MIN));
+    assert_eq!(Number::from(i64::MAX).as_i64(), Some(i64::MAX));
+    assert_eq!(
+        big_integer(&[0x80, 0, 0, 0, 0, 0, 0, 0]).as_i64(),
+        Some(i64::MIN)
+    );
+    assert_eq!(big_integer(&[0, 0x80, 0, 0, 0, 0, 0, 0, 0]).as_i64(), None);
+    assert_eq!(
+        big_integer(&[0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).as_i64(),
+        None
+    );
+    assert_eq!(big_integer(&[0; 17]).as_i64(), None);
+    assert_eq!(big_integer(&[]).as_i64(), None);
+    assert_eq!(Number::from(1.).as_i64(), None);
+}
+
+#[test]
+fn as_u64() {
+    assert_eq!(Number::from(0).as_u64(), Some(0));
+    assert_eq!(Number::from(-1).as_u64(), None);
+    assert_eq!(Number::from(i64::MIN).as_u64(), None);
+    assert_eq!(Number::from(i64::MAX).as_u64(), Some(i64::MAX as u64));
+    assert_eq!(Number::from(u64::MAX).as_u64(), Some(u64::MAX));
+    assert_eq!(big_integer(&[1, 0, 0, 0, 0, 0, 0, 0, 0]).as_u64(), None);
+    // 17 bytes is the widest BigInteger with a native representation, as a u128
+    let mut buf = vec![0xff; 17];
+    buf[0] = 0;
+    assert_eq!(big_integer(&buf).as_u64(), None);
+    assert_eq!(Number::from(1.).as_u64(), None);
+}
+
+#[test]
+fn as_f64() {
+    assert_eq!(Number::from(i32::MIN).as_f64(), Some(i32::MIN as f64));
+    assert_eq!(Number::from(i64::MIN).as_f64(), Some(i64::MIN as f64));
+    assert_eq!(Number::from(i64::MAX).as_f64(), None);
+    assert_eq!(Number::from((1i64 << 53) + 1).as_f64(), None);
+    assert_eq!(
+        Number::from(1u64 << 63).as_f64(),
+        Some(9_223_372_036_854_775_808.0)
+    );
+    assert_eq!(Number::from(u64::MAX).as_f64(), None);
+    assert_eq!(big_integer(&[1; 17]).as_f64(), None);
+    assert_eq!(Number::from(0.1f32).as_f64(), Some(f64::from(0.1f32)));
+    assert!(Number::from(f64::NAN).as_f64().unwrap().is_nan());
+    assert_eq!(
+        Number::from(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 0)).as_f64(),
+        None
+    );
+
+    // Value delegates to the same logic
+    assert_eq!(
+        Value::from(1u64 << 63).as_f64(),
+        Some(9_223_372_036_854_775_808.0)
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(Number::from(i64::MIN).to_string(), "-9223372036854775808");
+    assert_eq!(Number::from(u64::MAX).to_string(), "18446744073709551615");
+    assert_eq!(big_integer(&[0xff; 17]).to_string(), "-1");
+    assert_eq!(Number::from(1f32).to_string(), "1.0");
+    assert_eq!(Number::from(f64::NEG_INFINITY).to_string(), "-inf");
+    assert_eq!(
+        Number::from(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), -5)).to_string(),
+        "1E+5",
+    );
+}
+
+#[test]
+fn serialize() {
+    for n in [
+        Number::from(1),
+        Number::from(i64::MIN),
+        Number::from(u64::MAX),
+        Number::from(1.5f32),
+        Number::from(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 3)),
+    ] {
+        let value = Value::from(n.clone());
+        assert_eq!(crate::to_vec(&n).unwrap(), crate::to_vec(&value).unwrap());
+        assert_eq!(
+            crate::from_slice::<Value>(&crate::to_vec(&n).unwrap()).unwrap(),
+            value
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::ser::{Builder, Serializer};
+use crate::smile;
+use crate::value::Value;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Item {
+    name: &'static str,
+    count: u32,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    total: u64,
+    ratio: f64,
+}
+
+#[derive(Serialize)]
+struct Export {
+    id: i32,
+    name: &'static str,
+    items: Vec<Item>,
+    stats: Stats,
+    matrix: Vec<Vec<i32>>,
+    trailer: Option<String>,
+}
+
+fn export() -> Export {
+    Export {
+        id: 1,
+        name: "export",
+        items: vec![
+            Item {
+                name: "a",
+                count: 1,
+            },
+            Item {
+                name: "b",
+                count: 2,
+            },
+        ],
+        stats: Stats {
+            total: 3,
+            ratio: 0.5,
+        },
+        matrix: vec![vec![1, 2], vec![]],
+        trailer: None,
+    }
+}
+
+fn write_incrementally(ser: &mut Serializer<Vec<u8>>) {
+    let mut object = ser.begin_object().unwrap();
+    object.field("id", &1).unwrap();
+    object.field("name", "export").unwrap();
+
+    let mut items = object.nested_array("items").unwrap();
+    // expensive fields are computed between writes
+    for (name, count) in [("a", 1), ("b", 2)] {
+        let mut item = items.nested_object().unwrap();
+        item.field("name", name).unwrap();
+        item.field("count", &count).unwrap();
+        item.end().unwrap();
+    }
+    items.end().unwrap();
+
+    let mut stats = object.nested_object("stats").unwrap();
+    stats.field("total", &3u64).unwrap();
+    stats.field("ratio", &0.5).unwrap();
+    stats.end().unwrap();
+
+    let mut matrix = object.nested_array("matrix").unwrap();
+    let mut row = matrix.nested_array().unwrap();
+    row.element(&1).unwrap();
+    row.element(&2).unwrap();
+    row.end().unwrap();
+    matrix.nested_array().unwrap().end().unwrap();
+    matrix.end().unwrap();
+
+    object.field("trailer", &None::<String>).unwrap();
+    object.end().unwrap();
+}
+
+fn check(builder: &Builder) {
+    let mut ser = builder.build(vec![]);
+    export().serialize(&mut ser).unwrap();
+    let expected = ser.into_inner();
+
+    let mut ser = builder.build(vec![]);
+    write_incrementally(&mut ser);
+    assert_eq!(ser.into_inner(), expected);
+}
+
+#[test]
+fn matches_one_shot() {
+    check(&Builder::jackson_defaults());
+    check(&Builder::smallest_output());
+    check(&Builder::fastest());
+}
+
+#[test]
+fn shares_property_cache() {
+    let mut ser = Serializer::new(vec![]);
+    export().serialize(&mut ser).unwrap();
+    export().serialize(&mut ser).unwrap();
+    let expected = ser.into_inner();
+
+    // keys written by the object writer are back-referenced by later values, and vice versa
+    let mut ser = Serializer::new(vec![]);
+    write_incrementally(&mut ser);
+    export().serialize(&mut ser).unwrap();
+    assert_eq!(ser.into_inner(), expected);
+
+    let mut ser = Serializer::new(vec![]);
+    export().serialize(&mut ser).unwrap();
+    write_incrementally(&mut ser);
+    assert_eq!(ser.into_inner(), expected);
+}
+
+#[test]
+fn top_level_array() {
+    let mut ser = Serializer::new(vec![]);
+    let mut array = ser.begin_array().unwrap();
+    array.element(&1).unwrap();
+    let mut object = array.nested_object().unwrap();
+    object.field("a", "b").unwrap();
+    object.end().unwrap();
+    array.end().unwrap();
+
+    assert_eq!(
+        ser.into_inner(),
+        crate::to_vec(&smile!([1, {"a": "b"}])).unwrap()
+    );
+}
+
+#[test]
+fn drop_closes() {
+    let mut ser = Serializer::new(vec![]);
+    {
+        let mut object = ser.begin_object().unwrap();
+        object.field("a", &1).unwrap();
+        let mut array = object.nested_array("b").unwrap();
+        array.element(&2).unwrap();
+    }
+
+    let value = crate::from_slice::<Value>(&ser.into_inner()).unwrap();
+    assert_eq!(value, smile!({"a": 1, "b": [2]}));
+}
+
+#[test]
+fn recursion_limit() {
+    // the object itself counts against the limit
+    let mut ser = Serializer::new(vec![]);
+    let mut object = ser.begin_object().unwrap();
+    object.field("a", &nested(127)).unwrap();
+    let e = object.field("b", &nested(128)).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "recursion limit of 128 exceeded at depth 129"
+    );
+}
+
+fn nested(depth: usize) -> Value {
+    (0..depth).fold(Value::Null, |v, _| Value::Array(vec![v]))
+}
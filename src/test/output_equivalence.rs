@@ -0,0 +1,166 @@
+//! Checks that every way of serializing a value with the same settings produces the same bytes.
+use crate::ser::Builder;
+use crate::value::Value;
+use crate::{smile, Deserializer, Serializer};
+use std::ffi::OsStr;
+use std::fs;
+
+fn corpus() -> Vec<Value> {
+    let mut values = vec![];
+    for category in fs::read_dir("tests").unwrap() {
+        let category = category.unwrap().path();
+        // the documents in `tests/quirks` are written by nonconforming producers
+        if !category.is_dir() || category.ends_with("quirks") {
+            continue;
+        }
+        for path in fs::read_dir(category).unwrap() {
+            let path = path.unwrap().path();
+            if path.extension() == Some(OsStr::new("smile")) {
+                let buf = fs::read(&path).unwrap();
+                for value in Deserializer::from_slice(&buf).into_iter::<Value>() {
+                    values.push(value.unwrap());
+                }
+            }
+        }
+    }
+    assert!(!values.is_empty());
+
+    values.extend([
+        Value::Null,
+        smile!(""),
+        smile!([]),
+        smile!({}),
+        smile!([[], {}, null]),
+    ]);
+    values
+}
+
+fn builders() -> Vec<Builder> {
+    let mut builders = vec![];
+    for bits in 0..32 {
+        let mut builder = Builder::jackson_defaults();
+        builder
+            .raw_binary(bits & 1 != 0)
+            .shared_strings(bits & 2 != 0)
+            .shared_properties(bits & 4 != 0)
+            .memoize_keys(bits & 8 != 0)
+            .estimate_capacity(bits & 16 != 0);
+        builders.push(builder);
+    }
+    builders.extend([
+        Builder::jackson_defaults(),
+        Builder::smallest_output(),
+        Builder::fastest(),
+    ]);
+    builders
+}
+
+fn header(builder: &Builder) -> Vec<u8> {
+    let mut ser = builder.build(vec![]);
+    ser.write_header().unwrap();
+    ser.into_inner()
+}
+
+/// Asserts that the output is a single header followed by the value.
+fn check_document(builder: &Builder, buf: &[u8], value: &Value) {
+    let header = header(builder);
+    assert!(buf.starts_with(&header), "{:?}", builder);
+
+    // a second header would be rejected as a document boundary
+    let decoded = crate::from_slice::<Value>(buf).unwrap();
+    assert!(decoded.semantic_eq(value), "{:?}", builder);
+}
+
+#[test]
+fn default_settings() {
+    for value in corpus() {
+        let expected = crate::to_vec(&value).unwrap();
+
+        assert_eq!(crate::to_vec_with_capacity(&value, 1024).unwrap(), expected);
+
+        let mut buf = vec![];
+        crate::to_writer(&mut buf, &value).unwrap();
+        assert_eq!(buf, expected);
+
+        assert_eq!(Serializer::builder().to_vec(&value).unwrap(), expected);
+
+        let mut buf = vec![];
+        Serializer::builder().to_writer(&mut buf, &value).unwrap();
+        assert_eq!(buf, expected);
+
+        let mut ser = Serializer::new(vec![]);
+        serde::Serialize::serialize(&value, &mut ser).unwrap();
+        assert_eq!(ser.into_inner(), expected);
+
+        check_document(&Serializer::builder(), &expected, &value);
+    }
+}
+
+#[test]
+fn all_options() {
+    let corpus = corpus();
+    for builder in builders() {
+        for value in &corpus {
+            let mut ser = builder.build(vec![]);
+            serde::Serialize::serialize(value, &mut ser).unwrap();
+            let expected = ser.into_inner();
+
+            assert_eq!(builder.to_vec(value).unwrap(), expected, "{:?}", builder);
+
+            let mut buf = vec![];
+            builder.to_writer(&mut buf, value).unwrap();
+            assert_eq!(buf, expected, "{:?}", builder);
+
+            let mut end_builder = builder.clone();
+            end_builder.end_marker(true);
+            let with_end = [&expected[..], &[0xff]].concat();
+            assert_eq!(
+                end_builder.to_vec(value).unwrap(),
+                with_end,
+                "{:?}",
+                builder
+            );
+            let mut buf = vec![];
+            end_builder.to_writer(&mut buf, value).unwrap();
+            assert_eq!(buf, with_end, "{:?}", builder);
+
+            // writing the header up front doesn't change the output
+            let mut ser = builder.build(vec![]);
+            ser.write_header().unwrap();
+            serde::Serialize::serialize(value, &mut ser).unwrap();
+            assert_eq!(ser.into_inner(), expected, "{:?}", builder);
+
+            check_document(&builder, &expected, value);
+        }
+    }
+}
+
+#[test]
+fn end_marker() {
+    for builder in builders() {
+        let header = header(&builder);
+
+        let mut ser = builder.build(vec![]);
+        ser.end().unwrap();
+        let buf = ser.into_inner();
+        assert_eq!(buf, [&header[..], &[0xff]].concat(), "{:?}", builder);
+
+        let mut ser = builder.build(vec![]);
+        ser.write_header().unwrap();
+        ser.end().unwrap();
+        assert_eq!(ser.into_inner(), buf, "{:?}", builder);
+
+        let mut ser = builder.build(vec![]);
+        serde::Serialize::serialize(&Value::Null, &mut ser).unwrap();
+        ser.end().unwrap();
+        assert_eq!(
+            ser.into_inner(),
+            [&header[..], &[0x21, 0xff]].concat(),
+            "{:?}",
+            builder
+        );
+
+        // nothing is written until a value, header, or end marker is requested
+        assert_eq!(builder.build(vec![]).into_inner(), b"");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::value::{Map, Value};
+
+// the example document from RFC 6901
+fn document() -> Value {
+    let mut map = Map::new();
+    map.insert(
+        "foo".to_string(),
+        Value::Array(vec![Value::from("bar"), Value::from("baz")]),
+    );
+    for (i, key) in ["", "a/b", "c%d", "e^f", "g|h", "i\\j", "k\"l", " ", "m~n"]
+        .iter()
+        .enumerate()
+    {
+        map.insert(key.to_string(), Value::Integer(i as i32));
+    }
+    Value::Object(map)
+}
+
+#[test]
+fn rfc_examples() {
+    let value = document();
+
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/foo"), Some(&value["foo"]));
+    assert_eq!(*value.pointer("/foo/0").unwrap(), "bar");
+    assert_eq!(*value.pointer("/").unwrap(), 0);
+    assert_eq!(*value.pointer("/a~1b").unwrap(), 1);
+    assert_eq!(*value.pointer("/c%d").unwrap(), 2);
+    assert_eq!(*value.pointer("/e^f").unwrap(), 3);
+    assert_eq!(*value.pointer("/g|h").unwrap(), 4);
+    assert_eq!(*value.pointer("/i\\j").unwrap(), 5);
+    assert_eq!(*value.pointer("/k\"l").unwrap(), 6);
+    assert_eq!(*value.pointer("/ ").unwrap(), 7);
+    assert_eq!(*value.pointer("/m~0n").unwrap(), 8);
+}
+
+#[test]
+fn missing() {
+    let value = document();
+
+    assert_eq!(value.pointer("foo"), None);
+    assert_eq!(value.pointer("/missing"), None);
+    assert_eq!(value.pointer("/foo/2"), None);
+    assert_eq!(value.pointer("/foo/-"), None);
+    assert_eq!(value.pointer("/foo/01"), None);
+    assert_eq!(value.pointer("/foo/+1"), None);
+    assert_eq!(value.pointer("/foo/bar"), None);
+    assert_eq!(value.pointer("/foo/0/0"), None);
+    assert_eq!(value.pointer("/foo/99999999999999999999999"), None);
+}
+
+#[test]
+fn escape_order() {
+    let mut map = Map::new();
+    map.insert("~1".to_string(), Value::Integer(1));
+    map.insert("/".to_string(), Value::Integer(2));
+    let value = Value::Object(map);
+
+    assert_eq!(*value.pointer("/~01").unwrap(), 1);
+    assert_eq!(*value.pointer("/~1").unwrap(), 2);
+}
+
+#[test]
+fn pointer_mut() {
+    let mut value = document();
+
+    *value.pointer_mut("/foo/1").unwrap() = Value::from("qux");
+    assert_eq!(value["foo"][1], "qux");
+
+    *value.pointer_mut("/m~0n").unwrap() = Value::Null;
+    assert_eq!(value["m~n"], Value::Null);
+
+    *value.pointer_mut("").unwrap() = Value::Boolean(true);
+    assert_eq!(value, true);
+
+    assert_eq!(value.pointer_mut("/foo"), None);
+}
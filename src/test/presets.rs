@@ -0,0 +1,69 @@
+use crate::ser::Builder;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+fn header(builder: &Builder) -> Vec<u8> {
+    let mut ser = builder.build(vec![]);
+    ser.write_header().unwrap();
+    ser.into_inner()
+}
+
+#[test]
+fn jackson_defaults_header() {
+    assert_eq!(header(&Builder::jackson_defaults()), b":)\n\x01");
+}
+
+#[test]
+fn smallest_output_header() {
+    assert_eq!(header(&Builder::smallest_output()), b":)\n\x07");
+}
+
+#[test]
+fn fastest_header() {
+    assert_eq!(header(&Builder::fastest()), b":)\n\x04");
+}
+
+#[test]
+fn default_is_jackson_defaults() {
+    assert_eq!(
+        header(&crate::Serializer::builder()),
+        header(&Builder::jackson_defaults()),
+    );
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCase {
+    #[serde(default)]
+    raw_binary: bool,
+    #[serde(default)]
+    shared_strings: bool,
+    #[serde(default)]
+    shared_properties: bool,
+    #[serde(default)]
+    write_end_marker: bool,
+    value: Vec<IndexMap<String, i32>>,
+}
+
+fn jackson_fixture(name: &str) {
+    let test_case = fs::read(format!("tests/shared_property/{}.json", name)).unwrap();
+    let test_case = serde_json::from_slice::<TestCase>(&test_case).unwrap();
+    assert!(!test_case.raw_binary);
+    assert!(!test_case.shared_strings);
+    assert!(test_case.shared_properties);
+    assert!(!test_case.write_end_marker);
+
+    let expected = fs::read(format!("tests/shared_property/{}.smile", name)).unwrap();
+
+    let mut ser = Builder::jackson_defaults().build(vec![]);
+    test_case.value.serialize(&mut ser).unwrap();
+    assert_eq!(expected, ser.into_inner());
+}
+
+#[test]
+fn jackson_defaults_matches_jackson() {
+    jackson_fixture("ab");
+    jackson_fixture("large");
+    jackson_fixture("evict");
+}
@@ -0,0 +1,133 @@
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+
+#[test]
+#[cfg_attr(
+    not(feature = "preserve_order"),
+    ignore = "the expected output lists keys in insertion order"
+)]
+fn every_variant() {
+    let mut nested = Map::new();
+    nested.insert("empty array".to_string(), Value::Array(vec![]));
+    nested.insert("empty object".to_string(), Value::Object(Map::new()));
+    nested.insert(
+        "array".to_string(),
+        Value::Array(vec![Value::Integer(1), Value::Array(vec![Value::Null])]),
+    );
+
+    let mut map = Map::new();
+    map.insert("null".to_string(), Value::Null);
+    map.insert("true".to_string(), Value::Boolean(true));
+    map.insert("false".to_string(), Value::Boolean(false));
+    map.insert("integer".to_string(), Value::Integer(-5));
+    map.insert("long".to_string(), Value::Long(10_000_000_000));
+    map.insert(
+        "big integer".to_string(),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0xff, 0, 0, 0, 0, 0, 0, 0, 0,
+        ])),
+    );
+    map.insert("float".to_string(), Value::Float(1.5));
+    map.insert("double".to_string(), Value::Double(-0.25));
+    map.insert(
+        "big decimal".to_string(),
+        Value::BigDecimal(BigDecimal::new(
+            BigInteger::from_be_bytes(vec![0x27, 0x29]),
+            2,
+        )),
+    );
+    map.insert(
+        "string".to_string(),
+        Value::String("a \"quoted\"\nline".into()),
+    );
+    map.insert(
+        "binary".to_string(),
+        Value::binary(vec![0xde, 0xad, 0xbe, 0xef]),
+    );
+    map.insert("nested".to_string(), Value::Object(nested));
+
+    let expected = r#"{
+  "null": null,
+  "true": true,
+  "false": false,
+  "integer": -5,
+  "long": 10000000000L,
+  "big integer": -18446744073709551616BI,
+  "float": 1.5F,
+  "double": -0.25,
+  "big decimal": 100.25BD,
+  "string": "a \"quoted\"\nline",
+  "binary": <deadbeef>,
+  "nested": {
+    "empty array": [],
+    "empty object": {},
+    "array": [
+      1,
+      [
+        null
+      ]
+    ]
+  }
+}"#;
+
+    let value = Value::Object(map);
+    assert_eq!(value.to_string_pretty(), expected);
+    assert_eq!(value.pretty().to_string(), expected);
+    assert_eq!(format!("{}", value.pretty()), expected);
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(Value::Null.to_string_pretty(), "null");
+    assert_eq!(Value::Integer(1).to_string_pretty(), "1");
+    assert_eq!(Value::Long(1).to_string_pretty(), "1L");
+    assert_eq!(Value::Float(1.).to_string_pretty(), "1.0F");
+    assert_eq!(Value::Double(1.).to_string_pretty(), "1.0");
+    assert_eq!(Value::Double(f64::NAN).to_string_pretty(), "NaN");
+    assert_eq!(Value::Float(f32::NEG_INFINITY).to_string_pretty(), "-infF");
+    assert_eq!(Value::Array(vec![]).to_string_pretty(), "[]");
+    assert_eq!(Value::Object(Map::new()).to_string_pretty(), "{}");
+    assert_eq!(Value::binary(vec![]).to_string_pretty(), "<>");
+}
+
+#[test]
+fn key_order() {
+    let mut map = Map::new();
+    map.insert("b".to_string(), Value::Integer(1));
+    map.insert("a".to_string(), Value::Integer(2));
+    let expected = if cfg!(feature = "preserve_order") {
+        "{\n  \"b\": 1,\n  \"a\": 2\n}"
+    } else {
+        "{\n  \"a\": 2,\n  \"b\": 1\n}"
+    };
+    assert_eq!(Value::Object(map).to_string_pretty(), expected);
+}
+
+#[test]
+fn binary_truncation() {
+    let value = Value::Binary((0..40).collect());
+    assert_eq!(
+        value.to_string_pretty(),
+        "<000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f...> (40 bytes)",
+    );
+    assert_eq!(
+        value.pretty().max_binary_bytes(4).to_string(),
+        "<00010203...> (40 bytes)",
+    );
+    assert_eq!(
+        value.pretty().max_binary_bytes(0).to_string(),
+        "<...> (40 bytes)"
+    );
+    assert_eq!(
+        value.pretty().max_binary_bytes(40).to_string(),
+        format!(
+            "<{}>",
+            (0..40).map(|b| format!("{:02x}", b)).collect::<String>()
+        ),
+    );
+
+    let value = Value::Array(vec![Value::binary(vec![1, 2, 3])]);
+    assert_eq!(
+        value.pretty().max_binary_bytes(2).to_string(),
+        "[\n  <0102...> (3 bytes)\n]"
+    );
+}
@@ -0,0 +1,323 @@
+use crate::ser::Builder;
+use crate::smile;
+use crate::value::{self, BigInteger, Preview, PreviewLimits, Value};
+use std::ffi::OsStr;
+use std::fs;
+
+fn fixtures() -> Vec<Value> {
+    let mut values = vec![];
+    for category in fs::read_dir("tests").unwrap() {
+        let category = category.unwrap().path();
+        // the documents in `tests/quirks` are written by nonconforming producers
+        if !category.is_dir() || category.ends_with("quirks") {
+            continue;
+        }
+
+        for path in fs::read_dir(&category).unwrap() {
+            let path = path.unwrap().path();
+            if path.extension() == Some(OsStr::new("smile")) {
+                values.push(crate::from_slice::<Value>(&fs::read(&path).unwrap()).unwrap());
+            }
+        }
+    }
+    assert!(!values.is_empty());
+    values
+}
+
+/// Asserts that the untruncated portions of the preview match the value.
+fn check_prefix(preview: &Preview, value: &Value) {
+    match (preview, value) {
+        (Preview::Value(a), b) => assert_eq!(a, b),
+        (Preview::TruncatedString { prefix, len }, Value::String(v)) => {
+            assert!(v.starts_with(&**prefix));
+            assert!(prefix.len() < v.len());
+            assert_eq!(*len, v.len());
+        }
+        (Preview::TruncatedBinary { prefix, len }, Value::Binary(v)) => {
+            assert!(v.starts_with(prefix));
+            assert!(prefix.len() < v.len());
+            assert_eq!(*len, v.len());
+        }
+        (Preview::Array { elements, omitted }, Value::Array(v)) => {
+            assert_eq!(elements.len() + omitted, v.len());
+            for (a, b) in elements.iter().zip(v) {
+                check_prefix(a, b);
+            }
+        }
+        (Preview::Object { entries, omitted }, Value::Object(v)) => {
+            assert_eq!(entries.len() + omitted, v.len());
+            for (key, a) in entries {
+                check_prefix(a, &v[&**key]);
+            }
+        }
+        (a, b) => panic!("{:?} does not match {:?}", a, b),
+    }
+}
+
+fn count_nodes(preview: &Preview) -> usize {
+    match preview {
+        Preview::Array { elements, .. } => 1 + elements.iter().map(count_nodes).sum::<usize>(),
+        Preview::Object { entries, .. } => {
+            1 + entries.iter().map(|(_, v)| count_nodes(v)).sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
+#[test]
+fn fixtures_match_full_decode() {
+    for value in fixtures() {
+        for builder in [Builder::jackson_defaults(), Builder::smallest_output()] {
+            let buf = builder.to_vec(&value).unwrap();
+
+            let preview = value::preview(&buf, &PreviewLimits::new()).unwrap();
+            assert!(!preview.is_truncated(), "{:?}", value);
+            check_prefix(&preview, &value);
+
+            for max in [0, 1, 2, 5, 20] {
+                let mut limits = PreviewLimits::new();
+                limits
+                    .max_string_bytes(max)
+                    .max_binary_bytes(max)
+                    .max_container_elements(max)
+                    .max_total_nodes(max);
+                let preview = value::preview(&buf, &limits).unwrap();
+                check_prefix(&preview, &value);
+                assert!(count_nodes(&preview) <= max.max(1));
+            }
+        }
+    }
+}
+
+#[test]
+fn untruncated() {
+    let value = smile!({
+        "a": [1, 2.5, "hello", null, true],
+        "b": Value::binary(vec![1, 2, 3]),
+        "c": Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20])),
+        "d": {},
+        "e": [],
+    });
+    let buf = crate::to_vec(&value).unwrap();
+
+    let mut limits = PreviewLimits::new();
+    limits
+        .max_string_bytes(5)
+        .max_binary_bytes(3)
+        .max_container_elements(5)
+        .max_total_nodes(12);
+    let preview = value::preview(&buf, &limits).unwrap();
+    assert!(!preview.is_truncated());
+    check_prefix(&preview, &value);
+}
+
+#[test]
+fn strings() {
+    let buf = crate::to_vec(&smile!(["hello world", "h\u{e9}llo", "\u{1f642}"])).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_string_bytes(2);
+
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Array {
+            elements: vec![
+                Preview::TruncatedString {
+                    prefix: "he".to_string(),
+                    len: 11,
+                },
+                // the limit falls within the two byte encoding of é
+                Preview::TruncatedString {
+                    prefix: "h".to_string(),
+                    len: 6,
+                },
+                Preview::TruncatedString {
+                    prefix: String::new(),
+                    len: 4,
+                },
+            ],
+            omitted: 0,
+        }
+    );
+
+    // keys are never truncated
+    let buf = crate::to_vec(&smile!({"hello world": 1})).unwrap();
+    let preview = value::preview(&buf, &limits).unwrap();
+    assert!(!preview.is_truncated());
+}
+
+#[test]
+fn binary() {
+    let buf = crate::to_vec(&Value::binary((0..100).collect::<Vec<u8>>())).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_binary_bytes(4);
+
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::TruncatedBinary {
+            prefix: vec![0, 1, 2, 3],
+            len: 100,
+        }
+    );
+}
+
+#[test]
+fn container_elements() {
+    let buf = crate::to_vec(&smile!({"a": [1, 2, 3], "b": {"c": 1, "d": 2}, "e": 3})).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_container_elements(2);
+
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Object {
+            entries: vec![
+                (
+                    "a".to_string(),
+                    Preview::Array {
+                        elements: vec![
+                            Preview::Value(Value::Integer(1)),
+                            Preview::Value(Value::Integer(2)),
+                        ],
+                        omitted: 1,
+                    }
+                ),
+                (
+                    "b".to_string(),
+                    Preview::Object {
+                        entries: vec![
+                            ("c".to_string(), Preview::Value(Value::Integer(1))),
+                            ("d".to_string(), Preview::Value(Value::Integer(2))),
+                        ],
+                        omitted: 0,
+                    }
+                ),
+            ],
+            omitted: 1,
+        }
+    );
+
+    limits.max_container_elements(0);
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Object {
+            entries: vec![],
+            omitted: 3,
+        }
+    );
+}
+
+#[test]
+fn total_nodes() {
+    let buf = crate::to_vec(&smile!([[1, 2], [3, 4], 5])).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_total_nodes(4);
+
+    // the limit applies across containers, so every open container is cut off once it is reached
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Array {
+            elements: vec![Preview::Array {
+                elements: vec![
+                    Preview::Value(Value::Integer(1)),
+                    Preview::Value(Value::Integer(2)),
+                ],
+                omitted: 0,
+            }],
+            omitted: 2,
+        }
+    );
+
+    limits.max_total_nodes(0);
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Array {
+            elements: vec![],
+            omitted: 3,
+        }
+    );
+
+    let buf = crate::to_vec(&"hello").unwrap();
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Value(smile!("hello"))
+    );
+}
+
+#[test]
+fn big_numbers_in_omitted_values() {
+    let value = smile!([
+        1,
+        Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20])),
+        {"a": Value::BigInteger(BigInteger::from_be_bytes(vec![2; 20]))},
+    ]);
+    let buf = crate::to_vec(&value).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_container_elements(1);
+
+    let preview = value::preview(&buf, &limits).unwrap();
+    check_prefix(&preview, &value);
+    assert_eq!(count_nodes(&preview), 2);
+}
+
+#[test]
+fn shared_strings_in_omitted_values() {
+    // the omitted values define shared strings and keys which are referenced by the values that follow them
+    let value = smile!({
+        "first": [0, 1, "shared value", {"shared_key": "other value"}],
+        "second": ["shared value", {"shared_key": "other value"}],
+        "shared_key": "other value",
+    });
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+    let mut limits = PreviewLimits::new();
+    limits.max_container_elements(2);
+
+    assert_eq!(
+        value::preview(&buf, &limits).unwrap(),
+        Preview::Object {
+            entries: vec![
+                (
+                    "first".to_string(),
+                    Preview::Array {
+                        elements: vec![
+                            Preview::Value(Value::Integer(0)),
+                            Preview::Value(Value::Integer(1)),
+                        ],
+                        omitted: 2,
+                    }
+                ),
+                (
+                    "second".to_string(),
+                    Preview::Array {
+                        elements: vec![
+                            Preview::Value(smile!("shared value")),
+                            Preview::Object {
+                                entries: vec![(
+                                    "shared_key".to_string(),
+                                    Preview::Value(smile!("other value")),
+                                )],
+                                omitted: 0,
+                            },
+                        ],
+                        omitted: 0,
+                    }
+                ),
+            ],
+            omitted: 1,
+        }
+    );
+}
+
+#[test]
+fn malformed_documents() {
+    let mut limits = PreviewLimits::new();
+    limits.max_container_elements(1);
+
+    // errors are reported even in omitted values
+    let e = value::preview(b":)\n\x00\xf8\x21\xf8\xfb", &limits).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected token 0xfb at offset 7");
+
+    let e = value::preview(b":)\n\x00\xf8\x21\x21", &limits).unwrap_err();
+    assert_eq!(e.to_string(), "EOF while parsing array");
+
+    let e = value::preview(b":)\n\x00\x21\x21", &limits).unwrap_err();
+    assert_eq!(e.to_string(), "trailing data");
+}
@@ -0,0 +1,151 @@
+use crate::de::{IoRead, SliceRead};
+use crate::ser::Builder;
+use crate::value::{Map, Value};
+use crate::{Deserializer, Error};
+use serde::Deserialize;
+
+fn deserialize(buf: &[u8], max: usize) -> Result<Vec<Value>, Error> {
+    let result = Deserializer::builder()
+        .max_property_cache_bytes(max)
+        .build(SliceRead::new(buf))
+        .into_iter()
+        .collect::<Result<Vec<Value>, _>>();
+
+    let io_result = Deserializer::builder()
+        .max_property_cache_bytes(max)
+        .build(IoRead::new(buf))
+        .into_iter()
+        .collect::<Result<Vec<Value>, _>>();
+
+    // the limit applies the same way whether or not the strings are borrowed from the input
+    match (&result, &io_result) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b),
+        (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string()),
+        (a, b) => panic!("{:?} != {:?}", a, b),
+    }
+    result
+}
+
+fn expected_error(max: usize) -> String {
+    format!(
+        "shared string cache limit of {} bytes exceeded; the document may be hostile",
+        max
+    )
+}
+
+fn keys(n: usize) -> Value {
+    let mut map = Map::new();
+    for i in 0..n {
+        map.insert(format!("key_{:06}", i), Value::Null);
+    }
+    Value::Object(map)
+}
+
+fn strings(n: usize) -> Value {
+    Value::Array(
+        (0..n)
+            .map(|i| Value::from(format!("val_{:06}", i)))
+            .collect(),
+    )
+}
+
+fn both(n: usize) -> Value {
+    let mut map = Map::new();
+    for i in 0..n {
+        map.insert(
+            format!("key_{:06}", i),
+            Value::from(format!("val_{:06}", i)),
+        );
+    }
+    Value::Object(map)
+}
+
+#[test]
+fn keys_exceed_limit() {
+    let value = keys(10);
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+
+    assert_eq!(deserialize(&buf, 100).unwrap(), [value]);
+    let e = deserialize(&buf, 99).unwrap_err();
+    assert_eq!(e.to_string(), expected_error(99));
+    assert!(!e.is_retryable());
+}
+
+#[test]
+fn values_exceed_limit() {
+    let value = strings(10);
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+
+    assert_eq!(
+        deserialize(&buf, 100).unwrap(),
+        std::slice::from_ref(&value)
+    );
+    let e = deserialize(&buf, 99).unwrap_err();
+    assert_eq!(e.to_string(), expected_error(99));
+
+    // value strings are only cached when the document enables shared strings
+    let buf = Builder::jackson_defaults().to_vec(&value).unwrap();
+    assert_eq!(deserialize(&buf, 0).unwrap(), [value]);
+}
+
+#[test]
+fn both_exceed_limit() {
+    // neither cache exceeds the limit on its own
+    let value = both(5);
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+
+    assert_eq!(deserialize(&buf, 100).unwrap(), [value]);
+    let e = deserialize(&buf, 60).unwrap_err();
+    assert_eq!(e.to_string(), expected_error(60));
+}
+
+#[test]
+fn long_strings_are_not_cached() {
+    let value = Value::Array(vec![Value::from("a".repeat(65)); 3]);
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+    assert_eq!(deserialize(&buf, 0).unwrap(), [value]);
+}
+
+#[test]
+fn full_cache_is_reset() {
+    // the producer empties the cache after 1024 strings, so only those since count towards the limit
+    let value = keys(1500);
+    let buf = Builder::smallest_output().to_vec(&value).unwrap();
+
+    assert_eq!(deserialize(&buf, 1024 * 10).unwrap(), [value]);
+    let e = deserialize(&buf, 1024 * 10 - 1).unwrap_err();
+    assert_eq!(e.to_string(), expected_error(1024 * 10 - 1));
+}
+
+#[test]
+fn limit_spans_values_in_document() {
+    let mut first = Map::new();
+    let mut second = Map::new();
+    for i in 0..5 {
+        first.insert(format!("key_{:06}", i), Value::Null);
+        second.insert(format!("key_{:06}", i + 5), Value::Null);
+    }
+
+    let mut ser = Builder::smallest_output().build(vec![]);
+    serde::Serialize::serialize(&first, &mut ser).unwrap();
+    serde::Serialize::serialize(&second, &mut ser).unwrap();
+    let buf = ser.into_inner();
+
+    let mut it = Deserializer::builder()
+        .max_property_cache_bytes(50)
+        .build(SliceRead::new(&buf))
+        .into_iter::<Map>();
+    assert_eq!(it.next().unwrap().unwrap(), first);
+    let e = it.next().unwrap().unwrap_err();
+    assert_eq!(e.to_string(), expected_error(50));
+}
+
+#[test]
+fn single_value() {
+    let buf = Builder::smallest_output().to_vec(&keys(10)).unwrap();
+    let mut de = Deserializer::builder()
+        .max_property_cache_bytes(50)
+        .build(SliceRead::new(&buf));
+    let e = Value::deserialize(&mut de).unwrap_err();
+    assert_eq!(e.to_string(), expected_error(50));
+}
@@ -0,0 +1,103 @@
+use crate::value::{to_value, Value};
+use crate::{RawByteBuf, RawBytes, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Owned {
+    raw: RawByteBuf,
+    normal: ByteBuf,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    #[serde(borrow)]
+    raw: RawBytes<'a>,
+    normal: ByteBuf,
+}
+
+fn payload() -> Vec<u8> {
+    (0..100).collect()
+}
+
+fn serialize<T>(raw_binary: bool, value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = Serializer::builder().raw_binary(raw_binary).build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn count(buf: &[u8], token: u8) -> usize {
+    buf.iter().filter(|b| **b == token).count()
+}
+
+#[test]
+fn single_raw_field() {
+    let value = Owned {
+        raw: RawByteBuf(payload()),
+        normal: ByteBuf::from(vec![1, 2, 3]),
+    };
+    let buf = serialize(false, &value);
+
+    assert_eq!(buf[3] & 0x04, 0);
+    assert_eq!(count(&buf, 0xfd), 1);
+    assert_eq!(count(&buf, 0xe8), 1);
+
+    assert_eq!(crate::from_slice::<Owned>(&buf).unwrap(), value);
+    assert_eq!(
+        crate::from_mut_slice::<Owned>(&mut buf.clone()).unwrap(),
+        value
+    );
+    assert_eq!(crate::from_reader::<Owned, _>(&*buf).unwrap(), value);
+}
+
+#[test]
+fn borrowed_matches_owned() {
+    let payload = payload();
+    let borrowed = Borrowed {
+        raw: RawBytes(&payload),
+        normal: ByteBuf::from(vec![1, 2, 3]),
+    };
+    let owned = Owned {
+        raw: RawByteBuf(payload.clone()),
+        normal: ByteBuf::from(vec![1, 2, 3]),
+    };
+    let buf = serialize(false, &borrowed);
+    assert_eq!(buf, serialize(false, &owned));
+
+    assert_eq!(crate::from_slice::<Borrowed<'_>>(&buf).unwrap(), borrowed);
+}
+
+#[test]
+fn raw_binary_builder() {
+    let value = Owned {
+        raw: RawByteBuf(payload()),
+        normal: ByteBuf::from(vec![1, 2, 3]),
+    };
+    let buf = serialize(true, &value);
+
+    assert_eq!(count(&buf, 0xfd), 2);
+    assert_eq!(count(&buf, 0xe8), 0);
+    assert_eq!(crate::from_slice::<Owned>(&buf).unwrap(), value);
+}
+
+#[test]
+fn deserialize_7_bit() {
+    let buf = serialize(false, &ByteBuf::from(payload()));
+    assert_eq!(
+        crate::from_slice::<RawByteBuf>(&buf).unwrap(),
+        RawByteBuf(payload())
+    );
+    crate::from_slice::<RawBytes<'_>>(&buf).unwrap_err();
+}
+
+#[test]
+fn value() {
+    let value = to_value(&RawBytes(&[1, 2, 3])).unwrap();
+    assert_eq!(value, Value::binary(vec![1, 2, 3]));
+
+    let value = crate::from_slice::<Value>(&serialize(false, &RawBytes(&[1, 2, 3]))).unwrap();
+    assert_eq!(value, Value::binary(vec![1, 2, 3]));
+}
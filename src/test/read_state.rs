@@ -0,0 +1,104 @@
+use crate::de::{CowRead, IoRead, MutSliceRead, Read, ReadState, SliceRead};
+use crate::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::BufReader;
+
+/// Encodes a stream of values, returning the stream along with the offset of the end of each value.
+fn encode() -> (Vec<u8>, Vec<usize>) {
+    let mut ser = Serializer::new(vec![]);
+    let mut ends = vec![];
+    for value in ["hello", "world", "!"] {
+        value.serialize(&mut ser).unwrap();
+        ends.push(ser.get_ref().len());
+    }
+    (ser.into_inner(), ends)
+}
+
+/// Deserializes each value of the stream, returning the reported progress after each one.
+fn progress<'de, R>(de: &mut Deserializer<'de, R>) -> Vec<(u64, Option<Vec<u8>>)>
+where
+    R: Read<'de> + ReadState,
+{
+    let mut progress = vec![];
+    for _ in 0..3 {
+        String::deserialize(&mut *de).unwrap();
+        let reader = de.get_ref();
+        progress.push((reader.position(), reader.remaining().map(|s| s.to_vec())));
+    }
+    progress
+}
+
+fn expected(buf: &[u8], ends: &[usize], known: bool) -> Vec<(u64, Option<Vec<u8>>)> {
+    ends.iter()
+        .map(|&end| (end as u64, Some(buf[end..].to_vec()).filter(|_| known)))
+        .collect()
+}
+
+#[test]
+fn slice() {
+    let (buf, ends) = encode();
+
+    let mut de = Deserializer::new(SliceRead::new(&buf));
+    assert_eq!(progress(&mut de), expected(&buf, &ends, true));
+
+    let mut de = Deserializer::from_slice(&buf);
+    String::deserialize(&mut de).unwrap();
+    assert_eq!(de.get_ref().slice(), &buf[..]);
+    assert_eq!(de.get_ref().consumed(), ends[0]);
+    assert_eq!(de.get_ref().remaining_slice(), &buf[ends[0]..]);
+    assert_eq!(de.into_inner().into_inner(), &buf[ends[0]..]);
+}
+
+#[test]
+fn mut_slice() {
+    let (buf, ends) = encode();
+
+    let mut copy = buf.clone();
+    let mut de = Deserializer::new(MutSliceRead::new(&mut copy));
+    assert_eq!(progress(&mut de), expected(&buf, &ends, true));
+
+    let mut copy = buf.clone();
+    let mut de = Deserializer::from_mut_slice(&mut copy);
+    String::deserialize(&mut de).unwrap();
+    assert_eq!(de.get_ref().consumed(), ends[0]);
+    assert_eq!(de.get_ref().remaining_slice(), &buf[ends[0]..]);
+    assert_eq!(de.into_inner().into_inner(), &buf[ends[0]..]);
+}
+
+#[test]
+fn cow() {
+    let (buf, ends) = encode();
+
+    let mut de = Deserializer::new(CowRead::new(Cow::Borrowed(&buf)));
+    assert_eq!(progress(&mut de), expected(&buf, &ends, true));
+
+    let mut de = Deserializer::new(CowRead::new(Cow::Owned(buf.clone())));
+    assert_eq!(progress(&mut de), expected(&buf, &ends, true));
+}
+
+#[test]
+fn io() {
+    let (buf, ends) = encode();
+
+    let mut de = Deserializer::new(IoRead::new(BufReader::with_capacity(2, &*buf)));
+    assert_eq!(progress(&mut de), expected(&buf, &ends, false));
+}
+
+#[test]
+fn end_marker() {
+    let mut ser = Serializer::new(vec![]);
+    "hello".serialize(&mut ser).unwrap();
+    let end = ser.get_ref().len();
+    ser.end().unwrap();
+    let buf = ser.into_inner();
+
+    let mut de = Deserializer::from_slice(&buf);
+    assert_eq!(de.get_ref().consumed(), 0);
+    String::deserialize(&mut de).unwrap();
+    assert_eq!(de.get_ref().consumed(), end);
+    assert_eq!(de.get_ref().remaining_slice(), [0xff]);
+    de.end().unwrap();
+    assert_eq!(de.get_ref().consumed(), buf.len());
+    assert_eq!(de.get_ref().remaining_slice(), b"");
+}
@@ -0,0 +1,85 @@
+//! Exercises the readers directly at and past the end of their input.
+use crate::de::{CowRead, IoRead, MutSliceRead, Read, SliceRead};
+use std::borrow::Cow;
+use std::io::BufReader;
+
+const INPUT: &[u8] = &[1, 2, 3, 0xfc, 4];
+
+/// Reads all of `INPUT` and then checks that every method reports the end of the input without advancing.
+fn check<'de, R>(mut reader: R)
+where
+    R: Read<'de>,
+{
+    assert_eq!(reader.peek().unwrap(), Some(1));
+    assert_eq!(reader.next().unwrap(), Some(1));
+    assert_eq!(reader.peek().unwrap(), Some(2));
+    reader.consume();
+    assert_eq!(&*reader.read(1).unwrap().unwrap(), [3]);
+    assert_eq!(&*reader.read_until(0xfc).unwrap().unwrap(), b"");
+    assert_eq!(&*reader.read_mut(1).unwrap().unwrap(), [4]);
+    assert_eq!(reader.byte_offset(), 5);
+
+    for _ in 0..2 {
+        assert_eq!(reader.next().unwrap(), None);
+        assert_eq!(reader.peek().unwrap(), None);
+        assert_eq!(&*reader.read(0).unwrap().unwrap(), b"");
+        assert!(reader.read(1).unwrap().is_none());
+        assert_eq!(&*reader.read_mut(0).unwrap().unwrap(), b"");
+        assert!(reader.read_mut(1).unwrap().is_none());
+        assert!(reader.read_until(0xfc).unwrap().is_none());
+        assert_eq!(reader.byte_offset(), 5);
+    }
+}
+
+/// Checks that consuming past the end of the input is a no-op.
+fn check_consume<'de, R>(mut reader: R)
+where
+    R: Read<'de>,
+{
+    assert_eq!(&*reader.read(INPUT.len()).unwrap().unwrap(), INPUT);
+    reader.consume();
+    reader.consume();
+    assert_eq!(reader.byte_offset(), INPUT.len() as u64);
+    assert_eq!(reader.peek().unwrap(), None);
+    assert_eq!(reader.next().unwrap(), None);
+}
+
+#[test]
+fn slice() {
+    check(SliceRead::new(INPUT));
+    check_consume(SliceRead::new(INPUT));
+}
+
+#[test]
+fn mut_slice() {
+    check(MutSliceRead::new(&mut INPUT.to_vec()));
+    check_consume(MutSliceRead::new(&mut INPUT.to_vec()));
+}
+
+#[test]
+fn cow() {
+    check(CowRead::new(Cow::Borrowed(INPUT)));
+    check(CowRead::new(Cow::Owned(INPUT.to_vec())));
+    check_consume(CowRead::new(Cow::Borrowed(INPUT)));
+    check_consume(CowRead::new(Cow::Owned(INPUT.to_vec())));
+}
+
+#[test]
+fn io() {
+    for capacity in [1, 2, 16] {
+        check(IoRead::new(BufReader::with_capacity(capacity, INPUT)));
+    }
+}
+
+#[test]
+fn empty() {
+    let mut reader = SliceRead::new(b"");
+    reader.consume();
+    assert_eq!(reader.byte_offset(), 0);
+    assert_eq!(reader.next().unwrap(), None);
+
+    let mut reader = MutSliceRead::new(&mut []);
+    reader.consume();
+    assert_eq!(reader.byte_offset(), 0);
+    assert!(reader.read_until(0).unwrap().is_none());
+}
@@ -0,0 +1,96 @@
+use crate::value::Value;
+use crate::{Deserializer, Serializer};
+use serde::Deserialize;
+use std::io::{BufReader, Read};
+
+fn documents() -> Vec<Value> {
+    vec![
+        Value::Integer(1),
+        Value::Null,
+        crate::smile!("a string long enough to need the long string encoding, which is read until a terminator 🙂"),
+        crate::smile!({"a": [1, 2.5, "b"], "c": {"d": null}}),
+        Value::Binary((0..100).collect()),
+        crate::smile!([{"repeated": "value"}, {"repeated": "value"}]),
+    ]
+}
+
+fn encode(value: &Value, end: bool) -> Vec<u8> {
+    let mut serializer = crate::ser::Builder::smallest_output().build(vec![]);
+    serde::Serialize::serialize(value, &mut serializer).unwrap();
+    if end {
+        serializer.end().unwrap();
+    }
+    serializer.into_inner()
+}
+
+fn check(end: bool) {
+    for first in documents() {
+        for second in documents() {
+            let first_buf = encode(&first, end);
+            let second_buf = encode(&second, false);
+            let mut buf = first_buf.clone();
+            buf.extend_from_slice(&second_buf);
+
+            for capacity in [1, 3, 16, 1024] {
+                let reader = BufReader::with_capacity(capacity, &*buf);
+                let mut de = Deserializer::from_reader(reader);
+                assert_eq!(Value::deserialize(&mut de).unwrap(), first);
+                if end {
+                    de.end().unwrap();
+                }
+
+                let (reader, leftover) = de.into_reader_parts();
+                assert!(leftover.is_empty());
+
+                let mut rest = vec![];
+                (&*leftover).chain(reader).read_to_end(&mut rest).unwrap();
+                assert_eq!(rest, second_buf);
+                assert_eq!(crate::from_slice::<Value>(&rest).unwrap(), second);
+            }
+        }
+    }
+}
+
+#[test]
+fn without_end_marker() {
+    check(false);
+}
+
+#[test]
+fn with_end_marker() {
+    check(true);
+}
+
+#[test]
+fn reconstructed_reader() {
+    let mut buf = encode(&Value::Integer(1), false);
+    buf.extend_from_slice(&encode(&crate::smile!({"a": "b"}), true));
+
+    let mut de = Deserializer::from_reader(BufReader::with_capacity(2, &*buf));
+    assert_eq!(i32::deserialize(&mut de).unwrap(), 1);
+    let (reader, leftover) = de.into_reader_parts();
+
+    let value = crate::from_reader::<Value, _>((&*leftover).chain(reader)).unwrap();
+    assert_eq!(value, crate::smile!({"a": "b"}));
+}
+
+#[test]
+fn header_only_peek() {
+    // peeking the end-of-stream token of a header-only document doesn't consume it
+    let mut buf = vec![];
+    let mut serializer = Serializer::new(&mut buf);
+    serializer.write_header().unwrap();
+    serializer.end().unwrap();
+    let second_buf = encode(&Value::Integer(5), false);
+    buf.extend_from_slice(&second_buf);
+
+    let mut de = Deserializer::from_reader(BufReader::with_capacity(1, &*buf));
+    assert_eq!(Option::<Value>::deserialize(&mut de).unwrap(), None);
+    de.end().unwrap();
+    let (mut reader, leftover) = de.into_reader_parts();
+    assert!(leftover.is_empty());
+
+    let mut rest = vec![];
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, second_buf);
+}
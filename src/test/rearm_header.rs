@@ -0,0 +1,145 @@
+use crate::ser::Builder;
+use crate::value::Value;
+use crate::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Doc {
+    name: String,
+}
+
+fn doc() -> Doc {
+    Doc {
+        name: "hi".to_string(),
+    }
+}
+
+#[test]
+fn reconfigure_before_header() {
+    let mut ser = Serializer::builder().build(vec![]);
+    ser.rearm_header(&Builder::smallest_output()).unwrap();
+    ser.rearm_header(Builder::fastest().version(2)).unwrap();
+    ser.write_header().unwrap();
+    assert_eq!(ser.into_inner(), b":)\n\x24");
+}
+
+#[test]
+fn changed_flags_after_header() {
+    let changes = [
+        Builder::smallest_output(),
+        Builder::fastest(),
+        Serializer::builder().shared_strings(true).clone(),
+        Serializer::builder().shared_properties(false).clone(),
+        Serializer::builder().raw_binary(true).clone(),
+        Serializer::builder().version(1).clone(),
+    ];
+
+    for builder in &changes {
+        let mut ser = Serializer::builder().build(vec![]);
+        ser.write_header().unwrap();
+        let err = ser.rearm_header(builder).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "header flags changed from 0x01 to {:#04x} after the header was written",
+                header(builder)[3],
+            ),
+        );
+
+        // the serializer is unchanged, so no second header is written
+        doc().serialize(&mut ser).unwrap();
+        let buf = ser.into_inner();
+        assert_eq!(buf, crate::to_vec(&doc()).unwrap());
+    }
+}
+
+#[test]
+fn changed_flags_after_value() {
+    let mut ser = Builder::smallest_output().build(vec![]);
+    doc().serialize(&mut ser).unwrap();
+    let err = ser.rearm_header(&Builder::fastest()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "header flags changed from 0x07 to 0x04 after the header was written",
+    );
+}
+
+#[test]
+fn identical_flags_start_new_document() {
+    let builder = Builder::smallest_output();
+    let mut ser = builder.build(vec![]);
+    for _ in 0..3 {
+        doc().serialize(&mut ser).unwrap();
+        doc().serialize(&mut ser).unwrap();
+        ser.end().unwrap();
+        ser.rearm_header(&builder).unwrap();
+    }
+    let buf = ser.into_inner();
+
+    // each document is encoded from scratch, with fresh string caches
+    let mut document = builder.build(vec![]);
+    doc().serialize(&mut document).unwrap();
+    doc().serialize(&mut document).unwrap();
+    document.end().unwrap();
+    let document = document.into_inner();
+    assert_eq!(buf, document.repeat(3));
+
+    let mut reader = &buf[..];
+    for _ in 0..3 {
+        let values = Deserializer::from_reader(&mut reader)
+            .into_iter::<Doc>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(values, [doc(), doc()]);
+    }
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn identical_flags_after_header() {
+    let mut ser = Serializer::builder().build(vec![]);
+    ser.write_header().unwrap();
+    ser.rearm_header(&Serializer::builder()).unwrap();
+    ser.write_header().unwrap();
+    assert_eq!(ser.into_inner(), b":)\n\x01:)\n\x01");
+}
+
+#[test]
+fn other_settings_may_change() {
+    let mut ser = Serializer::builder().build(vec![]);
+    ser.write_header().unwrap();
+    ser.rearm_header(Serializer::builder().memoize_keys(true))
+        .unwrap();
+}
+
+fn header(builder: &Builder) -> Vec<u8> {
+    let mut ser = builder.build(vec![]);
+    ser.write_header().unwrap();
+    ser.into_inner()
+}
+
+#[test]
+fn recursion_limit() {
+    let nested = |depth| {
+        let mut value = Value::Null;
+        for _ in 0..depth {
+            value = Value::Array(vec![value]);
+        }
+        value
+    };
+
+    let mut ser = Builder::jackson_defaults().recursion_limit(5).build(vec![]);
+    let err = nested(6).serialize(&mut ser).unwrap_err();
+    assert_eq!(err.to_string(), "recursion limit of 5 exceeded at depth 6");
+
+    // the new limit applies, and the depth of the failed value doesn't carry over
+    ser.end().unwrap();
+    ser.rearm_header(Builder::jackson_defaults().recursion_limit(10))
+        .unwrap();
+    nested(10).serialize(&mut ser).unwrap();
+    let err = nested(11).serialize(&mut ser).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "recursion limit of 10 exceeded at depth 11"
+    );
+}
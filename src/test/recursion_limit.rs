@@ -0,0 +1,148 @@
+use crate::limits::DEFAULT_RECURSION_LIMIT;
+use crate::ser::Builder;
+use crate::value::Value;
+use serde::{Deserialize, Serialize};
+
+fn nested_array(depth: usize) -> Value {
+    let mut value = Value::Null;
+    for _ in 0..depth {
+        value = Value::Array(vec![value]);
+    }
+    value
+}
+
+fn encoded_nested_array(depth: usize) -> Vec<u8> {
+    let mut buf = b":)\n\x01".to_vec();
+    buf.extend_from_slice(&vec![0xf8; depth]);
+    buf.push(0x21);
+    buf.extend_from_slice(&vec![0xf9; depth]);
+    buf
+}
+
+fn expected_error() -> String {
+    format!(
+        "recursion limit of {} exceeded at depth {}",
+        DEFAULT_RECURSION_LIMIT,
+        DEFAULT_RECURSION_LIMIT + 1
+    )
+}
+
+#[test]
+fn array_at_limit() {
+    let value = nested_array(DEFAULT_RECURSION_LIMIT);
+    let buf = crate::to_vec(&value).unwrap();
+    assert_eq!(buf, encoded_nested_array(DEFAULT_RECURSION_LIMIT));
+
+    let actual = crate::from_slice::<Value>(&buf).unwrap();
+    assert_eq!(value, actual);
+}
+
+#[test]
+fn array_over_limit() {
+    let value = nested_array(DEFAULT_RECURSION_LIMIT + 1);
+    let err = crate::to_vec(&value).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+
+    let buf = encoded_nested_array(DEFAULT_RECURSION_LIMIT + 1);
+    let err = crate::from_slice::<Value>(&buf).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Tree {
+    Leaf,
+    Node(Box<Tree>),
+    Pair(Box<Tree>, i32),
+}
+
+fn nested_tree(depth: usize, pair: bool) -> Tree {
+    let mut value = Tree::Leaf;
+    for _ in 0..depth {
+        value = if pair {
+            Tree::Pair(Box::new(value), 0)
+        } else {
+            Tree::Node(Box::new(value))
+        };
+    }
+    value
+}
+
+#[test]
+fn enum_at_limit() {
+    for (depth, pair) in [
+        (DEFAULT_RECURSION_LIMIT, false),
+        (DEFAULT_RECURSION_LIMIT / 2, true),
+    ] {
+        let value = nested_tree(depth, pair);
+        let buf = crate::to_vec(&value).unwrap();
+        let actual = crate::from_slice::<Tree>(&buf).unwrap();
+        assert_eq!(value, actual);
+    }
+}
+
+#[test]
+fn enum_over_limit() {
+    let value = nested_tree(DEFAULT_RECURSION_LIMIT + 1, false);
+    let err = crate::to_vec(&value).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+
+    // the encoding of the value that would have been produced
+    let mut buf = crate::to_vec(&nested_tree(DEFAULT_RECURSION_LIMIT, false)).unwrap();
+    buf.splice(4..4, [0xfa, 0x83, b'N', b'o', b'd', b'e'].iter().copied());
+    buf.push(0xfb);
+    let err = crate::from_slice::<Tree>(&buf).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+}
+
+/// Drops a deeply nested array without recursing.
+fn drop_nested_array(mut value: Value) {
+    while let Value::Array(mut elements) = value {
+        value = elements.pop().unwrap_or(Value::Null);
+    }
+}
+
+#[test]
+fn pathological_array() {
+    let value = nested_array(100_000);
+    let err = crate::to_vec(&value).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+
+    let mut buf = vec![];
+    let err = crate::to_writer(&mut buf, &value).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+
+    drop_nested_array(value);
+}
+
+#[test]
+fn custom_limit() {
+    let mut builder = Builder::jackson_defaults();
+    builder.recursion_limit(5);
+
+    let buf = builder.to_vec(&nested_array(5)).unwrap();
+    assert_eq!(buf, encoded_nested_array(5));
+
+    let err = builder.to_vec(&nested_array(6)).unwrap_err();
+    assert_eq!(err.to_string(), "recursion limit of 5 exceeded at depth 6");
+    let err = builder.to_vec(&nested_tree(6, false)).unwrap_err();
+    assert_eq!(err.to_string(), "recursion limit of 5 exceeded at depth 6");
+
+    builder.recursion_limit(0);
+    builder.to_vec(&1).unwrap();
+    let err = builder.to_vec(&Value::Array(vec![])).unwrap_err();
+    assert_eq!(err.to_string(), "recursion limit of 0 exceeded at depth 1");
+}
+
+#[test]
+fn raised_limit() {
+    let depth = DEFAULT_RECURSION_LIMIT * 2;
+    let mut builder = Builder::jackson_defaults();
+    builder.recursion_limit(depth);
+
+    let buf = builder.to_vec(&nested_array(depth)).unwrap();
+    assert_eq!(buf, encoded_nested_array(depth));
+
+    // the deserializer still enforces the default limit
+    let err = crate::from_slice::<Value>(&buf).unwrap_err();
+    assert_eq!(err.to_string(), expected_error());
+}
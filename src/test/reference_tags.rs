@@ -0,0 +1,279 @@
+use crate::de::{Deserializer, ReferenceTags, SliceRead};
+use crate::ser::Builder;
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::{de, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+fn shared<T>(value: &T) -> Vec<u8>
+where
+    T: Serialize,
+{
+    let mut ser = Builder::smallest_output().build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn tagged(buf: &[u8]) -> Deserializer<'_, SliceRead<'_>> {
+    Deserializer::builder()
+        .reference_tags(true)
+        .build(SliceRead::new(buf))
+}
+
+/// An arena of strings which skips the content lookup for strings decoded from a back-reference it has seen before.
+struct Arena {
+    tags: ReferenceTags,
+    strings: Vec<String>,
+    by_content: HashMap<String, usize>,
+    by_reference: HashMap<u16, usize>,
+    reference_hits: usize,
+}
+
+impl Arena {
+    fn new(tags: ReferenceTags) -> Self {
+        Arena {
+            tags,
+            strings: vec![],
+            by_content: HashMap::new(),
+            by_reference: HashMap::new(),
+            reference_hits: 0,
+        }
+    }
+
+    fn intern(&mut self, s: &str) -> usize {
+        let reference = self.tags.last_string_reference();
+
+        // references are reused once the decoder's table fills, so the slot must still hold the same string
+        if let Some(&id) = reference.and_then(|r| self.by_reference.get(&r)) {
+            if self.strings[id] == s {
+                self.reference_hits += 1;
+                return id;
+            }
+        }
+
+        let id = match self.by_content.get(s) {
+            Some(&id) => id,
+            None => {
+                let id = self.strings.len();
+                self.strings.push(s.to_string());
+                self.by_content.insert(s.to_string(), id);
+                id
+            }
+        };
+        if let Some(reference) = reference {
+            self.by_reference.insert(reference, id);
+        }
+        id
+    }
+}
+
+struct InternSeq<'a>(&'a mut Arena);
+
+impl<'de> DeserializeSeed<'de> for InternSeq<'_> {
+    type Value = Vec<usize>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for InternSeq<'_> {
+    type Value = Vec<usize>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of strings")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut ids = vec![];
+        while let Some(id) = seq.next_element_seed(Intern(&mut *self.0))? {
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+}
+
+struct Intern<'a>(&'a mut Arena);
+
+impl<'de> DeserializeSeed<'de> for Intern<'_> {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Intern<'_> {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(self.0.intern(v))
+    }
+}
+
+/// Collects the tags of every key of a sequence of objects.
+struct KeyTags(ReferenceTags);
+
+impl<'de> DeserializeSeed<'de> for KeyTags {
+    type Value = Vec<(String, Option<u16>)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyTags {
+    type Value = Vec<(String, Option<u16>)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of maps")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut tags = vec![];
+        while let Some(()) = seq.next_element_seed(KeyTagsMap(&self.0, &mut tags))? {}
+        Ok(tags)
+    }
+}
+
+struct KeyTagsMap<'a>(&'a ReferenceTags, &'a mut Vec<(String, Option<u16>)>);
+
+impl<'de> DeserializeSeed<'de> for KeyTagsMap<'_> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeyTagsMap<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while let Some(key) = map.next_key::<String>()? {
+            self.1.push((key, self.0.last_string_reference()));
+            map.next_value::<de::IgnoredAny>()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Entry {
+    name: &'static str,
+    kind: &'static str,
+}
+
+#[test]
+fn disabled_by_default() {
+    let buf = shared(&["alpha", "alpha"]);
+    let mut de = Deserializer::from_slice(&buf);
+    assert!(de.reference_tags().is_none());
+
+    let value = Vec::<String>::deserialize(&mut de).unwrap();
+    assert_eq!(value, ["alpha", "alpha"]);
+    assert_eq!(de.last_string_reference(), None);
+}
+
+#[test]
+fn value_references() {
+    let buf = shared(&["alpha", "beta", "alpha"]);
+    let mut de = tagged(&buf);
+    assert_eq!(de.last_string_reference(), None);
+
+    let value = Vec::<String>::deserialize(&mut de).unwrap();
+    assert_eq!(value, ["alpha", "beta", "alpha"]);
+    assert_eq!(de.last_string_reference(), Some(0));
+}
+
+#[test]
+fn cleared_by_other_values() {
+    let buf = shared(&("alpha", "alpha", 1));
+    let mut de = tagged(&buf);
+    let tags = de.reference_tags().unwrap();
+
+    <(String, String, i32)>::deserialize(&mut de).unwrap();
+    assert_eq!(de.last_string_reference(), None);
+    assert_eq!(tags.last_string_reference(), None);
+}
+
+#[test]
+fn inline_without_shared_strings() {
+    let buf = crate::to_vec(&["alpha", "alpha"]).unwrap();
+    let mut de = tagged(&buf);
+
+    Vec::<String>::deserialize(&mut de).unwrap();
+    assert_eq!(de.last_string_reference(), None);
+}
+
+#[test]
+fn key_references() {
+    let buf = shared(&[
+        Entry {
+            name: "a",
+            kind: "x",
+        },
+        Entry {
+            name: "b",
+            kind: "y",
+        },
+    ]);
+    let mut de = tagged(&buf);
+    let tags = de.reference_tags().unwrap();
+
+    let keys = KeyTags(tags).deserialize(&mut de).unwrap();
+    assert_eq!(
+        keys,
+        [
+            ("name".to_string(), None),
+            ("kind".to_string(), None),
+            ("name".to_string(), Some(0)),
+            ("kind".to_string(), Some(1)),
+        ]
+    );
+}
+
+#[test]
+fn arena_dedupe() {
+    let words = ["alpha", "beta", "alpha", "gamma", "beta", "alpha"];
+    let buf = shared(&words);
+    let mut de = tagged(&buf);
+    let mut arena = Arena::new(de.reference_tags().unwrap());
+
+    let ids = InternSeq(&mut arena).deserialize(&mut de).unwrap();
+    de.end().unwrap();
+
+    assert_eq!(ids, [0, 1, 0, 2, 1, 0]);
+    assert_eq!(arena.strings, ["alpha", "beta", "gamma"]);
+    // the first back-reference to each string is resolved by content, later ones by reference alone
+    assert_eq!(arena.reference_hits, 1);
+}
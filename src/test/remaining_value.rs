@@ -0,0 +1,102 @@
+use crate::value::Value;
+use crate::{smile, Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+use std::io::BufReader;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Envelope {
+    kind: String,
+}
+
+fn envelope() -> Envelope {
+    Envelope {
+        kind: "event".to_string(),
+    }
+}
+
+fn encode(ser: &mut Serializer<Vec<u8>>, values: &[Value]) {
+    envelope().serialize(&mut *ser).unwrap();
+    for value in values {
+        value.serialize(&mut *ser).unwrap();
+    }
+}
+
+fn document(values: &[Value], end: bool) -> Vec<u8> {
+    let mut ser = crate::ser::Builder::smallest_output().build(vec![]);
+    encode(&mut ser, values);
+    if end {
+        ser.end().unwrap();
+    }
+    ser.into_inner()
+}
+
+#[test]
+fn typed_prefix() {
+    let body = smile!({"kind": "event", "payload": ["event", {"kind": "nested"}]});
+    for end in [false, true] {
+        let buf = document(std::slice::from_ref(&body), end);
+
+        let mut de = Deserializer::from_slice(&buf);
+        assert_eq!(Envelope::deserialize(&mut de).unwrap(), envelope());
+        assert_eq!(de.value().unwrap(), body);
+
+        let mut de = Deserializer::from_reader(BufReader::with_capacity(3, &*buf));
+        assert_eq!(Envelope::deserialize(&mut de).unwrap(), envelope());
+        assert_eq!(de.finish_value().unwrap(), body);
+    }
+}
+
+#[test]
+fn whole_document() {
+    let buf = crate::to_vec(&smile!([1, "a"])).unwrap();
+    assert_eq!(
+        Deserializer::from_slice(&buf).finish_value().unwrap(),
+        smile!([1, "a"])
+    );
+}
+
+#[test]
+fn reader_is_recoverable() {
+    let mut buf = document(&[smile!("body")], true);
+    buf.extend_from_slice(b"after");
+
+    let mut de = Deserializer::from_reader(&buf[..]);
+    Envelope::deserialize(&mut de).unwrap();
+    assert_eq!(de.value().unwrap(), smile!("body"));
+    let (reader, buffered) = de.into_reader_parts();
+    assert_eq!([&buffered[..], reader].concat(), b"after");
+}
+
+#[test]
+fn trailing_data() {
+    let buf = document(&[smile!(1), smile!(2)], false);
+    let mut de = Deserializer::from_slice(&buf);
+    Envelope::deserialize(&mut de).unwrap();
+    assert_eq!(de.value().unwrap_err().to_string(), "trailing data");
+
+    let mut buf = document(&[smile!(1)], false);
+    buf.extend_from_slice(&crate::to_vec(&2).unwrap());
+    let mut de = Deserializer::from_slice(&buf);
+    Envelope::deserialize(&mut de).unwrap();
+    let e = de.finish_value().unwrap_err();
+    assert!(
+        e.to_string().starts_with("unexpected Smile header"),
+        "{}",
+        e
+    );
+}
+
+#[test]
+fn missing_value() {
+    for end in [false, true] {
+        let buf = document(&[], end);
+        let mut de = Deserializer::from_slice(&buf);
+        Envelope::deserialize(&mut de).unwrap();
+        assert!(de.value().is_err());
+    }
+
+    let e = Deserializer::from_slice(b":)\n\x00\xf8")
+        .finish_value()
+        .unwrap_err();
+    assert_eq!(e.to_string(), "EOF while parsing array");
+}
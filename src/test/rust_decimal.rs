@@ -0,0 +1,165 @@
+#![cfg(feature = "rust_decimal")]
+
+use crate::testsuite::{self, TestCase, TextBigDecimal};
+use crate::value::{BigDecimal, BigInteger};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(transparent)]
+struct Wrapper(#[serde(with = "crate::rust_decimal")] Decimal);
+
+fn big_decimal(unscaled: i128, scale: i32) -> BigDecimal {
+    BigDecimal::new(BigInteger::from(unscaled), scale)
+}
+
+#[test]
+fn from_decimal() {
+    let cases = [
+        ("0", 0, 0),
+        ("1.50", 150, 2),
+        ("-0.000123", -123, 6),
+        (
+            "79228162514264337593543950335",
+            79_228_162_514_264_337_593_543_950_335,
+            0,
+        ),
+        (
+            "-7.9228162514264337593543950335",
+            -79_228_162_514_264_337_593_543_950_335,
+            28,
+        ),
+    ];
+
+    for (s, unscaled, scale) in cases {
+        let v = Decimal::from_str(s).unwrap();
+        let big_decimal = BigDecimal::from(v);
+        assert_eq!(big_decimal, self::big_decimal(unscaled, scale), "{}", s);
+
+        let round_tripped = Decimal::try_from(big_decimal).unwrap();
+        assert_eq!(round_tripped, v, "{}", s);
+        assert_eq!(round_tripped.to_string(), s, "{}", s);
+    }
+}
+
+#[test]
+fn to_decimal() {
+    let cases = [
+        (big_decimal(150, 2), "1.50"),
+        // negative scales are folded into the mantissa
+        (big_decimal(15, -3), "15000"),
+        (big_decimal(-1, -28), "-10000000000000000000000000000"),
+        // values which only fit after removing trailing zeros are normalized
+        (big_decimal(1_000, 30), "0.000000000000000000000000001"),
+        (
+            BigDecimal::new(format!("1{}", "0".repeat(40)).parse().unwrap(), 20),
+            "100000000000000000000",
+        ),
+        (big_decimal(0, 1000), "0"),
+        (big_decimal(0, -1000), "0"),
+    ];
+
+    for (v, expected) in cases {
+        assert_eq!(
+            Decimal::try_from(&v).unwrap().to_string(),
+            expected,
+            "{:?}",
+            v
+        );
+    }
+}
+
+#[test]
+fn out_of_range() {
+    let cases = [
+        // 2^96
+        big_decimal(79_228_162_514_264_337_593_543_950_336, 0),
+        big_decimal(-79_228_162_514_264_337_593_543_950_336, 5),
+        big_decimal(1, 29),
+        big_decimal(-123, i32::MAX),
+        big_decimal(1, -29),
+        big_decimal(8, -28),
+        big_decimal(1, i32::MIN),
+        BigDecimal::new(BigInteger::from_be_bytes(vec![1; 20]), 0),
+    ];
+
+    for v in cases {
+        let e = Decimal::try_from(&v).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "value out of range: expected Decimal",
+            "{:?}",
+            v
+        );
+    }
+}
+
+#[test]
+fn with_module() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Payment {
+        #[serde(with = "crate::rust_decimal")]
+        amount: Decimal,
+        currency: String,
+    }
+
+    let payment = Payment {
+        amount: Decimal::from_str("-1234.50").unwrap(),
+        currency: "EUR".to_string(),
+    };
+    let buf = crate::to_vec(&payment).unwrap();
+
+    #[derive(Serialize)]
+    struct Expected {
+        amount: BigDecimal,
+        currency: &'static str,
+    }
+    let expected = Expected {
+        amount: big_decimal(-123_450, 2),
+        currency: "EUR",
+    };
+    assert_eq!(buf, crate::to_vec(&expected).unwrap());
+    assert_eq!(crate::from_slice::<Payment>(&buf).unwrap(), payment);
+
+    let buf = crate::to_vec(&big_decimal(1, 29)).unwrap();
+    let e = crate::from_slice::<Wrapper>(&buf).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: value out of range: expected Decimal"
+    );
+
+    let buf = crate::to_vec(&"1.5").unwrap();
+    assert!(crate::from_slice::<Wrapper>(&buf).is_err());
+}
+
+#[test]
+fn reference_fixtures() {
+    let dir = testsuite::fixture_dir().join("big_decimal");
+    let mut converted = 0;
+    for path in testsuite::fixtures(&dir).unwrap() {
+        let test_case = TestCase::<TextBigDecimal>::load(&path).unwrap();
+        let expected = test_case.value().0.clone();
+
+        let decoded = match crate::from_slice::<Wrapper>(test_case.expected()) {
+            Ok(decoded) => decoded,
+            Err(_) => {
+                assert!(Decimal::try_from(&expected).is_err(), "{}", path.display());
+                continue;
+            }
+        };
+        let round_tripped = BigDecimal::from(decoded.0);
+        assert!(round_tripped.numeric_eq(&expected), "{}", path.display());
+
+        // Decimal can't represent negative scales, so those values are re-encoded differently
+        if expected.scale() >= 0 {
+            assert_eq!(round_tripped, expected, "{}", path.display());
+            let mut ser = test_case.serializer_builder().build(vec![]);
+            decoded.serialize(&mut ser).unwrap();
+            test_case.assert_encoded(&ser.into_inner());
+            converted += 1;
+        }
+    }
+    assert!(converted > 0);
+}
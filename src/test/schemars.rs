@@ -0,0 +1,72 @@
+#![cfg(feature = "schemars")]
+
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+use schemars::{JsonSchema, SchemaGenerator};
+use serde_json::json;
+
+fn subschema<T>(generator: &mut SchemaGenerator) -> serde_json::Value
+where
+    T: JsonSchema,
+{
+    serde_json::to_value(generator.subschema_for::<T>()).unwrap()
+}
+
+#[test]
+fn snapshot() {
+    let mut generator = SchemaGenerator::default();
+
+    assert_eq!(
+        subschema::<BigInteger>(&mut generator),
+        json!({"$ref": "#/$defs/BigInteger"}),
+    );
+    assert_eq!(
+        subschema::<BigDecimal>(&mut generator),
+        json!({"$ref": "#/$defs/BigDecimal"}),
+    );
+    assert_eq!(subschema::<Value>(&mut generator), json!(true));
+    assert_eq!(
+        subschema::<Map>(&mut generator),
+        json!({"type": "object", "additionalProperties": true}),
+    );
+    assert_eq!(
+        subschema::<Vec<Value>>(&mut generator),
+        json!({"type": "array", "items": true}),
+    );
+    assert_eq!(
+        subschema::<Option<BigInteger>>(&mut generator),
+        json!({"anyOf": [{"$ref": "#/$defs/BigInteger"}, {"type": "null"}]}),
+    );
+
+    let definitions = serde_json::to_value(generator.definitions()).unwrap();
+    assert_eq!(
+        definitions,
+        json!({
+            "BigInteger": {
+                "description": "An arbitrary-precision integer, represented as a number or as a string of decimal \
+                    digits.",
+                "type": ["integer", "string"],
+                "format": "big-integer",
+                "pattern": "^-?[0-9]+$",
+            },
+            "BigDecimal": {
+                "description": "An arbitrary-precision decimal number, represented as a number or as a string in \
+                    the format of Java's `BigDecimal.toString`.",
+                "type": ["number", "string"],
+                "format": "big-decimal",
+                "pattern": "^-?[0-9]+(\\.[0-9]+)?([eE][+-]?[0-9]+)?$",
+            },
+        }),
+    );
+}
+
+#[test]
+fn root_schemas() {
+    assert_eq!(
+        serde_json::to_value(schemars::schema_for!(Value)).unwrap(),
+        json!({"$schema": "https://json-schema.org/draft/2020-12/schema", "title": "AnyValue"}),
+    );
+
+    let schema = serde_json::to_value(schemars::schema_for!(BigInteger)).unwrap();
+    assert_eq!(schema["title"], "BigInteger");
+    assert_eq!(schema["type"], json!(["integer", "string"]));
+}
@@ -0,0 +1,114 @@
+use crate::smile;
+use crate::value::{BigDecimal, BigInteger, Value};
+
+fn big(v: i128) -> Value {
+    Value::BigInteger(BigInteger::from_be_bytes(v.to_be_bytes().to_vec()))
+}
+
+fn big_bytes(buf: &[u8]) -> Value {
+    Value::BigInteger(BigInteger::from_be_bytes(buf.to_vec()))
+}
+
+fn decimal(unscaled: i128, scale: i32) -> Value {
+    Value::BigDecimal(BigDecimal::new(
+        BigInteger::from_be_bytes(unscaled.to_be_bytes().to_vec()),
+        scale,
+    ))
+}
+
+fn assert_semantic_eq(a: &Value, b: &Value) {
+    assert!(a.semantic_eq(b), "{:?} == {:?}", a, b);
+    assert!(b.semantic_eq(a), "{:?} == {:?}", b, a);
+}
+
+fn assert_semantic_ne(a: &Value, b: &Value) {
+    assert!(!a.semantic_eq(b), "{:?} != {:?}", a, b);
+    assert!(!b.semantic_eq(a), "{:?} != {:?}", b, a);
+}
+
+#[test]
+fn integers() {
+    assert_semantic_eq(&Value::Integer(1), &Value::Long(1));
+    assert_semantic_eq(&Value::Integer(-1), &big(-1));
+    assert_semantic_eq(&Value::Long(0), &big_bytes(&[]));
+    assert_semantic_eq(&Value::Integer(0), &big_bytes(&[0, 0, 0]));
+    assert_semantic_eq(&Value::Integer(-2), &big_bytes(&[0xff, 0xff, 0xfe]));
+    assert_semantic_ne(&Value::Integer(1), &Value::Long(2));
+    assert_semantic_ne(&Value::Integer(-1), &big_bytes(&[0, 0xff]));
+}
+
+#[test]
+fn integer_bounds() {
+    for &v in &[i64::MIN, i64::MIN + 1, i64::MAX - 1, i64::MAX] {
+        assert_semantic_eq(&Value::Long(v), &big(i128::from(v)));
+        assert_semantic_ne(&Value::Long(v), &big(i128::from(v) + 1));
+        assert_semantic_ne(&Value::Long(v), &big(i128::from(v) - 1));
+    }
+
+    // just outside the range of an i64
+    let above = big(i128::from(i64::MAX) + 1);
+    let below = big(i128::from(i64::MIN) - 1);
+    assert_semantic_ne(&Value::Long(i64::MAX), &above);
+    assert_semantic_ne(&Value::Long(i64::MIN), &below);
+    assert_semantic_eq(&above, &big_bytes(&[0, 0x80, 0, 0, 0, 0, 0, 0, 0]));
+    assert_semantic_eq(
+        &below,
+        &big_bytes(&[0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+    );
+    assert_semantic_ne(&above, &below);
+
+    for &v in &[i32::MIN, i32::MAX] {
+        assert_semantic_eq(&Value::Integer(v), &Value::Long(i64::from(v)));
+        assert_semantic_eq(&Value::Integer(v), &big(i128::from(v)));
+    }
+}
+
+#[test]
+fn floats() {
+    assert_semantic_eq(&Value::Float(1.5), &Value::Double(1.5));
+    assert_semantic_eq(&Value::Float(0.), &Value::Double(-0.));
+    assert_semantic_ne(&Value::Float(0.1), &Value::Double(0.1));
+    assert_semantic_ne(&Value::Float(f32::NAN), &Value::Double(f64::NAN));
+    assert_semantic_ne(&Value::Double(f64::NAN), &Value::Double(f64::NAN));
+}
+
+#[test]
+fn decimals() {
+    assert_semantic_eq(&decimal(150, 2), &decimal(15, 1));
+    assert_semantic_eq(&decimal(1, -2), &decimal(100, 0));
+    assert_semantic_eq(&decimal(0, 5), &decimal(0, -3));
+    assert_semantic_eq(&decimal(-1500, 3), &decimal(-15, 1));
+    assert_semantic_ne(&decimal(15, 1), &decimal(15, 2));
+    assert_semantic_ne(&decimal(15, 1), &decimal(-15, 1));
+}
+
+#[test]
+fn integral_and_floating_never_equal() {
+    assert_semantic_ne(&Value::Integer(1), &Value::Double(1.));
+    assert_semantic_ne(&Value::Long(1), &Value::Float(1.));
+    assert_semantic_ne(&big(1), &decimal(1, 0));
+    assert_semantic_ne(&Value::Double(1.5), &decimal(15, 1));
+}
+
+#[test]
+fn other_values() {
+    assert_semantic_eq(&smile!(null), &smile!(null));
+    assert_semantic_eq(&smile!("a"), &smile!("a"));
+    assert_semantic_ne(&smile!("a"), &Value::binary(b"a".to_vec()));
+    assert_semantic_ne(&smile!(null), &smile!(false));
+    assert_semantic_ne(&smile!("1"), &smile!(1));
+}
+
+#[test]
+fn containers() {
+    let a = smile!({"a": [1, 2.5], "b": {"c": null}});
+    let b = smile!({"b": {"c": null}, "a": [(Value::Long(1)), (Value::Float(2.5))]});
+    assert_semantic_eq(&a, &b);
+    assert_ne!(a, b);
+
+    assert_semantic_ne(&smile!([1, 2]), &smile!([1]));
+    assert_semantic_ne(&smile!([1, 2]), &smile!([2, 1]));
+    assert_semantic_ne(&smile!({"a": 1}), &smile!({"a": 1, "b": 1}));
+    assert_semantic_ne(&smile!({"a": 1}), &smile!({"b": 1}));
+    assert_semantic_ne(&smile!([]), &smile!({}));
+}
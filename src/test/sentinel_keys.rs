@@ -0,0 +1,139 @@
+use crate::smile;
+use crate::value::{self, BigDecimal, BigInteger, Limits, Preview, PreviewLimits, Value, ValueRef};
+use crate::Deserializer;
+use serde::de::value::{Error, MapDeserializer};
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+const KEYS: &[&str] = &[
+    "\0SmileBigIntegerField",
+    "\0SmileBigDecimalScale",
+    "\0SmileBigDecimalValue",
+];
+
+fn document(key: &str) -> Vec<u8> {
+    crate::to_vec(&smile!({ key: 1, "b": true })).unwrap()
+}
+
+fn expected(key: &str) -> Value {
+    smile!({ key: 1, "b": true })
+}
+
+#[test]
+fn value() {
+    for key in KEYS {
+        let buf = document(key);
+        assert_eq!(crate::from_slice::<Value>(&buf).unwrap(), expected(key));
+        assert_eq!(
+            crate::from_reader::<Value, _>(&buf[..]).unwrap(),
+            expected(key)
+        );
+    }
+}
+
+#[test]
+fn value_ref() {
+    for key in KEYS {
+        let buf = document(key);
+        let value = crate::from_slice::<ValueRef<'_>>(&buf).unwrap();
+        assert_eq!(
+            value,
+            ValueRef::Object(vec![
+                (Cow::Borrowed(*key), ValueRef::Integer(1)),
+                (Cow::Borrowed("b"), ValueRef::Boolean(true)),
+            ])
+        );
+
+        let mut de = Deserializer::from_reader(&buf[..]);
+        let value = ValueRef::deserialize(&mut de).unwrap();
+        assert_eq!(value.to_owned(), expected(key));
+    }
+}
+
+#[test]
+fn limits() {
+    for key in KEYS {
+        let buf = document(key);
+        let value = value::from_slice_with_limits(&buf, &Limits::new()).unwrap();
+        assert_eq!(value, expected(key));
+    }
+}
+
+#[test]
+fn from_value() {
+    for key in KEYS {
+        let value = value::from_value::<Value>(expected(key)).unwrap();
+        assert_eq!(value, expected(key));
+    }
+}
+
+#[test]
+fn static_keys() {
+    // a foreign deserializer can hand out the very same static strings that name the synthetic fields
+    let keys = [
+        BigInteger::FIELD_NAME,
+        BigDecimal::SCALE_FIELD_NAME,
+        BigDecimal::VALUE_FIELD_NAME,
+    ];
+    for key in keys {
+        let de = MapDeserializer::<_, Error>::new(vec![(key, 1), ("b", 2)].into_iter());
+        let value = Value::deserialize(de).unwrap();
+        assert_eq!(value, smile!({ key: 1, "b": 2 }));
+
+        let de = MapDeserializer::<_, Error>::new(vec![(key, 1), ("b", 2)].into_iter());
+        let value = ValueRef::deserialize(de).unwrap();
+        assert_eq!(value.to_owned(), smile!({ key: 1, "b": 2 }));
+    }
+}
+
+#[test]
+fn synthetic_keys_are_field_names_to_other_visitors() {
+    let value = Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20]));
+    let buf = crate::to_vec(&value).unwrap();
+    let map = crate::from_slice::<BTreeMap<String, ByteBuf>>(&buf).unwrap();
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[BigInteger::FIELD_NAME], vec![1; 20]);
+
+    let map = value::from_value::<BTreeMap<String, ByteBuf>>(value).unwrap();
+    assert_eq!(map[BigInteger::FIELD_NAME], vec![1; 20]);
+}
+
+#[test]
+fn preview() {
+    for key in KEYS {
+        let buf = document(key);
+        let preview = value::preview(&buf, &PreviewLimits::new()).unwrap();
+        assert_eq!(
+            preview,
+            Preview::Object {
+                entries: vec![
+                    (key.to_string(), Preview::Value(Value::Integer(1))),
+                    ("b".to_string(), Preview::Value(Value::Boolean(true))),
+                ],
+                omitted: 0,
+            }
+        );
+    }
+}
+
+#[test]
+fn big_numbers_unaffected() {
+    let value = smile!([
+        Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20])),
+        Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), -5)),
+    ]);
+    let buf = crate::to_vec(&value).unwrap();
+
+    assert_eq!(crate::from_slice::<Value>(&buf).unwrap(), value);
+    assert_eq!(
+        crate::from_slice::<ValueRef<'_>>(&buf).unwrap().to_owned(),
+        value
+    );
+    assert_eq!(
+        value::from_slice_with_limits(&buf, &Limits::new()).unwrap(),
+        value
+    );
+    assert_eq!(value::from_value::<Value>(value.clone()).unwrap(), value);
+}
@@ -0,0 +1,123 @@
+use crate::ser::Serializer;
+use crate::test::size_corpus::from_json;
+use crate::value::{BigDecimal, BigInteger, Map, SizeOptions, Value};
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::fs;
+
+fn check(value: &Value) {
+    for flags in 0..16 {
+        let mut options = SizeOptions::new();
+        options
+            .raw_binary(flags & 8 != 0)
+            .shared_strings(flags & 4 != 0)
+            .shared_properties(flags & 2 != 0)
+            .write_end_marker(flags & 1 != 0);
+
+        let mut ser = Serializer::builder()
+            .raw_binary(flags & 8 != 0)
+            .shared_strings(flags & 4 != 0)
+            .shared_properties(flags & 2 != 0)
+            .build(vec![]);
+        value.serialize(&mut ser).unwrap();
+        if flags & 1 != 0 {
+            ser.end().unwrap();
+        }
+        let expected = ser.into_inner().len() as u64;
+
+        assert_eq!(
+            value.serialized_size(&options),
+            expected,
+            "flags {:04b} value {:?}",
+            flags,
+            value,
+        );
+    }
+}
+
+#[test]
+fn reference_documents() {
+    for category in fs::read_dir("tests").unwrap() {
+        let category = category.unwrap().path();
+        // the documents in `tests/quirks` are written by nonconforming producers
+        if !category.is_dir() || category.ends_with("quirks") {
+            continue;
+        }
+
+        for path in fs::read_dir(&category).unwrap() {
+            let path = path.unwrap().path();
+            if path.extension() == Some(OsStr::new("smile")) {
+                let value = crate::from_slice::<Value>(&fs::read(&path).unwrap()).unwrap();
+                check(&value);
+            } else if category.ends_with("size_corpus")
+                && path.extension() == Some(OsStr::new("json"))
+            {
+                let json = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+                check(&from_json(json));
+            }
+        }
+    }
+}
+
+#[test]
+fn scalars() {
+    let values = [
+        Value::Null,
+        Value::Boolean(true),
+        Value::Integer(-16),
+        Value::Integer(15),
+        Value::Integer(16),
+        Value::Integer(i32::MIN),
+        Value::Long(1),
+        Value::Long(i64::MAX),
+        Value::Long(i64::from(i32::MIN) - 1),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![0xff; 30])),
+        Value::Float(1.5),
+        Value::Double(1.5),
+        Value::BigDecimal(BigDecimal::new(
+            BigInteger::from_be_bytes(vec![1; 9]),
+            -100_000,
+        )),
+        Value::binary(vec![]),
+        Value::Binary((0..=255).cycle().take(10_000).collect()),
+    ];
+
+    for value in values {
+        check(&value);
+    }
+}
+
+#[test]
+fn strings() {
+    let mut strings = vec![String::new()];
+    for len in [1, 32, 33, 34, 56, 57, 64, 65, 100] {
+        strings.push("a".repeat(len));
+        strings.push(format!("é{}", "a".repeat(len - 1)));
+    }
+
+    let array = Value::Array(strings.iter().cloned().map(Value::from).collect());
+    check(&array);
+    check(&Value::Array(vec![array.clone(), array]));
+
+    let object = Value::Object(
+        strings
+            .iter()
+            .map(|s| (s.clone(), Value::from(s.clone())))
+            .collect(),
+    );
+    check(&object);
+    check(&Value::Array(vec![object.clone(), object]));
+}
+
+#[test]
+fn long_backrefs_and_cache_resets() {
+    let strings = (0..2500).map(|i| i.to_string()).collect::<Vec<_>>();
+
+    let mut object = Map::new();
+    for s in &strings {
+        object.insert(s.clone(), Value::from(s.clone()));
+    }
+    let object = Value::Object(object);
+
+    check(&Value::Array(vec![object.clone(), object.clone(), object]));
+}
@@ -0,0 +1,88 @@
+use crate::ser::Builder;
+use crate::value::{SizeOptions, Value};
+use crate::{smile, Serializer};
+
+fn builder(capacity: usize) -> Builder {
+    let mut builder = Serializer::builder();
+    builder
+        .shared_strings(true)
+        .shared_properties(true)
+        .shared_cache_capacity(capacity);
+    builder
+}
+
+fn check_round_trip(builder: &Builder, value: &Value) -> Vec<u8> {
+    let buf = builder.to_vec(value).unwrap();
+    assert_eq!(crate::from_slice::<Value>(&buf).unwrap(), *value);
+    buf
+}
+
+#[test]
+fn shared_strings() {
+    let value = smile!(["a", "b", "c", "a", "b", "c"]);
+    let buf = check_round_trip(&builder(2), &value);
+
+    // "c" isn't retained, so it's written out in full both times
+    assert_eq!(buf, b":)\n\x03\xf8\x40a\x40b\x40c\x01\x02\x40c\xf9");
+
+    let buf = check_round_trip(&builder(1024), &value);
+    assert_eq!(buf, b":)\n\x03\xf8\x40a\x40b\x40c\x01\x02\x03\xf9");
+}
+
+#[test]
+fn shared_properties() {
+    let value = smile!([{"a": 1, "b": 2}, {"a": 1, "b": 2}]);
+    for memoize_keys in [false, true] {
+        let mut builder = builder(1);
+        builder.memoize_keys(memoize_keys);
+        let buf = check_round_trip(&builder, &value);
+        assert_eq!(
+            buf, b":)\n\x03\xf8\xfa\x80a\xc2\x80b\xc4\xfb\xfa\x40\xc2\x80b\xc4\xfb\xf9",
+            "{}",
+            memoize_keys
+        );
+    }
+}
+
+#[test]
+fn ids_track_reader_resets() {
+    // the reader's tables reset after 1024 strings even when the writer retains fewer, so references made after the
+    // reset must restart from 0
+    let strings = (0..1100).map(|i| format!("s{}", i)).collect::<Vec<_>>();
+    let mut values = strings
+        .iter()
+        .map(|s| Value::from(s.clone()))
+        .collect::<Vec<_>>();
+    values.extend(strings[1024..1030].iter().map(|s| Value::from(s.clone())));
+    let mut objects = strings
+        .iter()
+        .map(|s| smile!({s.as_str(): 1}))
+        .collect::<Vec<_>>();
+    objects.extend(strings[1024..1030].iter().map(|s| smile!({s.as_str(): 1})));
+    let value = Value::Array(vec![Value::Array(values), Value::Array(objects)]);
+
+    for capacity in [1, 10, 1023, 1024] {
+        for memoize_keys in [false, true] {
+            let mut builder = builder(capacity);
+            builder.memoize_keys(memoize_keys);
+            let buf = check_round_trip(&builder, &value);
+
+            let mut options = SizeOptions::new();
+            options.shared_strings(true).shared_cache_capacity(capacity);
+            assert_eq!(value.serialized_size(&options), buf.len() as u64);
+        }
+    }
+}
+
+#[test]
+fn clamped() {
+    let value = smile!(["a", "b", "a", "b"]);
+    assert_eq!(
+        builder(0).to_vec(&value).unwrap(),
+        builder(1).to_vec(&value).unwrap()
+    );
+    assert_eq!(
+        builder(usize::MAX).to_vec(&value).unwrap(),
+        builder(1024).to_vec(&value).unwrap()
+    );
+}
@@ -0,0 +1,120 @@
+use crate::de::{Deserializer, IoRead};
+use crate::Serializer;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::io::BufReader;
+
+fn serialize<T>(shared_strings: bool, value: &T) -> Vec<u8>
+where
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::builder()
+        .shared_strings(shared_strings)
+        .build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn check_all_readers<T>(buf: &[u8], expected: &T)
+where
+    T: DeserializeOwned + PartialEq + Debug,
+{
+    assert_eq!(
+        crate::from_slice::<T>(buf).unwrap(),
+        *expected,
+        "from_slice"
+    );
+    assert_eq!(
+        crate::from_mut_slice::<T>(&mut buf.to_vec()).unwrap(),
+        *expected,
+        "from_mut_slice",
+    );
+    assert_eq!(
+        crate::from_cow::<T>(Cow::Owned(buf.to_vec())).unwrap(),
+        *expected,
+        "from_cow",
+    );
+    assert_eq!(
+        crate::from_reader::<T, _>(buf).unwrap(),
+        *expected,
+        "from_reader",
+    );
+
+    for &capacity in &[1, 3, 16] {
+        let reader = BufReader::with_capacity(capacity, buf);
+        let mut de = Deserializer::new(IoRead::new(reader));
+        let actual = T::deserialize(&mut de).unwrap();
+        de.end().unwrap();
+        assert_eq!(actual, *expected, "BufReader capacity {}", capacity);
+    }
+}
+
+fn multi_byte_chars() -> Vec<char> {
+    let alphabet = ['é', 'ß', '€', '日', '本', '😀', '🦀', 'a'];
+    (0..500).map(|i| alphabet[i * 7 % alphabet.len()]).collect()
+}
+
+#[test]
+fn repeated_chars() {
+    let chars = multi_byte_chars();
+
+    let shared = serialize(true, &chars);
+    let unshared = serialize(false, &chars);
+    check_all_readers(&shared, &chars);
+    check_all_readers(&unshared, &chars);
+
+    // each distinct char is written once as a literal and then referenced with a single byte token
+    let literals = ['é', 'ß', '€', '日', '本', '😀', '🦀', 'a']
+        .iter()
+        .map(|c| 1 + c.len_utf8())
+        .sum::<usize>();
+    assert_eq!(shared.len(), 4 + 2 + literals + (chars.len() - 8));
+    assert_eq!(
+        unshared.len(),
+        4 + 2 + chars.iter().map(|c| 1 + c.len_utf8()).sum::<usize>(),
+    );
+}
+
+#[test]
+fn chars_share_with_strings() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Entry {
+        c: char,
+        s: String,
+    }
+
+    let entries = multi_byte_chars()
+        .into_iter()
+        .zip(multi_byte_chars().into_iter().rev())
+        .map(|(c, s)| Entry {
+            c,
+            s: s.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    let buf = serialize(true, &entries);
+    check_all_readers(&buf, &entries);
+
+    // a char and a string with the same contents are interchangeable through the cache
+    let strings = multi_byte_chars()
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>();
+    check_all_readers(&serialize(true, &strings), &multi_byte_chars());
+    check_all_readers(&serialize(true, &multi_byte_chars()), &strings);
+}
+
+#[test]
+fn cache_reset() {
+    // more distinct strings than the shared string cache holds, so it is reset partway through. Each char is
+    // repeated back to back so that the second occurrence is always a reference.
+    let chars = (0..3000u32)
+        .map(|i| char::from_u32(0x4e00 + i / 2).unwrap())
+        .collect::<Vec<_>>();
+
+    let buf = serialize(true, &chars);
+    check_all_readers(&buf, &chars);
+    assert!(buf.len() < serialize(false, &chars).len());
+}
@@ -0,0 +1,117 @@
+use crate::Serializer;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+const COUNT: usize = 1000;
+
+// "repeated" as a tiny ASCII string
+const LITERAL: &[u8] = b"\x47repeated";
+// a back-reference to the first shared string
+const BACKREF: u8 = 0x01;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Wrapper<T>(T);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Variant<T> {
+    V(T),
+}
+
+/// Serializes `COUNT` copies of `value` and checks that the first copy is encoded as `first` and all others as `rest`.
+fn check<T>(value: T, first: &[u8], rest: &[u8])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let values = (0..COUNT).map(|_| &value).collect::<Vec<_>>();
+
+    let mut ser = Serializer::builder().shared_strings(true).build(vec![]);
+    values.serialize(&mut ser).unwrap();
+    let buf = ser.into_inner();
+
+    let mut expected = b":)\n\x03\xf8".to_vec();
+    expected.extend_from_slice(first);
+    for _ in 1..COUNT {
+        expected.extend_from_slice(rest);
+    }
+    expected.push(0xf9);
+    assert_eq!(buf, expected);
+
+    let back_references = buf.windows(rest.len()).filter(|w| *w == rest).count();
+    assert_eq!(back_references, COUNT - 1);
+
+    let actual = crate::from_slice::<Vec<T>>(&buf).unwrap();
+    assert_eq!(actual.len(), COUNT);
+    assert!(actual.iter().all(|v| *v == value));
+}
+
+#[test]
+fn flat() {
+    check("repeated".to_string(), LITERAL, &[BACKREF]);
+}
+
+#[test]
+fn option() {
+    check(Some("repeated".to_string()), LITERAL, &[BACKREF]);
+}
+
+#[test]
+fn newtype_struct() {
+    check(Wrapper("repeated".to_string()), LITERAL, &[BACKREF]);
+}
+
+#[test]
+fn newtype_variant() {
+    // the variant name goes through the property cache, and the value through the value cache
+    let mut first = b"\xfa\x80V".to_vec();
+    first.extend_from_slice(LITERAL);
+    first.push(0xfb);
+    check(
+        Variant::V("repeated".to_string()),
+        &first,
+        &[0xfa, 0x40, BACKREF, 0xfb],
+    );
+}
+
+#[test]
+fn tuple_of_one() {
+    let mut first = vec![0xf8];
+    first.extend_from_slice(LITERAL);
+    first.push(0xf9);
+    check(("repeated".to_string(),), &first, &[0xf8, BACKREF, 0xf9]);
+}
+
+#[test]
+fn nested() {
+    check(
+        Some(Wrapper(Some("repeated".to_string()))),
+        LITERAL,
+        &[BACKREF],
+    );
+    check(
+        Some(Wrapper(Wrapper(Some(Some("repeated".to_string()))))),
+        LITERAL,
+        &[BACKREF],
+    );
+
+    let mut first = b"\xfa\x80V".to_vec();
+    first.extend_from_slice(LITERAL);
+    first.push(0xfb);
+    check(
+        Some(Variant::V(Wrapper(Some("repeated".to_string())))),
+        &first,
+        &[0xfa, 0x40, BACKREF, 0xfb],
+    );
+}
+
+#[test]
+fn static_strs() {
+    // unit variants are written from `&'static str`s, which share the same cache as borrowed strings
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    enum Unit {
+        #[serde(rename = "repeated")]
+        Repeated,
+    }
+
+    check(Some(Wrapper(Unit::Repeated)), LITERAL, &[BACKREF]);
+}
@@ -0,0 +1,120 @@
+//! Size regression tests over the documents in `tests/size_corpus`.
+//!
+//! Each JSON document is converted to a `Value`, with integers using the smallest type that fits, and serialized under
+//! every preset. The encoded sizes are compared against the golden numbers in `tests/size_corpus/sizes.txt`. When a
+//! change to the output is intended, re-bless the numbers by running the tests with the `SERDE_SMILE_BLESS`
+//! environment variable set:
+//!
+//! ```text
+//! SERDE_SMILE_BLESS=1 cargo test size_corpus
+//! ```
+//!
+//! and commit the updated file alongside the change.
+use crate::ser::Builder;
+use crate::value::Value;
+use serde::Serialize;
+use std::env;
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::fs;
+
+const CORPUS: &str = "tests/size_corpus";
+const GOLDEN: &str = "tests/size_corpus/sizes.txt";
+
+fn presets() -> [(&'static str, Builder); 3] {
+    [
+        ("jackson_defaults", Builder::jackson_defaults()),
+        ("smallest_output", Builder::smallest_output()),
+        ("fastest", Builder::fastest()),
+    ]
+}
+
+pub(crate) fn from_json(json: serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(v) => Value::Boolean(v),
+        serde_json::Value::Number(v) => match (v.as_i64(), v.as_u64()) {
+            (Some(v), _) => Value::from(v),
+            (None, Some(v)) => Value::from(v),
+            (None, None) => Value::Double(v.as_f64().unwrap()),
+        },
+        serde_json::Value::String(v) => Value::from(v),
+        serde_json::Value::Array(v) => Value::Array(v.into_iter().map(from_json).collect()),
+        serde_json::Value::Object(v) => {
+            Value::Object(v.into_iter().map(|(k, v)| (k, from_json(v))).collect())
+        }
+    }
+}
+
+fn encoded_size(builder: &Builder, value: &Value) -> usize {
+    let mut ser = builder.build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner().len()
+}
+
+fn render_sizes() -> String {
+    let presets = presets();
+
+    let mut paths = fs::read_dir(CORPUS)
+        .unwrap()
+        .map(|r| r.unwrap().path())
+        .filter(|p| p.extension() == Some(OsStr::new("json")))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# Encoded sizes of the size corpus in bytes. Regenerate with `SERDE_SMILE_BLESS=1 cargo test size_corpus`."
+    )
+    .unwrap();
+    write!(out, "# file").unwrap();
+    for (name, _) in &presets {
+        write!(out, " {}", name).unwrap();
+    }
+    writeln!(out).unwrap();
+
+    let mut totals = [0; 3];
+    for path in &paths {
+        let json = fs::read(path).unwrap();
+        let value = from_json(serde_json::from_slice(&json).unwrap());
+
+        write!(out, "{}", path.file_name().unwrap().to_str().unwrap()).unwrap();
+        for ((_, builder), total) in presets.iter().zip(&mut totals) {
+            let size = encoded_size(builder, &value);
+            *total += size;
+            write!(out, " {}", size).unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    write!(out, "total").unwrap();
+    for total in &totals {
+        write!(out, " {}", total).unwrap();
+    }
+    writeln!(out).unwrap();
+
+    out
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "preserve_order"),
+    ignore = "the golden sizes were recorded with object keys in document order"
+)]
+fn size_corpus() {
+    let actual = render_sizes();
+
+    if env::var_os("SERDE_SMILE_BLESS").is_some() {
+        fs::write(GOLDEN, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(GOLDEN).unwrap();
+    assert!(
+        expected == actual,
+        "encoded sizes changed; if this is intended, rerun with SERDE_SMILE_BLESS=1\n\nexpected:\n{}\nactual:\n{}",
+        expected,
+        actual,
+    );
+}
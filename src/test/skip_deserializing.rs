@@ -0,0 +1,103 @@
+use crate::value::Value;
+use crate::Serializer;
+use serde::{Deserialize, Serialize};
+use std::io::BufReader;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Target {
+    before: Value,
+    #[serde(skip_deserializing)]
+    skipped: Option<String>,
+    after: Value,
+    last: String,
+}
+
+// serde treats skipped fields as unknown when unknown fields are denied, so this is an error rather than a misparse.
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(deny_unknown_fields)]
+struct StrictTarget {
+    before: Value,
+    #[serde(skip_deserializing)]
+    skipped: Option<String>,
+    after: Value,
+    last: String,
+}
+
+fn long_string(c: char) -> String {
+    let mut s = c.to_string().repeat(100);
+    s.push('🙂');
+    s
+}
+
+fn skipped_values() -> Vec<Value> {
+    vec![
+        Value::from(long_string('a')),
+        Value::String("shared".into()),
+        Value::Binary((0..=255).collect()),
+        crate::smile!({
+            "shared": "shared",
+            "after": ["shared", (long_string('b')), "other"],
+            "nested": {"last": (Value::binary(vec![0xfc, 0xff, 0xfc])), "shared": "other"},
+        }),
+        crate::smile!([(long_string('c')), (long_string('c'))]),
+    ]
+}
+
+fn after_values() -> Vec<Value> {
+    vec![
+        Value::String("shared".into()),
+        Value::from(long_string('d')),
+        Value::binary(vec![1, 2, 3]),
+        crate::smile!({"shared": "shared", "after": "other", "last": (long_string('a'))}),
+    ]
+}
+
+fn check(buf: &[u8], expected: &Target) {
+    assert_eq!(crate::from_slice::<Target>(buf).unwrap(), *expected);
+    assert_eq!(
+        crate::from_mut_slice::<Target>(&mut buf.to_vec()).unwrap(),
+        *expected
+    );
+    for capacity in [1, 2, 7, 64, 8192] {
+        let reader = BufReader::with_capacity(capacity, buf);
+        assert_eq!(crate::from_reader::<Target, _>(reader).unwrap(), *expected);
+    }
+}
+
+#[test]
+fn skipped_fields() {
+    for skipped in skipped_values() {
+        for after in after_values() {
+            let source = crate::smile!({
+                "before": "other",
+                "skipped": (skipped.clone()),
+                "after": (after.clone()),
+                "last": "shared",
+            });
+            let expected = Target {
+                before: Value::String("other".into()),
+                skipped: None,
+                after,
+                last: "shared".to_string(),
+            };
+
+            for flags in 0..8 {
+                let mut ser = Serializer::builder()
+                    .raw_binary(flags & 4 != 0)
+                    .shared_strings(flags & 2 != 0)
+                    .shared_properties(flags & 1 != 0)
+                    .build(vec![]);
+                source.serialize(&mut ser).unwrap();
+                ser.end().unwrap();
+                let buf = ser.into_inner();
+                check(&buf, &expected);
+
+                let err = crate::from_slice::<StrictTarget>(&buf).unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    "error in Deserialize impl: unknown field `skipped`, expected one of `before`, `after`, `last`",
+                );
+            }
+        }
+    }
+}
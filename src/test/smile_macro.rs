@@ -0,0 +1,165 @@
+use crate::smile;
+use crate::value::{BigInteger, Map, Value};
+
+fn object(entries: Vec<(&str, Value)>) -> Value {
+    Value::Object(
+        entries
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(smile!(null), Value::Null);
+    assert_eq!(smile!(true), Value::Boolean(true));
+    assert_eq!(smile!(false), Value::Boolean(false));
+    assert_eq!(smile!("hello"), Value::String("hello".into()));
+    assert_eq!(smile!('x'), Value::String("x".into()));
+    assert_eq!(smile!(1.5), Value::Double(1.5));
+    assert_eq!(smile!(-1.5), Value::Double(-1.5));
+}
+
+#[test]
+fn integer_literals() {
+    assert_eq!(smile!(0), Value::Integer(0));
+    assert_eq!(smile!(-5), Value::Integer(-5));
+    assert_eq!(smile!(2147483647), Value::Integer(i32::MAX));
+    assert_eq!(smile!(-2147483648), Value::Integer(i32::MIN));
+    assert_eq!(smile!(2147483648), Value::Long(2147483648));
+    assert_eq!(smile!(-2147483649), Value::Long(-2147483649));
+    assert_eq!(smile!(9223372036854775807), Value::Long(i64::MAX));
+    assert_eq!(smile!(-9223372036854775808), Value::Long(i64::MIN));
+    assert_eq!(
+        smile!(9223372036854775808),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0x00, 0x80, 0, 0, 0, 0, 0, 0, 0
+        ])),
+    );
+    assert_eq!(
+        smile!(18446744073709551616),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0])),
+    );
+    assert_eq!(
+        smile!(-9223372036854775809),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0xff, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ])),
+    );
+}
+
+#[test]
+fn arrays() {
+    assert_eq!(smile!([]), Value::Array(vec![]));
+    assert_eq!(
+        smile!([null, true, false, 1, "a", [], {}, [2], {"b": 3}]),
+        Value::Array(vec![
+            Value::Null,
+            Value::Boolean(true),
+            Value::Boolean(false),
+            Value::Integer(1),
+            Value::String("a".into()),
+            Value::Array(vec![]),
+            Value::Object(Map::new()),
+            Value::Array(vec![Value::Integer(2)]),
+            object(vec![("b", Value::Integer(3))]),
+        ]),
+    );
+    assert_eq!(
+        smile!([1, 2,]),
+        Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+    );
+    assert_eq!(
+        smile!([10000000000]),
+        Value::Array(vec![Value::Long(10000000000)]),
+    );
+}
+
+#[test]
+fn objects() {
+    assert_eq!(smile!({}), Value::Object(Map::new()));
+    assert_eq!(
+        smile!({
+            "null": null,
+            "bool": true,
+            "int": -1,
+            "long": 10000000000,
+            "double": 0.5,
+            "string": "s",
+            "array": [1, [2]],
+            "object": {"nested": {}},
+        }),
+        object(vec![
+            ("null", Value::Null),
+            ("bool", Value::Boolean(true)),
+            ("int", Value::Integer(-1)),
+            ("long", Value::Long(10000000000)),
+            ("double", Value::Double(0.5)),
+            ("string", Value::String("s".into())),
+            (
+                "array",
+                Value::Array(vec![
+                    Value::Integer(1),
+                    Value::Array(vec![Value::Integer(2)])
+                ]),
+            ),
+            ("object", object(vec![("nested", object(vec![]))])),
+        ]),
+    );
+    assert_eq!(smile!({"a": 1}), object(vec![("a", Value::Integer(1))]),);
+}
+
+#[test]
+fn interpolation() {
+    let name = "foo";
+    let key = String::from("key");
+    let count: u64 = u64::MAX;
+    let small: u8 = 7;
+    let nested = smile!([1]);
+
+    assert_eq!(
+        smile!({
+            key.clone(): name,
+            (format!("{}2", key)): (small + 1),
+            "count": count,
+            "nested": nested.clone(),
+            "none": (None::<i32>),
+            "float": (1.5f32),
+            "owned": (String::from("x")),
+        }),
+        object(vec![
+            ("key", Value::String("foo".into())),
+            ("key2", Value::Integer(8)),
+            (
+                "count",
+                Value::BigInteger(BigInteger::from_be_bytes(vec![
+                    0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+                ])),
+            ),
+            ("nested", Value::Array(vec![Value::Integer(1)])),
+            ("none", Value::Null),
+            ("float", Value::Float(1.5)),
+            ("owned", Value::String("x".into())),
+        ]),
+    );
+
+    assert_eq!(
+        smile!([name, small, nested, 1 + 2, (5u8)]),
+        Value::Array(vec![
+            Value::String("foo".into()),
+            Value::Integer(7),
+            Value::Array(vec![Value::Integer(1)]),
+            Value::Integer(3),
+            Value::Integer(5),
+        ]),
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    assert_eq!(
+        smile!({"a": 1, "b": 2, "a": 3}),
+        object(vec![("a", Value::Integer(3)), ("b", Value::Integer(2))]),
+    );
+}
@@ -0,0 +1,151 @@
+use crate::de::SliceRead;
+use crate::smile;
+use crate::value::Value;
+use crate::{Deserializer, Error};
+use serde::Deserialize;
+use std::fs;
+
+const HEADER: &[u8] = b":)\n\x00";
+
+fn fixture() -> Vec<u8> {
+    fs::read("tests/quirks/stray_string_terminators.smile").unwrap()
+}
+
+fn lenient<'de, T>(buf: &'de [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut de = Deserializer::builder()
+        .skip_stray_string_terminators(true)
+        .build(SliceRead::new(buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+fn document(body: &[u8]) -> Vec<u8> {
+    let mut buf = HEADER.to_vec();
+    buf.extend_from_slice(body);
+    buf
+}
+
+#[test]
+fn rejected_by_default() {
+    let e = crate::from_slice::<Value>(&fixture()).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected token 0xfc at offset 8");
+}
+
+#[test]
+fn archived_document() {
+    let expected = smile!({
+        "id": 17,
+        "user": "user-0001",
+        "tags": ["billing", "urgent"],
+        "note": "Customer reported intermittent failures; details redacted for the archive.",
+        "reply": {"user": "agent-0002"},
+    });
+    assert_eq!(lenient::<Value>(&fixture()).unwrap(), expected);
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Reply {
+        user: String,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Record {
+        id: u32,
+        user: String,
+        tags: Vec<String>,
+        note: Option<String>,
+        reply: Reply,
+    }
+
+    let expected = Record {
+        id: 17,
+        user: "user-0001".to_string(),
+        tags: vec!["billing".to_string(), "urgent".to_string()],
+        note: Some(
+            "Customer reported intermittent failures; details redacted for the archive."
+                .to_string(),
+        ),
+        reply: Reply {
+            user: "agent-0002".to_string(),
+        },
+    };
+    assert_eq!(lenient::<Record>(&fixture()).unwrap(), expected);
+}
+
+#[test]
+fn containers() {
+    // ["a", null, "b"] with terminators before each element and the end of the array
+    let buf = document(b"\xf8\xfc\x40a\xfc\xfc\x21\x40b\xfc\xf9\xfc");
+    assert_eq!(lenient::<Value>(&buf).unwrap(), smile!(["a", null, "b"]));
+    assert_eq!(
+        lenient::<Vec<Option<String>>>(&buf).unwrap(),
+        [Some("a".to_string()), None, Some("b".to_string())]
+    );
+
+    // {"a": "b"} with terminators after the key, the value, and the object
+    let buf = document(b"\xfa\x80a\xfc\x40b\xfc\xfb\xfc\xff");
+    assert_eq!(lenient::<Value>(&buf).unwrap(), smile!({"a": "b"}));
+}
+
+#[test]
+fn enums() {
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Enum {
+        Unit,
+        Newtype(String),
+    }
+
+    let buf = document(b"\xfc\x43Unit\xfc");
+    assert_eq!(lenient::<Enum>(&buf).unwrap(), Enum::Unit);
+
+    let buf = document(b"\xfa\x86Newtype\xfc\x40a\xfc\xfb");
+    assert_eq!(
+        lenient::<Enum>(&buf).unwrap(),
+        Enum::Newtype("a".to_string())
+    );
+}
+
+#[test]
+fn stream() {
+    let buf = document(b"\x40a\xfc\x40b\xfc\xff");
+    let values = Deserializer::builder()
+        .skip_stray_string_terminators(true)
+        .build(SliceRead::new(&buf))
+        .into_iter::<String>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, ["a", "b"]);
+
+    let values = Deserializer::from_slice(&buf)
+        .into_iter::<String>()
+        .collect::<Vec<_>>();
+    assert_eq!(values.len(), 2);
+    assert!(values[1].is_err());
+}
+
+#[test]
+fn missing_value() {
+    let e = lenient::<Value>(&document(b"\xfc\xfc")).unwrap_err();
+    assert_eq!(e.to_string(), "EOF while parsing a value");
+
+    let e = lenient::<Value>(&document(b"\xfa\x80a\xfc\xfb")).unwrap_err();
+    assert_eq!(e.to_string(), "unexpected token 0xfb at offset 8");
+}
+
+#[test]
+fn corrupt_documents() {
+    // a raw binary value whose length is 0xfc
+    let e = lenient::<Value>(&document(b"\xfd\xfc\x00")).unwrap_err();
+    assert_eq!(e.to_string(), "EOF while parsing a value");
+
+    // 0xfc within the contents of a short string
+    let e = lenient::<Value>(&document(b"\x41a\xfc")).unwrap_err();
+    assert_eq!(e.to_string(), "invalid UTF-8");
+
+    // 0xfc within the contents of a key
+    let e = lenient::<Value>(&document(b"\xfa\x81\xfca\x40b\xfb")).unwrap_err();
+    assert_eq!(e.to_string(), "invalid UTF-8");
+}
@@ -0,0 +1,166 @@
+use crate::de::SliceRead;
+use crate::value::{BigDecimal, BigInteger, Value};
+use crate::{Deserializer, Error};
+use serde::Deserialize;
+
+fn deserialize<'de, T>(buf: &'de [u8], strict: bool) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut de = Deserializer::builder()
+        .strict_big_integers(strict)
+        .build(SliceRead::new(buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+fn big_integer(bytes: &[u8]) -> Vec<u8> {
+    crate::to_vec(&BigInteger::from_be_bytes(bytes.to_vec())).unwrap()
+}
+
+fn big_decimal(bytes: &[u8]) -> Vec<u8> {
+    crate::to_vec(&BigDecimal::new(
+        BigInteger::from_be_bytes(bytes.to_vec()),
+        2,
+    ))
+    .unwrap()
+}
+
+const MINIMAL: &[&[u8]] = &[
+    &[0x00],
+    &[0x01],
+    &[0x7f],
+    &[0x80],
+    &[0xff],
+    &[0x00, 0x80],
+    &[0xff, 0x7f],
+    &[0x01, 0x00, 0x00],
+    &[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+];
+
+const NON_MINIMAL: &[&[u8]] = &[
+    &[0x00, 0x00],
+    &[0x00, 0x01],
+    &[0x00, 0x7f],
+    &[0x00, 0x00, 0x80],
+    &[0xff, 0xff],
+    &[0xff, 0x80],
+    &[0xff, 0xff, 0x7f],
+    &[0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+];
+
+#[test]
+fn crafted_bytes() {
+    // 0x26, a length of 2, then 0x00 0x01 split into 7 bit groups
+    let buf = b":)\n\x01\x26\x82\x00\x00\x01";
+    assert_eq!(big_integer(&[0x00, 0x01]), buf);
+
+    let e = deserialize::<Value>(buf, true).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "big integer has redundant sign extension bytes"
+    );
+    assert_eq!(
+        deserialize::<Value>(buf, false).unwrap(),
+        Value::BigInteger(BigInteger::from_be_bytes(vec![0x00, 0x01])),
+    );
+
+    let buf = b":)\n\x01\x26\x80";
+    assert_eq!(big_integer(&[]), buf);
+
+    let e = deserialize::<Value>(buf, true).unwrap_err();
+    assert_eq!(e.to_string(), "empty big integer");
+}
+
+#[test]
+fn minimal() {
+    for bytes in MINIMAL {
+        for strict in [false, true] {
+            let expected = BigInteger::from_be_bytes(bytes.to_vec());
+            let buf = big_integer(bytes);
+            assert_eq!(
+                deserialize::<BigInteger>(&buf, strict).unwrap(),
+                expected,
+                "{:?}",
+                bytes,
+            );
+            assert_eq!(
+                deserialize::<Value>(&buf, strict).unwrap(),
+                Value::BigInteger(expected.clone()),
+            );
+            assert_eq!(
+                deserialize::<i128>(&buf, strict).unwrap(),
+                expected.to_i128().unwrap(),
+            );
+
+            let buf = big_decimal(bytes);
+            assert_eq!(
+                deserialize::<BigDecimal>(&buf, strict).unwrap(),
+                BigDecimal::new(expected, 2),
+            );
+        }
+    }
+}
+
+#[test]
+fn non_minimal() {
+    for bytes in NON_MINIMAL {
+        let expected = BigInteger::from_be_bytes(bytes.to_vec());
+
+        let buf = big_integer(bytes);
+        for e in [
+            deserialize::<BigInteger>(&buf, true).unwrap_err(),
+            deserialize::<Value>(&buf, true).unwrap_err(),
+            deserialize::<i128>(&buf, true).unwrap_err(),
+        ] {
+            assert_eq!(
+                e.to_string(),
+                "big integer has redundant sign extension bytes",
+                "{:?}",
+                bytes,
+            );
+        }
+        // the representation is passed through unchanged when lenient
+        assert_eq!(deserialize::<BigInteger>(&buf, false).unwrap(), expected);
+
+        let buf = big_decimal(bytes);
+        let e = deserialize::<BigDecimal>(&buf, true).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "big integer has redundant sign extension bytes"
+        );
+        assert_eq!(
+            deserialize::<BigDecimal>(&buf, false).unwrap(),
+            BigDecimal::new(expected, 2),
+        );
+    }
+}
+
+#[test]
+fn empty() {
+    let zero = BigInteger::from_be_bytes(vec![0]);
+
+    let buf = big_integer(&[]);
+    assert_eq!(deserialize::<BigInteger>(&buf, false).unwrap(), zero);
+    assert_eq!(
+        deserialize::<Value>(&buf, false).unwrap(),
+        Value::BigInteger(zero.clone()),
+    );
+    assert_eq!(deserialize::<i32>(&buf, false).unwrap(), 0);
+    for e in [
+        deserialize::<BigInteger>(&buf, true).unwrap_err(),
+        deserialize::<Value>(&buf, true).unwrap_err(),
+        deserialize::<i32>(&buf, true).unwrap_err(),
+    ] {
+        assert_eq!(e.to_string(), "empty big integer");
+    }
+
+    let buf = big_decimal(&[]);
+    assert_eq!(
+        deserialize::<BigDecimal>(&buf, false).unwrap(),
+        BigDecimal::new(zero, 2),
+    );
+    let e = deserialize::<BigDecimal>(&buf, true).unwrap_err();
+    assert_eq!(e.to_string(), "empty big integer");
+}
@@ -0,0 +1,132 @@
+use crate::de::SliceRead;
+use crate::Deserializer;
+use serde::Deserialize;
+use std::fmt::Debug;
+
+const HEADER: &[u8] = b":)\n\x00";
+
+// Each wire numeric type, encoding 5 or 1.5.
+const SMALL_INTEGER: &[u8] = &[0xca];
+const INTEGER: &[u8] = &[0x24, 0x8a];
+const LONG: &[u8] = &[0x25, 0x8a];
+const BIG_INTEGER: &[u8] = &[0x26, 0x81, 0x02, 0x01];
+const FLOAT: &[u8] = &[0x28, 0x03, 0x7e, 0x00, 0x00, 0x00];
+const DOUBLE: &[u8] = &[
+    0x29, 0x00, 0x3f, 0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+const TOKENS: &[&[u8]] = &[SMALL_INTEGER, INTEGER, LONG, BIG_INTEGER, FLOAT, DOUBLE];
+
+fn deserialize<T>(strict: bool, token: &[u8]) -> Result<String, String>
+where
+    T: for<'de> Deserialize<'de> + Debug,
+{
+    let buf = [HEADER, token].concat();
+    let mut de = Deserializer::builder()
+        .strict_number_types(strict)
+        .build(SliceRead::new(&buf));
+    let value = T::deserialize(&mut de).map_err(|e| e.to_string())?;
+    de.end().unwrap();
+    Ok(format!("{:?}", value))
+}
+
+fn check<T>(lax: [Result<&str, &str>; 6], strict: [Result<&str, &str>; 6])
+where
+    T: for<'de> Deserialize<'de> + Debug,
+{
+    for (expected, strict) in [(lax, false), (strict, true)] {
+        for (token, expected) in TOKENS.iter().zip(expected) {
+            let expected = expected.map(str::to_string).map_err(str::to_string);
+            assert_eq!(
+                deserialize::<T>(strict, token),
+                expected,
+                "token {:02x?} strict {}",
+                token,
+                strict,
+            );
+        }
+    }
+}
+
+macro_rules! integer_tests {
+    ($($name:ident: $ty:ident,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let float_error = concat!("error in Deserialize impl: invalid type: floating point `1.5`, expected ", stringify!($ty));
+                let lax = [Ok("5"), Ok("5"), Ok("5"), Ok("5"), Err(float_error), Err(float_error)];
+                let mut strict = lax;
+                strict[3] = Err(concat!("invalid type: big integer, expected ", stringify!($ty)));
+                check::<$ty>(lax, strict);
+            }
+        )*
+    };
+}
+
+integer_tests! {
+    i8_target: i8,
+    i16_target: i16,
+    i32_target: i32,
+    i64_target: i64,
+    i128_target: i128,
+    u8_target: u8,
+    u16_target: u16,
+    u32_target: u32,
+    u64_target: u64,
+    u128_target: u128,
+}
+
+#[test]
+fn f32_target() {
+    let lax = [
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("1.5"),
+        Ok("1.5"),
+    ];
+    let strict = [
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Err("invalid type: big integer, expected f32"),
+        Ok("1.5"),
+        Err("invalid type: double, expected f32"),
+    ];
+    check::<f32>(lax, strict);
+}
+
+#[test]
+fn f64_target() {
+    let lax = [
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("1.5"),
+        Ok("1.5"),
+    ];
+    let strict = [
+        Ok("5.0"),
+        Ok("5.0"),
+        Ok("5.0"),
+        Err("invalid type: big integer, expected f64"),
+        Err("invalid type: float, expected f64"),
+        Ok("1.5"),
+    ];
+    check::<f64>(lax, strict);
+}
+
+#[test]
+fn value_unaffected() {
+    for token in TOKENS {
+        let buf = [HEADER, token].concat();
+        let lax = crate::from_slice::<crate::value::Value>(&buf).unwrap();
+        let mut de = Deserializer::builder()
+            .strict_number_types(true)
+            .build(SliceRead::new(&buf));
+        let strict = crate::value::Value::deserialize(&mut de).unwrap();
+        assert_eq!(lax, strict);
+    }
+}
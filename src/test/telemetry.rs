@@ -0,0 +1,309 @@
+#![cfg(feature = "metrics")]
+
+use crate::value::Value;
+use metrics::{
+    Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, PartialEq)]
+struct Event {
+    name: String,
+    labels: Vec<(String, String)>,
+    value: f64,
+}
+
+#[derive(Default)]
+struct CapturingRecorder(Arc<Mutex<Vec<Event>>>);
+
+struct Handle {
+    key: Key,
+    events: Arc<Mutex<Vec<Event>>>,
+}
+
+impl Handle {
+    fn push(&self, value: f64) {
+        self.events.lock().unwrap().push(Event {
+            name: self.key.name().to_string(),
+            labels: self
+                .key
+                .labels()
+                .map(|l| (l.key().to_string(), l.value().to_string()))
+                .collect(),
+            value,
+        });
+    }
+}
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        self.push(value as f64);
+    }
+
+    fn absolute(&self, value: u64) {
+        self.push(value as f64);
+    }
+}
+
+impl HistogramFn for Handle {
+    fn record(&self, value: f64) {
+        self.push(value);
+    }
+}
+
+impl CapturingRecorder {
+    fn handle(&self, key: &Key) -> Arc<Handle> {
+        Arc::new(Handle {
+            key: key.clone(),
+            events: self.0.clone(),
+        })
+    }
+}
+
+impl Recorder for CapturingRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::from_arc(self.handle(key))
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(self.handle(key))
+    }
+}
+
+/// Runs the closure, returning the metrics it emitted with durations replaced by `-1`.
+fn capture<F>(f: F) -> Vec<Event>
+where
+    F: FnOnce(),
+{
+    let recorder = CapturingRecorder::default();
+    metrics::with_local_recorder(&recorder, f);
+
+    let mut events = recorder.0.lock().unwrap().drain(..).collect::<Vec<_>>();
+    for event in &mut events {
+        if event.name.ends_with("_duration_seconds") {
+            assert!(event.value >= 0.);
+            event.value = -1.;
+        }
+    }
+    events
+}
+
+fn event(name: &str, labels: &[(&str, &str)], value: f64) -> Event {
+    Event {
+        name: name.to_string(),
+        labels: labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        value,
+    }
+}
+
+fn serialized(writer: &str, bytes: usize) -> Vec<Event> {
+    vec![
+        event(
+            "serde_smile_serialize_duration_seconds",
+            &[("writer", writer)],
+            -1.,
+        ),
+        event(
+            "serde_smile_serialize_bytes",
+            &[("writer", writer)],
+            bytes as f64,
+        ),
+    ]
+}
+
+fn deserialized(reader: &str, bytes: usize) -> Vec<Event> {
+    vec![
+        event(
+            "serde_smile_deserialize_duration_seconds",
+            &[("reader", reader)],
+            -1.,
+        ),
+        event(
+            "serde_smile_deserialize_bytes",
+            &[("reader", reader)],
+            bytes as f64,
+        ),
+    ]
+}
+
+fn deserialize_error(reader: &str, category: &str) -> Vec<Event> {
+    vec![
+        event(
+            "serde_smile_deserialize_duration_seconds",
+            &[("reader", reader)],
+            -1.,
+        ),
+        event(
+            "serde_smile_deserialize_errors_total",
+            &[("reader", reader), ("category", category)],
+            1.,
+        ),
+    ]
+}
+
+#[test]
+fn serialize() {
+    let value = vec!["hello", "world"];
+    let len = crate::to_vec(&value).unwrap().len();
+
+    let events = capture(|| {
+        crate::to_vec(&value).unwrap();
+    });
+    assert_eq!(events, serialized("vec", len));
+
+    let events = capture(|| {
+        crate::to_vec_with_capacity(&value, 100).unwrap();
+    });
+    assert_eq!(events, serialized("vec", len));
+
+    let events = capture(|| {
+        let mut buf = vec![];
+        crate::to_writer(&mut buf, &value).unwrap();
+    });
+    assert_eq!(events, serialized("io", len));
+}
+
+#[test]
+fn deserialize() {
+    let buf = crate::to_vec(&vec!["hello", "world"]).unwrap();
+
+    let events = capture(|| {
+        crate::from_slice::<Value>(&buf).unwrap();
+    });
+    assert_eq!(events, deserialized("slice", buf.len()));
+
+    let events = capture(|| {
+        crate::from_mut_slice::<Value>(&mut buf.clone()).unwrap();
+    });
+    assert_eq!(events, deserialized("mut_slice", buf.len()));
+
+    let events = capture(|| {
+        crate::from_cow::<Value>(Cow::Owned(buf.clone())).unwrap();
+    });
+    assert_eq!(events, deserialized("cow", buf.len()));
+
+    let events = capture(|| {
+        crate::from_reader::<Value, _>(&buf[..]).unwrap();
+    });
+    assert_eq!(events, deserialized("io", buf.len()));
+}
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn serialize_errors() {
+    let events = capture(|| {
+        crate::to_writer(FailingWriter, &"hello").unwrap_err();
+    });
+    assert_eq!(
+        events,
+        [
+            event(
+                "serde_smile_serialize_duration_seconds",
+                &[("writer", "io")],
+                -1.
+            ),
+            event(
+                "serde_smile_serialize_errors_total",
+                &[("writer", "io"), ("category", "io")],
+                1.
+            ),
+        ]
+    );
+
+    let mut map = std::collections::HashMap::new();
+    map.insert(vec![1u8], 1);
+    let events = capture(|| {
+        crate::to_vec(&map).unwrap_err();
+    });
+    assert_eq!(
+        events[1],
+        event(
+            "serde_smile_serialize_errors_total",
+            &[("writer", "vec"), ("category", "data")],
+            1.
+        )
+    );
+}
+
+#[test]
+fn deserialize_errors() {
+    let cases: &[(&[u8], &str)] = &[
+        (b":)\n\x00\xf8", "eof"),
+        (b":)\n\x00\xf8\xfb", "syntax"),
+        (b"{}\n\x00", "syntax"),
+    ];
+
+    for (buf, category) in cases {
+        let events = capture(|| {
+            crate::from_slice::<Value>(buf).unwrap_err();
+        });
+        assert_eq!(events, deserialize_error("slice", category), "{:?}", buf);
+    }
+
+    let events = capture(|| {
+        crate::from_slice::<u32>(b":)\n\x00\x22").unwrap_err();
+    });
+    assert_eq!(events, deserialize_error("slice", "data"));
+
+    let mut buf = b":)\n\x00".to_vec();
+    buf.extend_from_slice(&[0xf8; 1000]);
+    let events = capture(|| {
+        crate::from_slice::<Value>(&buf).unwrap_err();
+    });
+    assert_eq!(events, deserialize_error("slice", "limit"));
+
+    struct FailingReader;
+
+    impl io::Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+
+    let events = capture(|| {
+        crate::from_reader::<Value, _>(io::BufReader::new(FailingReader)).unwrap_err();
+    });
+    assert_eq!(events, deserialize_error("io", "io"));
+}
+
+#[test]
+fn direct_use_is_not_instrumented() {
+    let buf = crate::to_vec(&1).unwrap();
+    let events = capture(|| {
+        let mut de = crate::Deserializer::from_slice(&buf);
+        serde::Deserialize::deserialize(&mut de)
+            .map(|_: i32| ())
+            .unwrap();
+
+        let mut out = vec![];
+        serde::Serialize::serialize(&1, &mut crate::Serializer::new(&mut out)).unwrap();
+    });
+    assert_eq!(events, []);
+}
@@ -0,0 +1,191 @@
+use crate::value::{to_value, BigDecimal, BigInteger, Map, Value};
+use serde::Serialize;
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+fn run_test<T>(raw: T, value: Value)
+where
+    T: Serialize,
+{
+    let actual = to_value(&raw).unwrap();
+    assert_eq!(value, actual);
+
+    // without preserve_order, object keys are re-sorted so the encodings only match as values
+    if cfg!(feature = "preserve_order") {
+        let expected_bytes = crate::to_vec(&raw).unwrap();
+        let actual_bytes = crate::to_vec(&actual).unwrap();
+        assert_eq!(expected_bytes, actual_bytes);
+    }
+}
+
+fn object(entries: Vec<(&str, Value)>) -> Value {
+    Value::Object(Map::from_iter(
+        entries.into_iter().map(|(k, v)| (k.to_string(), v)),
+    ))
+}
+
+#[test]
+fn primitives() {
+    run_test((), Value::Null);
+    run_test(true, Value::Boolean(true));
+    run_test(1u8, Value::Integer(1));
+    run_test(-1i16, Value::Integer(-1));
+    run_test(i32::MIN, Value::Integer(i32::MIN));
+    run_test(1i64, Value::Integer(1));
+    run_test(u32::MAX, Value::Long(u32::MAX as i64));
+    run_test(i64::MIN, Value::Long(i64::MIN));
+    run_test(1.5f32, Value::Float(1.5));
+    run_test(1.5f64, Value::Double(1.5));
+    run_test('a', Value::String("a".into()));
+    run_test("hello", Value::String("hello".into()));
+    run_test(Some(1), Value::Integer(1));
+    run_test(None::<i32>, Value::Null);
+    run_test(ByteBuf::from(vec![1, 2, 3]), Value::binary(vec![1, 2, 3]));
+}
+
+#[test]
+fn big_integers() {
+    let mut buf = vec![0; 9];
+    buf[1..].copy_from_slice(&u64::MAX.to_be_bytes());
+    run_test(u64::MAX, Value::BigInteger(BigInteger::from_be_bytes(buf)));
+
+    run_test(
+        i128::MIN,
+        Value::BigInteger(BigInteger::from_be_bytes(i128::MIN.to_be_bytes().to_vec())),
+    );
+    run_test(u128::from(u32::MAX), Value::Long(u32::MAX as i64));
+
+    let mut buf = vec![0; 17];
+    buf[1..].copy_from_slice(&u128::MAX.to_be_bytes());
+    run_test(u128::MAX, Value::BigInteger(BigInteger::from_be_bytes(buf)));
+
+    // values which don't fill all 16 bytes use the minimal encoding, like the serializer
+    let mut buf = vec![0; 9];
+    buf[0] = 0x40;
+    run_test(
+        1i128 << 70,
+        Value::BigInteger(BigInteger::from_be_bytes(buf)),
+    );
+    let mut buf = vec![0; 9];
+    buf[0] = 0xc0;
+    run_test(
+        -(1i128 << 70),
+        Value::BigInteger(BigInteger::from_be_bytes(buf)),
+    );
+    let mut buf = vec![0; 10];
+    buf[1] = 0x80;
+    run_test(
+        1u128 << 71,
+        Value::BigInteger(BigInteger::from_be_bytes(buf)),
+    );
+
+    for v in [1i128 << 70, -(1i128 << 70), i128::MIN, i128::MAX] {
+        let value = to_value(&v).unwrap();
+        assert_eq!(crate::to_vec(&value).unwrap(), crate::to_vec(&v).unwrap());
+    }
+    for v in [1u128 << 71, u128::MAX] {
+        let value = to_value(&v).unwrap();
+        assert_eq!(crate::to_vec(&value).unwrap(), crate::to_vec(&v).unwrap());
+    }
+}
+
+#[test]
+fn big_number_types() {
+    let value = BigInteger::from_be_bytes(vec![0xff; 30]);
+    run_test(value.clone(), Value::BigInteger(value));
+
+    let value = BigDecimal::new(BigInteger::from_be_bytes(vec![0x12, 0x34]), -10);
+    run_test(value.clone(), Value::BigDecimal(value));
+}
+
+#[derive(Serialize)]
+struct Inner {
+    a: i32,
+    b: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Outer {
+    inner: Inner,
+    number: BigInteger,
+    map: BTreeMap<u32, bool>,
+}
+
+#[test]
+fn nested_structs() {
+    run_test(
+        Outer {
+            inner: Inner {
+                a: 1,
+                b: vec!["x".to_string()],
+            },
+            number: BigInteger::from_be_bytes(vec![1]),
+            map: BTreeMap::from_iter([(1, true), (2, false)]),
+        },
+        object(vec![
+            (
+                "inner",
+                object(vec![
+                    ("a", Value::Integer(1)),
+                    ("b", Value::Array(vec![Value::String("x".into())])),
+                ]),
+            ),
+            (
+                "number",
+                Value::BigInteger(BigInteger::from_be_bytes(vec![1])),
+            ),
+            (
+                "map",
+                object(vec![
+                    ("1", Value::Boolean(true)),
+                    ("2", Value::Boolean(false)),
+                ]),
+            ),
+        ]),
+    );
+}
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+enum TestEnum {
+    Unit,
+    Newtype(i32),
+    Tuple(i32, bool),
+    Struct { a: i32, b: bool },
+}
+
+#[test]
+fn enums() {
+    run_test(TestEnum::Unit, Value::String("Unit".into()));
+    run_test(
+        TestEnum::Newtype(42),
+        object(vec![("Newtype", Value::Integer(42))]),
+    );
+    run_test(
+        TestEnum::Tuple(42, true),
+        object(vec![(
+            "Tuple",
+            Value::Array(vec![Value::Integer(42), Value::Boolean(true)]),
+        )]),
+    );
+    run_test(
+        TestEnum::Struct { a: 42, b: true },
+        object(vec![(
+            "Struct",
+            object(vec![("a", Value::Integer(42)), ("b", Value::Boolean(true))]),
+        )]),
+    );
+}
+
+#[test]
+fn enum_keys() {
+    run_test(
+        BTreeMap::from_iter([(TestEnum::Unit, 1)]),
+        object(vec![("Unit", Value::Integer(1))]),
+    );
+}
+
+#[test]
+fn invalid_key() {
+    to_value(&BTreeMap::from_iter([(vec![1], 1)])).unwrap_err();
+}
@@ -0,0 +1,354 @@
+#![cfg(feature = "tools")]
+
+use crate::tools::{self, ConvertOptions, Direction, DumpFormat, Format};
+use crate::value::{BigDecimal, BigInteger, JsonBinary, Value};
+use crate::{Deserializer, Serializer};
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let path =
+            std::env::temp_dir().join(format!("serde-smile-tools-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let mut paths = vec![];
+    for category in fs::read_dir("tests").unwrap() {
+        let category = category.unwrap().path();
+        // the documents in `tests/quirks` are written by nonconforming producers
+        if !category.is_dir() || category.ends_with("quirks") {
+            continue;
+        }
+        for r in fs::read_dir(category).unwrap() {
+            let path = r.unwrap().path();
+            if path.extension() == Some(OsStr::new("smile")) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    assert!(!paths.is_empty());
+    paths
+}
+
+fn smile_values(buf: &[u8]) -> Vec<Value> {
+    Deserializer::from_slice(buf)
+        .into_iter::<Value>()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn sniff() {
+    assert_eq!(tools::sniff(b":)\n\x01\x21"), Format::Smile);
+    assert_eq!(tools::sniff(b":)\n"), Format::Smile);
+    assert_eq!(tools::sniff(b":)"), Format::Json);
+    assert_eq!(tools::sniff(b"{}"), Format::Json);
+    assert_eq!(tools::sniff(b""), Format::Json);
+}
+
+#[test]
+fn convert_fixtures() {
+    let dir = TempDir::new("convert");
+    let json = dir.0.join("out.json");
+    let smile = dir.0.join("out.smile");
+
+    for path in fixtures() {
+        println!("testing {}", path.display());
+        let expected = smile_values(&fs::read(&path).unwrap());
+
+        let stats =
+            tools::convert_file(&path, &json, Direction::Auto, &ConvertOptions::new()).unwrap();
+        assert_eq!(stats.direction(), Direction::SmileToJson);
+        assert_eq!(stats.values(), expected.len() as u64);
+        assert_eq!(stats.input_bytes(), fs::metadata(&path).unwrap().len());
+        assert_eq!(stats.output_bytes(), fs::metadata(&json).unwrap().len());
+
+        let out = fs::read_to_string(&json).unwrap();
+        let actual = out
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .collect::<Vec<_>>();
+        let expected_json = expected
+            .iter()
+            .map(|v| v.to_json_value(JsonBinary::Base64))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected_json);
+
+        let stats =
+            tools::convert_file(&json, &smile, Direction::Auto, &ConvertOptions::new()).unwrap();
+        assert_eq!(stats.direction(), Direction::JsonToSmile);
+        assert_eq!(stats.values(), expected.len() as u64);
+
+        let actual = smile_values(&fs::read(&smile).unwrap());
+        let expected = expected_json
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn convert_options() {
+    let dir = TempDir::new("options");
+    let input = dir.0.join("in.smile");
+    let output = dir.0.join("out");
+
+    let value = Value::Array(vec![Value::binary(vec![1, 2]), Value::Integer(3)]);
+    fs::write(&input, crate::to_vec(&value).unwrap()).unwrap();
+
+    let mut opts = ConvertOptions::new();
+    opts.json_binary(JsonBinary::Array).pretty(true);
+    tools::convert_file(&input, &output, Direction::SmileToJson, &opts).unwrap();
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "[\n  [\n    1,\n    2\n  ],\n  3\n]\n"
+    );
+
+    fs::write(&input, "{\"a\": \"b\"} {\"a\": \"b\"}").unwrap();
+    let mut opts = ConvertOptions::new();
+    opts.serializer(crate::ser::Builder::smallest_output())
+        .write_end_marker(true);
+    let stats = tools::convert_file(&input, &output, Direction::JsonToSmile, &opts).unwrap();
+    assert_eq!(stats.values(), 2);
+
+    let mut serializer = crate::ser::Builder::smallest_output().build(vec![]);
+    let value = Value::from(serde_json::json!({"a": "b"}));
+    value.serialize(&mut serializer).unwrap();
+    value.serialize(&mut serializer).unwrap();
+    serializer.end().unwrap();
+    assert_eq!(fs::read(&output).unwrap(), serializer.into_inner());
+}
+
+#[test]
+fn convert_empty() {
+    let dir = TempDir::new("empty");
+    let input = dir.0.join("in.json");
+    let output = dir.0.join("out.smile");
+
+    fs::write(&input, "").unwrap();
+    let stats =
+        tools::convert_file(&input, &output, Direction::Auto, &ConvertOptions::new()).unwrap();
+    assert_eq!(stats.values(), 0);
+    assert_eq!(fs::read(&output).unwrap(), b":)\n\x01");
+}
+
+#[test]
+fn convert_errors() {
+    let dir = TempDir::new("errors");
+    let input = dir.0.join("in");
+    let output = dir.0.join("out");
+
+    fs::write(&input, "[1, 2]").unwrap();
+    tools::convert_file(
+        &input,
+        &output,
+        Direction::SmileToJson,
+        &ConvertOptions::new(),
+    )
+    .unwrap_err();
+
+    fs::write(&input, crate::to_vec(&1).unwrap()).unwrap();
+    tools::convert_file(
+        &input,
+        &output,
+        Direction::JsonToSmile,
+        &ConvertOptions::new(),
+    )
+    .unwrap_err();
+
+    tools::convert_file(
+        &dir.0.join("missing"),
+        &output,
+        Direction::Auto,
+        &ConvertOptions::new(),
+    )
+    .unwrap_err();
+}
+
+fn dump_tokens(buf: &[u8]) -> (String, Result<(), crate::Error>) {
+    let mut out = vec![];
+    let r = tools::dump(buf, &mut out, DumpFormat::Tokens);
+    (String::from_utf8(out).unwrap(), r)
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "preserve_order"),
+    ignore = "the expected output lists keys in insertion order"
+)]
+fn dump_tokens_exact() {
+    let mut serializer = crate::ser::Builder::smallest_output().build(vec![]);
+    let value = crate::smile!({
+        "null": null,
+        "bools": [true, false],
+        "ints": [1, -20, 100000, 10000000000],
+        "big": [
+            (Value::BigInteger(BigInteger::from_be_bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]))),
+            (Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![0x27, 0x29]), 2)))
+        ],
+        "floats": [(Value::Float(1.5)), 2.25],
+        "strings": ["", "a", "a", "ü", (Value::string("x".repeat(70)))],
+        "bytes": (Value::binary(vec![1, 2, 3])),
+        "nested": {"null": {}}
+    });
+    value.serialize(&mut serializer).unwrap();
+    serializer.end().unwrap();
+    let buf = serializer.into_inner();
+
+    let (out, r) = dump_tokens(&buf);
+    r.unwrap();
+    let expected = r#"00000000  3a 29 0a 07                 header (raw binary, shared strings, shared properties)
+00000004  fa                          start object
+00000005  83 6e 75 6c 6c                key "null"
+0000000a  21                            null
+0000000b  84 62 6f 6f 6c 73             key "bools"
+00000011  f8                            start array
+00000012  23                              true
+00000013  22                              false
+00000014  f9                            end array
+00000015  83 69 6e 74 73                key "ints"
+0000001a  f8                            start array
+0000001b  c2                              int 1
+0000001c  24 a7                           int -20
+0000001e  24 18 35 80                     int 100000
+00000022  25 01 15 01 3e 20 80            long 10000000000
+00000029  f9                            end array
+0000002a  82 62 69 67                   key "big"
+0000002e  f8                            start array
+0000002f  26 89 00 40 00 00 00 00 ..      big integer 18446744073709551616
+0000003c  2a 84 82 13 4a 01               big decimal 100.25
+00000042  f9                            end array
+00000043  85 66 6c 6f 61 74 73          key "floats"
+0000004a  f8                            start array
+0000004b  28 03 7e 00 00 00               float 1.5
+00000051  29 00 40 01 00 00 00 00 ..      double 2.25
+0000005c  f9                            end array
+0000005d  86 73 74 72 69 6e 67 73       key "strings"
+00000065  f8                            start array
+00000066  20                              string ""
+00000067  40 61                           string "a"
+00000069  01                              shared string #0 "a"
+0000006a  80 c3 bc                        string "ü"
+0000006d  e0 78 78 78 78 78 78 78 ..      string "xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+000000b5  f9                            end array
+000000b6  84 62 79 74 65 73             key "bytes"
+000000bc  fd 83 01 02 03                raw binary (3 bytes)
+000000c1  85 6e 65 73 74 65 64          key "nested"
+000000c8  fa                            start object
+000000c9  40                              shared key #0 "null"
+000000ca  fa                              start object
+000000cb  fb                              end object
+000000cc  fb                            end object
+000000cd  fb                          end object
+000000ce  ff                          end of stream
+"#;
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn dump_tokens_fixtures() {
+    for path in fixtures() {
+        println!("testing {}", path.display());
+        let (out, r) = dump_tokens(&fs::read(&path).unwrap());
+        r.unwrap();
+
+        // the big number fixtures are named after their values
+        let category = path.parent().unwrap().file_name().unwrap();
+        let stem = path.file_stem().unwrap().to_str().unwrap();
+        if category == "big_integer" {
+            assert!(out.contains(&format!("big integer {}\n", stem)), "{}", out);
+        } else if category == "big_decimal" {
+            let expected = serde_json::from_slice::<serde_json::Value>(
+                &fs::read(path.with_extension("json")).unwrap(),
+            )
+            .unwrap();
+            let value = crate::from_slice::<Value>(&fs::read(&path).unwrap()).unwrap();
+            assert!(
+                out.contains(&format!("big decimal {}\n", value.to_json_string())),
+                "{} {}",
+                out,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn dump_binary_lengths() {
+    for len in 0..20 {
+        let value = Value::Binary((0..len).collect());
+        let (out, r) = dump_tokens(&crate::to_vec(&value).unwrap());
+        r.unwrap();
+        assert!(
+            out.contains(&format!("  binary ({} bytes)\n", len)),
+            "{}",
+            out
+        );
+
+        let value = Value::BigInteger(BigInteger::from_be_bytes(
+            (1..=len).map(|b| b.wrapping_mul(37)).collect(),
+        ));
+        let (out, r) = dump_tokens(&crate::to_vec(&value).unwrap());
+        r.unwrap();
+        assert!(
+            out.contains(&format!("big integer {}\n", value.to_json_string())),
+            "{}",
+            out
+        );
+    }
+}
+
+#[test]
+fn dump_tokens_malformed() {
+    let (out, r) = dump_tokens(b":)\n\x00\xf8\x21\x27");
+    assert_eq!(
+        out,
+        "00000000  3a 29 0a 00                 header\n\
+         00000004  f8                          start array\n\
+         00000005  21                            null\n"
+    );
+    r.unwrap_err();
+
+    dump_tokens(b"").1.unwrap_err();
+    dump_tokens(b"{}").1.unwrap_err();
+    dump_tokens(b":)\n\x00\xf8").1.unwrap_err();
+    dump_tokens(b":)\n\x00\xfa\x80a").1.unwrap_err();
+    dump_tokens(b":)\n\x00\x01").1.unwrap_err();
+    dump_tokens(b":)\n\x00\xff\x21").1.unwrap_err();
+}
+
+#[test]
+fn dump_json() {
+    let mut serializer = Serializer::new(vec![]);
+    crate::smile!({"a": [1, "b"]})
+        .serialize(&mut serializer)
+        .unwrap();
+    Value::binary(vec![0xff])
+        .serialize(&mut serializer)
+        .unwrap();
+    let buf = serializer.into_inner();
+
+    let mut out = vec![];
+    tools::dump(&*buf, &mut out, DumpFormat::Json).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "{\"a\":[1,\"b\"]}\n\"/w==\"\n"
+    );
+}
@@ -0,0 +1,148 @@
+use crate::de::SliceRead;
+use crate::value::Value;
+use crate::{smile, Deserializer, Error};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default)]
+struct Present;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Entry {
+    name: String,
+    #[serde(default)]
+    marker: Present,
+}
+
+fn deserialize<'de, T>(buf: &'de [u8], empty_containers_as_unit: bool) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut de = Deserializer::builder()
+        .empty_containers_as_unit(empty_containers_as_unit)
+        .build(SliceRead::new(buf));
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+fn set() -> HashMap<String, Present> {
+    let mut set = HashMap::new();
+    set.insert("a".to_string(), Present);
+    set.insert("b".to_string(), Present);
+    set
+}
+
+#[test]
+fn null() {
+    let buf = crate::to_vec(&set()).unwrap();
+    assert_eq!(
+        crate::from_slice::<Value>(&buf).unwrap(),
+        smile!({"a": null, "b": null})
+    );
+
+    for lenient in [false, true] {
+        assert_eq!(
+            deserialize::<HashMap<String, Present>>(&buf, lenient).unwrap(),
+            set()
+        );
+        assert_eq!(
+            deserialize::<()>(&crate::to_vec(&()).unwrap(), lenient).unwrap(),
+            ()
+        );
+    }
+}
+
+#[test]
+fn empty_object() {
+    let buf = crate::to_vec(&smile!({"a": {}, "b": {}})).unwrap();
+
+    let e = deserialize::<HashMap<String, Present>>(&buf, false).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: invalid type: map, expected unit struct Present"
+    );
+    assert_eq!(
+        deserialize::<HashMap<String, Present>>(&buf, true).unwrap(),
+        set()
+    );
+}
+
+#[test]
+fn empty_array() {
+    let buf = crate::to_vec(&smile!({"a": [], "b": []})).unwrap();
+
+    let e = deserialize::<HashMap<String, Present>>(&buf, false).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "error in Deserialize impl: invalid type: sequence, expected unit struct Present"
+    );
+    assert_eq!(
+        deserialize::<HashMap<String, Present>>(&buf, true).unwrap(),
+        set()
+    );
+
+    let buf = crate::to_vec(&smile!([])).unwrap();
+    assert!(deserialize::<()>(&buf, false).is_err());
+    assert_eq!(deserialize::<()>(&buf, true).unwrap(), ());
+}
+
+#[test]
+fn non_empty_containers() {
+    for lenient in [false, true] {
+        let buf = crate::to_vec(&smile!({"a": {"b": null}})).unwrap();
+        let e = deserialize::<HashMap<String, Present>>(&buf, lenient).unwrap_err();
+        assert_eq!(
+            e.detail().unwrap().to_string(),
+            "invalid type: map, expected unit struct Present"
+        );
+
+        let buf = crate::to_vec(&smile!({"a": [null]})).unwrap();
+        let e = deserialize::<HashMap<String, Present>>(&buf, lenient).unwrap_err();
+        assert_eq!(
+            e.detail().unwrap().to_string(),
+            "invalid type: sequence, expected unit struct Present"
+        );
+
+        let buf = crate::to_vec(&smile!({"a": 1})).unwrap();
+        let e = deserialize::<HashMap<String, Present>>(&buf, lenient).unwrap_err();
+        assert_eq!(
+            e.detail().unwrap().to_string(),
+            "invalid type: integer `1`, expected unit struct Present"
+        );
+    }
+
+    let e = deserialize::<Present>(b":)\n\x00\xfa", true).unwrap_err();
+    assert_eq!(e.to_string(), "EOF while parsing map");
+}
+
+#[test]
+fn default_fields() {
+    for lenient in [false, true] {
+        let buf = crate::to_vec(&smile!([{"name": "a"}, {"name": "b", "marker": null}])).unwrap();
+        let entries = deserialize::<Vec<Entry>>(&buf, lenient).unwrap();
+        assert_eq!(
+            entries,
+            [
+                Entry {
+                    name: "a".to_string(),
+                    marker: Present,
+                },
+                Entry {
+                    name: "b".to_string(),
+                    marker: Present,
+                },
+            ]
+        );
+    }
+
+    let buf = crate::to_vec(&smile!({"name": "a", "marker": {}})).unwrap();
+    assert!(deserialize::<Entry>(&buf, false).is_err());
+    assert_eq!(
+        deserialize::<Entry>(&buf, true).unwrap(),
+        Entry {
+            name: "a".to_string(),
+            marker: Present,
+        }
+    );
+}
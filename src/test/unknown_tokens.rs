@@ -0,0 +1,127 @@
+use crate::de::{Deserializer, IoRead};
+use crate::value::Value;
+use crate::Error;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::io::BufReader;
+
+fn document(body: &[u8]) -> Vec<u8> {
+    let mut buf = b":)\n\x00".to_vec();
+    buf.extend_from_slice(body);
+    buf
+}
+
+fn from_buf_reader(buf: &[u8]) -> Result<Value, Error> {
+    let mut de = Deserializer::new(IoRead::new(BufReader::with_capacity(1, buf)));
+    let value = Value::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+// Every reader must report the same token and offset.
+fn check(body: &[u8], token: u8, offset: u64, reserved: bool) {
+    let buf = document(body);
+
+    let results = vec![
+        ("from_slice", crate::from_slice::<Value>(&buf)),
+        (
+            "from_mut_slice",
+            crate::from_mut_slice::<Value>(&mut buf.clone()),
+        ),
+        (
+            "from_cow",
+            crate::from_cow::<Value>(Cow::Owned(buf.clone())),
+        ),
+        ("from_reader", crate::from_reader::<Value, _>(&buf[..])),
+        ("BufReader", from_buf_reader(&buf)),
+    ];
+
+    let expected = if reserved {
+        format!(
+            "reserved token {:#04x} at offset {}; the document may use a newer revision of the Smile format",
+            token, offset,
+        )
+    } else {
+        format!("unexpected token {:#04x} at offset {}", token, offset)
+    };
+
+    for (name, result) in results {
+        let e = result.unwrap_err();
+        assert_eq!(e.token(), Some(token), "{}", name);
+        assert_eq!(e.to_string(), expected, "{}", name);
+    }
+}
+
+const RESERVED_VALUES: &[u8] = &[
+    0x00, 0x27, 0x2b, 0x2c, 0x39, 0x3b, 0x3f, 0xe1, 0xe3, 0xe5, 0xe7, 0xe9, 0xeb, 0xf0, 0xf7, 0xfe,
+];
+
+const RESERVED_KEYS: &[u8] = &[
+    0x00, 0x1f, 0x21, 0x2f, 0x35, 0x39, 0x3b, 0x3f, 0xf8, 0xfa, 0xfc, 0xff,
+];
+
+#[test]
+fn reserved_value_tokens() {
+    for &token in RESERVED_VALUES {
+        check(&[token], token, 4, true);
+        check(&[0xf8, 0x21, 0x21, token, 0xf9], token, 7, true);
+        check(
+            &[0xfa, 0x80, b'a', 0x21, 0x80, b'b', token],
+            token,
+            10,
+            true,
+        );
+    }
+}
+
+#[test]
+fn reserved_key_tokens() {
+    for &token in RESERVED_KEYS {
+        check(&[0xfa, token], token, 5, true);
+        check(&[0xfa, 0x80, b'a', 0x21, token], token, 8, true);
+        check(&[0xf8, 0x21, 0xfa, 0xfb, 0xfa, token], token, 9, true);
+    }
+}
+
+#[test]
+fn unexpected_tokens() {
+    for &token in &[0xf9, 0xfb, 0xfc] {
+        check(&[token], token, 4, false);
+        check(&[0xfa, 0x80, b'a', token], token, 7, false);
+    }
+    check(&[0xf8, 0x21, 0xfb], 0xfb, 6, false);
+    check(&[0xfa, 0x80, b'a', 0x21, 0x3a], 0x3a, 8, false);
+}
+
+#[test]
+fn offsets_span_long_values() {
+    let mut body = vec![0xf8, 0xe0];
+    body.extend_from_slice(&[b'x'; 100]);
+    body.extend_from_slice(&[0xfc, 0xfd, 0x83, 1, 2, 3, 0xe9]);
+    check(&body, 0xe9, 4 + 2 + 100 + 1 + 5, true);
+}
+
+#[test]
+fn other_errors_have_no_token() {
+    let e = crate::from_slice::<Value>(&document(&[0xf8])).unwrap_err();
+    assert_eq!(e.token(), None);
+
+    let e = crate::from_slice::<Value>(&document(&[0x21, 0x21])).unwrap_err();
+    assert_eq!(e.token(), None);
+
+    let e = crate::from_slice::<Value>(b":)\n\x00\x21:)\n\x00\x21").unwrap_err();
+    assert_eq!(e.token(), None);
+}
+
+#[cfg(feature = "tools")]
+#[test]
+fn dump() {
+    let buf = document(&[0xf8, 0x21, 0xe9]);
+    let e =
+        crate::tools::dump(&buf[..], &mut vec![], crate::tools::DumpFormat::Tokens).unwrap_err();
+    assert_eq!(e.token(), Some(0xe9));
+    assert_eq!(
+        e.to_string(),
+        "reserved token 0xe9 at offset 6; the document may use a newer revision of the Smile format",
+    );
+}
@@ -1,8 +1,9 @@
-use crate::value::{BigDecimal, BigInteger, Value};
+use crate::value::{BigDecimal, BigInteger, Map, Value, ValueKind, ValueRef};
 use indexmap::IndexMap;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::de::{self, DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf;
+use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::iter::FromIterator;
 
@@ -42,6 +43,81 @@ fn long() {
     run_test(-(1i64 << 50), Value::Long(-(1 << 50)));
 }
 
+#[test]
+fn unsigned_long() {
+    fn big(v: u64) -> Value {
+        let mut buf = vec![0];
+        buf.extend_from_slice(&v.to_be_bytes());
+        Value::BigInteger(BigInteger::from_be_bytes(buf))
+    }
+
+    let cases = [
+        (u64::from(u32::MAX), Value::Long(i64::from(u32::MAX))),
+        (i64::MAX as u64, Value::Long(i64::MAX)),
+        (i64::MAX as u64 + 1, big(i64::MAX as u64 + 1)),
+        (u64::MAX - 1, big(u64::MAX - 1)),
+        (u64::MAX, big(u64::MAX)),
+    ];
+
+    for (raw, value) in cases {
+        run_test(raw, value.clone());
+
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(Value::deserialize(de).unwrap(), value);
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(ValueRef::deserialize(de).unwrap().to_owned(), value);
+    }
+}
+
+#[test]
+fn wide_integers() {
+    fn big(buf: &[u8]) -> Value {
+        Value::BigInteger(BigInteger::from_be_bytes(buf.to_vec()))
+    }
+
+    let mut above_u64 = vec![1];
+    above_u64.extend_from_slice(&[0; 8]);
+    let mut u128_max = vec![0];
+    u128_max.extend_from_slice(&[0xff; 16]);
+    let mut i128_min = vec![0x80];
+    i128_min.extend_from_slice(&[0; 15]);
+
+    let cases = [
+        (u128::from(u64::MAX) + 1, big(&above_u64)),
+        (u128::MAX, big(&u128_max)),
+        (i64::MAX as u128, Value::Long(i64::MAX)),
+    ];
+    for (raw, value) in cases {
+        let buf = crate::to_vec(&raw).unwrap();
+        let actual = crate::from_slice::<Value>(&buf).unwrap();
+        assert_eq!(u128::try_from(&actual).unwrap(), raw);
+        assert_eq!(crate::value::from_value::<u128>(actual).unwrap(), raw);
+
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(Value::deserialize(de).unwrap(), value);
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(ValueRef::deserialize(de).unwrap().to_owned(), value);
+    }
+
+    let cases = [
+        (i128::MIN, big(&i128_min)),
+        (i128::MAX, big(&i128::MAX.to_be_bytes())),
+        (-(1 << 64), big(&[0xff, 0, 0, 0, 0, 0, 0, 0, 0])),
+        (i128::from(i64::MIN), Value::Long(i64::MIN)),
+    ];
+    for (raw, value) in cases {
+        let buf = crate::to_vec(&raw).unwrap();
+        let actual = crate::from_slice::<Value>(&buf).unwrap();
+        assert_eq!(i128::try_from(&actual).unwrap(), raw);
+        assert_eq!(crate::value::from_value::<i128>(actual).unwrap(), raw);
+
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(Value::deserialize(de).unwrap(), value);
+        let de = IntoDeserializer::<de::value::Error>::into_deserializer(raw);
+        assert_eq!(ValueRef::deserialize(de).unwrap().to_owned(), value);
+    }
+}
+
 #[test]
 fn big_integer() {
     run_test(
@@ -83,17 +159,17 @@ fn big_decimal() {
 
 #[test]
 fn string() {
-    run_test("".to_string(), Value::String("".to_string()));
+    run_test("".to_string(), Value::String("".into()));
     run_test(
         "hello world".to_string(),
-        Value::String("hello world".to_string()),
+        Value::String("hello world".into()),
     );
 }
 
 #[test]
 fn binary() {
-    run_test(ByteBuf::from(vec![]), Value::Binary(vec![]));
-    run_test(ByteBuf::from(vec![0xff; 30]), Value::Binary(vec![0xff; 30]));
+    run_test(ByteBuf::from(vec![]), Value::binary(vec![]));
+    run_test(ByteBuf::from(vec![0xff; 30]), Value::binary(vec![0xff; 30]));
 }
 
 #[test]
@@ -107,15 +183,172 @@ fn array() {
 
 #[test]
 fn object() {
-    run_test(
-        IndexMap::<String, i32>::new(),
-        Value::Object(IndexMap::new()),
-    );
+    run_test(IndexMap::<String, i32>::new(), Value::Object(Map::new()));
     run_test(
         IndexMap::<_, _>::from_iter([("Hello".to_string(), 123)]),
-        Value::Object(IndexMap::<_, _>::from_iter([(
-            "Hello".to_string(),
-            Value::Integer(123),
-        )])),
+        Value::Object(Map::from_iter([("Hello".to_string(), Value::Integer(123))])),
     );
 }
+
+#[test]
+fn accessors() {
+    assert_eq!(Value::String("a".into()).as_str(), Some("a"));
+    assert_eq!(Value::binary(vec![1]).as_str(), None);
+
+    assert_eq!(Value::Boolean(true).as_bool(), Some(true));
+    assert_eq!(Value::Null.as_bool(), None);
+
+    assert_eq!(Value::binary(vec![1, 2]).as_bytes(), Some(&[1, 2][..]));
+    assert_eq!(Value::String("a".into()).as_bytes(), None);
+
+    assert_eq!(Value::Array(vec![]).as_array(), Some(&vec![]));
+    assert_eq!(Value::Null.as_array(), None);
+
+    assert_eq!(Value::Object(Map::new()).as_object(), Some(&Map::new()));
+    assert_eq!(Value::Array(vec![]).as_object(), None);
+}
+
+#[test]
+fn integer_accessors() {
+    assert_eq!(Value::Integer(-5).as_i64(), Some(-5));
+    assert_eq!(Value::Long(i64::MIN).as_i64(), Some(i64::MIN));
+    assert_eq!(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![0xff])).as_i64(),
+        Some(-1)
+    );
+    assert_eq!(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0, 0, 0, 0, 0, 0, 0
+        ]))
+        .as_i64(),
+        None
+    );
+    assert_eq!(Value::Double(0.5).as_i64(), None);
+    assert_eq!(Value::Double(1.0).as_i64(), None);
+
+    assert_eq!(Value::Integer(5).as_u64(), Some(5));
+    assert_eq!(Value::Integer(-5).as_u64(), None);
+    assert_eq!(Value::Long(-5).as_u64(), None);
+    assert_eq!(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0, 0, 0, 0, 0, 0, 0
+        ]))
+        .as_u64(),
+        Some(0xff << 56)
+    );
+    assert_eq!(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![0xff])).as_u64(),
+        None
+    );
+    assert_eq!(
+        Value::BigInteger(BigInteger::from_be_bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0])).as_u64(),
+        None
+    );
+}
+
+#[test]
+fn float_accessors() {
+    assert_eq!(Value::Float(0.5).as_f64(), Some(0.5));
+    assert_eq!(Value::Double(0.25).as_f64(), Some(0.25));
+    assert_eq!(Value::Integer(i32::MIN).as_f64(), Some(i32::MIN as f64));
+    assert_eq!(Value::Long(5).as_f64(), Some(5.));
+    assert_eq!(Value::Long(1 << 60).as_f64(), Some((1u64 << 60) as f64));
+    assert_eq!(Value::Long(i64::MIN).as_f64(), Some(i64::MIN as f64));
+    assert_eq!(Value::Long((1 << 53) + 1).as_f64(), None);
+    assert_eq!(Value::Long(i64::MAX).as_f64(), None);
+    assert_eq!(Value::String("1".into()).as_f64(), None);
+}
+
+#[test]
+fn kinds() {
+    let cases = vec![
+        (Value::Null, ValueKind::Null, "null"),
+        (Value::Boolean(true), ValueKind::Boolean, "boolean"),
+        (Value::Integer(1), ValueKind::Integer, "integer"),
+        (Value::Long(1), ValueKind::Long, "long"),
+        (
+            Value::BigInteger(BigInteger::from_be_bytes(vec![1])),
+            ValueKind::BigInteger,
+            "big integer",
+        ),
+        (Value::Float(1.), ValueKind::Float, "float"),
+        (Value::Double(1.), ValueKind::Double, "double"),
+        (
+            Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 1)),
+            ValueKind::BigDecimal,
+            "big decimal",
+        ),
+        (Value::String("".into()), ValueKind::String, "string"),
+        (Value::binary(vec![]), ValueKind::Binary, "binary"),
+        (Value::Array(vec![]), ValueKind::Array, "array"),
+        (Value::Object(Map::new()), ValueKind::Object, "object"),
+    ];
+
+    for (value, kind, name) in cases {
+        assert_eq!(value.kind(), kind);
+        assert_eq!(kind.to_string(), name);
+
+        assert_eq!(value.is_null(), kind == ValueKind::Null);
+        assert_eq!(value.is_boolean(), kind == ValueKind::Boolean);
+        assert_eq!(
+            value.is_integral(),
+            matches!(
+                kind,
+                ValueKind::Integer | ValueKind::Long | ValueKind::BigInteger
+            )
+        );
+        assert_eq!(
+            value.is_floating(),
+            matches!(
+                kind,
+                ValueKind::Float | ValueKind::Double | ValueKind::BigDecimal
+            )
+        );
+        assert_eq!(
+            value.is_number(),
+            value.is_integral() || value.is_floating()
+        );
+        assert_eq!(value.is_string(), kind == ValueKind::String);
+        assert_eq!(value.is_binary(), kind == ValueKind::Binary);
+        assert_eq!(value.is_array(), kind == ValueKind::Array);
+        assert_eq!(value.is_object(), kind == ValueKind::Object);
+    }
+}
+
+#[test]
+fn primitive_equality() {
+    let mut map = Map::new();
+    map.insert("name".to_string(), Value::String("bob".into()));
+    let value = Value::Object(map);
+    assert_eq!(value["name"], "bob");
+    assert_eq!(value["name"], *"bob");
+    assert_eq!(value["name"], "bob".to_string());
+    assert_eq!("bob", value["name"]);
+    assert_eq!(*"bob", value["name"]);
+    assert_eq!("bob".to_string(), value["name"]);
+    assert_ne!(value["name"], "alice");
+    assert_ne!(value["missing"], "bob");
+
+    assert_eq!(Value::Boolean(true), true);
+    assert_eq!(false, Value::Boolean(false));
+    assert_ne!(Value::Integer(1), true);
+
+    assert_eq!(Value::Long(1), 1i32);
+    assert_eq!(Value::Integer(1), 1i64);
+    assert_eq!(1i64, Value::Long(1));
+    assert_eq!(Value::Integer(1), 1u64);
+    assert_eq!(
+        u64::MAX,
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ]))
+    );
+    assert_ne!(Value::Integer(-1), u64::MAX);
+    assert_ne!(Value::Float(1.), 1i32);
+    assert_ne!(Value::Double(1.), 1u64);
+
+    assert_eq!(Value::Float(0.5), 0.5);
+    assert_eq!(0.5, Value::Double(0.5));
+    assert_ne!(Value::Integer(1), 1.);
+    assert_ne!(Value::Double(f64::NAN), f64::NAN);
+}
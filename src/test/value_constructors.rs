@@ -0,0 +1,58 @@
+use crate::smile;
+use crate::value::{BigDecimal, BigInteger, Map, Number, Value};
+
+#[test]
+fn default() {
+    assert_eq!(Value::default(), Value::Null);
+
+    #[derive(Default)]
+    struct Holder {
+        value: Value,
+    }
+    assert!(Holder::default().value.is_null());
+}
+
+#[test]
+fn containers() {
+    assert_eq!(Value::object(), Value::Object(Map::new()));
+    assert_eq!(Value::object_with_capacity(8), smile!({}));
+    assert_eq!(Value::array(), Value::Array(vec![]));
+    assert_eq!(Value::array_with_capacity(8), smile!([]));
+
+    match Value::array_with_capacity(8) {
+        Value::Array(vec) => assert!(vec.capacity() >= 8),
+        _ => unreachable!(),
+    }
+
+    let mut object = Value::object_with_capacity(2);
+    object["b"] = Value::number(1);
+    object["a"] = Value::string("x");
+    assert_eq!(object, smile!({"b": 1, "a": "x"}));
+    assert_eq!(object.as_object().unwrap().len(), 2);
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(Value::string("a"), smile!("a"));
+    assert_eq!(Value::string(String::from("a")), smile!("a"));
+    assert_eq!(Value::binary(vec![1, 2]).as_bytes(), Some(&[1, 2][..]));
+    assert_eq!(Value::binary(&[1u8, 2][..]).as_bytes(), Some(&[1, 2][..]));
+
+    assert_eq!(Value::number(1i32), Value::Integer(1));
+    assert_eq!(Value::number(1i64 << 40), Value::Long(1 << 40));
+    assert_eq!(Value::number(u64::MAX), Value::from(u64::MAX));
+    assert_eq!(Value::number(1.5f32), Value::Float(1.5));
+    assert_eq!(Value::number(1.5f64), Value::Double(1.5));
+
+    let big = BigInteger::from_be_bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(Value::number(big.clone()), Value::BigInteger(big.clone()));
+    let decimal = BigDecimal::new(big, 2);
+    assert_eq!(
+        Value::number(decimal.clone()),
+        Value::BigDecimal(decimal.clone())
+    );
+    assert_eq!(
+        Value::number(Number::from(decimal.clone())),
+        Value::BigDecimal(decimal)
+    );
+}
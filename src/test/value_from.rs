@@ -0,0 +1,82 @@
+use crate::value::{BigInteger, Map, Value};
+use serde::Serialize;
+use std::fmt::Debug;
+
+fn run_test<T>(raw: T, expected: Value)
+where
+    T: Serialize + Into<Value> + Copy + Debug,
+{
+    let value = raw.into();
+    assert_eq!(value, expected, "{:?}", raw);
+    assert_eq!(
+        crate::to_vec(&value).unwrap(),
+        crate::to_vec(&raw).unwrap(),
+        "{:?}",
+        raw
+    );
+}
+
+#[test]
+fn ints() {
+    run_test(-1i8, Value::Integer(-1));
+    run_test(i16::MIN, Value::Integer(i16::MIN.into()));
+    run_test(i32::MAX, Value::Integer(i32::MAX));
+    run_test(5i64, Value::Integer(5));
+    run_test(i64::MIN, Value::Long(i64::MIN));
+    run_test(u8::MAX, Value::Integer(u8::MAX.into()));
+    run_test(u16::MAX, Value::Integer(u16::MAX.into()));
+    run_test(5u32, Value::Integer(5));
+    run_test(u32::MAX, Value::Long(u32::MAX.into()));
+    run_test(5u64, Value::Integer(5));
+    run_test(1u64 << 40, Value::Long(1 << 40));
+    run_test(
+        u64::MAX,
+        Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        ])),
+    );
+}
+
+#[test]
+fn scalars() {
+    run_test(true, Value::Boolean(true));
+    run_test(1.5f32, Value::Float(1.5));
+    run_test(1.5f64, Value::Double(1.5));
+    run_test("foo", Value::String("foo".into()));
+    assert_eq!(Value::from("foo".to_string()), Value::String("foo".into()));
+    assert_eq!(Value::from(vec![1u8, 2]), Value::binary(vec![1, 2]));
+}
+
+#[test]
+fn options() {
+    run_test(Some(1i32), Value::Integer(1));
+    run_test(None::<i32>, Value::Null);
+    assert_eq!(
+        Value::from(Some("foo".to_string())),
+        Value::String("foo".into())
+    );
+}
+
+#[test]
+fn containers() {
+    let array = Value::from(vec![
+        Value::from(1),
+        Value::from("a"),
+        Value::from(None::<bool>),
+    ]);
+    assert_eq!(
+        array,
+        Value::Array(vec![
+            Value::Integer(1),
+            Value::String("a".into()),
+            Value::Null,
+        ])
+    );
+
+    let mut map = Map::new();
+    map.insert("a".to_string(), Value::from(1u64 << 40));
+    map.insert("b".to_string(), array);
+    let object = Value::from(map);
+    assert_eq!(object["a"], Value::Long(1 << 40));
+    assert_eq!(object["b"][1], Value::String("a".into()));
+}
@@ -0,0 +1,123 @@
+use crate::smile;
+use crate::value::Value;
+
+#[test]
+fn collect() {
+    let array = vec![smile!(1), smile!("a")].into_iter().collect::<Value>();
+    assert_eq!(array, smile!([1, "a"]));
+
+    let empty = Vec::<Value>::new().into_iter().collect::<Value>();
+    assert_eq!(empty, smile!([]));
+
+    let object = vec![("b".to_string(), smile!(1)), ("a".to_string(), smile!(2))]
+        .into_iter()
+        .collect::<Value>();
+    assert_eq!(object, smile!({"b": 1, "a": 2}));
+}
+
+#[test]
+fn extend() {
+    let mut array = smile!([1]);
+    array.extend(vec![smile!(2)]);
+    assert_eq!(array, smile!([1, 2]));
+
+    let mut array = Value::Null;
+    array.extend(vec![smile!(2)]);
+    assert_eq!(array, smile!([2]));
+
+    let mut object = smile!({"a": 1});
+    object.extend(vec![
+        ("a".to_string(), smile!(3)),
+        ("b".to_string(), smile!(2)),
+    ]);
+    assert_eq!(object, smile!({"a": 3, "b": 2}));
+
+    let mut object = Value::Null;
+    object.extend(vec![("a".to_string(), smile!(1))]);
+    assert_eq!(object, smile!({"a": 1}));
+}
+
+#[test]
+#[should_panic(expected = "cannot extend Smile object with elements")]
+fn extend_object_with_elements() {
+    smile!({}).extend(vec![smile!(1)]);
+}
+
+#[test]
+#[should_panic(expected = "cannot extend Smile integer with entries")]
+fn extend_scalar_with_entries() {
+    smile!(1).extend(vec![("a".to_string(), smile!(1))]);
+}
+
+#[test]
+fn iterate_array() {
+    let mut value = smile!([1, 2, 3]);
+
+    let it = (&value).into_iter();
+    assert_eq!(it.len(), 3);
+    let items = it.collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        [(None, &smile!(1)), (None, &smile!(2)), (None, &smile!(3))]
+    );
+
+    let reversed = (&value)
+        .into_iter()
+        .rev()
+        .map(|(_, v)| v.clone())
+        .collect::<Value>();
+    assert_eq!(reversed, smile!([3, 2, 1]));
+
+    for (_, v) in &mut value {
+        *v = Value::Long(v.as_i64().unwrap() * 10);
+    }
+    assert_eq!(
+        value.into_iter().collect::<Vec<_>>(),
+        [
+            (None, Value::Long(10)),
+            (None, Value::Long(20)),
+            (None, Value::Long(30))
+        ],
+    );
+}
+
+#[test]
+fn iterate_object() {
+    let mut value = smile!({"a": 1, "b": 2});
+
+    let keys = (&value)
+        .into_iter()
+        .map(|(k, _)| k.cloned())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, [Some("a".to_string()), Some("b".to_string())]);
+
+    for (_, v) in &mut value {
+        *v = smile!(null);
+    }
+
+    let mut it = value.into_iter();
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next_back(), Some((Some("b".to_string()), smile!(null))));
+    assert_eq!(it.next(), Some((Some("a".to_string()), smile!(null))));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn iterate_scalar() {
+    for mut value in [smile!(null), smile!(1), smile!("a"), smile!(true)] {
+        assert_eq!((&value).into_iter().len(), 0);
+        assert_eq!((&mut value).into_iter().next(), None);
+        assert_eq!(value.into_iter().next_back(), None);
+    }
+}
+
+#[test]
+fn round_trip() {
+    let value = smile!({"a": [1, 2], "b": {"c": 3}});
+    let copy = value
+        .clone()
+        .into_iter()
+        .map(|(k, v)| (k.unwrap(), v))
+        .collect::<Value>();
+    assert_eq!(copy, value);
+}
@@ -0,0 +1,241 @@
+use crate::value::map::Entry;
+use crate::value::{Map, Value};
+#[cfg(feature = "preserve_order")]
+use indexmap::IndexMap;
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+/// Returns entries in the order the map iterates them: insertion order by default, and sorted by key otherwise.
+fn ordered<T>(entries: &[T]) -> Vec<T>
+where
+    T: Ord + Clone,
+{
+    let mut entries = entries.to_vec();
+    if cfg!(not(feature = "preserve_order")) {
+        entries.sort();
+    }
+    entries
+}
+
+fn map() -> Map {
+    let mut map = Map::new();
+    map.insert("c".to_string(), Value::Integer(1));
+    map.insert("a".to_string(), Value::Integer(2));
+    map.insert("b".to_string(), Value::Integer(3));
+    map
+}
+
+#[test]
+fn insertion_order() {
+    let mut map = map();
+    assert_eq!(map.keys().collect::<Vec<_>>(), ordered(&["c", "a", "b"]));
+
+    assert_eq!(
+        map.insert("a".to_string(), Value::Null),
+        Some(Value::Integer(2))
+    );
+    assert_eq!(map.keys().collect::<Vec<_>>(), ordered(&["c", "a", "b"]));
+    assert_eq!(
+        map.iter()
+            .map(|(k, v)| (k.as_str(), v.as_i64()))
+            .collect::<Vec<_>>(),
+        ordered(&[("c", Some(1)), ("a", None), ("b", Some(3))])
+    );
+
+    assert_eq!(map.remove("c"), Some(Value::Integer(1)));
+    assert_eq!(map.remove("c"), None);
+    assert_eq!(map.keys().collect::<Vec<_>>(), ordered(&["a", "b"]));
+}
+
+#[test]
+fn lookup() {
+    let mut map = map();
+    assert_eq!(map.len(), 3);
+    assert!(!map.is_empty());
+    assert!(map.contains_key("a"));
+    assert!(!map.contains_key("d"));
+    assert_eq!(map.get("b"), Some(&Value::Integer(3)));
+    assert_eq!(map.get(&"b".to_string()), Some(&Value::Integer(3)));
+    assert_eq!(map.get("d"), None);
+    assert_eq!(map["c"], Value::Integer(1));
+
+    *map.get_mut("b").unwrap() = Value::Boolean(true);
+    map["c"] = Value::Boolean(false);
+    assert_eq!(map["b"], Value::Boolean(true));
+    assert_eq!(map["c"], Value::Boolean(false));
+    assert_eq!(map.get_mut("d"), None);
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "key not present in Smile map")]
+fn index_missing() {
+    let _ = &map()["d"];
+}
+
+#[test]
+fn entry() {
+    let mut map = map();
+
+    *map.entry("a").or_insert(Value::Null) = Value::Integer(10);
+    map.entry("d").or_insert(Value::Integer(4));
+    map.entry("e".to_string())
+        .or_insert_with(|| Value::Integer(5));
+    map.entry("b")
+        .and_modify(|v| *v = Value::Integer(30))
+        .or_insert(Value::Null);
+    map.entry("f")
+        .and_modify(|v| *v = Value::Integer(30))
+        .or_insert(Value::Integer(6));
+
+    assert_eq!(
+        map.iter()
+            .map(|(k, v)| (k.as_str(), v.as_i64().unwrap()))
+            .collect::<Vec<_>>(),
+        ordered(&[("c", 1), ("a", 10), ("b", 30), ("d", 4), ("e", 5), ("f", 6)])
+    );
+
+    match map.entry("a") {
+        Entry::Occupied(mut e) => {
+            assert_eq!(e.key(), "a");
+            assert_eq!(e.get(), &Value::Integer(10));
+            assert_eq!(e.insert(Value::Null), Value::Integer(10));
+            assert_eq!(e.remove(), Value::Null);
+        }
+        Entry::Vacant(_) => panic!("expected occupied entry"),
+    }
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        ordered(&["c", "b", "d", "e", "f"])
+    );
+
+    match map.entry("z") {
+        Entry::Vacant(e) => {
+            assert_eq!(e.key(), "z");
+            *e.insert(Value::Null) = Value::Integer(26);
+        }
+        Entry::Occupied(_) => panic!("expected vacant entry"),
+    }
+    assert_eq!(map["z"], Value::Integer(26));
+}
+
+#[test]
+fn iteration() {
+    let mut map = map();
+
+    for (_, v) in map.iter_mut() {
+        *v = Value::from(v.as_i64().unwrap() * 2);
+    }
+    for v in map.values_mut() {
+        *v = Value::from(v.as_i64().unwrap() + 1);
+    }
+    for (_, v) in &mut map {
+        *v = Value::from(v.as_i64().unwrap() * 10);
+    }
+
+    let entries = (&map)
+        .into_iter()
+        .map(|(k, v)| (k.clone(), v.as_i64().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        ordered(&[
+            ("c".to_string(), 30),
+            ("a".to_string(), 50),
+            ("b".to_string(), 70)
+        ])
+    );
+
+    assert_eq!(map.iter().len(), 3);
+    assert_eq!(
+        map.keys().rev().collect::<Vec<_>>(),
+        ordered(&["c", "a", "b"])
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>()
+    );
+
+    let owned = map.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(owned, ordered(&["c", "a", "b"]));
+}
+
+#[test]
+fn retain() {
+    let mut map = map();
+    map.insert("d".to_string(), Value::Integer(4));
+    map.retain(|k, v| {
+        *v = Value::from(v.as_i64().unwrap() * 2);
+        k != "a"
+    });
+    assert_eq!(
+        map.iter()
+            .map(|(k, v)| (k.as_str(), v.as_i64().unwrap()))
+            .collect::<Vec<_>>(),
+        ordered(&[("c", 2), ("b", 6), ("d", 8)])
+    );
+}
+
+#[test]
+#[cfg(feature = "preserve_order")]
+fn conversions() {
+    let index_map = IndexMap::<String, Value>::from_iter(map());
+    assert_eq!(index_map.keys().collect::<Vec<_>>(), ["c", "a", "b"]);
+
+    let map = Map::from(index_map.clone());
+    assert_eq!(Value::from(index_map), Value::Object(map.clone()));
+    assert_eq!(
+        IndexMap::from(map.clone()),
+        IndexMap::<String, Value>::from_iter(map.clone())
+    );
+
+    let mut extended = Map::new();
+    extended.extend(map.clone());
+    assert_eq!(extended, map);
+}
+
+#[test]
+#[cfg(not(feature = "preserve_order"))]
+fn btree_map_conversions() {
+    let btree_map = BTreeMap::<String, Value>::from_iter(map());
+    assert_eq!(btree_map.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+    let map = Map::from(btree_map.clone());
+    assert_eq!(Value::from(btree_map), Value::Object(map.clone()));
+    assert_eq!(
+        BTreeMap::from(map.clone()),
+        BTreeMap::<String, Value>::from_iter(map.clone())
+    );
+}
+
+#[test]
+#[cfg(not(feature = "preserve_order"))]
+fn sorted_serialization() {
+    let buf = crate::to_vec(&map()).unwrap();
+    let expected = crate::to_vec(&crate::smile!({"a": 2, "b": 3, "c": 1})).unwrap();
+    assert_eq!(buf, expected);
+}
+
+#[test]
+#[cfg(feature = "preserve_order")]
+fn serialization_unchanged() {
+    let map = map();
+    let index_map = IndexMap::from(map.clone());
+    assert_eq!(
+        crate::to_vec(&map).unwrap(),
+        crate::to_vec(&index_map).unwrap()
+    );
+    assert_eq!(
+        crate::to_vec(&Value::Object(map.clone())).unwrap(),
+        crate::to_vec(&index_map).unwrap()
+    );
+
+    let buf = crate::to_vec(&map).unwrap();
+    assert_eq!(crate::from_slice::<Map>(&buf).unwrap(), map);
+    assert_eq!(
+        crate::from_slice::<Value>(&buf).unwrap(),
+        Value::Object(map)
+    );
+}
@@ -0,0 +1,124 @@
+use crate::value::{BigDecimal, BigInteger, Value, ValueRef};
+use crate::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+fn serialize<T>(raw_binary: bool, value: &T) -> Vec<u8>
+where
+    T: Serialize + ?Sized,
+{
+    let mut ser = Serializer::builder().raw_binary(raw_binary).build(vec![]);
+    value.serialize(&mut ser).unwrap();
+    ser.into_inner()
+}
+
+fn assert_borrowed_from(s: &[u8], buf: &[u8]) {
+    let range = buf.as_ptr_range();
+    assert!(range.contains(&s.as_ptr()));
+}
+
+fn long_string() -> String {
+    "a long string which is more than sixty four bytes in length, 🙂 even with some unicode"
+        .to_string()
+}
+
+#[test]
+fn borrows_from_slice() {
+    let value = crate::smile!({
+        "short": "ascii",
+        "long": (long_string()),
+        "binary": (Value::Binary((0..100).collect())),
+    });
+    let buf = serialize(true, &value);
+
+    let value_ref = crate::from_slice::<ValueRef<'_>>(&buf).unwrap();
+    let entries = match &value_ref {
+        ValueRef::Object(entries) => entries,
+        v => panic!("unexpected value {:?}", v),
+    };
+    assert_eq!(entries.len(), 3);
+
+    for (key, value) in entries {
+        match key {
+            Cow::Borrowed(key) => assert_borrowed_from(key.as_bytes(), &buf),
+            Cow::Owned(key) => panic!("key {} was not borrowed", key),
+        }
+        match value {
+            ValueRef::String(Cow::Borrowed(s)) => assert_borrowed_from(s.as_bytes(), &buf),
+            ValueRef::Binary(Cow::Borrowed(b)) => assert_borrowed_from(b, &buf),
+            v => panic!("value {:?} was not borrowed", v),
+        }
+    }
+
+    assert_eq!(value_ref.to_owned(), value);
+}
+
+#[test]
+fn seven_bit_binary() {
+    let buf = serialize(false, &Value::binary(vec![1, 2, 3]));
+
+    let value_ref = crate::from_slice::<ValueRef<'_>>(&buf).unwrap();
+    assert!(matches!(value_ref, ValueRef::Binary(Cow::Owned(_))));
+    assert_eq!(value_ref.to_owned(), Value::binary(vec![1, 2, 3]));
+
+    let mut buf = buf;
+    let value_ref = crate::from_mut_slice::<ValueRef<'_>>(&mut buf).unwrap();
+    assert!(matches!(value_ref, ValueRef::Binary(Cow::Borrowed(_))));
+    assert_eq!(value_ref.to_owned(), Value::binary(vec![1, 2, 3]));
+}
+
+#[test]
+fn reader() {
+    let buf = serialize(false, &crate::smile!({"a": ["b", (long_string())]}));
+    let mut de = Deserializer::from_reader(&*buf);
+    let value_ref = ValueRef::deserialize(&mut de).unwrap();
+    assert_eq!(
+        value_ref,
+        ValueRef::Object(vec![(
+            Cow::Owned("a".to_string()),
+            ValueRef::Array(vec![
+                ValueRef::String(Cow::Owned("b".to_string())),
+                ValueRef::String(Cow::Owned(long_string())),
+            ])
+        )])
+    );
+}
+
+#[test]
+fn round_trip() {
+    let value = crate::smile!([
+        null,
+        true,
+        1,
+        (Value::Long(1 << 40)),
+        (Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20]))),
+        (Value::Float(1.5)),
+        2.5,
+        (Value::BigDecimal(BigDecimal::new(BigInteger::from_be_bytes(vec![1]), 3))),
+        "foo",
+        (Value::binary(vec![1, 2, 3])),
+        {"a": {}, "b": []},
+    ]);
+    let buf = serialize(false, &value);
+
+    let value_ref = crate::from_slice::<ValueRef<'_>>(&buf).unwrap();
+    assert_eq!(value_ref.to_owned(), value);
+    assert_eq!(serialize(false, &value_ref), buf);
+}
+
+#[test]
+fn duplicate_keys() {
+    let buf = [
+        b':', b')', b'\n', 0x00, 0xfa, 0x80, b'a', 0xc2, 0x80, b'a', 0xc4, 0xfb,
+    ];
+
+    let value_ref = crate::from_slice::<ValueRef<'_>>(&buf).unwrap();
+    assert_eq!(
+        value_ref,
+        ValueRef::Object(vec![
+            (Cow::Borrowed("a"), ValueRef::Integer(1)),
+            (Cow::Borrowed("a"), ValueRef::Integer(2)),
+        ])
+    );
+    assert_eq!(value_ref.to_owned(), crate::smile!({"a": 2}));
+}
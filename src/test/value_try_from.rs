@@ -0,0 +1,150 @@
+use crate::value::{BigInteger, Map, Value};
+#[cfg(feature = "preserve_order")]
+use indexmap::IndexMap;
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+#[test]
+fn integers() {
+    assert_eq!(i64::try_from(Value::Integer(-5)).unwrap(), -5);
+    assert_eq!(i64::try_from(&Value::Long(i64::MIN)).unwrap(), i64::MIN);
+    assert_eq!(u8::try_from(Value::Long(255)).unwrap(), 255);
+    assert_eq!(i8::try_from(Value::Integer(-128)).unwrap(), -128);
+    assert_eq!(usize::try_from(Value::Integer(7)).unwrap(), 7);
+    assert_eq!(
+        u64::try_from(Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff
+        ])))
+        .unwrap(),
+        u64::MAX
+    );
+    assert_eq!(
+        i32::try_from(Value::BigInteger(BigInteger::from_be_bytes(vec![
+            0xff, 0xfe
+        ])))
+        .unwrap(),
+        -2
+    );
+    assert_eq!(
+        u128::try_from(Value::BigInteger(BigInteger::from_be_bytes(
+            u128::MAX.to_be_bytes().iter().fold(vec![0], |mut v, b| {
+                v.push(*b);
+                v
+            })
+        )))
+        .unwrap(),
+        u128::MAX
+    );
+}
+
+#[test]
+fn integer_errors() {
+    assert_eq!(
+        u8::try_from(Value::Integer(256)).unwrap_err().to_string(),
+        "value out of range: expected u8"
+    );
+    assert_eq!(
+        u32::try_from(Value::Integer(-1)).unwrap_err().to_string(),
+        "value out of range: expected u32"
+    );
+    assert_eq!(
+        i64::try_from(Value::BigInteger(BigInteger::from_be_bytes(vec![1; 20])))
+            .unwrap_err()
+            .to_string(),
+        "value out of range: expected i64"
+    );
+    assert_eq!(
+        i64::try_from(Value::Double(1.0)).unwrap_err().to_string(),
+        "invalid value type: expected i64, found double"
+    );
+    assert_eq!(
+        i64::try_from(Value::String("1".into()))
+            .unwrap_err()
+            .to_string(),
+        "invalid value type: expected i64, found string"
+    );
+}
+
+#[test]
+fn floats() {
+    assert_eq!(f32::try_from(Value::Float(0.5)).unwrap(), 0.5);
+    assert_eq!(f64::try_from(Value::Float(0.5)).unwrap(), 0.5);
+    assert_eq!(f64::try_from(&Value::Double(0.25)).unwrap(), 0.25);
+    assert_eq!(f64::try_from(Value::Long(3)).unwrap(), 3.);
+
+    assert_eq!(
+        f32::try_from(Value::Double(0.5)).unwrap_err().to_string(),
+        "invalid value type: expected f32, found double"
+    );
+    assert_eq!(
+        f64::try_from(Value::Long(i64::MAX))
+            .unwrap_err()
+            .to_string(),
+        "value out of range: expected f64"
+    );
+    assert_eq!(
+        f64::try_from(Value::Null).unwrap_err().to_string(),
+        "invalid value type: expected f64, found null"
+    );
+}
+
+#[test]
+fn others() {
+    assert!(bool::try_from(Value::Boolean(true)).unwrap());
+    assert_eq!(
+        bool::try_from(&Value::Integer(1)).unwrap_err().to_string(),
+        "invalid value type: expected boolean, found integer"
+    );
+
+    assert_eq!(
+        String::try_from(Value::String("foo".into())).unwrap(),
+        "foo"
+    );
+    assert_eq!(
+        String::try_from(&Value::String("foo".into())).unwrap(),
+        "foo"
+    );
+    assert_eq!(
+        String::try_from(Value::binary(vec![]))
+            .unwrap_err()
+            .to_string(),
+        "invalid value type: expected string, found binary"
+    );
+
+    assert_eq!(Vec::<u8>::try_from(Value::binary(vec![1])).unwrap(), [1]);
+    assert_eq!(
+        Vec::<Value>::try_from(Value::Array(vec![Value::Null])).unwrap(),
+        [Value::Null]
+    );
+    assert_eq!(
+        Map::try_from(&Value::Object(Map::new())).unwrap(),
+        Map::new()
+    );
+    #[cfg(feature = "preserve_order")]
+    {
+        assert_eq!(
+            IndexMap::try_from(&Value::Object(Map::new())).unwrap(),
+            IndexMap::<String, Value>::new()
+        );
+        assert_eq!(
+            IndexMap::try_from(Value::binary(vec![]))
+                .unwrap_err()
+                .to_string(),
+            "invalid value type: expected object, found binary"
+        );
+    }
+    #[cfg(not(feature = "preserve_order"))]
+    {
+        assert_eq!(
+            BTreeMap::try_from(&Value::Object(Map::new())).unwrap(),
+            BTreeMap::<String, Value>::new()
+        );
+        assert_eq!(
+            BTreeMap::try_from(Value::binary(vec![]))
+                .unwrap_err()
+                .to_string(),
+            "invalid value type: expected object, found binary"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Deserializer, Serializer};
+use serde::{Deserialize, Serialize};
+
+fn serialize(version: u8) -> Result<Vec<u8>, crate::Error> {
+    let mut ser = Serializer::builder().version(version).build(vec![]);
+    "hi".serialize(&mut ser)?;
+    Ok(ser.into_inner())
+}
+
+#[test]
+fn default_header() {
+    let buf = crate::to_vec(&"hi").unwrap();
+    assert_eq!(buf[..4], *b":)\n\x01");
+    assert_eq!(serialize(0).unwrap(), buf);
+}
+
+#[test]
+fn header_bytes() {
+    for &(version, flags) in &[(1, 0x11), (2, 0x21), (9, 0x91), (15, 0xf1)] {
+        let buf = serialize(version).unwrap();
+        assert_eq!(buf[..4], [b':', b')', b'\n', flags]);
+    }
+
+    let mut ser = Serializer::builder()
+        .version(5)
+        .raw_binary(true)
+        .shared_strings(true)
+        .shared_properties(false)
+        .build(vec![]);
+    ser.write_header().unwrap();
+    assert_eq!(ser.into_inner(), b":)\n\x56");
+}
+
+#[test]
+#[should_panic(expected = "invalid version 16: expected a value from 0 to 15")]
+fn invalid_version() {
+    Serializer::builder().version(16);
+}
+
+#[test]
+fn rejected_by_default() {
+    let buf = serialize(3).unwrap();
+    assert_eq!(
+        crate::from_slice::<String>(&buf).unwrap_err().to_string(),
+        "unsupported version",
+    );
+}
+
+#[test]
+fn round_trip() {
+    for version in 0..=15 {
+        let buf = serialize(version).unwrap();
+
+        let mut de = Deserializer::builder()
+            .accept_any_version(true)
+            .build(crate::de::SliceRead::new(&buf));
+        assert_eq!(de.header(), None);
+        assert_eq!(String::deserialize(&mut de).unwrap(), "hi");
+        de.end().unwrap();
+
+        let header = de.header().unwrap();
+        assert_eq!(header.version(), version);
+        assert!(!header.raw_binary());
+        assert!(!header.shared_strings());
+        assert!(header.shared_properties());
+    }
+}
+
+#[test]
+fn header_before_value() {
+    let buf = crate::to_vec(&"hi").unwrap();
+    let mut de = Deserializer::from_slice(&buf);
+    de.read_header().unwrap();
+    assert_eq!(de.header().unwrap().version(), 0);
+    assert_eq!(String::deserialize(&mut de).unwrap(), "hi");
+}
@@ -0,0 +1,220 @@
+use crate::limits::DEFAULT_RECURSION_LIMIT;
+use crate::smile;
+use crate::value::{Value, WalkControl};
+
+fn paths(value: &Value) -> Vec<(String, usize)> {
+    let mut paths = vec![];
+    value.walk(|path, _| {
+        paths.push((path.to_string(), path.depth()));
+        WalkControl::Continue
+    });
+    paths
+}
+
+#[test]
+fn preorder() {
+    let value = smile!({"a": [1, [2]], "b": {"c": null}, "d": 3});
+    let expected = [
+        ("", 0),
+        ("/a", 1),
+        ("/a/0", 2),
+        ("/a/1", 2),
+        ("/a/1/0", 3),
+        ("/b", 1),
+        ("/b/c", 2),
+        ("/d", 1),
+    ];
+    let actual = paths(&value);
+    assert_eq!(
+        actual,
+        expected
+            .iter()
+            .map(|(p, d)| (p.to_string(), *d))
+            .collect::<Vec<_>>()
+    );
+
+    for (path, _) in &actual {
+        assert!(value.pointer(path).is_some(), "{}", path);
+    }
+}
+
+#[test]
+fn scalar_root() {
+    assert_eq!(paths(&smile!(1)), [(String::new(), 0)]);
+    assert_eq!(paths(&smile!([])), [(String::new(), 0)]);
+}
+
+#[test]
+fn escaped_keys() {
+    let value = smile!({"a/b": {"c~d": 1}});
+    let actual = paths(&value);
+    assert_eq!(actual[1].0, "/a~1b");
+    assert_eq!(actual[2].0, "/a~1b/c~0d");
+    assert_eq!(value.pointer(&actual[2].0), Some(&smile!(1)));
+}
+
+#[test]
+fn skip() {
+    let value = smile!({"a": [1, 2], "b": [3]});
+    let mut visited = vec![];
+    value.walk(|path, _| {
+        visited.push(path.to_string());
+        if path.as_str() == "/a" {
+            WalkControl::Skip
+        } else {
+            WalkControl::Continue
+        }
+    });
+    assert_eq!(visited, ["", "/a", "/b", "/b/0"]);
+}
+
+#[test]
+fn stop() {
+    let value = smile!([[1, 2], 3]);
+    let mut visited = vec![];
+    value.walk(|path, _| {
+        visited.push(path.to_string());
+        if path.as_str() == "/0/0" {
+            WalkControl::Stop
+        } else {
+            WalkControl::Continue
+        }
+    });
+    assert_eq!(visited, ["", "/0", "/0/0"]);
+
+    let mut value = value;
+    let mut count = 0;
+    value.walk_mut(|_, _| {
+        count += 1;
+        WalkControl::Stop
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn rewrite_strings() {
+    let mut value = smile!({
+        "name": "alice",
+        "tags": ["a", 1, {"nested": "b"}],
+        "count": 2,
+    });
+    value.walk_mut(|_, value| {
+        if let Value::String(s) = value {
+            *value = Value::from(s.to_uppercase());
+        }
+        WalkControl::Continue
+    });
+    assert_eq!(
+        value,
+        smile!({
+            "name": "ALICE",
+            "tags": ["A", 1, {"nested": "B"}],
+            "count": 2,
+        })
+    );
+}
+
+#[test]
+fn redact_by_path() {
+    let mut value = smile!({"user": {"name": "alice", "ssn": "123"}, "ssn": "456"});
+    value.walk_mut(|path, value| {
+        if path.as_str().ends_with("/ssn") {
+            *value = smile!("<redacted>");
+        }
+        WalkControl::Continue
+    });
+    assert_eq!(
+        value,
+        smile!({"user": {"name": "alice", "ssn": "<redacted>"}, "ssn": "<redacted>"})
+    );
+}
+
+#[test]
+fn delete_keys() {
+    let mut value = smile!({
+        "keep": 1,
+        "secret": 2,
+        "nested": [{"secret": 3, "keep": {"secret": 4}}],
+    });
+
+    let mut visited = vec![];
+    value.walk_mut(|path, value| {
+        visited.push(path.to_string());
+        if let Value::Object(map) = value {
+            map.remove("secret");
+        }
+        WalkControl::Continue
+    });
+
+    assert_eq!(value, smile!({"keep": 1, "nested": [{"keep": {}}]}));
+    // removed entries are never visited
+    assert!(
+        visited.iter().all(|p| !p.contains("secret")),
+        "{:?}",
+        visited
+    );
+}
+
+#[test]
+fn replace_containers() {
+    // the contents of a container are walked after it's replaced
+    let mut value = smile!([1, [2]]);
+    value.walk_mut(|path, value| {
+        if path.as_str() == "/1" {
+            *value = smile!({"a": "b"});
+        } else if let Value::String(s) = value {
+            *value = Value::from(format!("{}!", s));
+        }
+        WalkControl::Continue
+    });
+    assert_eq!(value, smile!([1, {"a": "b!"}]));
+}
+
+fn nested(depth: usize) -> Value {
+    let mut value = Value::Null;
+    for _ in 0..depth {
+        value = Value::Array(vec![value]);
+    }
+    value
+}
+
+// dismantle the value iteratively so that dropping it doesn't overflow the stack
+fn dismantle(value: Value) {
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        if let Value::Array(elements) = value {
+            stack.extend(elements);
+        }
+    }
+}
+
+#[test]
+fn deeply_nested() {
+    let buf = crate::to_vec(&nested(DEFAULT_RECURSION_LIMIT)).unwrap();
+    let mut value = crate::from_slice::<Value>(&buf).unwrap();
+
+    let mut max_depth = 0;
+    value.walk(|path, _| {
+        max_depth = max_depth.max(path.depth());
+        WalkControl::Continue
+    });
+    assert_eq!(max_depth, DEFAULT_RECURSION_LIMIT);
+
+    value.walk_mut(|path, value| {
+        if path.depth() == DEFAULT_RECURSION_LIMIT {
+            *value = smile!(true);
+        }
+        WalkControl::Continue
+    });
+    let pointer = "/0".repeat(DEFAULT_RECURSION_LIMIT);
+    assert_eq!(value.pointer(&pointer), Some(&smile!(true)));
+
+    let value = nested(10_000);
+    let mut count = 0;
+    value.walk(|_, _| {
+        count += 1;
+        WalkControl::Continue
+    });
+    assert_eq!(count, 10_001);
+    dismantle(value);
+}
@@ -0,0 +1,83 @@
+use crate::value::BigInteger;
+use serde::de::DeserializeOwned;
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+fn check<T>(buf: &[u8], expected: &T)
+where
+    T: DeserializeOwned + PartialEq + Debug,
+{
+    let actual = crate::from_slice::<T>(buf).unwrap();
+    assert_eq!(*expected, actual, "from_slice");
+
+    let actual = crate::from_mut_slice::<T>(&mut buf.to_vec()).unwrap();
+    assert_eq!(*expected, actual, "from_mut_slice");
+
+    let actual = crate::from_reader::<T, _>(buf).unwrap();
+    assert_eq!(*expected, actual, "from_reader");
+}
+
+fn run_test<T>(token: &[u8], expected: T)
+where
+    T: DeserializeOwned + PartialEq + Debug + Clone,
+{
+    // at the very end of the input
+    let mut buf = b":)\n\x04".to_vec();
+    buf.extend_from_slice(token);
+    check(&buf, &expected);
+
+    // followed by an end marker
+    buf.push(0xff);
+    check(&buf, &expected);
+
+    // in the middle of a document
+    let mut buf = b":)\n\x04\xf8".to_vec();
+    buf.extend_from_slice(token);
+    buf.extend_from_slice(token);
+    buf.push(0xf9);
+    check(&buf, &vec![expected.clone(), expected.clone()]);
+
+    // as the last value of an object
+    let mut buf = b":)\n\x04\xfa\x80a".to_vec();
+    buf.extend_from_slice(token);
+    buf.push(0xfb);
+    let mut map = BTreeMap::new();
+    map.insert("a".to_string(), expected);
+    check(&buf, &map);
+}
+
+#[test]
+fn empty_string() {
+    run_test(b"\x20", String::new());
+}
+
+#[test]
+fn empty_long_string() {
+    run_test(b"\xe0\xfc", String::new());
+}
+
+#[test]
+fn empty_7_bit_binary() {
+    run_test(b"\xe8\x80", ByteBuf::new());
+}
+
+#[test]
+fn empty_raw_binary() {
+    run_test(b"\xfd\x80", ByteBuf::new());
+}
+
+#[test]
+fn empty_big_integer() {
+    run_test(b"\x26\x80", BigInteger::from_be_bytes(vec![0]));
+}
+
+#[test]
+fn truncated_binary() {
+    let bufs: [&[u8]; 3] = [b":)\n\x04\xfd\x81", b":)\n\x04\xe8\x81", b":)\n\x04\xe8"];
+    for buf in bufs {
+        crate::from_slice::<ByteBuf>(buf).unwrap_err();
+        crate::from_mut_slice::<ByteBuf>(&mut buf.to_vec()).unwrap_err();
+        crate::from_reader::<ByteBuf, _>(buf).unwrap_err();
+    }
+}
@@ -0,0 +1,432 @@
+//! A runner for the Smile fixture corpus, for use by other Smile implementations.
+//!
+//! The corpus lives in this crate's `tests` directory, with one subdirectory per category of value (e.g. `integer` or
+//! `shared_string`). Each test case is a pair of files: `<name>.json` describes the value and the encoding options,
+//! and `<name>.smile` contains the expected encoding. The JSON file is an object with the value in its `value` field,
+//! and the optional boolean fields `rawBinary`, `sharedStrings`, `sharedProperties`, and `writeEndMarker` which
+//! default to `false`.
+//!
+//! JSON can't represent every Smile value directly, so the [`Base64Binary`], [`TextBigInteger`], and
+//! [`TextBigDecimal`] types can be used to load binary, big integer, and big decimal values respectively.
+//!
+//! The `assert_*` methods panic with a description of the mismatch on failure, so they can be used directly from
+//! tests.
+//!
+//! Requires the `testsuite` Cargo feature.
+use crate::de::Deserializer;
+use crate::ser::{Builder, Serializer};
+use crate::value::{BigDecimal, BigInteger};
+use crate::{encoding, Error};
+use serde::de::{self, Error as _, IntoDeserializer, MapAccess, Visitor};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use std::convert::TryFrom;
+use std::ffi::OsStr;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// Returns the path of the directory containing the fixture corpus.
+///
+/// Each category of fixtures is a subdirectory of this directory.
+pub fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+/// Returns the paths of the JSON files of every test case in a category directory, in sorted order.
+pub fn fixtures(category_dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(category_dir).map_err(Error::io)? {
+        let path = entry.map_err(Error::io)?.path();
+        if path.extension() == Some(OsStr::new("json")) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Loads and runs every test case in a category directory with [`TestCase::run`].
+///
+/// # Panics
+///
+/// Panics if a test case can't be loaded or fails.
+pub fn run_category<T>(category_dir: &Path)
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    let paths = fixtures(category_dir)
+        .unwrap_or_else(|e| panic!("error listing {}: {}", category_dir.display(), e));
+    for path in paths {
+        let test_case = TestCase::<T>::load(&path)
+            .unwrap_or_else(|e| panic!("error loading {}: {}", path.display(), e));
+        test_case.run();
+    }
+}
+
+/// A single test case from the fixture corpus.
+#[derive(Debug)]
+pub struct TestCase<T> {
+    path: PathBuf,
+    raw_binary: bool,
+    shared_strings: bool,
+    shared_properties: bool,
+    write_end_marker: bool,
+    value: T,
+    expected: Vec<u8>,
+}
+
+impl<T> TestCase<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    /// Loads a test case from the path of its JSON file.
+    ///
+    /// The expected encoding is read from the file with the same name and a `.smile` extension.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let json = fs::read(path).map_err(Error::io)?;
+        let file = serde_json::from_slice::<TestCaseFile<T>>(&json).map_err(Error::message)?;
+        let value = file
+            .value
+            .ok_or_else(|| Error::message("missing `value`"))?;
+
+        let expected = fs::read(path.with_extension("smile")).map_err(Error::io)?;
+
+        Ok(TestCase {
+            path: path.to_path_buf(),
+            raw_binary: file.raw_binary,
+            shared_strings: file.shared_strings,
+            shared_properties: file.shared_properties,
+            write_end_marker: file.write_end_marker,
+            value,
+            expected,
+        })
+    }
+}
+
+impl<T> TestCase<T> {
+    /// Returns the path of the test case's JSON file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns whether the value is encoded with raw binary data.
+    pub fn raw_binary(&self) -> bool {
+        self.raw_binary
+    }
+
+    /// Returns whether the value is encoded with shared string values.
+    pub fn shared_strings(&self) -> bool {
+        self.shared_strings
+    }
+
+    /// Returns whether the value is encoded with shared property names.
+    pub fn shared_properties(&self) -> bool {
+        self.shared_properties
+    }
+
+    /// Returns whether the encoding ends with the end-of-stream token.
+    pub fn write_end_marker(&self) -> bool {
+        self.write_end_marker
+    }
+
+    /// Returns the value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the expected Smile encoding of the value.
+    pub fn expected(&self) -> &[u8] {
+        &self.expected
+    }
+
+    /// Returns a serializer builder configured with the test case's encoding options.
+    pub fn serializer_builder(&self) -> Builder {
+        let mut builder = Serializer::builder();
+        builder
+            .raw_binary(self.raw_binary)
+            .shared_strings(self.shared_strings)
+            .shared_properties(self.shared_properties);
+        builder
+    }
+
+    /// Asserts that an encoding of the value matches the expected encoding.
+    pub fn assert_encoded(&self, actual: &[u8]) {
+        assert_eq!(
+            self.expected,
+            actual,
+            "encoding mismatch for {}",
+            self.path.display(),
+        );
+    }
+
+    /// Asserts that a decoded value matches the test case's value.
+    pub fn assert_decoded(&self, actual: &T)
+    where
+        T: PartialEq + Debug,
+    {
+        assert_eq!(
+            self.value,
+            *actual,
+            "decoding mismatch for {}",
+            self.path.display(),
+        );
+    }
+}
+
+impl<T> TestCase<T>
+where
+    T: Serialize,
+{
+    /// Encodes the value with this crate's serializer, using the test case's encoding options.
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let mut serializer = self.serializer_builder().build(vec![]);
+        self.value.serialize(&mut serializer)?;
+        if self.write_end_marker {
+            serializer.end()?;
+        }
+        Ok(serializer.into_inner())
+    }
+}
+
+impl<T> TestCase<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    /// Runs the test case against this crate.
+    ///
+    /// The value is encoded and compared with the expected encoding, and the expected encoding is decoded from a slice,
+    /// a mutable slice, and a reader and compared with the value.
+    pub fn run(&self) {
+        let actual = self
+            .encode()
+            .unwrap_or_else(|e| panic!("error encoding {}: {}", self.path.display(), e));
+        self.assert_encoded(&actual);
+
+        let actual = crate::from_slice::<T>(&self.expected);
+        self.assert_decoded(&self.unwrap_decoded(actual));
+
+        let actual = crate::from_reader::<T, _>(&*self.expected);
+        self.assert_decoded(&self.unwrap_decoded(actual));
+
+        let mut buf = self.expected.clone();
+        let mut de = Deserializer::from_mut_slice(&mut buf);
+        let actual = T::deserialize(&mut de).and_then(|v| de.end().map(|()| v));
+        self.assert_decoded(&self.unwrap_decoded(actual));
+    }
+
+    fn unwrap_decoded(&self, r: Result<T, Error>) -> T {
+        r.unwrap_or_else(|e| panic!("error decoding {}: {}", self.path.display(), e))
+    }
+}
+
+struct TestCaseFile<T> {
+    raw_binary: bool,
+    shared_strings: bool,
+    shared_properties: bool,
+    write_end_marker: bool,
+    value: Option<T>,
+}
+
+impl<'de, T> Deserialize<'de> for TestCaseFile<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(TestCaseFileVisitor(PhantomData))
+    }
+}
+
+struct TestCaseFileVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for TestCaseFileVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = TestCaseFile<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a test case")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut file = TestCaseFile {
+            raw_binary: false,
+            shared_strings: false,
+            shared_properties: false,
+            write_end_marker: false,
+            value: None,
+        };
+
+        while let Some(key) = map.next_key::<String>()? {
+            match &*key {
+                "rawBinary" => file.raw_binary = map.next_value()?,
+                "sharedStrings" => file.shared_strings = map.next_value()?,
+                "sharedProperties" => file.shared_properties = map.next_value()?,
+                "writeEndMarker" => file.write_end_marker = map.next_value()?,
+                "value" => file.value = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+/// A binary value which is represented in JSON as a base64 string.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Base64Binary(pub Vec<u8>);
+
+impl Serialize for Base64Binary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Base64Binary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            encoding::decode_base64(&s)
+                .map(Base64Binary)
+                .ok_or_else(|| D::Error::custom("invalid base64"))
+        } else {
+            ByteBuf::deserialize(deserializer).map(|v| Base64Binary(v.into_vec()))
+        }
+    }
+}
+
+/// A big integer value which is represented in JSON as a number.
+///
+/// The value must fit in an `i128`, and is stored in the minimal number of bytes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TextBigInteger(pub BigInteger);
+
+impl Serialize for TextBigInteger {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextBigInteger {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            i128::deserialize(deserializer).map(|v| TextBigInteger(BigInteger::from(v)))
+        } else {
+            BigInteger::deserialize(deserializer).map(TextBigInteger)
+        }
+    }
+}
+
+/// A big decimal value which is represented in JSON as either a number or a string.
+///
+/// Numbers are parsed as `f32`s, while strings such as `"1.50"` or `"15E-1"` preserve the exact scale. The unscaled
+/// value must fit in an `i128`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TextBigDecimal(pub BigDecimal);
+
+impl Serialize for TextBigDecimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextBigDecimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TextBigDecimalVisitor)
+        } else {
+            BigDecimal::deserialize(deserializer).map(TextBigDecimal)
+        }
+    }
+}
+
+struct TextBigDecimalVisitor;
+
+impl<'de> Visitor<'de> for TextBigDecimalVisitor {
+    type Value = TextBigDecimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a number or decimal string")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_str(&(v as f32).to_string())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_str(&(v as f32).to_string())
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // Jackson creates the BigDecimal from the text of the JSON number, so this uses the shortest decimal form of the
+        // value rather than the exact expansion produced by BigDecimal::from_f64, which would give 0.1 a scale of 55
+        self.visit_str(&(v as f32).to_string())
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let invalid = || E::invalid_value(de::Unexpected::Str(v), &self);
+
+        let (mantissa, exponent) = match v.find('E') {
+            Some(idx) => (
+                &v[..idx],
+                v[idx + 1..].parse::<i32>().map_err(|_| invalid())?,
+            ),
+            None => (v, 0),
+        };
+        let value = mantissa
+            .replace('.', "")
+            .parse::<i128>()
+            .map_err(|_| invalid())?;
+        let scale = match mantissa.find('.') {
+            Some(idx) => mantissa.len() - idx - 1,
+            None => 0,
+        };
+        let scale = i32::try_from(scale)
+            .ok()
+            .and_then(|s| s.checked_sub(exponent))
+            .ok_or_else(invalid)?;
+
+        let value = TextBigInteger::deserialize(value.into_deserializer())?.0;
+        Ok(TextBigDecimal(BigDecimal::new(value, scale)))
+    }
+}
@@ -0,0 +1,365 @@
+use crate::de::string_cache::StringCache;
+use crate::value::json::{write_big_decimal, write_big_integer};
+use crate::value::{BigDecimal, BigInteger};
+use crate::Error;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::str;
+
+/// The number of token bytes shown in each line of the dump.
+const MAX_BYTES: usize = 8;
+
+enum Container {
+    Array,
+    Object { key: bool },
+}
+
+/// Writes an annotated listing of the tokens of a Smile stream.
+pub(crate) struct TokenDump<'a, W> {
+    buf: &'a [u8],
+    pos: usize,
+    out: W,
+    shared_strings: Option<StringCache<'a>>,
+    shared_properties: Option<StringCache<'a>>,
+    stack: Vec<Container>,
+}
+
+impl<'a, W> TokenDump<'a, W>
+where
+    W: Write,
+{
+    pub(crate) fn new(buf: &'a [u8], out: W) -> Self {
+        TokenDump {
+            buf,
+            pos: 0,
+            out,
+            shared_strings: None,
+            shared_properties: None,
+            stack: vec![],
+        }
+    }
+
+    pub(crate) fn run(&mut self) -> Result<(), Error> {
+        self.header()?;
+
+        loop {
+            match self.stack.last_mut() {
+                None => match self.buf.get(self.pos) {
+                    None => return Ok(()),
+                    Some(0xff) => {
+                        let start = self.pos;
+                        self.pos += 1;
+                        self.line(start, 0, "end of stream")?;
+                        if self.pos != self.buf.len() {
+                            return Err(Error::trailing_data());
+                        }
+                        return Ok(());
+                    }
+                    Some(_) => self.value()?,
+                },
+                Some(Container::Array) => match self.buf.get(self.pos) {
+                    None => return Err(Error::eof_while_parsing_array()),
+                    Some(0xf9) => {
+                        self.stack.pop();
+                        let start = self.pos;
+                        self.pos += 1;
+                        let depth = self.stack.len();
+                        self.line(start, depth, "end array")?;
+                    }
+                    Some(_) => self.value()?,
+                },
+                Some(Container::Object { key }) => {
+                    let is_key = *key;
+                    *key = !is_key;
+                    match self.buf.get(self.pos) {
+                        None => return Err(Error::eof_while_parsing_map()),
+                        Some(0xfb) if is_key => {
+                            self.stack.pop();
+                            let start = self.pos;
+                            self.pos += 1;
+                            let depth = self.stack.len();
+                            self.line(start, depth, "end object")?;
+                        }
+                        Some(_) if is_key => self.key()?,
+                        Some(_) => self.value()?,
+                    }
+                }
+            }
+        }
+    }
+
+    fn header(&mut self) -> Result<(), Error> {
+        let header = self
+            .take(4)
+            .map_err(|_| Error::eof_while_parsing_header())?;
+        if !header.starts_with(b":)\n") {
+            return Err(Error::invalid_header());
+        }
+
+        let info = header[3];
+        if info & 0xf0 != 0 {
+            return Err(Error::unsupported_version());
+        }
+
+        let mut flags = vec![];
+        if info & 0x04 != 0 {
+            flags.push("raw binary");
+        }
+        if info & 0x02 != 0 {
+            flags.push("shared strings");
+            self.shared_strings = Some(StringCache::new());
+        }
+        if info & 0x01 != 0 {
+            flags.push("shared properties");
+            self.shared_properties = Some(StringCache::new());
+        }
+
+        let mut desc = "header".to_string();
+        if !flags.is_empty() {
+            write!(desc, " ({})", flags.join(", ")).unwrap();
+        }
+        self.line(0, 0, &desc)
+    }
+
+    fn value(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let depth = self.stack.len();
+        let desc = match self.next_token()? {
+            0x00 => return Err(Error::reserved_token(0x00, start as u64)),
+            token @ 0x01..=0x1f => self.shared_string(token as u16 - 1)?,
+            0x20 => "string \"\"".to_string(),
+            0x21 => "null".to_string(),
+            0x22 => "false".to_string(),
+            0x23 => "true".to_string(),
+            0x24 => format!("int {}", zigzag_i64(self.vint(5)?)),
+            0x25 => format!("long {}", zigzag_i64(self.vint(10)?)),
+            0x26 => {
+                let mut desc = "big integer ".to_string();
+                write_big_integer(&mut desc, &BigInteger::from_be_bytes(self.seven_bit()?));
+                desc
+            }
+            0x28 => {
+                let raw = self.take(5)?.iter().fold(0, |v, b| v << 7 | *b as u32);
+                format!("float {:?}", f32::from_bits(raw))
+            }
+            0x29 => {
+                let raw = self.take(10)?.iter().fold(0, |v, b| v << 7 | *b as u64);
+                format!("double {:?}", f64::from_bits(raw))
+            }
+            0x2a => {
+                let scale = i32::try_from(zigzag_i64(self.vint(5)?))
+                    .map_err(|_| Error::scale_out_of_range())?;
+                let value = BigInteger::from_be_bytes(self.seven_bit()?);
+                let mut desc = "big decimal ".to_string();
+                write_big_decimal(&mut desc, &BigDecimal::new(value, scale));
+                desc
+            }
+            b':' if self.at_header() => return Err(Error::unexpected_document_boundary()),
+            token @ (0x27 | 0x2b..=0x3f) => return Err(Error::reserved_token(token, start as u64)),
+            token @ 0x40..=0x5f => self.short_string(token as usize - (0x40 - 1))?,
+            token @ 0x60..=0x7f => self.short_string(token as usize - (0x60 - 33))?,
+            token @ 0x80..=0x9f => self.short_string(token as usize - (0x80 - 2))?,
+            token @ 0xa0..=0xbf => self.short_string(token as usize - (0xa0 - 34))?,
+            token @ 0xc0..=0xdf => format!("int {}", zigzag_i64(token as u64 - 0xc0)),
+            0xe0 | 0xe4 => format!("string {:?}", self.long_str()?),
+            0xe8 => format!("binary ({} bytes)", self.seven_bit()?.len()),
+            token @ 0xec..=0xef => {
+                let reference = (token as u16 - 0xec) << 8 | self.next_token()? as u16;
+                self.shared_string(reference)?
+            }
+            0xf8 => {
+                self.stack.push(Container::Array);
+                "start array".to_string()
+            }
+            0xfa => {
+                self.stack.push(Container::Object { key: true });
+                "start object".to_string()
+            }
+            0xfd => {
+                let len =
+                    usize::try_from(self.vint(10)?).map_err(|_| Error::buffer_length_overflow())?;
+                self.take(len)?;
+                format!("raw binary ({} bytes)", len)
+            }
+            token @ (0xf9 | 0xfb | 0xfc) => {
+                return Err(Error::unexpected_token(token, start as u64))
+            }
+            token @ (0xe1..=0xe3 | 0xe5..=0xe7 | 0xe9..=0xeb | 0xf0..=0xf7 | 0xfe) => {
+                return Err(Error::reserved_token(token, start as u64))
+            }
+            0xff => return Err(Error::eof_while_parsing_value()),
+        };
+
+        self.line(start, depth, &desc)
+    }
+
+    fn key(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let depth = self.stack.len();
+        let desc = match self.next_token()? {
+            0x20 => "key \"\"".to_string(),
+            token @ 0x30..=0x33 => {
+                let reference = (token as u16 - 0x30) << 8 | self.next_token()? as u16;
+                self.shared_key(reference)?
+            }
+            0x34 => {
+                let s = self.long_str()?;
+                self.intern_key(s);
+                format!("key {:?}", s)
+            }
+            token @ 0x40..=0x7f => self.shared_key(token as u16 - 0x40)?,
+            token @ 0x80..=0xbf => self.short_key(token as usize - (0x80 - 1))?,
+            token @ 0xc0..=0xf7 => self.short_key(token as usize - (0xc0 - 2))?,
+            b':' if self.at_header() => return Err(Error::unexpected_document_boundary()),
+            token @ (0x3a | 0xfb) => return Err(Error::unexpected_token(token, start as u64)),
+            token => return Err(Error::reserved_token(token, start as u64)),
+        };
+
+        self.line(start, depth, &desc)
+    }
+
+    /// Returns `true` if the rest of a Smile header follows a `:` token that was just read.
+    fn at_header(&self) -> bool {
+        self.buf[self.pos..].starts_with(b")\n")
+    }
+
+    fn short_string(&mut self, len: usize) -> Result<String, Error> {
+        let s = str::from_utf8(self.take(len)?).map_err(|_| Error::invalid_utf8())?;
+        if let Some(shared_strings) = &mut self.shared_strings {
+            if s.len() <= 64 {
+                shared_strings.intern(Cow::Borrowed(s));
+            }
+        }
+        Ok(format!("string {:?}", s))
+    }
+
+    fn shared_string(&mut self, reference: u16) -> Result<String, Error> {
+        let s = self
+            .shared_strings
+            .as_ref()
+            .and_then(|c| c.get(reference))
+            .ok_or_else(Error::invalid_string_reference)?;
+        Ok(format!("shared string #{} {:?}", reference, s))
+    }
+
+    fn short_key(&mut self, len: usize) -> Result<String, Error> {
+        let s = str::from_utf8(self.take(len)?).map_err(|_| Error::invalid_utf8())?;
+        self.intern_key(s);
+        Ok(format!("key {:?}", s))
+    }
+
+    fn shared_key(&mut self, reference: u16) -> Result<String, Error> {
+        let s = self
+            .shared_properties
+            .as_ref()
+            .and_then(|c| c.get(reference))
+            .ok_or_else(Error::invalid_string_reference)?;
+        Ok(format!("shared key #{} {:?}", reference, s))
+    }
+
+    fn intern_key(&mut self, s: &'a str) {
+        if let Some(shared_properties) = &mut self.shared_properties {
+            if s.len() <= 64 {
+                shared_properties.intern(Cow::Borrowed(s));
+            }
+        }
+    }
+
+    fn long_str(&mut self) -> Result<&'a str, Error> {
+        let len = memchr::memchr(0xfc, &self.buf[self.pos..])
+            .ok_or_else(Error::eof_while_parsing_value)?;
+        let s = str::from_utf8(self.take(len)?).map_err(|_| Error::invalid_utf8())?;
+        self.pos += 1;
+        Ok(s)
+    }
+
+    fn next_token(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let buf = self
+            .buf
+            .get(self.pos..)
+            .and_then(|b| b.get(..len))
+            .ok_or_else(Error::eof_while_parsing_value)?;
+        self.pos += len;
+        Ok(buf)
+    }
+
+    fn vint(&mut self, byte_limit: usize) -> Result<u64, Error> {
+        let mut value = 0;
+        for _ in 0..byte_limit {
+            let byte = self.next_token()?;
+            let end = byte & 0x80 != 0;
+
+            let shift = if end { 6 } else { 7 };
+            value = value << shift | byte as u64 & 0x7f;
+
+            if end {
+                return Ok(value);
+            }
+        }
+
+        Err(Error::unterminated_vint())
+    }
+
+    fn seven_bit(&mut self) -> Result<Vec<u8>, Error> {
+        let raw_len = self.vint(10)?;
+        let chunks = raw_len / 7;
+        let remainder = raw_len % 7;
+        let encoded_remainder = if remainder == 0 { 0 } else { remainder + 1 };
+
+        let encoded_len = chunks
+            .checked_mul(8)
+            .and_then(|v| v.checked_add(encoded_remainder))
+            .and_then(|v| usize::try_from(v).ok())
+            .ok_or_else(Error::buffer_length_overflow)?;
+        let encoded = self.take(encoded_len)?;
+
+        // each group of 8 encoded bytes holds 7 raw bytes, and the last byte of a final partial group holds only the
+        // remaining low bits
+        let mut out = Vec::with_capacity(raw_len as usize);
+        for group in encoded.chunks(8) {
+            let (last, rest) = group.split_last().unwrap();
+            let last_bits = rest.len();
+            let value = rest.iter().fold(0u64, |v, b| v << 7 | (*b & 0x7f) as u64);
+            let value = value << last_bits | (*last & ((1 << last_bits) - 1) as u8) as u64;
+            for i in (0..rest.len()).rev() {
+                out.push((value >> (i * 8)) as u8);
+            }
+        }
+
+        Ok(out)
+    }
+
+    fn line(&mut self, start: usize, depth: usize, desc: &str) -> Result<(), Error> {
+        let bytes = &self.buf[start..self.pos];
+
+        let mut hex = String::new();
+        for b in bytes.iter().take(MAX_BYTES) {
+            write!(hex, "{:02x} ", b).unwrap();
+        }
+        if bytes.len() > MAX_BYTES {
+            hex.push_str("..");
+        }
+
+        writeln!(
+            self.out,
+            "{:08x}  {:<width$}  {:indent$}{}",
+            start,
+            hex.trim_end(),
+            "",
+            desc,
+            width = MAX_BYTES * 3 + 2,
+            indent = depth * 2,
+        )
+        .map_err(Error::io)
+    }
+}
+
+fn zigzag_i64(v: u64) -> i64 {
+    ((v >> 1) as i64) ^ (-((v & 1) as i64))
+}
@@ -0,0 +1,242 @@
+//! Helpers for command line tools which inspect and convert Smile files.
+//!
+//! Requires the `tools` Cargo feature.
+use crate::de::Deserializer;
+use crate::ser::Builder;
+use crate::tools::dump::TokenDump;
+use crate::value::{JsonBinary, Value};
+use crate::{Error, Serializer};
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+mod dump;
+
+/// A data format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Smile.
+    Smile,
+    /// JSON.
+    Json,
+}
+
+/// Determines the format of data from its first bytes.
+///
+/// Data starting with the Smile header `:)\n` is considered Smile, and everything else is considered JSON.
+pub fn sniff(prefix: &[u8]) -> Format {
+    if prefix.starts_with(b":)\n") {
+        Format::Smile
+    } else {
+        Format::Json
+    }
+}
+
+/// The direction of a conversion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Converts Smile to JSON.
+    SmileToJson,
+    /// Converts JSON to Smile.
+    JsonToSmile,
+    /// Converts Smile to JSON or JSON to Smile based on the [`sniff`]ed format of the input.
+    #[default]
+    Auto,
+}
+
+/// Options for [`convert_file`].
+#[derive(Clone, Debug)]
+pub struct ConvertOptions {
+    serializer: Builder,
+    write_end_marker: bool,
+    json_binary: JsonBinary,
+    pretty: bool,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        ConvertOptions::new()
+    }
+}
+
+impl ConvertOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        ConvertOptions {
+            serializer: Serializer::builder(),
+            write_end_marker: false,
+            json_binary: JsonBinary::Base64,
+            pretty: false,
+        }
+    }
+
+    /// Sets the configuration used to write Smile output.
+    ///
+    /// Defaults to [`Builder::jackson_defaults`].
+    pub fn serializer(&mut self, serializer: Builder) -> &mut Self {
+        self.serializer = serializer;
+        self
+    }
+
+    /// If enabled, Smile output is terminated by the end-of-stream token.
+    ///
+    /// Defaults to `false`.
+    pub fn write_end_marker(&mut self, write_end_marker: bool) -> &mut Self {
+        self.write_end_marker = write_end_marker;
+        self
+    }
+
+    /// Sets the representation of binary data in JSON output.
+    ///
+    /// Defaults to [`JsonBinary::Base64`].
+    pub fn json_binary(&mut self, json_binary: JsonBinary) -> &mut Self {
+        self.json_binary = json_binary;
+        self
+    }
+
+    /// If enabled, JSON output is indented.
+    ///
+    /// Defaults to `false`.
+    pub fn pretty(&mut self, pretty: bool) -> &mut Self {
+        self.pretty = pretty;
+        self
+    }
+}
+
+/// Statistics about a conversion performed by [`convert_file`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    direction: Direction,
+    values: u64,
+    input_bytes: u64,
+    output_bytes: u64,
+}
+
+impl Stats {
+    /// Returns the direction of the conversion.
+    ///
+    /// This is never [`Direction::Auto`].
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the number of top-level values converted.
+    pub fn values(&self) -> u64 {
+        self.values
+    }
+
+    /// Returns the size of the input file in bytes.
+    pub fn input_bytes(&self) -> u64 {
+        self.input_bytes
+    }
+
+    /// Returns the size of the output file in bytes.
+    pub fn output_bytes(&self) -> u64 {
+        self.output_bytes
+    }
+}
+
+/// Converts a file between Smile and JSON.
+///
+/// Both formats may contain a sequence of values. JSON output contains one value per line, and Smile output contains
+/// all values in a single stream. Values are converted as described in [`Value::to_json_value`] and the
+/// `From<serde_json::Value>` implementation for [`Value`].
+pub fn convert_file(
+    input: &Path,
+    output: &Path,
+    direction: Direction,
+    opts: &ConvertOptions,
+) -> Result<Stats, Error> {
+    let buf = fs::read(input).map_err(Error::io)?;
+
+    let direction = match direction {
+        Direction::Auto => match sniff(&buf) {
+            Format::Smile => Direction::SmileToJson,
+            Format::Json => Direction::JsonToSmile,
+        },
+        direction => direction,
+    };
+
+    let mut out = vec![];
+    let mut values = 0;
+    match direction {
+        Direction::SmileToJson => {
+            for value in Deserializer::from_slice(&buf).into_iter::<Value>() {
+                write_json(&mut out, &value?, opts)?;
+                values += 1;
+            }
+        }
+        Direction::JsonToSmile | Direction::Auto => {
+            let mut serializer = opts.serializer.build(&mut out);
+            for value in serde_json::Deserializer::from_slice(&buf).into_iter::<serde_json::Value>()
+            {
+                let value = Value::from(value.map_err(Error::message)?);
+                value.serialize(&mut serializer)?;
+                values += 1;
+            }
+            if opts.write_end_marker {
+                serializer.end()?;
+            } else {
+                serializer.write_header()?;
+            }
+        }
+    }
+
+    fs::write(output, &out).map_err(Error::io)?;
+
+    Ok(Stats {
+        direction,
+        values,
+        input_bytes: buf.len() as u64,
+        output_bytes: out.len() as u64,
+    })
+}
+
+fn write_json(out: &mut Vec<u8>, value: &Value, opts: &ConvertOptions) -> Result<(), Error> {
+    let value = value.to_json_value(opts.json_binary);
+    if opts.pretty {
+        serde_json::to_writer_pretty(&mut *out, &value)
+    } else {
+        serde_json::to_writer(&mut *out, &value)
+    }
+    .map_err(Error::message)?;
+    out.push(b'\n');
+    Ok(())
+}
+
+/// The output format of [`dump`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DumpFormat {
+    /// An annotated listing of the tokens in the stream.
+    ///
+    /// Each line contains the offset of a token, its encoded bytes, and a description indented by its nesting depth.
+    Tokens,
+    /// Each value in the stream as compact JSON, one per line.
+    ///
+    /// Values are rendered by [`Value::to_json_string`].
+    Json,
+}
+
+/// Writes a human-readable rendering of a Smile stream.
+///
+/// If the stream is malformed, everything up to the error is written before it is returned.
+pub fn dump<R, W>(mut input: R, mut out: W, format: DumpFormat) -> Result<(), Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    match format {
+        DumpFormat::Tokens => {
+            let mut buf = vec![];
+            input.read_to_end(&mut buf).map_err(Error::io)?;
+            TokenDump::new(&buf, out).run()
+        }
+        DumpFormat::Json => {
+            for value in Deserializer::from_reader(input).into_iter::<Value>() {
+                writeln!(out, "{}", value?.to_json_string()).map_err(Error::io)?;
+            }
+            Ok(())
+        }
+    }
+}
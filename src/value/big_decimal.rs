@@ -1,15 +1,23 @@
 use crate::value::BigInteger;
+use crate::Error;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_bytes::{ByteBuf, Bytes};
+use std::cmp::Ordering;
 use std::fmt;
 
 /// A parsed Smile `BigDecimal` value.
 ///
 /// This is a "magic" type which corresponds to the `BigDecimal` type defined in Smile. It is intended to be used only
 /// for serialization and deserialization, and it intentionally does *not* implement any kind of traditional big decimal
-/// math API.
+/// math API. With the `bigdecimal` Cargo feature enabled, it can be converted to and from `bigdecimal::BigDecimal`
+/// instead, and with the `rust_decimal` Cargo feature enabled, to and from `rust_decimal::Decimal`.
+///
+/// Equality compares unscaled values and scales, so `1.50` and `1.5` are not equal even though they represent the same
+/// number; use [`BigDecimal::numeric_eq`] to compare values numerically, or [`BigDecimal::normalized`] to produce a
+/// representation suitable for hashing. Values are ordered numerically, and two representations of the same number are
+/// ordered by scale.
 ///
 /// It should only be used with the `serde-smile` serializers and deserializers; it will produce a nonsensical encoding
 /// when used with other `serde` libraries.
@@ -49,6 +57,235 @@ impl BigDecimal {
     pub fn scale(&self) -> i32 {
         self.scale
     }
+
+    /// Returns the `BigDecimal`'s unscaled value as an `i128` along with its scale.
+    ///
+    /// Representations with redundant sign extension bytes are accepted. An error is returned if the unscaled value
+    /// doesn't fit in an `i128`.
+    pub fn to_parts(&self) -> Result<(i128, i32), Error> {
+        let value = self
+            .value
+            .to_i128()
+            .ok_or_else(|| Error::value_out_of_range("i128"))?;
+        Ok((value, self.scale))
+    }
+
+    /// Converts the `BigDecimal` to the nearest `f64`.
+    ///
+    /// The conversion is lossy, but correctly rounded: the result is the `f64` closest to the exact value, with ties
+    /// rounded to even. Values too large in magnitude to be represented become infinite, and values too small become
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::{BigDecimal, BigInteger};
+    ///
+    /// let v = BigDecimal::new(BigInteger::from(-25i64), 1);
+    /// assert_eq!(v.to_f64(), -2.5);
+    ///
+    /// let v = BigDecimal::new(BigInteger::from(1i64), -400);
+    /// assert_eq!(v.to_f64(), f64::INFINITY);
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        let (negative, digits) = self.value.to_decimal_parts();
+        let sign = if negative { "-" } else { "" };
+        // the standard library's parser rounds correctly regardless of the number of digits, and saturates exponents
+        // outside of the range of f64
+        let s = format!("{}{}e{}", sign, digits, -i64::from(self.scale));
+        s.parse().unwrap()
+    }
+
+    /// Creates a `BigDecimal` holding the exact value of an `f64`.
+    ///
+    /// Every finite `f64` has a finite decimal expansion, so no rounding is performed: `0.1` becomes
+    /// `0.1000000000000000055511151231257827021181583404541015625`, matching Java's `new BigDecimal(double)`. The scale
+    /// is the number of digits after the decimal point, or 0 for integers, so it is at most 1074. Returns `None` for
+    /// infinite and NaN values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::{BigDecimal, BigInteger};
+    ///
+    /// let v = BigDecimal::from_f64(-2.5).unwrap();
+    /// assert_eq!(v, BigDecimal::new(BigInteger::from(-25i64), 1));
+    /// assert_eq!(v.to_f64(), -2.5);
+    ///
+    /// assert_eq!(BigDecimal::from_f64(f64::NAN), None);
+    /// ```
+    pub fn from_f64(v: f64) -> Option<BigDecimal> {
+        if !v.is_finite() {
+            return None;
+        }
+
+        let bits = v.to_bits();
+        let negative = bits >> 63 != 0;
+        let biased_exponent = (bits >> 52 & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+
+        // v = mantissa * 2^exponent
+        let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+            // subnormal
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, biased_exponent - 1075)
+        };
+        if mantissa == 0 {
+            return Some(BigDecimal::new(BigInteger::from(0i64), 0));
+        }
+
+        // an odd mantissa times a power of 5 isn't divisible by 10, so the scale will be minimal
+        if exponent < 0 {
+            let zeros = mantissa.trailing_zeros().min(exponent.unsigned_abs());
+            mantissa >>= zeros;
+            exponent += zeros as i32;
+        }
+
+        let mut limbs = vec![mantissa as u32, (mantissa >> 32) as u32];
+        let scale = if exponent >= 0 {
+            // v = mantissa * 2^exponent
+            let mut remaining = exponent as u32;
+            while remaining > 0 {
+                let shift = remaining.min(31);
+                mul_small(&mut limbs, 1 << shift);
+                remaining -= shift;
+            }
+            0
+        } else {
+            // v = mantissa * 2^exponent = mantissa * 5^-exponent * 10^exponent
+            let mut remaining = exponent.unsigned_abs();
+            while remaining > 0 {
+                // 5^13 is the largest power of 5 which fits in a u32
+                let power = remaining.min(13);
+                mul_small(&mut limbs, 5u32.pow(power));
+                remaining -= power;
+            }
+            -exponent
+        };
+
+        let magnitude = limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect::<Vec<_>>();
+        Some(BigDecimal::new(
+            BigInteger::from_magnitude(negative, &magnitude),
+            scale,
+        ))
+    }
+
+    /// Returns an equivalent `BigDecimal` with trailing zeros removed from its unscaled value and its scale reduced to
+    /// match.
+    ///
+    /// Two values represent the same number exactly when their normalized forms are numerically equal. Zero is
+    /// normalized to a scale of 0, and the scale is never reduced below `i32::MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::{BigDecimal, BigInteger};
+    ///
+    /// let a = BigDecimal::new(BigInteger::from(150i64), 2);
+    /// let b = a.normalized();
+    /// assert_eq!(b.unscaled_value(), &BigInteger::from(15i64));
+    /// assert_eq!(b.scale(), 1);
+    /// ```
+    pub fn normalized(&self) -> BigDecimal {
+        let max = (i64::from(self.scale) - i64::from(i32::MIN)) as u64;
+        let (value, stripped) = self.value.strip_trailing_zeros(max);
+        let scale = if value.as_be_bytes() == [0] {
+            0
+        } else {
+            (i64::from(self.scale) - stripped as i64) as i32
+        };
+        BigDecimal { value, scale }
+    }
+
+    /// Determines if two values represent the same number, ignoring their scales.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::{BigDecimal, BigInteger};
+    ///
+    /// let a = BigDecimal::new(BigInteger::from(150i64), 2);
+    /// let b = BigDecimal::new(BigInteger::from(15i64), 1);
+    /// assert_ne!(a, b);
+    /// assert!(a.numeric_eq(&b));
+    /// ```
+    pub fn numeric_eq(&self, other: &BigDecimal) -> bool {
+        self.numeric_cmp(other) == Ordering::Equal
+    }
+
+    /// Compares two values numerically, ignoring their scales.
+    fn numeric_cmp(&self, other: &BigDecimal) -> Ordering {
+        let (a_negative, a_digits) = self.value.to_decimal_parts();
+        let (b_negative, b_digits) = other.value.to_decimal_parts();
+
+        let a_sign = sign(a_negative, &a_digits);
+        let b_sign = sign(b_negative, &b_digits);
+        match a_sign.cmp(&b_sign) {
+            Ordering::Equal if a_sign != 0 => {}
+            ordering => return ordering,
+        }
+
+        // aligning the scales of the unscaled values directly could require multiplying by up to 10^(2^32), so we
+        // instead compare the position of the most significant digit and then the digits themselves
+        let a_exponent = a_digits.len() as i64 - 1 - i64::from(self.scale);
+        let b_exponent = b_digits.len() as i64 - 1 - i64::from(other.scale);
+        let ordering = a_exponent.cmp(&b_exponent).then_with(|| {
+            a_digits
+                .trim_end_matches('0')
+                .cmp(b_digits.trim_end_matches('0'))
+        });
+
+        if a_sign < 0 {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Multiplies a little-endian sequence of 32 bit limbs by a small factor.
+fn mul_small(limbs: &mut Vec<u32>, factor: u32) {
+    let mut carry = 0;
+    for limb in limbs.iter_mut() {
+        let v = u64::from(*limb) * u64::from(factor) + carry;
+        *limb = v as u32;
+        carry = v >> 32;
+    }
+    if carry != 0 {
+        limbs.push(carry as u32);
+    }
+}
+
+fn sign(negative: bool, digits: &str) -> i8 {
+    if negative {
+        -1
+    } else if digits == "0" {
+        0
+    } else {
+        1
+    }
+}
+
+impl Ord for BigDecimal {
+    /// Orders values numerically, and two representations of the same number by scale and then by the representation
+    /// of their unscaled values.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numeric_cmp(other)
+            .then_with(|| self.scale.cmp(&other.scale))
+            .then_with(|| self.value.cmp(&other.value))
+    }
+}
+
+impl PartialOrd for BigDecimal {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Serialize for BigDecimal {
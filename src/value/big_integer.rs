@@ -1,14 +1,28 @@
+use crate::Error;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_bytes::{ByteBuf, Bytes};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
 
 /// A parsed Smile `BigInteger` value.
 ///
 /// This is a "magic" type which corresponds to the `BigInteger` type defined in Smile. It is intended to be used only
 /// for serialization and deserialization, and it intentionally does *not* implement any kind of traditional big integer
-/// math API.
+/// math API. Values in range can be converted to native integers with methods like [`BigInteger::to_i64`] or with
+/// `TryFrom`, and values can be formatted as and parsed from decimal strings with `Display` and `FromStr`. With the `num-bigint` Cargo feature enabled, it can be converted to and from `num_bigint::BigInt` and
+/// `num_bigint::BigUint` instead.
+///
+/// Equality compares representations, so values with and without redundant sign extension bytes are not equal even
+/// if they represent the same number; use [`BigInteger::numeric_eq`] to compare values numerically or
+/// [`BigInteger::normalize`] to remove them. Values are ordered
+/// numerically, and two representations of the same number are ordered by length.
+///
+/// The deserializer reads an empty payload as zero, represented by a single zero byte. Non-minimal and empty payloads
+/// can instead be rejected with [`Builder::strict_big_integers`](crate::de::Builder::strict_big_integers).
 ///
 /// It should only be used with the `serde-smile` serializers and deserializers; it will produce a nonsensical encoding
 /// when used with other `serde` libraries.
@@ -20,6 +34,12 @@ impl BigInteger {
     pub(crate) const FIELD_NAME: &'static str = "\0SmileBigIntegerField";
 
     /// Creates a `BigInteger` from its representation as a byte buffer in two's complement big-endian.
+    ///
+    /// This is the representation produced by Java's `BigInteger.toByteArray`. The buffer is used as-is, so values
+    /// built by hand should not include redundant sign extension bytes if they are expected to match Jackson's
+    /// encoding; [`BigInteger::normalize`] removes them, and the `From` implementations for native integer types always
+    /// produce the shortest representation.
+    #[doc(alias = "from_signed_bytes_be")]
     #[inline]
     pub fn from_be_bytes(buf: Vec<u8>) -> Self {
         BigInteger(buf)
@@ -36,6 +56,369 @@ impl BigInteger {
     pub fn into_be_bytes(self) -> Vec<u8> {
         self.0
     }
+
+    /// Determines if two values represent the same number, ignoring redundant sign extension bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::BigInteger;
+    ///
+    /// let a = BigInteger::from_be_bytes(vec![0x01]);
+    /// let b = BigInteger::from_be_bytes(vec![0x00, 0x00, 0x01]);
+    /// assert_ne!(a, b);
+    /// assert!(a.numeric_eq(&b));
+    /// ```
+    pub fn numeric_eq(&self, other: &BigInteger) -> bool {
+        self.numeric_cmp(other) == Ordering::Equal
+    }
+
+    /// Compares two values numerically, ignoring redundant sign extension bytes.
+    fn numeric_cmp(&self, other: &BigInteger) -> Ordering {
+        let negative = self.is_negative();
+        match negative.cmp(&other.is_negative()) {
+            Ordering::Equal => {}
+            ordering => return ordering.reverse(),
+        }
+
+        // sign extend the shorter value; the two's complement representations of numbers with the same sign then compare
+        // like unsigned integers
+        let (a, b) = (&self.0, &other.0);
+        let extension = if negative { 0xff } else { 0 };
+        let len = a.len().max(b.len());
+        let a_bytes = (0..len).map(|i| i.checked_sub(len - a.len()).map_or(extension, |i| a[i]));
+        let b_bytes = (0..len).map(|i| i.checked_sub(len - b.len()).map_or(extension, |i| b[i]));
+
+        a_bytes.cmp(b_bytes)
+    }
+
+    /// Returns the sign of the value as its ordering relative to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::BigInteger;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!(BigInteger::from(-5i64).sign(), Ordering::Less);
+    /// assert_eq!(BigInteger::from(0i64).sign(), Ordering::Equal);
+    /// assert_eq!(BigInteger::from(5i64).sign(), Ordering::Greater);
+    /// ```
+    pub fn sign(&self) -> Ordering {
+        if self.is_negative() {
+            Ordering::Less
+        } else if self.is_zero() {
+            Ordering::Equal
+        } else {
+            Ordering::Greater
+        }
+    }
+
+    /// Determines if the value is zero.
+    ///
+    /// An empty buffer is treated as zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|b| *b == 0)
+    }
+
+    /// Determines if the value is less than zero.
+    pub fn is_negative(&self) -> bool {
+        matches!(self.0.first(), Some(b) if b & 0x80 != 0)
+    }
+
+    /// Removes redundant sign extension bytes from the value.
+    ///
+    /// The result is the shortest two's complement representation of the number, which is identical to the output of
+    /// Java's `BigInteger.toByteArray`. An empty buffer is normalized to a single zero byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::BigInteger;
+    ///
+    /// let mut v = BigInteger::from_be_bytes(vec![0x00, 0x00, 0x80]);
+    /// v.normalize();
+    /// assert_eq!(v.as_be_bytes(), [0x00, 0x80]);
+    /// ```
+    pub fn normalize(&mut self) {
+        if self.0.is_empty() {
+            self.0.push(0);
+            return;
+        }
+
+        let redundant = self.0.len() - strip_sign_extension(&self.0).len();
+        self.0.drain(..redundant);
+    }
+
+    /// Returns a copy of the value with redundant sign extension bytes removed.
+    ///
+    /// See [`BigInteger::normalize`] for details.
+    pub fn normalized(&self) -> BigInteger {
+        let mut v = self.clone();
+        v.normalize();
+        v
+    }
+
+    /// Returns the value as an `i64` if it is in range.
+    ///
+    /// Representations with redundant sign extension bytes are accepted.
+    #[inline]
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_native()
+    }
+
+    /// Returns the value as a `u64` if it is in range.
+    ///
+    /// Representations with redundant sign extension bytes are accepted.
+    #[inline]
+    pub fn to_u64(&self) -> Option<u64> {
+        self.to_native()
+    }
+
+    /// Returns the value as an `i128` if it is in range.
+    ///
+    /// Representations with redundant sign extension bytes are accepted.
+    #[inline]
+    pub fn to_i128(&self) -> Option<i128> {
+        self.to_native()
+    }
+
+    /// Returns the value as a `u128` if it is in range.
+    ///
+    /// Representations with redundant sign extension bytes are accepted.
+    #[inline]
+    pub fn to_u128(&self) -> Option<u128> {
+        self.to_native()
+    }
+
+    fn to_native<T>(&self) -> Option<T>
+    where
+        T: TryFrom<i128> + TryFrom<u128>,
+    {
+        match NativeInteger::from_be_bytes(strip_sign_extension(&self.0))? {
+            NativeInteger::I64(v) => T::try_from(i128::from(v)).ok(),
+            NativeInteger::U64(v) => T::try_from(u128::from(v)).ok(),
+            NativeInteger::I128(v) => T::try_from(v).ok(),
+            NativeInteger::U128(v) => T::try_from(v).ok(),
+        }
+    }
+
+    /// Returns the capacity of the buffer holding the value's representation.
+    pub(crate) fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the sign and the big-endian unsigned magnitude of the value.
+    fn to_magnitude(&self) -> (bool, Vec<u8>) {
+        let negative = self.is_negative();
+
+        let mut magnitude = self.0.clone();
+        if negative {
+            negate(&mut magnitude);
+        }
+
+        (negative, magnitude)
+    }
+
+    /// Creates a `BigInteger` from a sign and a big-endian unsigned magnitude, using the fewest bytes which represent
+    /// it.
+    pub(crate) fn from_magnitude(negative: bool, magnitude: &[u8]) -> Self {
+        // we need an extra byte for the sign bit
+        let mut buf = Vec::with_capacity(magnitude.len() + 1);
+        buf.push(0);
+        buf.extend_from_slice(magnitude);
+        if negative {
+            negate(&mut buf);
+        }
+        BigInteger(strip_sign_extension(&buf).to_vec())
+    }
+
+    /// Removes up to `max` trailing decimal zeros from the value, returning the result and the number of zeros
+    /// removed.
+    pub(crate) fn strip_trailing_zeros(&self, max: u64) -> (Self, u64) {
+        let (negative, mut magnitude) = self.to_magnitude();
+        let mut quotient = magnitude.clone();
+
+        let mut stripped = 0;
+        while stripped < max && magnitude.iter().any(|b| *b != 0) {
+            let mut remainder = 0;
+            for (q, b) in quotient.iter_mut().zip(&magnitude) {
+                let cur = remainder << 8 | u32::from(*b);
+                *q = (cur / 10) as u8;
+                remainder = cur % 10;
+            }
+            if remainder != 0 {
+                break;
+            }
+
+            magnitude.copy_from_slice(&quotient);
+            stripped += 1;
+        }
+
+        (BigInteger::from_magnitude(negative, &magnitude), stripped)
+    }
+
+    /// Returns the sign and the decimal digits of the magnitude of the value.
+    pub(crate) fn to_decimal_parts(&self) -> (bool, String) {
+        let (negative, mut magnitude) = self.to_magnitude();
+
+        // repeatedly divide the big-endian magnitude by 10^9, collecting the remainders from least significant
+        const BASE: u64 = 1_000_000_000;
+        let mut chunks = vec![];
+        let mut start = 0;
+        loop {
+            while start < magnitude.len() && magnitude[start] == 0 {
+                start += 1;
+            }
+            if start == magnitude.len() {
+                break;
+            }
+
+            let mut remainder = 0;
+            for b in &mut magnitude[start..] {
+                let cur = remainder << 8 | *b as u64;
+                *b = (cur / BASE) as u8;
+                remainder = cur % BASE;
+            }
+            chunks.push(remainder);
+        }
+
+        let mut digits = match chunks.pop() {
+            Some(chunk) => chunk.to_string(),
+            None => return (false, "0".to_string()),
+        };
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+
+        (negative, digits)
+    }
+}
+
+impl From<i64> for BigInteger {
+    #[inline]
+    fn from(v: i64) -> Self {
+        BigInteger::from(i128::from(v))
+    }
+}
+
+impl From<u64> for BigInteger {
+    #[inline]
+    fn from(v: u64) -> Self {
+        BigInteger::from(u128::from(v))
+    }
+}
+
+impl From<i128> for BigInteger {
+    /// Converts the value using the fewest bytes which represent it.
+    fn from(v: i128) -> Self {
+        BigInteger(strip_sign_extension(&v.to_be_bytes()).to_vec())
+    }
+}
+
+impl From<u128> for BigInteger {
+    /// Converts the value using the fewest bytes which represent it.
+    fn from(v: u128) -> Self {
+        // we need an extra byte for the sign bit
+        let mut buf = [0; 17];
+        buf[1..].copy_from_slice(&v.to_be_bytes());
+        BigInteger(strip_sign_extension(&buf).to_vec())
+    }
+}
+
+macro_rules! try_from_big_integer {
+    ($($t:ident => $method:ident),*) => {
+        $(
+            impl TryFrom<&BigInteger> for $t {
+                type Error = Error;
+
+                /// Converts values which fit in the target type.
+                ///
+                /// Representations with redundant sign extension bytes are accepted.
+                #[inline]
+                fn try_from(v: &BigInteger) -> Result<Self, Self::Error> {
+                    v.$method()
+                        .ok_or_else(|| Error::value_out_of_range(stringify!($t)))
+                }
+            }
+
+            impl TryFrom<BigInteger> for $t {
+                type Error = Error;
+
+                /// Converts values which fit in the target type.
+                ///
+                /// Representations with redundant sign extension bytes are accepted.
+                #[inline]
+                fn try_from(v: BigInteger) -> Result<Self, Self::Error> {
+                    $t::try_from(&v)
+                }
+            }
+        )*
+    };
+}
+
+try_from_big_integer!(i64 => to_i64, u64 => to_u64, i128 => to_i128, u128 => to_u128);
+
+impl Ord for BigInteger {
+    /// Orders values numerically, and two representations of the same number by length.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numeric_cmp(other)
+            .then_with(|| self.0.len().cmp(&other.0.len()))
+    }
+}
+
+impl PartialOrd for BigInteger {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInteger {
+    /// Formats the value as a signed decimal integer.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (negative, digits) = self.to_decimal_parts();
+        fmt.pad_integral(!negative, "", &digits)
+    }
+}
+
+impl FromStr for BigInteger {
+    type Err = Error;
+
+    /// Parses a decimal integer with an optional leading `-`.
+    ///
+    /// The result uses the fewest bytes which represent the value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::invalid_big_integer());
+        }
+
+        // accumulate the little-endian magnitude 9 digits at a time, multiplying the existing value by 10^9 and adding
+        // the new chunk
+        let mut magnitude = vec![];
+        for chunk in digits.as_bytes().rchunks(9).rev() {
+            let mut carry = chunk
+                .iter()
+                .fold(0, |acc, b| acc * 10 + u64::from(b - b'0'));
+            let multiplier = 10u64.pow(chunk.len() as u32);
+            for b in &mut magnitude {
+                let cur = u64::from(*b) * multiplier + carry;
+                *b = cur as u8;
+                carry = cur >> 8;
+            }
+            while carry != 0 {
+                magnitude.push(carry as u8);
+                carry >>= 8;
+            }
+        }
+        magnitude.reverse();
+
+        Ok(BigInteger::from_magnitude(negative, &magnitude))
+    }
 }
 
 impl Serialize for BigInteger {
@@ -120,3 +503,95 @@ impl<'de> Deserialize<'de> for BigIntegerKey {
         deserializer.deserialize_identifier(KeyVisitor)
     }
 }
+
+/// A `BigInteger` small enough to be represented by a native Rust integer type.
+pub(crate) enum NativeInteger {
+    I64(i64),
+    U64(u64),
+    I128(i128),
+    U128(u128),
+}
+
+impl NativeInteger {
+    /// Converts a two's complement big-endian buffer into the smallest native integer type that can hold it.
+    ///
+    /// Unsigned types are only used when the value cannot be represented by the signed type of the same width.
+    pub(crate) fn from_be_bytes(buf: &[u8]) -> Option<Self> {
+        if buf.is_empty() {
+            return None;
+        }
+
+        if buf.len() <= 8 {
+            let mut out = [0; 8];
+            let (extra, number) = out.split_at_mut(8 - buf.len());
+            number.copy_from_slice(buf);
+            sign_extend(extra, number);
+            return Some(NativeInteger::I64(i64::from_be_bytes(out)));
+        }
+
+        if buf.len() == 9 && buf[0] == 0 {
+            let mut out = [0; 8];
+            out.copy_from_slice(&buf[1..]);
+            return Some(NativeInteger::U64(u64::from_be_bytes(out)));
+        }
+
+        if buf.len() <= 16 {
+            let mut out = [0; 16];
+            let (extra, number) = out.split_at_mut(16 - buf.len());
+            number.copy_from_slice(buf);
+            sign_extend(extra, number);
+            return Some(NativeInteger::I128(i128::from_be_bytes(out)));
+        }
+
+        if buf.len() == 17 && buf[0] == 0 {
+            let mut out = [0; 16];
+            out.copy_from_slice(&buf[1..]);
+            return Some(NativeInteger::U128(u128::from_be_bytes(out)));
+        }
+
+        None
+    }
+
+    pub(crate) fn visit<'de, V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            NativeInteger::I64(v) => visitor.visit_i64(v),
+            NativeInteger::U64(v) => visitor.visit_u64(v),
+            NativeInteger::I128(v) => visitor.visit_i128(v),
+            NativeInteger::U128(v) => visitor.visit_u128(v),
+        }
+    }
+}
+
+fn sign_extend(extra: &mut [u8], number: &[u8]) {
+    let extension = (number[0] as i8 >> 7) as u8;
+    extra.fill(extension);
+}
+
+/// Negates a two's complement big-endian value in place.
+fn negate(buf: &mut [u8]) {
+    for b in &mut *buf {
+        *b = !*b;
+    }
+    for b in buf.iter_mut().rev() {
+        let (v, overflow) = b.overflowing_add(1);
+        *b = v;
+        if !overflow {
+            break;
+        }
+    }
+}
+
+/// Removes leading bytes which only repeat the sign of the two's complement value.
+pub(crate) fn strip_sign_extension(mut buf: &[u8]) -> &[u8] {
+    while let [first, second, ..] = *buf {
+        if (first == 0 && second & 0x80 == 0) || (first == 0xff && second & 0x80 != 0) {
+            buf = &buf[1..];
+        } else {
+            break;
+        }
+    }
+    buf
+}
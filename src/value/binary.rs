@@ -0,0 +1,124 @@
+#[cfg(feature = "bytes")]
+use crate::value::{Strings, Value, ValueSeed};
+#[cfg(feature = "bytes")]
+use crate::{Deserializer, Error};
+#[cfg(feature = "bytes")]
+use serde::de::DeserializeSeed;
+
+/// The buffer type of [`Value::Binary`](crate::value::Value::Binary).
+///
+/// This is a `Vec<u8>` by default. If the `bytes` Cargo feature is enabled, it is instead a reference counted
+/// `bytes::Bytes`, so cloning a binary value does not copy its contents. Code which needs to work in both
+/// configurations can construct buffers with `.into()` from a `Vec<u8>`, and read them through `Deref<Target = [u8]>`.
+#[cfg(not(feature = "bytes"))]
+pub type BinaryBuf = Vec<u8>;
+
+/// The buffer type of [`Value::Binary`](crate::value::Value::Binary).
+///
+/// This is a `Vec<u8>` by default. If the `bytes` Cargo feature is enabled, it is instead a reference counted
+/// `bytes::Bytes`, so cloning a binary value does not copy its contents. Code which needs to work in both
+/// configurations can construct buffers with `.into()` from a `Vec<u8>`, and read them through `Deref<Target = [u8]>`.
+#[cfg(feature = "bytes")]
+pub type BinaryBuf = bytes::Bytes;
+
+#[cfg(not(feature = "bytes"))]
+#[inline]
+pub(crate) fn from_vec(v: Vec<u8>) -> BinaryBuf {
+    v
+}
+
+#[cfg(feature = "bytes")]
+#[inline]
+pub(crate) fn from_vec(v: Vec<u8>) -> BinaryBuf {
+    BinaryBuf::from(v)
+}
+
+#[cfg(not(feature = "bytes"))]
+#[inline]
+pub(crate) fn from_slice(v: &[u8]) -> BinaryBuf {
+    v.to_vec()
+}
+
+#[cfg(feature = "bytes")]
+#[inline]
+pub(crate) fn from_slice(v: &[u8]) -> BinaryBuf {
+    BinaryBuf::copy_from_slice(v)
+}
+
+#[cfg(not(feature = "bytes"))]
+#[inline]
+pub(crate) fn into_vec(v: BinaryBuf) -> Vec<u8> {
+    v
+}
+
+/// Converts the buffer into a `Vec<u8>`, which only copies if the buffer is shared.
+#[cfg(feature = "bytes")]
+#[inline]
+pub(crate) fn into_vec(v: BinaryBuf) -> Vec<u8> {
+    Vec::from(v)
+}
+
+/// Returns the heap memory used by the buffer.
+#[cfg(not(feature = "bytes"))]
+#[inline]
+pub(crate) fn size_of(v: &BinaryBuf) -> usize {
+    v.capacity()
+}
+
+/// Returns the heap memory used by the buffer.
+///
+/// The storage of a `Bytes` may be shared with other buffers, in which case it's counted by each of them.
+#[cfg(feature = "bytes")]
+#[inline]
+pub(crate) fn size_of(v: &BinaryBuf) -> usize {
+    v.len()
+}
+
+/// Returns a buffer sharing the storage of `source` for `v` if it's a subslice of `source`, or copying it otherwise.
+#[cfg(feature = "bytes")]
+pub(crate) fn share(source: &BinaryBuf, v: &[u8]) -> BinaryBuf {
+    let range = source.as_ptr_range();
+    let v_range = v.as_ptr_range();
+    if range.start <= v_range.start && v_range.end <= range.end {
+        source.slice_ref(v)
+    } else {
+        BinaryBuf::copy_from_slice(v)
+    }
+}
+
+/// Deserializes a [`Value`] from a `Bytes` buffer of Smile data.
+///
+/// Binary values encoded with the raw encoding share the storage of the input rather than being copied out of it, so
+/// large binary values can be decoded without additional allocations. Values with the 7-bit encoding must still be
+/// decoded into a new buffer. Use [`Builder::raw_binary`](crate::ser::Builder::raw_binary) when producing data which
+/// will be deserialized this way.
+///
+/// Requires the `bytes` Cargo feature.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use serde_smile::value::{self, Value};
+///
+/// let value = Value::binary(vec![0xff; 1024]);
+/// let buf = serde_smile::Serializer::builder()
+///     .raw_binary(true)
+///     .to_vec(&value)
+///     .unwrap();
+/// let buf = Bytes::from(buf);
+///
+/// let decoded = value::from_bytes(&buf).unwrap();
+/// assert_eq!(decoded, value);
+/// ```
+#[cfg(feature = "bytes")]
+pub fn from_bytes(bytes: &BinaryBuf) -> Result<Value, Error> {
+    let mut de = Deserializer::from_slice(bytes);
+    let value = ValueSeed {
+        source: Some(bytes),
+        strings: Strings::default(),
+    }
+    .deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
@@ -0,0 +1,223 @@
+use crate::value::{BigInteger, Map, Value};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+impl Value {
+    /// Compares two values with a total order.
+    ///
+    /// Values of different variants are ordered by variant, in the order they are declared in [`Value`]: `Null` sorts
+    /// first and `Object` last. Numbers of different variants are therefore never equal, even if they represent the
+    /// same number. Within a variant:
+    ///
+    /// * `false` sorts before `true`.
+    /// * `Integer` and `Long` values are ordered numerically.
+    /// * `BigInteger` values are ordered numerically, and two encodings of the same number are ordered by length.
+    /// * `Float` and `Double` values are ordered by the IEEE 754 `totalOrder` predicate: `-NaN < -inf < -0.0 < 0.0
+    ///   < inf < NaN`. Values are only equal if their bit patterns are, so `0.0` and `-0.0` differ and `NaN` is equal to
+    ///   itself.
+    /// * `BigDecimal` values are ordered by unscaled value and then by scale.
+    /// * `String` and `Binary` values are ordered lexicographically by their bytes.
+    /// * `Array` values are ordered lexicographically by their elements.
+    /// * `Object` values are ordered lexicographically by their entries sorted by key, comparing keys before values.
+    ///   The order in which entries were inserted does not matter.
+    ///
+    /// This ordering is part of the crate's stable API and will not change between releases.
+    pub fn canonical_cmp(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Boolean(a), Value::Boolean(b)) => a.cmp(b),
+            (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+            (Value::Long(a), Value::Long(b)) => a.cmp(b),
+            (Value::BigInteger(a), Value::BigInteger(b)) => a.cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.total_cmp(b),
+            (Value::Double(a), Value::Double(b)) => a.total_cmp(b),
+            (Value::BigDecimal(a), Value::BigDecimal(b)) => a
+                .unscaled_value()
+                .cmp(b.unscaled_value())
+                .then_with(|| a.scale().cmp(&b.scale())),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Binary(a), Value::Binary(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => {
+                for (a, b) in a.iter().zip(b) {
+                    match a.canonical_cmp(b) {
+                        Ordering::Equal => {}
+                        ordering => return ordering,
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let a = sorted_entries(a);
+                let b = sorted_entries(b);
+                for ((ak, av), (bk, bv)) in a.iter().zip(&b) {
+                    match ak.cmp(bk).then_with(|| av.canonical_cmp(bv)) {
+                        Ordering::Equal => {}
+                        ordering => return ordering,
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
+
+    /// Determines if two values are equal by meaning rather than by representation.
+    ///
+    /// `Integer`, `Long`, and `BigInteger` values are compared by their mathematical value, so `Integer(1)`,
+    /// `Long(1)`, and a `BigInteger` of 1 with any amount of sign extension are all equal. `Float` and `Double` values
+    /// are compared after widening to `f64`, following the `PartialEq` semantics of floats: `NaN` is not equal to
+    /// itself and `0.0` is equal to `-0.0`. `BigDecimal` values are equal if they represent the same number at any
+    /// scale, so `1.50` is equal to `1.5`.
+    ///
+    /// Integral and floating point values are never equal to each other, so `Integer(1)` is not equal to `Double(1.0)`,
+    /// and `BigDecimal` values are only ever equal to other `BigDecimal` values. Strings and binary values are compared
+    /// bytewise, arrays element by element, and objects by their entries regardless of order.
+    pub fn semantic_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.semantic_eq(b))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, a)| matches!(b.get(k), Some(b) if a.semantic_eq(b)))
+            }
+            (Value::BigDecimal(a), Value::BigDecimal(b)) => a.numeric_eq(b),
+            (a, b) => match (integral(a), integral(b)) {
+                (Some(a), Some(b)) => a.numeric_eq(&b),
+                _ => match (floating(a), floating(b)) {
+                    (Some(a), Some(b)) => a == b,
+                    _ => a == b,
+                },
+            },
+        }
+    }
+}
+
+fn integral(value: &Value) -> Option<Cow<'_, BigInteger>> {
+    match value {
+        Value::Integer(v) => Some(Cow::Owned(BigInteger::from(i128::from(*v)))),
+        Value::Long(v) => Some(Cow::Owned(BigInteger::from(i128::from(*v)))),
+        Value::BigInteger(v) => Some(Cow::Borrowed(v)),
+        _ => None,
+    }
+}
+
+fn floating(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(v) => Some(f64::from(*v)),
+        Value::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Boolean(_) => 1,
+        Value::Integer(_) => 2,
+        Value::Long(_) => 3,
+        Value::BigInteger(_) => 4,
+        Value::Float(_) => 5,
+        Value::Double(_) => 6,
+        Value::BigDecimal(_) => 7,
+        Value::String(_) => 8,
+        Value::Binary(_) => 9,
+        Value::Array(_) => 10,
+        Value::Object(_) => 11,
+    }
+}
+
+fn sorted_entries(map: &Map) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// A wrapper around a [`Value`] implementing `Eq`, `Ord`, and `Hash`.
+///
+/// Values are compared with [`Value::canonical_cmp`]. Unlike the `PartialEq` implementation of `Value`, floating point
+/// numbers are compared by bit pattern, so `NaN` is equal to itself and `0.0` is not equal to `-0.0`. This makes it
+/// possible to store values in a `HashSet` or `BTreeSet`, or to sort them deterministically.
+#[derive(Clone, Debug)]
+pub struct HashValue(pub Value);
+
+impl HashValue {
+    /// Returns the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> Value {
+        self.0
+    }
+}
+
+impl From<Value> for HashValue {
+    #[inline]
+    fn from(value: Value) -> Self {
+        HashValue(value)
+    }
+}
+
+impl PartialEq for HashValue {
+    fn eq(&self, other: &HashValue) -> bool {
+        self.0.canonical_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl Eq for HashValue {}
+
+impl PartialOrd for HashValue {
+    fn partial_cmp(&self, other: &HashValue) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HashValue {
+    fn cmp(&self, other: &HashValue) -> Ordering {
+        self.0.canonical_cmp(&other.0)
+    }
+}
+
+impl Hash for HashValue {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        hash_value(&self.0, state);
+    }
+}
+
+fn hash_value<H>(value: &Value, state: &mut H)
+where
+    H: Hasher,
+{
+    rank(value).hash(state);
+    match value {
+        Value::Null => {}
+        Value::Boolean(v) => v.hash(state),
+        Value::Integer(v) => v.hash(state),
+        Value::Long(v) => v.hash(state),
+        Value::BigInteger(v) => v.as_be_bytes().hash(state),
+        Value::Float(v) => v.to_bits().hash(state),
+        Value::Double(v) => v.to_bits().hash(state),
+        Value::BigDecimal(v) => {
+            v.unscaled_value().as_be_bytes().hash(state);
+            v.scale().hash(state);
+        }
+        Value::String(v) => v.hash(state),
+        Value::Binary(v) => v.hash(state),
+        Value::Array(v) => {
+            v.len().hash(state);
+            for value in v {
+                hash_value(value, state);
+            }
+        }
+        Value::Object(v) => {
+            v.len().hash(state);
+            for (key, value) in sorted_entries(v) {
+                key.hash(state);
+                hash_value(value, state);
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::value::{BigDecimal, BigInteger};
+use crate::Error;
+use num_bigint::BigInt;
+use std::convert::TryFrom;
+
+impl TryFrom<bigdecimal::BigDecimal> for BigDecimal {
+    type Error = Error;
+
+    /// Converts values whose scale fits in an `i32`.
+    #[inline]
+    fn try_from(v: bigdecimal::BigDecimal) -> Result<Self, Self::Error> {
+        BigDecimal::try_from(&v)
+    }
+}
+
+impl TryFrom<&bigdecimal::BigDecimal> for BigDecimal {
+    type Error = Error;
+
+    /// Converts values whose scale fits in an `i32`.
+    fn try_from(v: &bigdecimal::BigDecimal) -> Result<Self, Self::Error> {
+        let (value, scale) = v.as_bigint_and_exponent();
+        let scale = i32::try_from(scale).map_err(|_| Error::value_out_of_range("i32 scale"))?;
+        Ok(BigDecimal::new(BigInteger::from(value), scale))
+    }
+}
+
+impl From<BigDecimal> for bigdecimal::BigDecimal {
+    #[inline]
+    fn from(v: BigDecimal) -> Self {
+        bigdecimal::BigDecimal::from(&v)
+    }
+}
+
+impl From<&BigDecimal> for bigdecimal::BigDecimal {
+    fn from(v: &BigDecimal) -> Self {
+        bigdecimal::BigDecimal::new(BigInt::from(v.unscaled_value()), i64::from(v.scale()))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::value::{binary, string, Value};
+use std::mem;
+
+impl Value {
+    /// Returns an estimate of the total memory used by the value, in bytes.
+    ///
+    /// This includes `size_of::<Value>()` for the value itself along with all of the heap memory it owns: the
+    /// capacities of strings, binary buffers, and the byte buffers of `BigInteger` and `BigDecimal` values, the
+    /// capacities of arrays, and the storage of objects along with their keys and values, recursively.
+    ///
+    /// The size of an object's table is an approximation based on the layout of the underlying map, and allocator
+    /// overhead is not included. The estimate is intended for purposes like bounding the size of a cache of values
+    /// rather than for exact accounting.
+    ///
+    /// The value is traversed iteratively, so arbitrarily deeply nested values will not overflow the stack.
+    pub fn deep_size_of(&self) -> usize {
+        let mut size = mem::size_of::<Value>();
+        let mut stack = vec![self];
+
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Null
+                | Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Long(_)
+                | Value::Float(_)
+                | Value::Double(_) => {}
+                Value::BigInteger(v) => size += v.capacity(),
+                Value::BigDecimal(v) => size += v.unscaled_value().capacity(),
+                Value::String(v) => size += string::size_of(v),
+                Value::Binary(v) => size += binary::size_of(v),
+                Value::Array(v) => {
+                    size += v.capacity() * mem::size_of::<Value>();
+                    stack.extend(v);
+                }
+                Value::Object(v) => {
+                    size += v.table_size_of();
+                    for (key, value) in v {
+                        size += key.capacity();
+                        stack.push(value);
+                    }
+                }
+            }
+        }
+
+        size
+    }
+}
@@ -0,0 +1,159 @@
+use crate::value::Value;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The kind of a [`Difference`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DifferenceKind {
+    /// A value is present on the right but not on the left.
+    Added,
+    /// A value is present on the left but not on the right.
+    Removed,
+    /// Values of the same kind differ.
+    Changed,
+    /// Values are of different kinds.
+    TypeMismatch,
+}
+
+/// A difference between two values, as returned by [`diff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    path: String,
+    kind: DifferenceKind,
+    left: Option<Value>,
+    right: Option<Value>,
+}
+
+impl Difference {
+    /// Returns the JSON Pointer to the location of the difference.
+    ///
+    /// The pointer can be passed to [`Value::pointer`] to look up the values being compared. The empty pointer refers
+    /// to the root values.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the kind of the difference.
+    #[inline]
+    pub fn kind(&self) -> DifferenceKind {
+        self.kind
+    }
+
+    /// Returns the value on the left, or `None` if it was added.
+    #[inline]
+    pub fn left(&self) -> Option<&Value> {
+        self.left.as_ref()
+    }
+
+    /// Returns the value on the right, or `None` if it was removed.
+    #[inline]
+    pub fn right(&self) -> Option<&Value> {
+        self.right.as_ref()
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "root"
+        } else {
+            &self.path
+        };
+        match (&self.left, &self.right) {
+            (Some(left), Some(right)) => write!(f, "{}: {:?} != {:?}", path, left, right),
+            (Some(left), None) => write!(f, "{}: removed {:?}", path, left),
+            (None, Some(right)) => write!(f, "{}: added {:?}", path, right),
+            (None, None) => write!(f, "{}", path),
+        }
+    }
+}
+
+/// Returns the structural differences between two values.
+///
+/// Objects are compared by key and arrays by position, recursively; entries present on only one side are reported as
+/// [`DifferenceKind::Added`] or [`DifferenceKind::Removed`]. Values of different kinds, including numbers of different
+/// variants, are reported as [`DifferenceKind::TypeMismatch`], and other unequal values as
+/// [`DifferenceKind::Changed`]. Values are compared with [`Value::canonical_cmp`], so `NaN` is equal to itself and
+/// `0.0` is not equal to `-0.0`.
+///
+/// Differences are returned in a deterministic order: depth-first, with array elements in index order and object
+/// entries in key order, independent of the order in which entries were inserted. An empty vector is returned if the
+/// values are equal.
+pub fn diff(left: &Value, right: &Value) -> Vec<Difference> {
+    let mut differences = vec![];
+    diff_inner(&mut String::new(), left, right, &mut differences);
+    differences
+}
+
+fn diff_inner(path: &mut String, left: &Value, right: &Value, out: &mut Vec<Difference>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys = left.keys().chain(right.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let len = path.len();
+                push_token(path, key);
+                match (left.get(key), right.get(key)) {
+                    (Some(left), Some(right)) => diff_inner(path, left, right, out),
+                    (left, right) => out.push(missing(path, left, right)),
+                }
+                path.truncate(len);
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for i in 0..left.len().max(right.len()) {
+                let len = path.len();
+                push_token(path, &i.to_string());
+                match (left.get(i), right.get(i)) {
+                    (Some(left), Some(right)) => diff_inner(path, left, right, out),
+                    (left, right) => out.push(missing(path, left, right)),
+                }
+                path.truncate(len);
+            }
+        }
+        (left, right) if left.kind() != right.kind() => out.push(Difference {
+            path: path.clone(),
+            kind: DifferenceKind::TypeMismatch,
+            left: Some(left.clone()),
+            right: Some(right.clone()),
+        }),
+        (left, right) => {
+            if left.canonical_cmp(right) != Ordering::Equal {
+                out.push(Difference {
+                    path: path.clone(),
+                    kind: DifferenceKind::Changed,
+                    left: Some(left.clone()),
+                    right: Some(right.clone()),
+                });
+            }
+        }
+    }
+}
+
+// A difference where a value is present on only one side.
+fn missing(path: &str, left: Option<&Value>, right: Option<&Value>) -> Difference {
+    Difference {
+        path: path.to_string(),
+        kind: if left.is_some() {
+            DifferenceKind::Removed
+        } else {
+            DifferenceKind::Added
+        },
+        left: left.cloned(),
+        right: right.cloned(),
+    }
+}
+
+pub(crate) fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    for c in token.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::value::{binary, string, BigInteger, Map, Value};
+#[cfg(feature = "preserve_order")]
+use indexmap::IndexMap;
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+macro_rules! from_small_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                #[inline]
+                fn from(v: $t) -> Self {
+                    Value::Integer(i32::from(v))
+                }
+            }
+        )*
+    };
+}
+
+from_small_int!(i8, i16, i32, u8, u16);
+
+impl From<i64> for Value {
+    /// Converts to an `Integer` if the value fits in an `i32`, and a `Long` otherwise.
+    fn from(v: i64) -> Self {
+        match i32::try_from(v) {
+            Ok(v) => Value::Integer(v),
+            Err(_) => Value::Long(v),
+        }
+    }
+}
+
+impl From<u32> for Value {
+    /// Converts to an `Integer` if the value fits in an `i32`, and a `Long` otherwise.
+    #[inline]
+    fn from(v: u32) -> Self {
+        Value::from(i64::from(v))
+    }
+}
+
+impl From<u64> for Value {
+    /// Converts to an `Integer` if the value fits in an `i32`, a `Long` if it fits in an `i64`, and a `BigInteger`
+    /// otherwise.
+    fn from(v: u64) -> Self {
+        match i64::try_from(v) {
+            Ok(v) => Value::from(v),
+            Err(_) => {
+                // we need an extra byte for the sign bit
+                let mut buf = vec![0; 9];
+                buf[1..].copy_from_slice(&v.to_be_bytes());
+                Value::BigInteger(BigInteger::from_be_bytes(buf))
+            }
+        }
+    }
+}
+
+impl From<bool> for Value {
+    #[inline]
+    fn from(v: bool) -> Self {
+        Value::Boolean(v)
+    }
+}
+
+impl From<f32> for Value {
+    #[inline]
+    fn from(v: f32) -> Self {
+        Value::Float(v)
+    }
+}
+
+impl From<f64> for Value {
+    #[inline]
+    fn from(v: f64) -> Self {
+        Value::Double(v)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(v: &str) -> Self {
+        Value::String(string::from_str(v))
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(v: String) -> Self {
+        Value::String(string::from_string(v))
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    #[inline]
+    fn from(v: Vec<u8>) -> Self {
+        Value::Binary(binary::from_vec(v))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    #[inline]
+    fn from(v: Vec<Value>) -> Self {
+        Value::Array(v)
+    }
+}
+
+impl From<Map> for Value {
+    #[inline]
+    fn from(v: Map) -> Self {
+        Value::Object(v)
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl From<IndexMap<String, Value>> for Value {
+    #[inline]
+    fn from(v: IndexMap<String, Value>) -> Self {
+        Value::Object(Map::from(v))
+    }
+}
+
+#[cfg(not(feature = "preserve_order"))]
+impl From<BTreeMap<String, Value>> for Value {
+    #[inline]
+    fn from(v: BTreeMap<String, Value>) -> Self {
+        Value::Object(Map::from(v))
+    }
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    /// Converts `None` to `Null`.
+    #[inline]
+    fn from(v: Option<T>) -> Self {
+        match v {
+            Some(v) => v.into(),
+            None => Value::Null,
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use crate::value::{map, Map, Value};
+use std::iter::FromIterator;
+use std::{slice, vec};
+
+/// Collects values into a `Value::Array`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let value = (1..4).map(Value::from).collect::<Value>();
+/// assert_eq!(value, smile!([1, 2, 3]));
+/// ```
+impl FromIterator<Value> for Value {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = Value>,
+    {
+        Value::Array(iter.into_iter().collect())
+    }
+}
+
+/// Collects key-value pairs into a `Value::Object`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let value = ["a", "b"]
+///     .iter()
+///     .map(|k| (k.to_string(), Value::from(k.len() as i32)))
+///     .collect::<Value>();
+/// assert_eq!(value, smile!({"a": 1, "b": 1}));
+/// ```
+impl FromIterator<(String, Value)> for Value {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (String, Value)>,
+    {
+        Value::Object(iter.into_iter().collect())
+    }
+}
+
+/// Appends values to a `Value::Array`.
+///
+/// A `Null` value is replaced with an empty array first.
+///
+/// # Panics
+///
+/// Panics if the value is neither an array nor `Null`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let mut value = smile!([1]);
+/// value.extend(vec![smile!(2), smile!("three")]);
+/// assert_eq!(value, smile!([1, 2, "three"]));
+/// ```
+impl Extend<Value> for Value {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = Value>,
+    {
+        if let Value::Null = self {
+            *self = Value::Array(vec![]);
+        }
+        match self {
+            Value::Array(vec) => vec.extend(iter),
+            _ => panic!("cannot extend Smile {} with elements", self.kind()),
+        }
+    }
+}
+
+/// Inserts key-value pairs into a `Value::Object`.
+///
+/// A `Null` value is replaced with an empty object first.
+///
+/// # Panics
+///
+/// Panics if the value is neither an object nor `Null`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let mut value = Value::Null;
+/// value.extend(vec![("a".to_string(), smile!(1))]);
+/// assert_eq!(value, smile!({"a": 1}));
+/// ```
+impl Extend<(String, Value)> for Value {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (String, Value)>,
+    {
+        if let Value::Null = self {
+            *self = Value::Object(Map::new());
+        }
+        match self {
+            Value::Object(map) => map.extend(iter),
+            _ => panic!("cannot extend Smile {} with entries", self.kind()),
+        }
+    }
+}
+
+macro_rules! value_iterator {
+    (
+        $name:ident $(<$lt:lifetime>)?,
+        $array:ty,
+        $object:ty,
+        $key:ty,
+        $value:ty,
+        $doc:expr
+    ) => {
+        #[doc = $doc]
+        ///
+        /// Array elements are yielded with a key of `None`, and object entries with their key. Other values yield
+        /// nothing.
+        pub struct $name$(<$lt>)? {
+            inner: Inner<$array, $object>,
+        }
+
+        impl$(<$lt>)? Iterator for $name$(<$lt>)? {
+            type Item = (Option<$key>, $value);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.inner {
+                    Inner::Array(it) => it.next().map(|v| (None, v)),
+                    Inner::Object(it) => it.next().map(|(k, v)| (Some(k), v)),
+                    Inner::Empty => None,
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.len();
+                (len, Some(len))
+            }
+        }
+
+        impl$(<$lt>)? DoubleEndedIterator for $name$(<$lt>)? {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.inner {
+                    Inner::Array(it) => it.next_back().map(|v| (None, v)),
+                    Inner::Object(it) => it.next_back().map(|(k, v)| (Some(k), v)),
+                    Inner::Empty => None,
+                }
+            }
+        }
+
+        impl$(<$lt>)? ExactSizeIterator for $name$(<$lt>)? {
+            #[inline]
+            fn len(&self) -> usize {
+                match &self.inner {
+                    Inner::Array(it) => it.len(),
+                    Inner::Object(it) => it.len(),
+                    Inner::Empty => 0,
+                }
+            }
+        }
+    };
+}
+
+enum Inner<A, O> {
+    Array(A),
+    Object(O),
+    Empty,
+}
+
+value_iterator!(
+    Iter<'a>,
+    slice::Iter<'a, Value>,
+    map::Iter<'a>,
+    &'a String,
+    &'a Value,
+    "An iterator over the elements of a [`Value`]."
+);
+value_iterator!(
+    IterMut<'a>,
+    slice::IterMut<'a, Value>,
+    map::IterMut<'a>,
+    &'a String,
+    &'a mut Value,
+    "A mutable iterator over the elements of a [`Value`]."
+);
+value_iterator!(
+    IntoIter,
+    vec::IntoIter<Value>,
+    map::IntoIter,
+    String,
+    Value,
+    "An owning iterator over the elements of a [`Value`]."
+);
+
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+///
+/// let value = smile!({"a": 1, "b": 2});
+/// let keys = (&value)
+///     .into_iter()
+///     .map(|(k, _)| k.unwrap().as_str())
+///     .collect::<Vec<_>>();
+/// assert_eq!(keys, ["a", "b"]);
+/// ```
+impl<'a> IntoIterator for &'a Value {
+    type Item = (Option<&'a String>, &'a Value);
+
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self {
+            Value::Array(vec) => Inner::Array(vec.iter()),
+            Value::Object(map) => Inner::Object(map.iter()),
+            _ => Inner::Empty,
+        };
+        Iter { inner }
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let mut value = smile!([1, 2]);
+/// for (_, element) in &mut value {
+///     *element = Value::Boolean(true);
+/// }
+/// assert_eq!(value, smile!([true, true]));
+/// ```
+impl<'a> IntoIterator for &'a mut Value {
+    type Item = (Option<&'a String>, &'a mut Value);
+
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self {
+            Value::Array(vec) => Inner::Array(vec.iter_mut()),
+            Value::Object(map) => Inner::Object(map.iter_mut()),
+            _ => Inner::Empty,
+        };
+        IterMut { inner }
+    }
+}
+
+/// # Examples
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// let value = smile!({"a": [1, 2], "b": [3]});
+/// let flattened = value
+///     .into_iter()
+///     .flat_map(|(_, v)| v)
+///     .map(|(_, v)| v)
+///     .collect::<Value>();
+/// assert_eq!(flattened, smile!([1, 2, 3]));
+/// ```
+impl IntoIterator for Value {
+    type Item = (Option<String>, Value);
+
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self {
+            Value::Array(vec) => Inner::Array(vec.into_iter()),
+            Value::Object(map) => Inner::Object(map.into_iter()),
+            _ => Inner::Empty,
+        };
+        IntoIter { inner }
+    }
+}
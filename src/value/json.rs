@@ -0,0 +1,166 @@
+use crate::encoding;
+use crate::value::{BigDecimal, BigInteger, Value};
+use std::fmt::Write;
+
+impl Value {
+    /// Converts the value to a compact JSON string.
+    ///
+    /// Smile types without a direct JSON equivalent are converted following the conventions of Jackson's default
+    /// configuration:
+    ///
+    /// * `Binary` values are encoded as strings containing the standard, padded base64 encoding of the data.
+    /// * `BigInteger` values are written as integer literals.
+    /// * `BigDecimal` values are written as number literals in the format of Java's `BigDecimal.toString`, which uses
+    ///   exponential notation for very large or small values.
+    /// * Non-finite `Float` and `Double` values are written as the strings `"NaN"`, `"Infinity"`, and `"-Infinity"`.
+    pub fn to_json_string(&self) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self);
+        out
+    }
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Boolean(true) => out.push_str("true"),
+        Value::Boolean(false) => out.push_str("false"),
+        Value::Integer(v) => write!(out, "{}", v).unwrap(),
+        Value::Long(v) => write!(out, "{}", v).unwrap(),
+        Value::BigInteger(v) => write_big_integer(out, v),
+        Value::Float(v) => {
+            if v.is_finite() {
+                write!(out, "{:?}", v).unwrap()
+            } else {
+                write_non_finite(out, f64::from(*v))
+            }
+        }
+        Value::Double(v) => {
+            if v.is_finite() {
+                write!(out, "{:?}", v).unwrap()
+            } else {
+                write_non_finite(out, *v)
+            }
+        }
+        Value::BigDecimal(v) => write_big_decimal(out, v),
+        Value::String(v) => write_str(out, v),
+        Value::Binary(v) => {
+            out.push('"');
+            encoding::encode_base64(out, v);
+            out.push('"');
+        }
+        Value::Array(v) => {
+            out.push('[');
+            for (i, value) in v.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                write_value(out, value);
+            }
+            out.push(']');
+        }
+        Value::Object(v) => {
+            out.push('{');
+            for (i, (key, value)) in v.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                write_str(out, key);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_non_finite(out: &mut String, v: f64) {
+    let s = if v.is_nan() {
+        "\"NaN\""
+    } else if v > 0. {
+        "\"Infinity\""
+    } else {
+        "\"-Infinity\""
+    };
+    out.push_str(s);
+}
+
+pub(crate) fn write_big_integer(out: &mut String, v: &BigInteger) {
+    let (negative, digits) = v.to_decimal_parts();
+    if negative {
+        out.push('-');
+    }
+    out.push_str(&digits);
+}
+
+// This follows the algorithm used by Java's BigDecimal.toString.
+pub(crate) fn write_big_decimal(out: &mut String, v: &BigDecimal) {
+    let (negative, digits) = v.unscaled_value().to_decimal_parts();
+    if negative {
+        out.push('-');
+    }
+
+    let scale = i64::from(v.scale());
+    let adjusted = -scale + (digits.len() as i64 - 1);
+
+    if scale >= 0 && adjusted >= -6 {
+        let scale = scale as usize;
+        if scale == 0 {
+            out.push_str(&digits);
+        } else if digits.len() > scale {
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            out.push_str(int);
+            out.push('.');
+            out.push_str(frac);
+        } else {
+            out.push_str("0.");
+            for _ in 0..scale - digits.len() {
+                out.push('0');
+            }
+            out.push_str(&digits);
+        }
+    } else {
+        out.push_str(&digits[..1]);
+        if digits.len() > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        if adjusted != 0 {
+            out.push('E');
+            if adjusted > 0 {
+                out.push('+');
+            }
+            write!(out, "{}", adjusted).unwrap();
+        }
+    }
+}
+
+pub(crate) fn write_str(out: &mut String, v: &str) {
+    out.push('"');
+
+    let mut start = 0;
+    for (i, b) in v.bytes().enumerate() {
+        let escape = match b {
+            b'"' => "\\\"",
+            b'\\' => "\\\\",
+            b'\n' => "\\n",
+            b'\r' => "\\r",
+            b'\t' => "\\t",
+            0x08 => "\\b",
+            0x0c => "\\f",
+            0x00..=0x1f => "",
+            _ => continue,
+        };
+
+        out.push_str(&v[start..i]);
+        if escape.is_empty() {
+            write!(out, "\\u{:04x}", b).unwrap();
+        } else {
+            out.push_str(escape);
+        }
+        start = i + 1;
+    }
+    out.push_str(&v[start..]);
+
+    out.push('"');
+}
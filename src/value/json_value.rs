@@ -0,0 +1,161 @@
+use crate::encoding;
+use crate::value::big_integer::NativeInteger;
+use crate::value::json::{write_big_decimal, write_big_integer};
+use crate::value::{string, BigDecimal, BigInteger, Map, Value};
+use serde_json::Number;
+
+/// The representation of `Binary` values when converting a [`Value`] to a `serde_json::Value`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum JsonBinary {
+    /// Binary values are converted to strings containing the standard, padded base64 encoding of the data.
+    ///
+    /// This matches the behavior of Jackson's default configuration.
+    #[default]
+    Base64,
+    /// Binary values are converted to arrays of numbers, one per byte.
+    Array,
+}
+
+impl Value {
+    /// Converts the value to a `serde_json::Value`.
+    ///
+    /// Smile types without a direct JSON equivalent are converted as follows:
+    ///
+    /// * `Binary` values are converted according to `binary`.
+    /// * `BigInteger` values are converted to numbers if they fit in an `i64` or `u64`, and strings containing their
+    ///   decimal representation otherwise.
+    /// * `BigDecimal` values are converted to numbers if an `f64` represents them exactly when formatted, and strings in
+    ///   the format of Java's `BigDecimal.toString` otherwise.
+    /// * `Float` values are widened to `f64`.
+    /// * Non-finite `Float` and `Double` values are converted to the strings `"NaN"`, `"Infinity"`, and `"-Infinity"`.
+    ///
+    /// Requires the `json` Cargo feature.
+    pub fn to_json_value(&self, binary: JsonBinary) -> serde_json::Value {
+        match self {
+            Value::Null => serde_json::Value::Null,
+            Value::Boolean(v) => serde_json::Value::Bool(*v),
+            Value::Integer(v) => serde_json::Value::from(*v),
+            Value::Long(v) => serde_json::Value::from(*v),
+            Value::BigInteger(v) => big_integer_to_json(v),
+            Value::Float(v) => f64_to_json(f64::from(*v)),
+            Value::Double(v) => f64_to_json(*v),
+            Value::BigDecimal(v) => big_decimal_to_json(v),
+            Value::String(v) => serde_json::Value::String(v.to_string()),
+            Value::Binary(v) => match binary {
+                JsonBinary::Base64 => {
+                    let mut out = String::new();
+                    encoding::encode_base64(&mut out, v);
+                    serde_json::Value::String(out)
+                }
+                JsonBinary::Array => {
+                    serde_json::Value::Array(v.iter().map(|b| (*b).into()).collect())
+                }
+            },
+            Value::Array(v) => {
+                serde_json::Value::Array(v.iter().map(|v| v.to_json_value(binary)).collect())
+            }
+            Value::Object(v) => serde_json::Value::Object(
+                v.iter()
+                    .map(|(k, v)| (k.clone(), v.to_json_value(binary)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<Value> for serde_json::Value {
+    /// Converts using the [`JsonBinary::Base64`] representation for binary data.
+    ///
+    /// See [`Value::to_json_value`] for details.
+    fn from(v: Value) -> Self {
+        v.to_json_value(JsonBinary::Base64)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    /// Integers are converted to `Integer`, `Long`, or `BigInteger` values depending on their magnitude, and all other
+    /// numbers are converted to `Double` values.
+    fn from(v: serde_json::Value) -> Self {
+        match v {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(v) => Value::Boolean(v),
+            serde_json::Value::Number(v) => {
+                if let Some(v) = v.as_i64() {
+                    Value::from(v)
+                } else if let Some(v) = v.as_u64() {
+                    Value::from(v)
+                } else {
+                    Value::Double(v.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            serde_json::Value::String(v) => Value::String(string::from_string(v)),
+            serde_json::Value::Array(v) => Value::Array(v.into_iter().map(Value::from).collect()),
+            serde_json::Value::Object(v) => Value::Object(
+                v.into_iter()
+                    .map(|(k, v)| (k, Value::from(v)))
+                    .collect::<Map>(),
+            ),
+        }
+    }
+}
+
+fn f64_to_json(v: f64) -> serde_json::Value {
+    match Number::from_f64(v) {
+        Some(v) => serde_json::Value::Number(v),
+        None if v.is_nan() => serde_json::Value::String("NaN".to_string()),
+        None if v > 0. => serde_json::Value::String("Infinity".to_string()),
+        None => serde_json::Value::String("-Infinity".to_string()),
+    }
+}
+
+fn big_integer_to_json(v: &BigInteger) -> serde_json::Value {
+    match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+        Some(NativeInteger::I64(v)) => serde_json::Value::from(v),
+        Some(NativeInteger::U64(v)) => serde_json::Value::from(v),
+        _ => {
+            let mut out = String::new();
+            write_big_integer(&mut out, v);
+            serde_json::Value::String(out)
+        }
+    }
+}
+
+fn big_decimal_to_json(v: &BigDecimal) -> serde_json::Value {
+    let mut out = String::new();
+    write_big_decimal(&mut out, v);
+
+    // Rust's float formatting produces the shortest digit string that round trips, so the conversion is lossless
+    // exactly when those digits match the decimal's.
+    if let Ok(f) = out.parse::<f64>() {
+        if f.is_finite() {
+            let (negative, digits) = v.unscaled_value().to_decimal_parts();
+            let decimal = normalize(negative, &digits, -i64::from(v.scale()));
+
+            let formatted = format!("{:e}", f.abs());
+            let (mantissa, exponent) = formatted.split_once('e').unwrap();
+            let mantissa = mantissa.replace('.', "");
+            let exponent = exponent.parse::<i64>().unwrap() - (mantissa.len() as i64 - 1);
+            let float = normalize(f.is_sign_negative(), &mantissa, exponent);
+
+            if decimal == float {
+                if let Some(f) = Number::from_f64(f) {
+                    return serde_json::Value::Number(f);
+                }
+            }
+        }
+    }
+
+    serde_json::Value::String(out)
+}
+
+/// Returns the sign, significant digits, and exponent of the value `digits * 10^exponent`, with zero normalized to
+/// an empty digit string.
+fn normalize(negative: bool, digits: &str, exponent: i64) -> (bool, &str, i64) {
+    let digits = digits.trim_start_matches('0');
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        return (false, "", 0);
+    }
+    let exponent = exponent + (digits.len() - trimmed.len()) as i64;
+    (negative, trimmed, exponent)
+}
@@ -0,0 +1,352 @@
+use crate::de::{Deserializer, IoRead, Read, SliceRead};
+use crate::value::synthetic_key::{SyntheticKey, SyntheticMap};
+use crate::value::{
+    BigDecimalVisitor, BigInteger, BigIntegerVisitor, Map, Source, Strings, Value, ValueVisitor,
+};
+use crate::Error;
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::fmt;
+use std::io::BufRead;
+
+/// Resource limits for [`from_slice_with_limits`] and [`from_reader_with_limits`].
+#[derive(Clone, Debug)]
+pub struct Limits {
+    max_string_bytes: usize,
+    max_binary_bytes: usize,
+    max_array_elements: usize,
+    max_object_entries: usize,
+    max_total_nodes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_string_bytes: usize::MAX,
+            max_binary_bytes: usize::MAX,
+            max_array_elements: usize::MAX,
+            max_object_entries: usize::MAX,
+            max_total_nodes: usize::MAX,
+        }
+    }
+}
+
+impl Limits {
+    /// Returns limits which allow any value.
+    pub fn new() -> Self {
+        Limits::default()
+    }
+
+    /// Sets the maximum length in bytes of string values and object keys.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_string_bytes(&mut self, max_string_bytes: usize) -> &mut Self {
+        self.max_string_bytes = max_string_bytes;
+        self
+    }
+
+    /// Sets the maximum length in bytes of binary values.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_binary_bytes(&mut self, max_binary_bytes: usize) -> &mut Self {
+        self.max_binary_bytes = max_binary_bytes;
+        self
+    }
+
+    /// Sets the maximum number of elements in a single array.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_array_elements(&mut self, max_array_elements: usize) -> &mut Self {
+        self.max_array_elements = max_array_elements;
+        self
+    }
+
+    /// Sets the maximum number of entries in a single object.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_object_entries(&mut self, max_object_entries: usize) -> &mut Self {
+        self.max_object_entries = max_object_entries;
+        self
+    }
+
+    /// Sets the maximum number of values in the entire document, including arrays and objects themselves.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_total_nodes(&mut self, max_total_nodes: usize) -> &mut Self {
+        self.max_total_nodes = max_total_nodes;
+        self
+    }
+}
+
+/// Deserializes a [`Value`] from a slice of Smile data, enforcing resource limits.
+///
+/// The limits are checked as the value is decoded, before the memory for an oversized string, binary value, array, or
+/// object is allocated, so they can be used to safely decode untrusted input. An error is returned as soon as one is
+/// exceeded.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::value::{self, Limits};
+///
+/// let buf = serde_smile::to_vec(&vec![1, 2, 3]).unwrap();
+///
+/// let mut limits = Limits::new();
+/// limits.max_array_elements(2);
+/// let e = value::from_slice_with_limits(&buf, &limits).unwrap_err();
+/// assert_eq!(e.to_string(), "max_array_elements limit of 2 exceeded");
+/// ```
+pub fn from_slice_with_limits(slice: &[u8], limits: &Limits) -> Result<Value, Error> {
+    deserialize(SliceRead::new(slice), limits)
+}
+
+/// Deserializes a [`Value`] from an IO stream of Smile data, enforcing resource limits.
+///
+/// See [`from_slice_with_limits`] for details.
+pub fn from_reader_with_limits<R>(reader: R, limits: &Limits) -> Result<Value, Error>
+where
+    R: BufRead,
+{
+    deserialize(IoRead::new(reader), limits)
+}
+
+fn deserialize<'de, R>(reader: R, limits: &Limits) -> Result<Value, Error>
+where
+    R: Read<'de>,
+{
+    let state = State {
+        limits,
+        nodes: Cell::new(0),
+        exceeded: Cell::new(None),
+    };
+
+    let mut de = Deserializer::new(reader);
+    let r = LimitedSeed { state: &state }
+        .deserialize(&mut de)
+        .and_then(|value| de.end().map(|()| value));
+
+    // the visitor can only report a custom error, so it's replaced by the specific one here
+    r.map_err(|e| match state.exceeded.get() {
+        Some((limit, max)) => Error::limit_exceeded(limit, max),
+        None => e,
+    })
+}
+
+struct State<'a> {
+    limits: &'a Limits,
+    nodes: Cell<usize>,
+    exceeded: Cell<Option<(&'static str, usize)>>,
+}
+
+impl State<'_> {
+    fn check<E>(&self, len: usize, max: usize, limit: &'static str) -> Result<(), E>
+    where
+        E: de::Error,
+    {
+        if len > max {
+            self.exceeded.set(Some((limit, max)));
+            return Err(E::custom(format_args!(
+                "{} limit of {} exceeded",
+                limit, max
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone)]
+struct LimitedSeed<'a> {
+    state: &'a State<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for LimitedSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let nodes = self.state.nodes.get() + 1;
+        self.state
+            .check(nodes, self.state.limits.max_total_nodes, "max_total_nodes")?;
+        self.state.nodes.set(nodes);
+
+        // See ValueSeed for the reason behind the BigInteger hint.
+        deserializer.deserialize_struct(BigInteger::STRUCT_NAME, &[BigInteger::FIELD_NAME], self)
+    }
+}
+
+impl LimitedSeed<'_> {
+    fn inner(&self) -> ValueVisitor<'static> {
+        ValueVisitor {
+            source: Source::default(),
+            strings: Strings::default(),
+        }
+    }
+}
+
+macro_rules! forward_scalar {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.inner().$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for LimitedSeed<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any Smile value")
+    }
+
+    forward_scalar! {
+        visit_bool(bool),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let limits = self.state.limits;
+        self.state
+            .check(v.len(), limits.max_string_bytes, "max_string_bytes")?;
+        self.inner().visit_str(v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let limits = self.state.limits;
+        self.state
+            .check(v.len(), limits.max_binary_bytes, "max_binary_bytes")?;
+        self.inner().visit_bytes(v)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner().visit_unit()
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let max = self.state.limits.max_array_elements;
+        let mut vec = vec![];
+        while let Some(value) = seq.next_element_seed(self)? {
+            self.state.check(vec.len() + 1, max, "max_array_elements")?;
+            vec.push(value);
+        }
+        Ok(Value::Array(vec))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let max = self.state.limits.max_object_entries;
+        let mut v = Map::new();
+
+        let key = match map.next_key_seed(KeySeed { state: self.state })? {
+            Some(Key::BigInteger) => {
+                return BigIntegerVisitor.finish_map(map).map(Value::BigInteger)
+            }
+            Some(Key::BigDecimal) => {
+                return BigDecimalVisitor.finish_map(map).map(Value::BigDecimal)
+            }
+            Some(Key::Other(key)) => key,
+            None => return Ok(Value::Object(v)),
+        };
+
+        // duplicate keys don't grow the map, but they still count against the limit
+        let mut entries = 1;
+        self.state.check(entries, max, "max_object_entries")?;
+        v.insert(key, map.next_value_seed(self)?);
+        while let Some(key) = map.next_key_seed(KeySeed { state: self.state })? {
+            let key = match key {
+                Key::Other(key) => key,
+                Key::BigInteger | Key::BigDecimal => unreachable!(),
+            };
+            entries += 1;
+            self.state.check(entries, max, "max_object_entries")?;
+            let value = map.next_value_seed(self)?;
+            v.insert(key, value);
+        }
+
+        Ok(Value::Object(v))
+    }
+}
+
+enum Key {
+    BigInteger,
+    BigDecimal,
+    Other(String),
+}
+
+struct KeySeed<'a> {
+    state: &'a State<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for KeySeed<'_> {
+    type Value = Key;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(SyntheticKey::MARKER, self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed<'_> {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let limits = self.state.limits;
+        self.state
+            .check(v.len(), limits.max_string_bytes, "max_string_bytes")?;
+        Ok(Key::Other(v.to_string()))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+
+    // the synthetic BigInteger and BigDecimal keys aren't subject to the limit
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match SyntheticMap::from_enum(data)? {
+            SyntheticMap::BigInteger => Ok(Key::BigInteger),
+            SyntheticMap::BigDecimal => Ok(Key::BigDecimal),
+        }
+    }
+}
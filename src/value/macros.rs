@@ -0,0 +1,365 @@
+use crate::value::{string, BigInteger, Value};
+use std::convert::TryFrom;
+
+/// Constructs a [`Value`](crate::value::Value) from a JSON-like literal.
+///
+/// ```
+/// use serde_smile::smile;
+///
+/// let name = "serde-smile";
+/// let value = smile!({
+///     "name": name,
+///     "version": [0, 2, 0],
+///     "downloads": 10000000000,
+///     "yanked": false,
+///     "license": null,
+/// });
+/// ```
+///
+/// Arrays and objects nest, and trailing commas are permitted. Object keys can be any expression implementing
+/// `Into<String>`, and values can be any expression implementing `Into<Value>`. Keys and values which are not
+/// literals or single tokens can be wrapped in parentheses.
+///
+/// Unsuffixed integer literals become an `Integer` if they fit in an `i32`, a `Long` if they fit in an `i64`, and a
+/// `BigInteger` otherwise. Unsuffixed floating point literals become a `Double`. Suffixed numeric literals are not
+/// supported directly, but can be interpolated as expressions by wrapping them in parentheses:
+///
+/// ```
+/// use serde_smile::smile;
+/// use serde_smile::value::Value;
+///
+/// assert_eq!(smile!([(1.5f32)]), Value::Array(vec![Value::Float(1.5)]));
+/// ```
+#[macro_export]
+macro_rules! smile {
+    ($($smile:tt)+) => {
+        $crate::smile_internal!($($smile)+)
+    };
+}
+
+/// The implementation of [`smile!`]. Arrays and objects are parsed by munching tokens one at a time.
+///
+/// Malformed input is rejected at compile time:
+///
+/// ```compile_fail
+/// serde_smile::smile!([1 2]);
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!({"a" 1});
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!({"a": });
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!({"a": 1 "b": 2});
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!({: 1});
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!({"a", "b": 1});
+/// ```
+///
+/// ```compile_fail
+/// serde_smile::smile!([1,, 2]);
+/// ```
+///
+/// Suffixed literals must be parenthesized:
+///
+/// ```compile_fail
+/// serde_smile::smile!(5u8);
+/// ```
+///
+/// Values must implement `Into<Value>`:
+///
+/// ```compile_fail
+/// struct Foo;
+/// serde_smile::smile!([Foo]);
+/// ```
+#[macro_export]
+#[doc(hidden)]
+macro_rules! smile_internal {
+    // Array parsing. The first bracketed group holds the elements parsed so far.
+
+    // Done with trailing comma.
+    (@array [$($elems:expr,)*]) => {
+        vec![$($elems,)*]
+    };
+
+    // Done without trailing comma.
+    (@array [$($elems:expr),*]) => {
+        vec![$($elems),*]
+    };
+
+    // Next element is `null`.
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!(null)] $($rest)*)
+    };
+
+    // Next element is `true`.
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!(true)] $($rest)*)
+    };
+
+    // Next element is `false`.
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!(false)] $($rest)*)
+    };
+
+    // Next element is an array.
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!([$($array)*])] $($rest)*)
+    };
+
+    // Next element is an object.
+    (@array [$($elems:expr,)*] {$($object:tt)*} $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!({$($object)*})] $($rest)*)
+    };
+
+    // Next element is a literal followed by a comma.
+    (@array [$($elems:expr,)*] $next:literal, $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!($next),] $($rest)*)
+    };
+
+    // Last element is a literal with no trailing comma.
+    (@array [$($elems:expr,)*] $last:literal) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!($last)])
+    };
+
+    // Next element is an expression followed by a comma.
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!(@expr $next),] $($rest)*)
+    };
+
+    // Last element is an expression with no trailing comma.
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::smile_internal!(@array [$($elems,)* $crate::smile_internal!(@expr $last)])
+    };
+
+    // Comma after the most recent element.
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::smile_internal!(@array [$($elems,)*] $($rest)*)
+    };
+
+    // Unexpected token after the most recent element.
+    (@array [$($elems:expr),*] $unexpected:tt $($rest:tt)*) => {
+        $crate::smile_unexpected!($unexpected)
+    };
+
+    // Object parsing. The object is built up in the `$object` variable. Each entry's key is munched one token at a
+    // time into a parenthesized group, and then its value is parsed into a bracketed group alongside the key. A copy
+    // of the remaining tokens is carried along to produce better error messages.
+
+    // Done.
+    (@object $object:ident () () ()) => {};
+
+    // Insert the current entry followed by a trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+        $crate::smile_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+
+    // Current entry followed by an unexpected token.
+    (@object $object:ident [$($key:tt)+] ($value:expr) $unexpected:tt $($rest:tt)*) => {
+        $crate::smile_unexpected!($unexpected);
+    };
+
+    // Insert the last entry without a trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+    };
+
+    // Next value is `null`.
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!(null)) $($rest)*);
+    };
+
+    // Next value is `true`.
+    (@object $object:ident ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!(true)) $($rest)*);
+    };
+
+    // Next value is `false`.
+    (@object $object:ident ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!(false)) $($rest)*);
+    };
+
+    // Next value is an array.
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!([$($array)*])) $($rest)*);
+    };
+
+    // Next value is an object.
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!({$($map)*})) $($rest)*);
+    };
+
+    // Next value is a literal followed by a comma.
+    (@object $object:ident ($($key:tt)+) (: $value:literal , $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!($value)) , $($rest)*);
+    };
+
+    // Last value is a literal with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:literal) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!($value)));
+    };
+
+    // Next value is an expression followed by a comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!(@expr $value)) , $($rest)*);
+    };
+
+    // Last value is an expression with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::smile_internal!(@object $object [$($key)+] ($crate::smile_internal!(@expr $value)));
+    };
+
+    // Missing value for the last entry.
+    (@object $object:ident ($($key:tt)+) (:) $copy:tt) => {
+        $crate::smile_internal!();
+    };
+
+    // Missing colon and value for the last entry.
+    (@object $object:ident ($($key:tt)+) () $copy:tt) => {
+        $crate::smile_internal!();
+    };
+
+    // Misplaced colon.
+    (@object $object:ident () (: $($rest:tt)*) ($colon:tt $($copy:tt)*)) => {
+        $crate::smile_unexpected!($colon);
+    };
+
+    // Found a comma inside a key.
+    (@object $object:ident ($($key:tt)*) (, $($rest:tt)*) ($comma:tt $($copy:tt)*)) => {
+        $crate::smile_unexpected!($comma);
+    };
+
+    // Key is fully parenthesized.
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Munch a token into the current key.
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::smile_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    // Interpolated expressions.
+    (@expr $value:expr) => {
+        $crate::value::Value::from($value)
+    };
+
+    // Main implementation.
+
+    (null) => {
+        $crate::value::Value::Null
+    };
+
+    (true) => {
+        $crate::value::Value::Boolean(true)
+    };
+
+    (false) => {
+        $crate::value::Value::Boolean(false)
+    };
+
+    ([]) => {
+        $crate::value::Value::Array(vec![])
+    };
+
+    ([ $($tt:tt)+ ]) => {
+        $crate::value::Value::Array($crate::smile_internal!(@array [] $($tt)+))
+    };
+
+    ({}) => {
+        $crate::value::Value::Object($crate::value::Map::new())
+    };
+
+    ({ $($tt:tt)+ }) => {
+        $crate::value::Value::Object({
+            let mut object = $crate::value::Map::new();
+            $crate::smile_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+
+    ($value:literal) => {
+        $crate::value::__private::from_literal($value)
+    };
+
+    ($other:expr) => {
+        $crate::smile_internal!(@expr $other)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! smile_unexpected {
+    () => {};
+}
+
+/// A type an unsuffixed literal can be inferred as in [`smile!`].
+///
+/// Only one integer and one floating point type implement this trait so that unsuffixed literals are inferred as
+/// `i128` and `f64` rather than falling back to `i32`.
+pub trait Literal {
+    #[doc(hidden)]
+    fn into_value(self) -> Value;
+}
+
+impl Literal for i128 {
+    fn into_value(self) -> Value {
+        if let Ok(v) = i64::try_from(self) {
+            return Value::from(v);
+        }
+        if let Ok(v) = u64::try_from(self) {
+            return Value::from(v);
+        }
+
+        let buf = self.to_be_bytes();
+        // strip redundant sign extension bytes
+        let start = buf
+            .windows(2)
+            .position(|w| !(w[0] == 0 && w[1] & 0x80 == 0 || w[0] == 0xff && w[1] & 0x80 != 0))
+            .unwrap_or(buf.len() - 1);
+        Value::BigInteger(BigInteger::from_be_bytes(buf[start..].to_vec()))
+    }
+}
+
+impl Literal for f64 {
+    fn into_value(self) -> Value {
+        Value::Double(self)
+    }
+}
+
+impl Literal for &str {
+    fn into_value(self) -> Value {
+        Value::String(string::from_str(self))
+    }
+}
+
+impl Literal for char {
+    fn into_value(self) -> Value {
+        Value::String(string::from_string(self.to_string()))
+    }
+}
+
+impl Literal for bool {
+    fn into_value(self) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn from_literal<T>(v: T) -> Value
+where
+    T: Literal,
+{
+    v.into_value()
+}
@@ -0,0 +1,537 @@
+//! The map type used by [`Value::Object`].
+use crate::value::Value;
+#[cfg(feature = "preserve_order")]
+use indexmap::{map as map_impl, IndexMap};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::{btree_map as map_impl, BTreeMap};
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::{mem, ops};
+
+#[cfg(feature = "preserve_order")]
+type MapImpl = IndexMap<String, Value>;
+#[cfg(not(feature = "preserve_order"))]
+type MapImpl = BTreeMap<String, Value>;
+
+/// A map of strings to Smile values, used to represent `Value::Object`.
+///
+/// By default, entries are kept in insertion order, which is also the order in which they are serialized. If the
+/// `preserve_order` Cargo feature is disabled, entries are instead sorted by key.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Map {
+    map: MapImpl,
+}
+
+impl Map {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Map {
+            map: MapImpl::new(),
+        }
+    }
+
+    /// Creates an empty map with space for at least `capacity` entries.
+    ///
+    /// The capacity is ignored if the `preserve_order` Cargo feature is disabled.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        #[cfg(feature = "preserve_order")]
+        let map = IndexMap::with_capacity(capacity);
+        #[cfg(not(feature = "preserve_order"))]
+        let map = {
+            let _ = capacity;
+            BTreeMap::new()
+        };
+        Map { map }
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all entries from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns `true` if the map contains an entry for the key.
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Ord + Eq + Hash,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&Value>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Ord + Eq + Hash,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut Value>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Ord + Eq + Hash,
+    {
+        self.map.get_mut(key)
+    }
+
+    /// Inserts a key-value pair into the map, returning the previous value for the key if present.
+    ///
+    /// If the key was already present, its position in the map is unchanged.
+    #[inline]
+    pub fn insert(&mut self, key: String, value: Value) -> Option<Value> {
+        self.map.insert(key, value)
+    }
+
+    /// Removes a key from the map, returning its value if present.
+    ///
+    /// The order of the remaining entries is preserved.
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<Value>
+    where
+        String: Borrow<Q>,
+        Q: ?Sized + Ord + Eq + Hash,
+    {
+        #[cfg(feature = "preserve_order")]
+        return self.map.shift_remove(key);
+        #[cfg(not(feature = "preserve_order"))]
+        return self.map.remove(key);
+    }
+
+    /// Returns the entry for the key for in-place manipulation.
+    pub fn entry<S>(&mut self, key: S) -> Entry<'_>
+    where
+        S: Into<String>,
+    {
+        match self.map.entry(key.into()) {
+            map_impl::Entry::Vacant(entry) => Entry::Vacant(VacantEntry { entry }),
+            map_impl::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry { entry }),
+        }
+    }
+
+    /// Retains only the entries for which the predicate returns `true`.
+    ///
+    /// The order of the retained entries is preserved.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&String, &mut Value) -> bool,
+    {
+        self.map.retain(f)
+    }
+
+    /// Returns an iterator over the entries of the map.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            it: self.map.iter(),
+        }
+    }
+
+    /// Returns an iterator over mutable references to the entries of the map.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            it: self.map.iter_mut(),
+        }
+    }
+
+    /// Returns an iterator over the keys of the map.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_> {
+        Keys {
+            it: self.map.keys(),
+        }
+    }
+
+    /// Returns an iterator over the values of the map.
+    #[inline]
+    pub fn values(&self) -> Values<'_> {
+        Values {
+            it: self.map.values(),
+        }
+    }
+
+    /// Returns an iterator over mutable references to the values of the map.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_> {
+        ValuesMut {
+            it: self.map.values_mut(),
+        }
+    }
+
+    /// Returns an estimate of the heap memory used by the map's own storage, excluding the heap memory owned by its
+    /// keys and values.
+    pub(crate) fn table_size_of(&self) -> usize {
+        let entry = mem::size_of::<String>() + mem::size_of::<Value>();
+
+        // an IndexMap stores its entries along with their hashes in a vector, and indices into that vector in a
+        // hashbrown table with one control byte per bucket
+        #[cfg(feature = "preserve_order")]
+        let size = {
+            let capacity = self.map.capacity();
+            let buckets = if capacity == 0 {
+                0
+            } else {
+                (capacity * 8 / 7).next_power_of_two()
+            };
+            capacity * (entry + mem::size_of::<usize>()) + buckets * (mem::size_of::<usize>() + 1)
+        };
+        // a BTreeMap stores up to 11 entries per node, along with a parent pointer and some bookkeeping; internal
+        // nodes additionally hold child pointers but are comparatively rare
+        #[cfg(not(feature = "preserve_order"))]
+        let size = {
+            let len = self.map.len();
+            let nodes = if len == 0 { 0 } else { len / 11 + 1 };
+            nodes * (11 * entry + mem::size_of::<usize>() * 2)
+        };
+
+        size
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl From<IndexMap<String, Value>> for Map {
+    #[inline]
+    fn from(map: IndexMap<String, Value>) -> Self {
+        Map { map }
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+impl From<Map> for IndexMap<String, Value> {
+    #[inline]
+    fn from(map: Map) -> Self {
+        map.map
+    }
+}
+
+#[cfg(not(feature = "preserve_order"))]
+impl From<BTreeMap<String, Value>> for Map {
+    #[inline]
+    fn from(map: BTreeMap<String, Value>) -> Self {
+        Map { map }
+    }
+}
+
+#[cfg(not(feature = "preserve_order"))]
+impl From<Map> for BTreeMap<String, Value> {
+    #[inline]
+    fn from(map: Map) -> Self {
+        map.map
+    }
+}
+
+impl FromIterator<(String, Value)> for Map {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (String, Value)>,
+    {
+        Map {
+            map: MapImpl::from_iter(iter),
+        }
+    }
+}
+
+impl Extend<(String, Value)> for Map {
+    fn extend<T>(&mut self, iter: T)
+    where
+        T: IntoIterator<Item = (String, Value)>,
+    {
+        self.map.extend(iter)
+    }
+}
+
+impl<Q> ops::Index<&Q> for Map
+where
+    String: Borrow<Q>,
+    Q: ?Sized + Ord + Eq + Hash,
+{
+    type Output = Value;
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index(&self, key: &Q) -> &Value {
+        self.map.get(key).expect("key not present in Smile map")
+    }
+}
+
+impl<Q> ops::IndexMut<&Q> for Map
+where
+    String: Borrow<Q>,
+    Q: ?Sized + Ord + Eq + Hash,
+{
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    fn index_mut(&mut self, key: &Q) -> &mut Value {
+        self.map.get_mut(key).expect("key not present in Smile map")
+    }
+}
+
+impl Serialize for Map {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.map.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Map {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        MapImpl::deserialize(deserializer).map(|map| Map { map })
+    }
+}
+
+/// An entry in a [`Map`], which may be vacant or occupied.
+pub enum Entry<'a> {
+    /// A vacant entry.
+    Vacant(VacantEntry<'a>),
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the entry's key.
+    pub fn key(&self) -> &String {
+        match self {
+            Entry::Vacant(e) => e.key(),
+            Entry::Occupied(e) => e.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the entry's value.
+    pub fn or_insert(self, default: Value) -> &'a mut Value {
+        match self {
+            Entry::Vacant(e) => e.insert(default),
+            Entry::Occupied(e) => e.into_mut(),
+        }
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a mutable reference to the entry's value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut Value
+    where
+        F: FnOnce() -> Value,
+    {
+        match self {
+            Entry::Vacant(e) => e.insert(default()),
+            Entry::Occupied(e) => e.into_mut(),
+        }
+    }
+
+    /// Calls `f` with the entry's value if the entry is occupied.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Value),
+    {
+        match self {
+            Entry::Vacant(e) => Entry::Vacant(e),
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+        }
+    }
+}
+
+/// A vacant entry in a [`Map`].
+pub struct VacantEntry<'a> {
+    entry: map_impl::VacantEntry<'a, String, Value>,
+}
+
+impl<'a> VacantEntry<'a> {
+    /// Returns the entry's key.
+    #[inline]
+    pub fn key(&self) -> &String {
+        self.entry.key()
+    }
+
+    /// Inserts a value into the entry, returning a mutable reference to it.
+    #[inline]
+    pub fn insert(self, value: Value) -> &'a mut Value {
+        self.entry.insert(value)
+    }
+}
+
+/// An occupied entry in a [`Map`].
+pub struct OccupiedEntry<'a> {
+    entry: map_impl::OccupiedEntry<'a, String, Value>,
+}
+
+impl<'a> OccupiedEntry<'a> {
+    /// Returns the entry's key.
+    #[inline]
+    pub fn key(&self) -> &String {
+        self.entry.key()
+    }
+
+    /// Returns a reference to the entry's value.
+    #[inline]
+    pub fn get(&self) -> &Value {
+        self.entry.get()
+    }
+
+    /// Returns a mutable reference to the entry's value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Value {
+        self.entry.get_mut()
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut Value {
+        self.entry.into_mut()
+    }
+
+    /// Replaces the entry's value, returning the old value.
+    #[inline]
+    pub fn insert(&mut self, value: Value) -> Value {
+        self.entry.insert(value)
+    }
+
+    /// Removes the entry from the map, returning its value.
+    ///
+    /// The order of the remaining entries is preserved.
+    #[inline]
+    pub fn remove(self) -> Value {
+        #[cfg(feature = "preserve_order")]
+        return self.entry.shift_remove();
+        #[cfg(not(feature = "preserve_order"))]
+        return self.entry.remove();
+    }
+}
+
+macro_rules! delegate_iterator {
+    ($name:ident $(<$lt:lifetime>)? => $inner:ty, $item:ty, $doc:expr) => {
+        #[doc = $doc]
+        pub struct $name$(<$lt>)? {
+            it: $inner,
+        }
+
+        impl$(<$lt>)? Iterator for $name$(<$lt>)? {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.it.next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.it.size_hint()
+            }
+        }
+
+        impl$(<$lt>)? DoubleEndedIterator for $name$(<$lt>)? {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.it.next_back()
+            }
+        }
+
+        impl$(<$lt>)? ExactSizeIterator for $name$(<$lt>)? {
+            #[inline]
+            fn len(&self) -> usize {
+                self.it.len()
+            }
+        }
+    };
+}
+
+delegate_iterator!(
+    Iter<'a> => map_impl::Iter<'a, String, Value>,
+    (&'a String, &'a Value),
+    "An iterator over the entries of a [`Map`]."
+);
+delegate_iterator!(
+    IterMut<'a> => map_impl::IterMut<'a, String, Value>,
+    (&'a String, &'a mut Value),
+    "A mutable iterator over the entries of a [`Map`]."
+);
+delegate_iterator!(
+    IntoIter => map_impl::IntoIter<String, Value>,
+    (String, Value),
+    "An owning iterator over the entries of a [`Map`]."
+);
+delegate_iterator!(
+    Keys<'a> => map_impl::Keys<'a, String, Value>,
+    &'a String,
+    "An iterator over the keys of a [`Map`]."
+);
+delegate_iterator!(
+    Values<'a> => map_impl::Values<'a, String, Value>,
+    &'a Value,
+    "An iterator over the values of a [`Map`]."
+);
+delegate_iterator!(
+    ValuesMut<'a> => map_impl::ValuesMut<'a, String, Value>,
+    &'a mut Value,
+    "A mutable iterator over the values of a [`Map`]."
+);
+
+impl<'a> IntoIterator for &'a Map {
+    type Item = (&'a String, &'a Value);
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Map {
+    type Item = (&'a String, &'a mut Value);
+
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = (String, Value);
+
+    type IntoIter = IntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            it: self.map.into_iter(),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::value::Value;
+
+/// The strategy used to merge arrays in [`Value::merge_with`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ArrayMerge {
+    /// The array in the other value replaces the existing array.
+    #[default]
+    Replace,
+    /// The elements of the array in the other value are appended to the existing array.
+    Concatenate,
+}
+
+/// Options for [`Value::merge_with`].
+#[derive(Clone, Debug, Default)]
+pub struct MergeOptions {
+    keep_nulls: bool,
+    arrays: ArrayMerge,
+}
+
+impl MergeOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        MergeOptions::default()
+    }
+
+    /// If enabled, `Null` values in an object being merged replace existing values rather than removing their keys.
+    ///
+    /// Defaults to `false`.
+    pub fn keep_nulls(&mut self, keep_nulls: bool) -> &mut Self {
+        self.keep_nulls = keep_nulls;
+        self
+    }
+
+    /// Sets the strategy used to merge arrays.
+    ///
+    /// Defaults to [`ArrayMerge::Replace`].
+    pub fn arrays(&mut self, arrays: ArrayMerge) -> &mut Self {
+        self.arrays = arrays;
+        self
+    }
+}
+
+impl Value {
+    /// Deep merges another value into this one with default options.
+    ///
+    /// See [`Value::merge_with`] for details.
+    pub fn merge(&mut self, other: Value) {
+        self.merge_with(other, &MergeOptions::new())
+    }
+
+    /// Deep merges another value into this one.
+    ///
+    /// If both values are objects, each entry of `other` is merged into the entry of `self` with the same key,
+    /// recursively. Keys not already present are appended in their order in `other`, with their values inserted
+    /// unchanged. A `Null` value removes its key unless [`MergeOptions::keep_nulls`] is enabled.
+    ///
+    /// If both values are arrays, they are merged according to [`MergeOptions::arrays`].
+    ///
+    /// In all other cases, `other` replaces `self`.
+    pub fn merge_with(&mut self, other: Value, options: &MergeOptions) {
+        match (self, other) {
+            (Value::Object(base), Value::Object(other)) => {
+                for (key, value) in other {
+                    if value.is_null() && !options.keep_nulls {
+                        base.remove(&key);
+                        continue;
+                    }
+
+                    match base.get_mut(&key) {
+                        Some(base) => base.merge_with(value, options),
+                        None => {
+                            base.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (Value::Array(base), Value::Array(other))
+                if options.arrays == ArrayMerge::Concatenate =>
+            {
+                base.extend(other);
+            }
+            (base, other) => *base = other,
+        }
+    }
+}
@@ -4,16 +4,84 @@ pub use crate::value::big_decimal::BigDecimal;
 use crate::value::big_decimal::BigDecimalVisitor;
 pub use crate::value::big_integer::BigInteger;
 use crate::value::big_integer::BigIntegerVisitor;
-use indexmap::IndexMap;
-use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+#[cfg(feature = "bytes")]
+pub use crate::value::binary::from_bytes;
+pub use crate::value::binary::BinaryBuf;
+pub use crate::value::canonical::HashValue;
+pub use crate::value::diff::{diff, Difference, DifferenceKind};
+pub use crate::value::index::Index;
+pub use crate::value::iter::{IntoIter, Iter, IterMut};
+#[cfg(feature = "json")]
+pub use crate::value::json_value::JsonBinary;
+pub use crate::value::limits::{from_reader_with_limits, from_slice_with_limits, Limits};
+pub use crate::value::map::Map;
+pub use crate::value::merge::{ArrayMerge, MergeOptions};
+pub use crate::value::normalize::NormalizeOptions;
+pub use crate::value::number::Number;
+pub use crate::value::pretty::Pretty;
+pub use crate::value::preview::{preview, Preview, PreviewLimits};
+pub use crate::value::serialized_size::SizeOptions;
+pub use crate::value::string::StringBuf;
+#[cfg(feature = "arc-strings")]
+pub use crate::value::string::{from_reader_with_shared_strings, from_slice_with_shared_strings};
+use crate::value::synthetic_key::{SyntheticKey, SyntheticMap};
+pub use crate::value::value_deserializer::from_value;
+pub use crate::value::value_ref::ValueRef;
+pub use crate::value::value_serializer::to_value;
+pub use crate::value::walk::{Path, WalkControl};
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
 use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
 
-mod big_decimal;
-mod big_integer;
+pub(crate) mod big_decimal;
+pub(crate) mod big_integer;
+mod binary;
+mod canonical;
+#[cfg(feature = "bigdecimal")]
+mod decimal;
+mod deep_size;
+mod diff;
+mod from;
+mod index;
+mod iter;
+pub(crate) mod json;
+#[cfg(feature = "json")]
+mod json_value;
+mod limits;
+mod macros;
+pub mod map;
+mod merge;
+mod normalize;
+#[cfg(feature = "num-bigint")]
+mod num;
+mod number;
+mod partial_eq;
+mod pointer;
+mod pretty;
+mod preview;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "schemars")]
+mod schemars;
+mod serialized_size;
+mod string;
+pub(crate) mod synthetic_key;
+mod try_from;
+mod value_deserializer;
+mod value_ref;
+mod value_serializer;
+mod walk;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::value::macros::from_literal;
+}
 
 /// A representation of a Smile value.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Value {
     /// A null value.
     Null,
@@ -32,13 +100,356 @@ pub enum Value {
     /// A big decimal value.
     BigDecimal(BigDecimal),
     /// A string value.
-    String(String),
+    String(StringBuf),
     /// A binary value.
-    Binary(Vec<u8>),
+    Binary(BinaryBuf),
     /// An array value.
     Array(Vec<Value>),
     /// An object value.
-    Object(IndexMap<String, Value>),
+    Object(Map),
+}
+
+/// The kind of a [`Value`], without its contents.
+///
+/// The `Display` implementation produces a lowercase description suitable for use in error messages.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ValueKind {
+    /// A null value.
+    Null,
+    /// A boolean value.
+    Boolean,
+    /// An integer value.
+    Integer,
+    /// A long value.
+    Long,
+    /// A big integer value.
+    BigInteger,
+    /// A float value.
+    Float,
+    /// A double value.
+    Double,
+    /// A big decimal value.
+    BigDecimal,
+    /// A string value.
+    String,
+    /// A binary value.
+    Binary,
+    /// An array value.
+    Array,
+    /// An object value.
+    Object,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ValueKind::Null => "null",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Integer => "integer",
+            ValueKind::Long => "long",
+            ValueKind::BigInteger => "big integer",
+            ValueKind::Float => "float",
+            ValueKind::Double => "double",
+            ValueKind::BigDecimal => "big decimal",
+            ValueKind::String => "string",
+            ValueKind::Binary => "binary",
+            ValueKind::Array => "array",
+            ValueKind::Object => "object",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Returns `Value::Null`.
+impl Default for Value {
+    #[inline]
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+impl Value {
+    /// Returns an empty object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::smile;
+    /// use serde_smile::value::Value;
+    ///
+    /// let mut value = Value::object();
+    /// value["a"] = Value::from(1);
+    /// assert_eq!(value, smile!({"a": 1}));
+    /// ```
+    #[inline]
+    pub fn object() -> Self {
+        Value::Object(Map::new())
+    }
+
+    /// Returns an empty object with space for at least `capacity` entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::Value;
+    ///
+    /// let value = Value::object_with_capacity(10);
+    /// assert!(value.as_object().unwrap().is_empty());
+    /// ```
+    #[inline]
+    pub fn object_with_capacity(capacity: usize) -> Self {
+        Value::Object(Map::with_capacity(capacity))
+    }
+
+    /// Returns an empty array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::smile;
+    /// use serde_smile::value::Value;
+    ///
+    /// let mut value = Value::array();
+    /// value.extend(vec![Value::from("a")]);
+    /// assert_eq!(value, smile!(["a"]));
+    /// ```
+    #[inline]
+    pub fn array() -> Self {
+        Value::Array(vec![])
+    }
+
+    /// Returns an empty array with space for at least `capacity` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::Value;
+    ///
+    /// let value = Value::array_with_capacity(10);
+    /// assert!(value.as_array().unwrap().capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn array_with_capacity(capacity: usize) -> Self {
+        Value::Array(Vec::with_capacity(capacity))
+    }
+
+    /// Returns a string value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::Value;
+    ///
+    /// assert_eq!(Value::string("hi"), Value::String("hi".into()));
+    /// ```
+    #[inline]
+    pub fn string<T>(v: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Value::String(string::from_string(v.into()))
+    }
+
+    /// Returns a binary value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::Value;
+    ///
+    /// assert_eq!(Value::binary(&b"hi"[..]), Value::Binary(b"hi".to_vec().into()));
+    /// ```
+    #[inline]
+    pub fn binary<T>(v: T) -> Self
+    where
+        T: Into<Vec<u8>>,
+    {
+        Value::Binary(binary::from_vec(v.into()))
+    }
+
+    /// Returns a numeric value.
+    ///
+    /// The variant is determined by the [`Number`] conversion of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::value::Value;
+    ///
+    /// assert_eq!(Value::number(1u8), Value::Integer(1));
+    /// assert_eq!(Value::number(1.5), Value::Double(1.5));
+    /// ```
+    #[inline]
+    pub fn number<T>(v: T) -> Self
+    where
+        T: Into<Number>,
+    {
+        Value::from(v.into())
+    }
+
+    /// Returns the kind of the value.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::Null => ValueKind::Null,
+            Value::Boolean(_) => ValueKind::Boolean,
+            Value::Integer(_) => ValueKind::Integer,
+            Value::Long(_) => ValueKind::Long,
+            Value::BigInteger(_) => ValueKind::BigInteger,
+            Value::Float(_) => ValueKind::Float,
+            Value::Double(_) => ValueKind::Double,
+            Value::BigDecimal(_) => ValueKind::BigDecimal,
+            Value::String(_) => ValueKind::String,
+            Value::Binary(_) => ValueKind::Binary,
+            Value::Array(_) => ValueKind::Array,
+            Value::Object(_) => ValueKind::Object,
+        }
+    }
+
+    /// Returns `true` if the value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns `true` if the value is a boolean.
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, Value::Boolean(_))
+    }
+
+    /// Returns `true` if the value is a number of any kind.
+    pub fn is_number(&self) -> bool {
+        self.is_integral() || self.is_floating()
+    }
+
+    /// Returns `true` if the value is an `Integer`, `Long`, or `BigInteger`.
+    pub fn is_integral(&self) -> bool {
+        matches!(
+            self,
+            Value::Integer(_) | Value::Long(_) | Value::BigInteger(_)
+        )
+    }
+
+    /// Returns `true` if the value is a `Float`, `Double`, or `BigDecimal`.
+    pub fn is_floating(&self) -> bool {
+        matches!(
+            self,
+            Value::Float(_) | Value::Double(_) | Value::BigDecimal(_)
+        )
+    }
+
+    /// Returns `true` if the value is a string.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    /// Returns `true` if the value is binary.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Value::Binary(_))
+    }
+
+    /// Returns `true` if the value is an array.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    /// Returns `true` if the value is an object.
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
+    }
+
+    /// If the value is a string, returns a reference to it.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// If the value is a boolean, returns it.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// If the value is an integer which fits in an `i64`, returns it.
+    ///
+    /// `Integer`, `Long`, and `BigInteger` values are supported.
+    pub fn as_i64(&self) -> Option<i64> {
+        number::as_i64(self)
+    }
+
+    /// If the value is an integer which fits in a `u64`, returns it.
+    ///
+    /// `Integer`, `Long`, and `BigInteger` values are supported.
+    pub fn as_u64(&self) -> Option<u64> {
+        number::as_u64(self)
+    }
+
+    /// If the value is a number which can be represented exactly as an `f64`, returns it.
+    ///
+    /// `Float` and `Double` values are always supported. `Integer`, `Long`, and `BigInteger` values are supported if
+    /// the conversion is lossless.
+    pub fn as_f64(&self) -> Option<f64> {
+        number::as_f64(self)
+    }
+
+    /// If the value is binary, returns a reference to its bytes.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Binary(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// If the value is an array, returns a reference to its elements.
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Value::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// If the value is an object, returns a reference to its entries.
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Value::Object(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to a value in an array or object.
+    ///
+    /// A string index can be used to access a value in an object, and a `usize` index can be used to access an
+    /// element of an array.
+    ///
+    /// Returns `None` if the type of `self` does not match the type of the index, or if the key or index is not
+    /// present.
+    pub fn get<I>(&self, index: I) -> Option<&Value>
+    where
+        I: Index,
+    {
+        index.index_into(self)
+    }
+
+    /// Returns a mutable reference to a value in an array or object.
+    ///
+    /// A string index can be used to access a value in an object, and a `usize` index can be used to access an
+    /// element of an array.
+    ///
+    /// Returns `None` if the type of `self` does not match the type of the index, or if the key or index is not
+    /// present.
+    pub fn get_mut<I>(&mut self, index: I) -> Option<&mut Value>
+    where
+        I: Index,
+    {
+        index.index_into_mut(self)
+    }
+
+    /// Takes the value out, leaving `Value::Null` in its place.
+    pub fn take(&mut self) -> Value {
+        mem::replace(self, Value::Null)
+    }
 }
 
 impl Serialize for Value {
@@ -65,6 +476,41 @@ impl Serialize for Value {
 
 impl<'de> Deserialize<'de> for Value {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        ValueSeed {
+            source: Source::default(),
+            strings: Strings::default(),
+        }
+        .deserialize(deserializer)
+    }
+}
+
+/// The buffer being deserialized from, which binary values can share rather than copying out of.
+#[cfg(feature = "bytes")]
+pub(crate) type Source<'a> = Option<&'a bytes::Bytes>;
+
+#[cfg(not(feature = "bytes"))]
+pub(crate) type Source<'a> = PhantomData<&'a ()>;
+
+/// The back-referenced strings decoded so far, which string values can share rather than allocating.
+#[cfg(feature = "arc-strings")]
+pub(crate) type Strings<'a> = Option<&'a string::SharedStrings>;
+
+#[cfg(not(feature = "arc-strings"))]
+pub(crate) type Strings<'a> = PhantomData<&'a ()>;
+
+#[derive(Copy, Clone)]
+pub(crate) struct ValueSeed<'a> {
+    pub(crate) source: Source<'a>,
+    pub(crate) strings: Strings<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for ValueSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -75,14 +521,20 @@ impl<'de> Deserialize<'de> for Value {
         deserializer.deserialize_struct(
             BigInteger::STRUCT_NAME,
             &[BigInteger::FIELD_NAME],
-            ValueVisitor,
+            ValueVisitor {
+                source: self.source,
+                strings: self.strings,
+            },
         )
     }
 }
 
-struct ValueVisitor;
+struct ValueVisitor<'a> {
+    source: Source<'a>,
+    strings: Strings<'a>,
+}
 
-impl<'de> Visitor<'de> for ValueVisitor {
+impl<'de> Visitor<'de> for ValueVisitor<'_> {
     type Value = Value;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -110,6 +562,33 @@ impl<'de> Visitor<'de> for ValueVisitor {
         Ok(Value::Long(v))
     }
 
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Ok(Value::BigInteger(BigInteger::from(v))),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_u128(u128::from(v))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Ok(Value::BigInteger(BigInteger::from(v))),
+        }
+    }
+
     fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -128,28 +607,43 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::String(v.to_string()))
+        #[cfg(feature = "arc-strings")]
+        if let Some(strings) = self.strings {
+            return Ok(Value::String(strings.get(v)));
+        }
+        Ok(Value::String(string::from_str(v)))
     }
 
     fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Ok(Value::String(v))
+        Ok(Value::String(string::from_string(v)))
     }
 
     fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Ok(Value::Binary(v.to_vec()))
+        Ok(Value::Binary(binary::from_slice(v)))
+    }
+
+    #[cfg(feature = "bytes")]
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.source {
+            Some(source) => Ok(Value::Binary(binary::share(source, v))),
+            None => self.visit_bytes(v),
+        }
     }
 
     fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Ok(Value::Binary(v))
+        Ok(Value::Binary(binary::from_vec(v)))
     }
 
     fn visit_unit<E>(self) -> Result<Self::Value, E>
@@ -163,8 +657,12 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         A: SeqAccess<'de>,
     {
+        let seed = ValueSeed {
+            source: self.source,
+            strings: self.strings,
+        };
         let mut vec = vec![];
-        while let Some(value) = seq.next_element()? {
+        while let Some(value) = seq.next_element_seed(seed)? {
             vec.push(value);
         }
         Ok(Value::Array(vec))
@@ -174,7 +672,11 @@ impl<'de> Visitor<'de> for ValueVisitor {
     where
         A: MapAccess<'de>,
     {
-        let mut v = IndexMap::new();
+        let seed = ValueSeed {
+            source: self.source,
+            strings: self.strings,
+        };
+        let mut v = Map::new();
 
         let mut key = String::new();
         match map.next_key_seed(FirstKeySeed { key: &mut key })? {
@@ -188,8 +690,8 @@ impl<'de> Visitor<'de> for ValueVisitor {
             None => return Ok(Value::Object(v)),
         }
 
-        v.insert(key, map.next_value()?);
-        while let Some((key, value)) = map.next_entry()? {
+        v.insert(key, map.next_value_seed(seed)?);
+        while let Some((key, value)) = map.next_entry_seed(PhantomData, seed)? {
             v.insert(key, value);
         }
 
@@ -214,7 +716,7 @@ impl<'de> DeserializeSeed<'de> for FirstKeySeed<'_> {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(self)
+        deserializer.deserialize_newtype_struct(SyntheticKey::MARKER, self)
     }
 }
 
@@ -229,27 +731,33 @@ impl<'de> Visitor<'de> for FirstKeySeed<'_> {
     where
         E: de::Error,
     {
-        match v {
-            BigInteger::FIELD_NAME => Ok(FirstKey::BigInteger),
-            BigDecimal::SCALE_FIELD_NAME => Ok(FirstKey::BigDecimal),
-            _ => {
-                self.key.push_str(v);
-                Ok(FirstKey::Other)
-            }
-        }
+        self.key.push_str(v);
+        Ok(FirstKey::Other)
     }
 
     fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        match &*v {
-            BigInteger::FIELD_NAME => Ok(FirstKey::BigInteger),
-            BigDecimal::SCALE_FIELD_NAME => Ok(FirstKey::BigDecimal),
-            _ => {
-                *self.key = v;
-                Ok(FirstKey::Other)
-            }
+        *self.key = v;
+        Ok(FirstKey::Other)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+
+    // only the keys of synthetic maps respond to the marker with an enum
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match SyntheticMap::from_enum(data)? {
+            SyntheticMap::BigInteger => Ok(FirstKey::BigInteger),
+            SyntheticMap::BigDecimal => Ok(FirstKey::BigDecimal),
         }
     }
 }
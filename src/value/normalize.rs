@@ -0,0 +1,116 @@
+use crate::value::big_integer::{self, NativeInteger};
+use crate::value::Value;
+use std::convert::TryFrom;
+
+/// Options for [`Value::normalize_numbers_with`].
+#[derive(Clone, Debug, Default)]
+pub struct NormalizeOptions {
+    narrow_doubles: bool,
+}
+
+impl NormalizeOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        NormalizeOptions::default()
+    }
+
+    /// If enabled, `Double` values which convert to `f32` and back without changing their bits are rewritten as
+    /// `Float`s.
+    ///
+    /// Defaults to `false`.
+    pub fn narrow_doubles(&mut self, narrow_doubles: bool) -> &mut Self {
+        self.narrow_doubles = narrow_doubles;
+        self
+    }
+}
+
+impl Value {
+    /// Rewrites every number in the value to its smallest lossless representation with default options.
+    ///
+    /// See [`Value::normalize_numbers_with`] for details.
+    pub fn normalize_numbers(&mut self) {
+        self.normalize_numbers_with(&NormalizeOptions::new())
+    }
+
+    /// Rewrites every number in the value to its smallest lossless representation.
+    ///
+    /// `BigInteger` and `Long` values are rewritten as `Long`s if they fit in an `i64`, and as `Integer`s if they fit
+    /// in an `i32`. `Double` values are rewritten as `Float`s only if [`NormalizeOptions::narrow_doubles`] is enabled.
+    /// `BigDecimal` values are left unchanged. Arrays and objects are normalized recursively.
+    ///
+    /// This is useful when comparing values produced by encoders which disagree about the widths of numbers.
+    pub fn normalize_numbers_with(&mut self, options: &NormalizeOptions) {
+        match self {
+            Value::Long(_) | Value::BigInteger(_) => self.narrow_integer(),
+            Value::Double(v) if options.narrow_doubles => {
+                let narrowed = *v as f32;
+                if f64::from(narrowed).to_bits() == v.to_bits() {
+                    *self = Value::Float(narrowed);
+                }
+            }
+            Value::Array(values) => {
+                for value in values {
+                    value.normalize_numbers_with(options);
+                }
+            }
+            Value::Object(map) => {
+                for value in map.values_mut() {
+                    value.normalize_numbers_with(options);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Rewrites every number in the value to a canonical representation.
+    ///
+    /// Integers are narrowed as by [`Value::normalize_numbers`], and `BigInteger` values too large for an `i64` have
+    /// redundant sign extension bytes removed. `BigDecimal` values have trailing zeros removed from their unscaled
+    /// values, reducing their scales to match, so `1.50` becomes `1.5` and `100` becomes `1E+2`; zero always has a
+    /// scale of 0. Floating point numbers are left unchanged. Arrays and objects are canonicalized recursively.
+    ///
+    /// Two values holding the same numbers in different representations compare equal after canonicalization. Object
+    /// entries are not reordered; [`HashValue`](crate::value::HashValue) can be used to compare values independently
+    /// of entry order.
+    pub fn canonicalize(&mut self) {
+        match self {
+            Value::Long(_) => self.narrow_integer(),
+            Value::BigInteger(v) => {
+                v.normalize();
+                self.narrow_integer();
+            }
+            Value::BigDecimal(v) => *v = v.normalized(),
+            Value::Array(values) => {
+                for value in values {
+                    value.canonicalize();
+                }
+            }
+            Value::Object(map) => {
+                for value in map.values_mut() {
+                    value.canonicalize();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn narrow_integer(&mut self) {
+        let v = match self {
+            Value::Long(v) => *v,
+            Value::BigInteger(v) => {
+                match NativeInteger::from_be_bytes(big_integer::strip_sign_extension(
+                    v.as_be_bytes(),
+                )) {
+                    Some(NativeInteger::I64(v)) => v,
+                    _ => return,
+                }
+            }
+            _ => return,
+        };
+
+        *self = match i32::try_from(v) {
+            Ok(v) => Value::Integer(v),
+            Err(_) => Value::Long(v),
+        };
+    }
+}
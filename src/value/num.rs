@@ -0,0 +1,70 @@
+use crate::value::BigInteger;
+use crate::Error;
+use num_bigint::{BigInt, BigUint, Sign};
+use std::convert::TryFrom;
+
+impl From<BigInt> for BigInteger {
+    #[inline]
+    fn from(v: BigInt) -> Self {
+        BigInteger::from(&v)
+    }
+}
+
+impl From<&BigInt> for BigInteger {
+    fn from(v: &BigInt) -> Self {
+        BigInteger::from_be_bytes(v.to_signed_bytes_be())
+    }
+}
+
+impl From<BigUint> for BigInteger {
+    #[inline]
+    fn from(v: BigUint) -> Self {
+        BigInteger::from(&v)
+    }
+}
+
+impl From<&BigUint> for BigInteger {
+    fn from(v: &BigUint) -> Self {
+        let mut buf = v.to_bytes_be();
+        // a leading zero byte is needed to keep values with the high bit set positive
+        if buf[0] & 0x80 != 0 {
+            buf.insert(0, 0);
+        }
+        BigInteger::from_be_bytes(buf)
+    }
+}
+
+impl From<BigInteger> for BigInt {
+    #[inline]
+    fn from(v: BigInteger) -> Self {
+        BigInt::from(&v)
+    }
+}
+
+impl From<&BigInteger> for BigInt {
+    fn from(v: &BigInteger) -> Self {
+        BigInt::from_signed_bytes_be(v.as_be_bytes())
+    }
+}
+
+impl TryFrom<BigInteger> for BigUint {
+    type Error = Error;
+
+    /// Converts non-negative values.
+    #[inline]
+    fn try_from(v: BigInteger) -> Result<Self, Self::Error> {
+        BigUint::try_from(&v)
+    }
+}
+
+impl TryFrom<&BigInteger> for BigUint {
+    type Error = Error;
+
+    /// Converts non-negative values.
+    fn try_from(v: &BigInteger) -> Result<Self, Self::Error> {
+        match BigInt::from(v).into_parts() {
+            (Sign::Minus, _) => Err(Error::value_out_of_range("BigUint")),
+            (_, v) => Ok(v),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::value::big_integer::NativeInteger;
+use crate::value::json::write_big_decimal;
+use crate::value::{BigDecimal, BigInteger, Value};
+use serde::{Serialize, Serializer};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A numeric Smile value of any kind.
+///
+/// This wraps the `Integer`, `Long`, `BigInteger`, `Float`, `Double`, and `BigDecimal` variants of [`Value`] so that
+/// numeric data can be handled without matching on each of them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Number(Value);
+
+impl Number {
+    /// Returns `true` if the number is an `Integer`, `Long`, or `BigInteger`.
+    pub fn is_integer(&self) -> bool {
+        self.0.is_integral()
+    }
+
+    /// Returns `true` if the number is a `Float`, `Double`, or `BigDecimal`.
+    pub fn is_float(&self) -> bool {
+        self.0.is_floating()
+    }
+
+    /// If the number is an integer which fits in an `i64`, returns it.
+    pub fn as_i64(&self) -> Option<i64> {
+        as_i64(&self.0)
+    }
+
+    /// If the number is an integer which fits in a `u64`, returns it.
+    pub fn as_u64(&self) -> Option<u64> {
+        as_u64(&self.0)
+    }
+
+    /// If the number can be represented exactly as an `f64`, returns it.
+    ///
+    /// `Float` and `Double` values are always supported. `Integer`, `Long`, and `BigInteger` values are supported if
+    /// the conversion is lossless. `BigDecimal` values are not supported.
+    pub fn as_f64(&self) -> Option<f64> {
+        as_f64(&self.0)
+    }
+
+    /// Returns a reference to the underlying [`Value`].
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+}
+
+impl Value {
+    /// If the value is a number of any kind, returns it as a [`Number`].
+    pub fn as_number(&self) -> Option<Number> {
+        if self.is_number() {
+            Some(Number(self.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(v) => Some(i64::from(*v)),
+        Value::Long(v) => Some(*v),
+        Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+            Some(NativeInteger::I64(v)) => Some(v),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub(crate) fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Integer(v) => u64::try_from(*v).ok(),
+        Value::Long(v) => u64::try_from(*v).ok(),
+        Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+            Some(NativeInteger::I64(v)) => u64::try_from(v).ok(),
+            Some(NativeInteger::U64(v)) => Some(v),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub(crate) fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(v) => Some(f64::from(*v)),
+        Value::Long(v) => i64_as_f64(*v),
+        Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+            Some(NativeInteger::I64(v)) => i64_as_f64(v),
+            Some(NativeInteger::U64(v)) => {
+                let f = v as f64;
+                // u64::MAX rounds up to 2^64, which saturates back to u64::MAX when cast
+                if f < 18_446_744_073_709_551_616.0 && f as u64 == v {
+                    Some(f)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        Value::Float(v) => Some(f64::from(*v)),
+        Value::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn i64_as_f64(v: i64) -> Option<f64> {
+    let f = v as f64;
+    // i64::MAX rounds up to 2^63, which saturates back to i64::MAX when cast
+    if f < 9_223_372_036_854_775_808.0 && f as i64 == v {
+        Some(f)
+    } else {
+        None
+    }
+}
+
+macro_rules! from_native {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Number {
+                #[inline]
+                fn from(v: $t) -> Self {
+                    Number(Value::from(v))
+                }
+            }
+        )*
+    };
+}
+
+from_native!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl From<BigInteger> for Number {
+    #[inline]
+    fn from(v: BigInteger) -> Self {
+        Number(Value::BigInteger(v))
+    }
+}
+
+impl From<BigDecimal> for Number {
+    #[inline]
+    fn from(v: BigDecimal) -> Self {
+        Number(Value::BigDecimal(v))
+    }
+}
+
+impl From<Number> for Value {
+    #[inline]
+    fn from(v: Number) -> Self {
+        v.0
+    }
+}
+
+impl TryFrom<Value> for Number {
+    type Error = Value;
+
+    /// Returns the value back if it is not a number.
+    fn try_from(v: Value) -> Result<Self, Self::Error> {
+        if v.is_number() {
+            Ok(Number(v))
+        } else {
+            Err(v)
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    /// Floating point values are formatted as by their `Debug` implementations, and `BigDecimal` values in the format
+    /// of Java's `BigDecimal.toString`.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Value::Integer(v) => write!(fmt, "{}", v),
+            Value::Long(v) => write!(fmt, "{}", v),
+            Value::Float(v) => write!(fmt, "{:?}", v),
+            Value::Double(v) => write!(fmt, "{:?}", v),
+            Value::BigInteger(v) => write!(fmt, "{}", v),
+            Value::BigDecimal(v) => {
+                let mut out = String::new();
+                write_big_decimal(&mut out, v);
+                fmt.write_str(&out)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
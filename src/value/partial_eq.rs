@@ -0,0 +1,87 @@
+use crate::value::Value;
+
+fn eq_str(value: &Value, other: &str) -> bool {
+    value.as_str() == Some(other)
+}
+
+fn eq_bool(value: &Value, other: bool) -> bool {
+    value.as_bool() == Some(other)
+}
+
+fn eq_i64(value: &Value, other: i64) -> bool {
+    value.as_i64() == Some(other)
+}
+
+fn eq_u64(value: &Value, other: u64) -> bool {
+    value.as_u64() == Some(other)
+}
+
+// Unlike as_f64, this does not consider integral values.
+fn eq_f64(value: &Value, other: f64) -> bool {
+    match value {
+        Value::Float(v) => f64::from(*v) == other,
+        Value::Double(v) => *v == other,
+        _ => false,
+    }
+}
+
+impl PartialEq<str> for Value {
+    fn eq(&self, other: &str) -> bool {
+        eq_str(self, other)
+    }
+}
+
+impl PartialEq<&str> for Value {
+    fn eq(&self, other: &&str) -> bool {
+        eq_str(self, other)
+    }
+}
+
+impl PartialEq<Value> for str {
+    fn eq(&self, other: &Value) -> bool {
+        eq_str(other, self)
+    }
+}
+
+impl PartialEq<Value> for &str {
+    fn eq(&self, other: &Value) -> bool {
+        eq_str(other, self)
+    }
+}
+
+impl PartialEq<String> for Value {
+    fn eq(&self, other: &String) -> bool {
+        eq_str(self, other)
+    }
+}
+
+impl PartialEq<Value> for String {
+    fn eq(&self, other: &Value) -> bool {
+        eq_str(other, self)
+    }
+}
+
+macro_rules! partial_eq_primitive {
+    ($($eq:ident [$($t:ty)*])*) => {
+        $($(
+            impl PartialEq<$t> for Value {
+                fn eq(&self, other: &$t) -> bool {
+                    $eq(self, (*other).into())
+                }
+            }
+
+            impl PartialEq<Value> for $t {
+                fn eq(&self, other: &Value) -> bool {
+                    $eq(other, (*self).into())
+                }
+            }
+        )*)*
+    };
+}
+
+partial_eq_primitive! {
+    eq_bool[bool]
+    eq_i64[i32 i64]
+    eq_u64[u64]
+    eq_f64[f64]
+}
@@ -0,0 +1,59 @@
+use crate::value::Value;
+
+impl Value {
+    /// Looks up a value by a JSON Pointer.
+    ///
+    /// JSON Pointer, defined in [RFC 6901], is a string syntax for identifying a specific value within a document. A
+    /// pointer is a sequence of `/`-prefixed reference tokens, where `~1` and `~0` escape `/` and `~` respectively.
+    /// Tokens address object keys, or array elements when they are a non-negative integer without leading zeros.
+    ///
+    /// The empty pointer refers to the whole value. `None` is returned if the pointer is malformed or does not refer
+    /// to an existing value.
+    ///
+    /// [RFC 6901]: https://tools.ietf.org/html/rfc6901
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Value::Object(map) => map.get(&*token)?,
+                Value::Array(vec) => vec.get(parse_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+
+    /// Looks up a value by a JSON Pointer and returns a mutable reference to it.
+    ///
+    /// See [`Value::pointer`] for details.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Value::Object(map) => map.get_mut(&*token)?,
+                Value::Array(vec) => vec.get_mut(parse_index(&token)?)?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+}
+
+fn tokens(pointer: &str) -> Option<impl Iterator<Item = String> + '_> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+
+    Some(pointer.split('/').skip(1).map(unescape))
+}
+
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() != 1) {
+        return None;
+    }
+    token.parse().ok()
+}
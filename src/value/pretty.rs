@@ -0,0 +1,136 @@
+use crate::encoding;
+use crate::value::json::{write_big_decimal, write_big_integer, write_str};
+use crate::value::Value;
+use std::fmt::{self, Write};
+
+const DEFAULT_MAX_BINARY_BYTES: usize = 32;
+
+impl Value {
+    /// Returns a [`Display`](fmt::Display) adapter which renders the value in an indented, human-readable form.
+    ///
+    /// See [`Pretty`] for details of the format.
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty {
+            value: self,
+            max_binary_bytes: DEFAULT_MAX_BINARY_BYTES,
+        }
+    }
+
+    /// Renders the value in an indented, human-readable form.
+    ///
+    /// This is equivalent to `value.pretty().to_string()`.
+    pub fn to_string_pretty(&self) -> String {
+        self.pretty().to_string()
+    }
+}
+
+/// A [`Display`](fmt::Display) adapter which renders a [`Value`] in an indented, human-readable form.
+///
+/// The output is intended for inspecting and diffing values rather than parsing. It resembles JSON, but distinguishes
+/// each Smile type:
+///
+/// * Arrays and objects place each element on its own line, indented by two spaces per level. Object entries are
+///   rendered in their stored order.
+/// * `Integer` and `Double` values are written as plain numbers. `Long`, `Float`, `BigInteger`, and `BigDecimal`
+///   values have the suffixes `L`, `F`, `BI`, and `BD` respectively.
+/// * `Binary` values are written as lowercase hex enclosed in `<` and `>`. Values longer than
+///   [`Pretty::max_binary_bytes`] are truncated, with the full length appended.
+/// * Strings are quoted and escaped as in JSON.
+pub struct Pretty<'a> {
+    value: &'a Value,
+    max_binary_bytes: usize,
+}
+
+impl Pretty<'_> {
+    /// Sets the maximum number of bytes of each `Binary` value to render.
+    ///
+    /// Defaults to 32.
+    pub fn max_binary_bytes(&mut self, max_binary_bytes: usize) -> &mut Self {
+        self.max_binary_bytes = max_binary_bytes;
+        self
+    }
+}
+
+impl fmt::Display for Pretty<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        self.write_value(&mut out, self.value, 0);
+        fmt.write_str(&out)
+    }
+}
+
+impl Pretty<'_> {
+    fn write_value(&self, out: &mut String, value: &Value, depth: usize) {
+        match value {
+            Value::Null => out.push_str("null"),
+            Value::Boolean(true) => out.push_str("true"),
+            Value::Boolean(false) => out.push_str("false"),
+            Value::Integer(v) => write!(out, "{}", v).unwrap(),
+            Value::Long(v) => write!(out, "{}L", v).unwrap(),
+            Value::BigInteger(v) => {
+                write_big_integer(out, v);
+                out.push_str("BI");
+            }
+            Value::Float(v) => write!(out, "{:?}F", v).unwrap(),
+            Value::Double(v) => write!(out, "{:?}", v).unwrap(),
+            Value::BigDecimal(v) => {
+                write_big_decimal(out, v);
+                out.push_str("BD");
+            }
+            Value::String(v) => write_str(out, v),
+            Value::Binary(v) => {
+                out.push('<');
+                if v.len() > self.max_binary_bytes {
+                    encoding::encode_hex(out, &v[..self.max_binary_bytes]);
+                    write!(out, "...> ({} bytes)", v.len()).unwrap();
+                } else {
+                    encoding::encode_hex(out, v);
+                    out.push('>');
+                }
+            }
+            Value::Array(v) => {
+                if v.is_empty() {
+                    out.push_str("[]");
+                    return;
+                }
+
+                out.push('[');
+                for (i, value) in v.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    newline(out, depth + 1);
+                    self.write_value(out, value, depth + 1);
+                }
+                newline(out, depth);
+                out.push(']');
+            }
+            Value::Object(v) => {
+                if v.is_empty() {
+                    out.push_str("{}");
+                    return;
+                }
+
+                out.push('{');
+                for (i, (key, value)) in v.iter().enumerate() {
+                    if i != 0 {
+                        out.push(',');
+                    }
+                    newline(out, depth + 1);
+                    write_str(out, key);
+                    out.push_str(": ");
+                    self.write_value(out, value, depth + 1);
+                }
+                newline(out, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn newline(out: &mut String, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
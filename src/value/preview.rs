@@ -0,0 +1,403 @@
+use crate::de::{Deserializer, SliceRead};
+use crate::value::synthetic_key::{SyntheticKey, SyntheticMap};
+use crate::value::{
+    BigDecimalVisitor, BigInteger, BigIntegerVisitor, Source, Strings, Value, ValueVisitor,
+};
+use crate::Error;
+use serde::de::{self, DeserializeSeed, EnumAccess, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::fmt;
+
+/// Truncation limits for [`preview`].
+#[derive(Clone, Debug)]
+pub struct PreviewLimits {
+    max_string_bytes: usize,
+    max_binary_bytes: usize,
+    max_container_elements: usize,
+    max_total_nodes: usize,
+}
+
+impl Default for PreviewLimits {
+    fn default() -> Self {
+        PreviewLimits {
+            max_string_bytes: usize::MAX,
+            max_binary_bytes: usize::MAX,
+            max_container_elements: usize::MAX,
+            max_total_nodes: usize::MAX,
+        }
+    }
+}
+
+impl PreviewLimits {
+    /// Returns limits which never truncate.
+    pub fn new() -> Self {
+        PreviewLimits::default()
+    }
+
+    /// Sets the maximum length in bytes of string values before they are truncated.
+    ///
+    /// Truncated strings are cut at a character boundary, so their prefix may be slightly shorter than the limit.
+    /// Object keys are never truncated.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_string_bytes(&mut self, max_string_bytes: usize) -> &mut Self {
+        self.max_string_bytes = max_string_bytes;
+        self
+    }
+
+    /// Sets the maximum length in bytes of binary values before they are truncated.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_binary_bytes(&mut self, max_binary_bytes: usize) -> &mut Self {
+        self.max_binary_bytes = max_binary_bytes;
+        self
+    }
+
+    /// Sets the maximum number of elements of an array or entries of an object which are included in the preview.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_container_elements(&mut self, max_container_elements: usize) -> &mut Self {
+        self.max_container_elements = max_container_elements;
+        self
+    }
+
+    /// Sets the maximum number of nodes in the entire preview, including arrays and objects themselves.
+    ///
+    /// Once the limit is reached, the remaining elements of every open array and object are omitted. The root value
+    /// is always included, even if the limit is 0.
+    ///
+    /// Defaults to `usize::MAX`.
+    pub fn max_total_nodes(&mut self, max_total_nodes: usize) -> &mut Self {
+        self.max_total_nodes = max_total_nodes;
+        self
+    }
+}
+
+/// A possibly truncated view of a Smile value, produced by [`preview`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum Preview {
+    /// A value which was decoded in full.
+    ///
+    /// This is never an array or object; those are always represented by [`Preview::Array`] and
+    /// [`Preview::Object`].
+    Value(Value),
+    /// A string which was truncated.
+    TruncatedString {
+        /// The start of the string.
+        prefix: String,
+        /// The length of the full string in bytes.
+        len: usize,
+    },
+    /// A binary value which was truncated.
+    TruncatedBinary {
+        /// The start of the binary value.
+        prefix: Vec<u8>,
+        /// The length of the full binary value in bytes.
+        len: usize,
+    },
+    /// An array.
+    Array {
+        /// The leading elements of the array.
+        elements: Vec<Preview>,
+        /// The number of trailing elements which were omitted.
+        omitted: usize,
+    },
+    /// An object.
+    Object {
+        /// The leading entries of the object, in document order.
+        entries: Vec<(String, Preview)>,
+        /// The number of trailing entries which were omitted.
+        omitted: usize,
+    },
+}
+
+impl Preview {
+    /// Determines if any part of the value was truncated or omitted.
+    pub fn is_truncated(&self) -> bool {
+        match self {
+            Preview::Value(_) => false,
+            Preview::TruncatedString { .. } | Preview::TruncatedBinary { .. } => true,
+            Preview::Array { elements, omitted } => {
+                *omitted != 0 || elements.iter().any(Preview::is_truncated)
+            }
+            Preview::Object { entries, omitted } => {
+                *omitted != 0 || entries.iter().any(|(_, v)| v.is_truncated())
+            }
+        }
+    }
+}
+
+/// Decodes a truncated preview of a slice of Smile data.
+///
+/// Rather than failing when a limit is exceeded like [`from_slice_with_limits`](crate::value::from_slice_with_limits),
+/// the oversized part of the value is truncated or omitted. The rest of the document is still parsed, so shared string
+/// references after an omitted value resolve correctly and an error is returned if the document is malformed, but the
+/// memory used by the preview is bounded by the limits.
+///
+/// # Examples
+///
+/// ```
+/// use serde_smile::value::{self, Preview, PreviewLimits, Value};
+///
+/// let buf = serde_smile::to_vec(&vec![1, 2, 3]).unwrap();
+///
+/// let mut limits = PreviewLimits::new();
+/// limits.max_container_elements(2);
+/// let preview = value::preview(&buf, &limits).unwrap();
+/// assert_eq!(
+///     preview,
+///     Preview::Array {
+///         elements: vec![
+///             Preview::Value(Value::Integer(1)),
+///             Preview::Value(Value::Integer(2)),
+///         ],
+///         omitted: 1,
+///     },
+/// );
+/// ```
+pub fn preview(slice: &[u8], limits: &PreviewLimits) -> Result<Preview, Error> {
+    let state = State {
+        limits,
+        nodes: Cell::new(0),
+    };
+
+    let mut de = Deserializer::new(SliceRead::new(slice));
+    let preview = PreviewSeed { state: &state }.deserialize(&mut de)?;
+    de.end()?;
+    Ok(preview)
+}
+
+struct State<'a> {
+    limits: &'a PreviewLimits,
+    nodes: Cell<usize>,
+}
+
+impl State<'_> {
+    /// Determines if another element can be added to a container which already holds `len` elements.
+    fn has_room(&self, len: usize) -> bool {
+        len < self.limits.max_container_elements && self.nodes.get() < self.limits.max_total_nodes
+    }
+}
+
+#[derive(Copy, Clone)]
+struct PreviewSeed<'a> {
+    state: &'a State<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for PreviewSeed<'_> {
+    type Value = Preview;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.state.nodes.set(self.state.nodes.get() + 1);
+
+        // See ValueSeed for the reason behind the BigInteger hint.
+        deserializer.deserialize_struct(BigInteger::STRUCT_NAME, &[BigInteger::FIELD_NAME], self)
+    }
+}
+
+impl PreviewSeed<'_> {
+    fn inner(&self) -> ValueVisitor<'static> {
+        ValueVisitor {
+            source: Source::default(),
+            strings: Strings::default(),
+        }
+    }
+}
+
+macro_rules! forward_scalar {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.inner().$method(v).map(Preview::Value)
+            }
+        )*
+    };
+}
+
+impl<'de> Visitor<'de> for PreviewSeed<'_> {
+    type Value = Preview;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any Smile value")
+    }
+
+    forward_scalar! {
+        visit_bool(bool),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let max = self.state.limits.max_string_bytes;
+        if v.len() <= max {
+            return self.inner().visit_str(v).map(Preview::Value);
+        }
+
+        let mut end = max;
+        while !v.is_char_boundary(end) {
+            end -= 1;
+        }
+        Ok(Preview::TruncatedString {
+            prefix: v[..end].to_string(),
+            len: v.len(),
+        })
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let max = self.state.limits.max_binary_bytes;
+        if v.len() <= max {
+            return self.inner().visit_bytes(v).map(Preview::Value);
+        }
+
+        Ok(Preview::TruncatedBinary {
+            prefix: v[..max].to_vec(),
+            len: v.len(),
+        })
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner().visit_unit().map(Preview::Value)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut elements = vec![];
+        while self.state.has_room(elements.len()) {
+            match seq.next_element_seed(self)? {
+                Some(element) => elements.push(element),
+                None => {
+                    return Ok(Preview::Array {
+                        elements,
+                        omitted: 0,
+                    })
+                }
+            }
+        }
+
+        let mut omitted = 0;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            omitted += 1;
+        }
+        Ok(Preview::Array { elements, omitted })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = vec![];
+        let mut omitted = 0;
+
+        match map.next_key_seed(KeySeed)? {
+            Some(Key::BigInteger) => {
+                return BigIntegerVisitor
+                    .finish_map(map)
+                    .map(|v| Preview::Value(Value::BigInteger(v)))
+            }
+            Some(Key::BigDecimal) => {
+                return BigDecimalVisitor
+                    .finish_map(map)
+                    .map(|v| Preview::Value(Value::BigDecimal(v)))
+            }
+            Some(Key::Other(key)) => {
+                // we have to read the first key to tell the object apart from a BigInteger or BigDecimal
+                if self.state.has_room(0) {
+                    let value = map.next_value_seed(self)?;
+                    entries.push((key, value));
+                } else {
+                    map.next_value::<IgnoredAny>()?;
+                    omitted += 1;
+                }
+            }
+            None => return Ok(Preview::Object { entries, omitted }),
+        }
+
+        while omitted == 0 && self.state.has_room(entries.len()) {
+            match map.next_key::<String>()? {
+                Some(key) => {
+                    let value = map.next_value_seed(self)?;
+                    entries.push((key, value));
+                }
+                None => return Ok(Preview::Object { entries, omitted }),
+            }
+        }
+
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {
+            omitted += 1;
+        }
+        Ok(Preview::Object { entries, omitted })
+    }
+}
+
+enum Key {
+    BigInteger,
+    BigDecimal,
+    Other(String),
+}
+
+struct KeySeed;
+
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Key;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(SyntheticKey::MARKER, self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed {
+    type Value = Key;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Key::Other(v.to_string()))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match SyntheticMap::from_enum(data)? {
+            SyntheticMap::BigInteger => Ok(Key::BigInteger),
+            SyntheticMap::BigDecimal => Ok(Key::BigDecimal),
+        }
+    }
+}
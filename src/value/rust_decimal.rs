@@ -0,0 +1,50 @@
+use crate::value::{BigDecimal, BigInteger};
+use crate::Error;
+use rust_decimal::Decimal;
+use std::convert::TryFrom;
+
+impl From<Decimal> for BigDecimal {
+    fn from(v: Decimal) -> Self {
+        // the scale of a Decimal is at most 28
+        BigDecimal::new(BigInteger::from(v.mantissa()), v.scale() as i32)
+    }
+}
+
+impl TryFrom<BigDecimal> for Decimal {
+    type Error = Error;
+
+    /// Converts values which fit in a `Decimal`'s 96 bit mantissa and scale of at most 28.
+    #[inline]
+    fn try_from(v: BigDecimal) -> Result<Self, Self::Error> {
+        Decimal::try_from(&v)
+    }
+}
+
+impl TryFrom<&BigDecimal> for Decimal {
+    type Error = Error;
+
+    /// Converts values which fit in a `Decimal`'s 96 bit mantissa and scale of at most 28.
+    ///
+    /// The scale is preserved if possible, so `1.50` converts to a `Decimal` which is also formatted as `1.50`. Values
+    /// which only fit after removing trailing zeros, like `1.000` with a scale of 30, are converted to their normalized
+    /// form.
+    fn try_from(v: &BigDecimal) -> Result<Self, Self::Error> {
+        to_decimal(v)
+            .or_else(|| to_decimal(&v.normalized()))
+            .ok_or_else(|| Error::value_out_of_range("Decimal"))
+    }
+}
+
+fn to_decimal(v: &BigDecimal) -> Option<Decimal> {
+    let mut mantissa = v.unscaled_value().to_i128()?;
+    let mut scale = v.scale();
+
+    // Decimal doesn't support negative scales, so the exponent has to be folded into the mantissa
+    if scale < 0 {
+        let factor = 10i128.checked_pow(scale.unsigned_abs())?;
+        mantissa = mantissa.checked_mul(factor)?;
+        scale = 0;
+    }
+
+    Decimal::try_from_i128_with_scale(mantissa, u32::try_from(scale).ok()?).ok()
+}
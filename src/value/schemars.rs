@@ -0,0 +1,79 @@
+//! JSON Schema descriptions of the Smile-specific value types.
+//!
+//! The schemas describe the JSON forms produced by `Value::to_json_value`.
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+impl JsonSchema for BigInteger {
+    fn schema_name() -> Cow<'static, str> {
+        "BigInteger".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "serde_smile::value::BigInteger".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "An arbitrary-precision integer, represented as a number or as a string of decimal digits.",
+            "type": ["integer", "string"],
+            "format": "big-integer",
+            "pattern": "^-?[0-9]+$",
+        })
+    }
+}
+
+impl JsonSchema for BigDecimal {
+    fn schema_name() -> Cow<'static, str> {
+        "BigDecimal".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "serde_smile::value::BigDecimal".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "An arbitrary-precision decimal number, represented as a number or as a string in the \
+                format of Java's `BigDecimal.toString`.",
+            "type": ["number", "string"],
+            "format": "big-decimal",
+            "pattern": "^-?[0-9]+(\\.[0-9]+)?([eE][+-]?[0-9]+)?$",
+        })
+    }
+}
+
+// like serde_json::Value, any JSON value is accepted
+impl JsonSchema for Value {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "AnyValue".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        true.into()
+    }
+}
+
+impl JsonSchema for Map {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <BTreeMap<String, Value>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <BTreeMap<String, Value>>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <BTreeMap<String, Value>>::json_schema(generator)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::ser::string_cache::{self, CachedStr, StringCache};
+use crate::ser::{zigzag_i32, zigzag_i64};
+use crate::value::{BigDecimal, BigInteger, Map, Value};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+/// Encoding options used by [`Value::serialized_size`].
+///
+/// The options correspond to those of [`Builder`](crate::ser::Builder), and default to the same
+/// [`Builder::jackson_defaults`](crate::ser::Builder::jackson_defaults) configuration.
+#[derive(Clone, Debug)]
+pub struct SizeOptions {
+    raw_binary: bool,
+    shared_strings: bool,
+    shared_properties: bool,
+    write_end_marker: bool,
+    shared_cache_capacity: usize,
+}
+
+impl Default for SizeOptions {
+    fn default() -> Self {
+        SizeOptions::new()
+    }
+}
+
+impl SizeOptions {
+    /// Creates a new `SizeOptions` with the default configuration.
+    pub fn new() -> Self {
+        SizeOptions {
+            raw_binary: false,
+            shared_strings: false,
+            shared_properties: true,
+            write_end_marker: false,
+            shared_cache_capacity: string_cache::LIMIT,
+        }
+    }
+
+    /// Sets whether binary data is encoded raw rather than with the 7-bit encoding.
+    ///
+    /// Defaults to `false`.
+    pub fn raw_binary(&mut self, raw_binary: bool) -> &mut Self {
+        self.raw_binary = raw_binary;
+        self
+    }
+
+    /// Sets whether string values are shared.
+    ///
+    /// Defaults to `false`.
+    pub fn shared_strings(&mut self, shared_strings: bool) -> &mut Self {
+        self.shared_strings = shared_strings;
+        self
+    }
+
+    /// Sets whether map keys are shared.
+    ///
+    /// Defaults to `true`.
+    pub fn shared_properties(&mut self, shared_properties: bool) -> &mut Self {
+        self.shared_properties = shared_properties;
+        self
+    }
+
+    /// Sets the maximum number of strings retained by each of the shared string and shared property caches.
+    ///
+    /// Defaults to 1024.
+    pub fn shared_cache_capacity(&mut self, shared_cache_capacity: usize) -> &mut Self {
+        self.shared_cache_capacity = shared_cache_capacity;
+        self
+    }
+
+    /// Sets whether the end-of-stream token is written after the value.
+    ///
+    /// Defaults to `false`.
+    pub fn write_end_marker(&mut self, write_end_marker: bool) -> &mut Self {
+        self.write_end_marker = write_end_marker;
+        self
+    }
+}
+
+impl Value {
+    /// Returns the number of bytes in the Smile encoding of the value, including the header.
+    ///
+    /// This computes the size without encoding the value, but it models the shared string and property caches, so the
+    /// result is exactly the length of the output of a [`Serializer`](crate::Serializer) configured with the same
+    /// options. The value is assumed to be serializable; a value nested more deeply than the serializer's recursion
+    /// limit will fail to serialize but still has a size.
+    pub fn serialized_size(&self, options: &SizeOptions) -> u64 {
+        let mut sizer = Sizer {
+            raw_binary: options.raw_binary,
+            shared_strings: if options.shared_strings {
+                Some(StringCache::new(options.shared_cache_capacity))
+            } else {
+                None
+            },
+            shared_properties: if options.shared_properties {
+                Some(StringCache::new(options.shared_cache_capacity))
+            } else {
+                None
+            },
+        };
+
+        let header = 4;
+        let end_marker = u64::from(options.write_end_marker);
+        header + sizer.value(self) + end_marker
+    }
+}
+
+struct Sizer {
+    raw_binary: bool,
+    shared_strings: Option<StringCache>,
+    shared_properties: Option<StringCache>,
+}
+
+impl Sizer {
+    fn value(&mut self, value: &Value) -> u64 {
+        match value {
+            Value::Null | Value::Boolean(_) => 1,
+            Value::Integer(v) => integer(*v),
+            Value::Long(v) => match i32::try_from(*v) {
+                Ok(v) => integer(v),
+                Err(_) => 1 + vint(zigzag_i64(*v)),
+            },
+            Value::BigInteger(v) => big_integer(v),
+            Value::Float(_) => 6,
+            Value::Double(_) => 11,
+            Value::BigDecimal(v) => big_decimal(v),
+            Value::String(v) => self.string(v),
+            Value::Binary(v) => {
+                let len = v.len() as u64;
+                if self.raw_binary {
+                    1 + vint(len) + len
+                } else {
+                    1 + seven_bit_binary(len)
+                }
+            }
+            Value::Array(v) => 2 + v.iter().map(|v| self.value(v)).sum::<u64>(),
+            Value::Object(v) => 2 + self.object(v),
+        }
+    }
+
+    fn object(&mut self, map: &Map) -> u64 {
+        let mut size = 0;
+        for (key, value) in map {
+            size += self.key(key);
+            size += self.value(value);
+        }
+        size
+    }
+
+    fn string(&mut self, v: &str) -> u64 {
+        if v.is_empty() {
+            return 1;
+        }
+
+        if let Some(backref) = shared(&mut self.shared_strings, v) {
+            return if backref <= 30 { 1 } else { 2 };
+        }
+
+        let len = v.len() as u64;
+        if len <= 64 {
+            1 + len
+        } else {
+            2 + len
+        }
+    }
+
+    fn key(&mut self, v: &str) -> u64 {
+        if v.is_empty() {
+            return 1;
+        }
+
+        if let Some(backref) = shared(&mut self.shared_properties, v) {
+            return if backref <= 63 { 1 } else { 2 };
+        }
+
+        let len = v.len() as u64;
+        if (len <= 64 && v.is_ascii()) || len < 57 {
+            1 + len
+        } else {
+            2 + len
+        }
+    }
+}
+
+/// Looks up a string in a cache, interning it if it's not present.
+fn shared(cache: &mut Option<StringCache>, v: &str) -> Option<u16> {
+    let cache = cache.as_mut()?;
+    if v.len() > 64 {
+        return None;
+    }
+
+    match cache.get(v) {
+        Some(backref) => Some(backref),
+        None => {
+            cache.intern(CachedStr::Shared(Arc::from(v)));
+            None
+        }
+    }
+}
+
+fn integer(v: i32) -> u64 {
+    let zigzag = zigzag_i32(v);
+    if zigzag < 32 {
+        1
+    } else {
+        1 + vint(zigzag)
+    }
+}
+
+fn big_integer(v: &BigInteger) -> u64 {
+    1 + seven_bit_binary(v.as_be_bytes().len() as u64)
+}
+
+fn big_decimal(v: &BigDecimal) -> u64 {
+    1 + vint(zigzag_i32(v.scale()))
+        + seven_bit_binary(v.unscaled_value().as_be_bytes().len() as u64)
+}
+
+fn vint(mut v: u64) -> u64 {
+    // the last byte only stores 6 bits
+    let mut size = 1;
+    v >>= 6;
+    while v != 0 {
+        size += 1;
+        v >>= 7;
+    }
+    size
+}
+
+/// The size of 7-bit encoded binary data, including its length prefix.
+fn seven_bit_binary(len: u64) -> u64 {
+    let remainder = len % 7;
+    let encoded_remainder = if remainder == 0 { 0 } else { remainder + 1 };
+    vint(len) + len / 7 * 8 + encoded_remainder
+}
@@ -0,0 +1,140 @@
+use crate::value::{BigDecimal, BigInteger};
+use crate::Error;
+use serde::de::value::U32Deserializer;
+use serde::de::{self, DeserializeSeed, EnumAccess, Unexpected, VariantAccess, Visitor};
+use serde::forward_to_deserialize_any;
+
+/// A key of the synthetic map yielded for a `BigInteger` or `BigDecimal` token.
+///
+/// To most visitors the key looks like its field name. `Value` and its relatives need to tell the synthetic maps apart
+/// from real maps with the same keys, so they ask for a newtype struct named [`SyntheticKey::MARKER`], which only this
+/// deserializer answers with an enum identifying the key. Other key deserializers visit a newtype struct or a string
+/// instead.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum SyntheticKey {
+    IntegerValue,
+    DecimalScale,
+    DecimalValue,
+}
+
+impl SyntheticKey {
+    pub(crate) const MARKER: &'static str = "\0SmileSyntheticKey";
+
+    fn name(self) -> &'static str {
+        match self {
+            SyntheticKey::IntegerValue => BigInteger::FIELD_NAME,
+            SyntheticKey::DecimalScale => BigDecimal::SCALE_FIELD_NAME,
+            SyntheticKey::DecimalValue => BigDecimal::VALUE_FIELD_NAME,
+        }
+    }
+}
+
+/// The kind of synthetic map, identified by its first key.
+pub(crate) enum SyntheticMap {
+    BigInteger,
+    BigDecimal,
+}
+
+impl SyntheticMap {
+    /// Identifies the map from the enum visited in response to a [`SyntheticKey::MARKER`] request for its first key.
+    pub(crate) fn from_enum<'de, A>(data: A) -> Result<Self, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (index, variant) = data.variant::<u32>()?;
+        variant.unit_variant()?;
+        match index {
+            i if i == SyntheticKey::IntegerValue as u32 => Ok(SyntheticMap::BigInteger),
+            i if i == SyntheticKey::DecimalScale as u32 => Ok(SyntheticMap::BigDecimal),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Unsigned(u64::from(index)),
+                &"the first key of a synthetic map",
+            )),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for SyntheticKey {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.name())
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == Self::MARKER {
+            visitor.visit_enum(self)
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de> EnumAccess<'de> for SyntheticKey {
+    type Error = Error;
+    type Variant = UnitVariant;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(U32Deserializer::<Error>::new(self as u32))?;
+        Ok((value, UnitVariant))
+    }
+}
+
+pub(crate) struct UnitVariant;
+
+impl<'de> VariantAccess<'de> for UnitVariant {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(Error::mismatched_type(
+            Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _: usize, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::mismatched_type(
+            Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(self, _: &'static [&'static str], _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::mismatched_type(
+            Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
@@ -0,0 +1,160 @@
+use crate::value::big_integer::NativeInteger;
+use crate::value::{string, Map, Value};
+use crate::Error;
+#[cfg(feature = "preserve_order")]
+use indexmap::IndexMap;
+#[cfg(not(feature = "preserve_order"))]
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+enum WideInteger {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl Value {
+    fn to_wide_integer(&self, expected: &'static str) -> Result<WideInteger, Error> {
+        match self {
+            Value::Integer(v) => Ok(WideInteger::Signed(i128::from(*v))),
+            Value::Long(v) => Ok(WideInteger::Signed(i128::from(*v))),
+            Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+                Some(NativeInteger::I64(v)) => Ok(WideInteger::Signed(i128::from(v))),
+                Some(NativeInteger::U64(v)) => Ok(WideInteger::Signed(i128::from(v))),
+                Some(NativeInteger::I128(v)) => Ok(WideInteger::Signed(v)),
+                Some(NativeInteger::U128(v)) => Ok(WideInteger::Unsigned(v)),
+                None => Err(Error::value_out_of_range(expected)),
+            },
+            _ => Err(Error::invalid_value_type(expected, self.kind())),
+        }
+    }
+}
+
+macro_rules! try_from_integer {
+    ($($t:ident),*) => {
+        $(
+            impl TryFrom<&Value> for $t {
+                type Error = Error;
+
+                /// Converts `Integer`, `Long`, and `BigInteger` values which fit in the target type.
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    let v = match value.to_wide_integer(stringify!($t))? {
+                        WideInteger::Signed(v) => $t::try_from(v).ok(),
+                        WideInteger::Unsigned(v) => $t::try_from(v).ok(),
+                    };
+                    v.ok_or_else(|| Error::value_out_of_range(stringify!($t)))
+                }
+            }
+
+            impl TryFrom<Value> for $t {
+                type Error = Error;
+
+                /// Converts `Integer`, `Long`, and `BigInteger` values which fit in the target type.
+                #[inline]
+                fn try_from(value: Value) -> Result<Self, Self::Error> {
+                    $t::try_from(&value)
+                }
+            }
+        )*
+    };
+}
+
+try_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl TryFrom<&Value> for bool {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        value
+            .as_bool()
+            .ok_or_else(|| Error::invalid_value_type("boolean", value.kind()))
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        bool::try_from(&value)
+    }
+}
+
+impl TryFrom<&Value> for f32 {
+    type Error = Error;
+
+    /// Converts `Float` values.
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Float(v) => Ok(*v),
+            _ => Err(Error::invalid_value_type("f32", value.kind())),
+        }
+    }
+}
+
+impl TryFrom<Value> for f32 {
+    type Error = Error;
+
+    /// Converts `Float` values.
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        f32::try_from(&value)
+    }
+}
+
+impl TryFrom<&Value> for f64 {
+    type Error = Error;
+
+    /// Converts values following the rules of [`Value::as_f64`].
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value.as_f64() {
+            Some(v) => Ok(v),
+            None if value.is_integral() => Err(Error::value_out_of_range("f64")),
+            None => Err(Error::invalid_value_type("f64", value.kind())),
+        }
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = Error;
+
+    /// Converts values following the rules of [`Value::as_f64`].
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        f64::try_from(&value)
+    }
+}
+
+macro_rules! try_from_container {
+    ($t:ty, $variant:ident, $expected:expr, $convert:path) => {
+        impl TryFrom<Value> for $t {
+            type Error = Error;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok($convert(v)),
+                    _ => Err(Error::invalid_value_type($expected, value.kind())),
+                }
+            }
+        }
+
+        impl TryFrom<&Value> for $t {
+            type Error = Error;
+
+            fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok($convert(v.clone())),
+                    _ => Err(Error::invalid_value_type($expected, value.kind())),
+                }
+            }
+        }
+    };
+}
+
+try_from_container!(String, String, "string", string::into_string);
+try_from_container!(Vec<u8>, Binary, "binary", From::from);
+try_from_container!(Vec<Value>, Array, "array", From::from);
+try_from_container!(Map, Object, "object", From::from);
+#[cfg(feature = "preserve_order")]
+try_from_container!(IndexMap<String, Value>, Object, "object", From::from);
+#[cfg(not(feature = "preserve_order"))]
+try_from_container!(BTreeMap<String, Value>, Object, "object", From::from);
@@ -0,0 +1,842 @@
+use crate::value::big_integer::NativeInteger;
+use crate::value::map::{self, Map};
+use crate::value::synthetic_key::SyntheticKey;
+use crate::value::{binary, string, BigDecimal, BigInteger, Value};
+use crate::{integer_key, Error};
+use serde::de::value::{BorrowedStrDeserializer, BytesDeserializer, StringDeserializer};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Unexpected,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::borrow::Borrow;
+use std::{slice, vec};
+
+/// Interprets a [`Value`] as an instance of type `T`.
+///
+/// `BigInteger` and `BigDecimal` values are handled the same way as they are when deserializing from Smile directly,
+/// so they can be deserialized into both the [`BigInteger`] and [`BigDecimal`] types and Rust integers where
+/// appropriate.
+///
+/// To deserialize from a borrowed value without consuming it, use `T::deserialize(&value)` instead. Strings and binary
+/// data are then borrowed from the value where the target type supports it.
+pub fn from_value<T>(value: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+impl Value {
+    pub(crate) fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Value::Null => Unexpected::Unit,
+            Value::Boolean(v) => Unexpected::Bool(*v),
+            Value::Integer(v) => Unexpected::Signed(i64::from(*v)),
+            Value::Long(v) => Unexpected::Signed(*v),
+            Value::BigInteger(_) => Unexpected::Other("big integer"),
+            Value::Float(v) => Unexpected::Float(f64::from(*v)),
+            Value::Double(v) => Unexpected::Float(*v),
+            Value::BigDecimal(_) => Unexpected::Other("big decimal"),
+            Value::String(v) => Unexpected::Str(v),
+            Value::Binary(v) => Unexpected::Bytes(v),
+            Value::Array(_) => Unexpected::Seq,
+            Value::Object(_) => Unexpected::Map,
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Boolean(v) => visitor.visit_bool(v),
+            Value::Integer(v) => visitor.visit_i32(v),
+            Value::Long(v) => visitor.visit_i64(v),
+            Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+                Some(v) => v.visit(visitor),
+                None => visitor.visit_map(BigIntegerDeserializer {
+                    buf: Some(v.into_be_bytes()),
+                }),
+            },
+            Value::Float(v) => visitor.visit_f32(v),
+            Value::Double(v) => visitor.visit_f64(v),
+            Value::BigDecimal(v) => visitor.visit_map(BigDecimalDeserializer::new(v)),
+            Value::String(v) => visitor.visit_string(string::into_string(v)),
+            Value::Binary(v) => visitor.visit_byte_buf(binary::into_vec(v)),
+            Value::Array(v) => visit_array(v, visitor),
+            Value::Object(v) => visit_object(v, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::String(variant) => {
+                visitor.visit_enum(StringDeserializer::new(string::into_string(variant)))
+            }
+            Value::Object(map) => {
+                let mut it = map.into_iter();
+                let (variant, value) = match it.next() {
+                    Some(entry) => entry,
+                    None => {
+                        return Err(Error::mismatched_value(
+                            Unexpected::Map,
+                            &"map with a single key",
+                        ))
+                    }
+                };
+                if it.next().is_some() {
+                    return Err(Error::mismatched_value(
+                        Unexpected::Map,
+                        &"map with a single key",
+                    ));
+                }
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(Error::mismatched_type(other.unexpected(), &"string or map")),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == BigInteger::STRUCT_NAME && fields == [BigInteger::FIELD_NAME] {
+            if let Value::BigInteger(v) = self {
+                return visitor.visit_map(BigIntegerDeserializer {
+                    buf: Some(v.into_be_bytes()),
+                });
+            }
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string unit
+        unit_struct seq tuple tuple_struct map identifier ignored_any
+        bytes byte_buf
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+fn visit_array<'de, V>(array: Vec<Value>, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = array.len();
+    let mut deserializer = SeqDeserializer {
+        iter: array.into_iter(),
+    };
+    let value = visitor.visit_seq(&mut deserializer)?;
+    if deserializer.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(Error::mismatched_length(len, &"fewer elements in array"))
+    }
+}
+
+fn visit_object<'de, V>(object: Map, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = object.len();
+    let mut deserializer = MapDeserializer {
+        iter: object.into_iter(),
+        value: None,
+    };
+    let value = visitor.visit_map(&mut deserializer)?;
+    if deserializer.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(Error::mismatched_length(len, &"fewer elements in map"))
+    }
+}
+
+struct SeqDeserializer {
+    iter: vec::IntoIter<Value>,
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer {
+    iter: map::IntoIter,
+    value: Option<Value>,
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyDeserializer { key }).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+macro_rules! deserialize_integer_key {
+    ($method:ident => $visit:ident) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            match integer_key::parse(&self.key) {
+                Some(integer) => visitor.$visit(integer?),
+                None => self.deserialize_any(visitor),
+            }
+        }
+    };
+}
+
+struct KeyDeserializer {
+    key: String,
+}
+
+impl<'de> serde::Deserializer<'de> for KeyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.key)
+    }
+
+    deserialize_integer_key!(deserialize_i8 => visit_i8);
+    deserialize_integer_key!(deserialize_i16 => visit_i16);
+    deserialize_integer_key!(deserialize_i32 => visit_i32);
+    deserialize_integer_key!(deserialize_i64 => visit_i64);
+    deserialize_integer_key!(deserialize_i128 => visit_i128);
+    deserialize_integer_key!(deserialize_u8 => visit_u8);
+    deserialize_integer_key!(deserialize_u16 => visit_u16);
+    deserialize_integer_key!(deserialize_u32 => visit_u32);
+    deserialize_integer_key!(deserialize_u64 => visit_u64);
+    deserialize_integer_key!(deserialize_u128 => visit_u128);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(StringDeserializer::new(self.key))
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(StringDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+struct VariantDeserializer {
+    value: Value,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.value)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Array(v) => visit_array(v, visitor),
+            other => Err(Error::mismatched_type(other.unexpected(), &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Object(v) => visit_object(v, visitor),
+            other => Err(Error::mismatched_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for &'de Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Boolean(v) => visitor.visit_bool(*v),
+            Value::Integer(v) => visitor.visit_i32(*v),
+            Value::Long(v) => visitor.visit_i64(*v),
+            Value::BigInteger(v) => match NativeInteger::from_be_bytes(v.as_be_bytes()) {
+                Some(v) => v.visit(visitor),
+                None => visitor.visit_map(BigIntegerDeserializer {
+                    buf: Some(v.as_be_bytes()),
+                }),
+            },
+            Value::Float(v) => visitor.visit_f32(*v),
+            Value::Double(v) => visitor.visit_f64(*v),
+            Value::BigDecimal(v) => visitor.visit_map(BigDecimalDeserializer::new(v)),
+            Value::String(v) => visitor.visit_borrowed_str(v),
+            Value::Binary(v) => visitor.visit_borrowed_bytes(v),
+            Value::Array(v) => visit_array_ref(v, visitor),
+            Value::Object(v) => visit_object_ref(v, visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::String(variant) => visitor.visit_enum(BorrowedStrDeserializer::new(variant)),
+            Value::Object(map) => {
+                let mut it = map.iter();
+                let (variant, value) = match it.next() {
+                    Some(entry) => entry,
+                    None => {
+                        return Err(Error::mismatched_value(
+                            Unexpected::Map,
+                            &"map with a single key",
+                        ))
+                    }
+                };
+                if it.next().is_some() {
+                    return Err(Error::mismatched_value(
+                        Unexpected::Map,
+                        &"map with a single key",
+                    ));
+                }
+                visitor.visit_enum(EnumRefDeserializer { variant, value })
+            }
+            other => Err(Error::mismatched_type(other.unexpected(), &"string or map")),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name == BigInteger::STRUCT_NAME && fields == [BigInteger::FIELD_NAME] {
+            if let Value::BigInteger(v) = self {
+                return visitor.visit_map(BigIntegerDeserializer {
+                    buf: Some(v.as_be_bytes()),
+                });
+            }
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string unit
+        unit_struct seq tuple tuple_struct map identifier ignored_any
+        bytes byte_buf
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+fn visit_array_ref<'de, V>(array: &'de [Value], visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = array.len();
+    let mut deserializer = SeqRefDeserializer { iter: array.iter() };
+    let value = visitor.visit_seq(&mut deserializer)?;
+    if deserializer.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(Error::mismatched_length(len, &"fewer elements in array"))
+    }
+}
+
+fn visit_object_ref<'de, V>(object: &'de Map, visitor: V) -> Result<V::Value, Error>
+where
+    V: Visitor<'de>,
+{
+    let len = object.len();
+    let mut deserializer = MapRefDeserializer {
+        iter: object.iter(),
+        value: None,
+    };
+    let value = visitor.visit_map(&mut deserializer)?;
+    if deserializer.iter.len() == 0 {
+        Ok(value)
+    } else {
+        Err(Error::mismatched_length(len, &"fewer elements in map"))
+    }
+}
+
+struct SeqRefDeserializer<'de> {
+    iter: slice::Iter<'de, Value>,
+}
+
+impl<'de> SeqAccess<'de> for SeqRefDeserializer<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => seed.deserialize(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapRefDeserializer<'de> {
+    iter: map::Iter<'de>,
+    value: Option<&'de Value>,
+}
+
+impl<'de> MapAccess<'de> for MapRefDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(KeyRefDeserializer { key }).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct KeyRefDeserializer<'de> {
+    key: &'de str,
+}
+
+impl<'de> serde::Deserializer<'de> for KeyRefDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.key)
+    }
+
+    deserialize_integer_key!(deserialize_i8 => visit_i8);
+    deserialize_integer_key!(deserialize_i16 => visit_i16);
+    deserialize_integer_key!(deserialize_i32 => visit_i32);
+    deserialize_integer_key!(deserialize_i64 => visit_i64);
+    deserialize_integer_key!(deserialize_i128 => visit_i128);
+    deserialize_integer_key!(deserialize_u8 => visit_u8);
+    deserialize_integer_key!(deserialize_u16 => visit_u16);
+    deserialize_integer_key!(deserialize_u32 => visit_u32);
+    deserialize_integer_key!(deserialize_u64 => visit_u64);
+    deserialize_integer_key!(deserialize_u128 => visit_u128);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.key))
+    }
+
+    forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct EnumRefDeserializer<'de> {
+    variant: &'de str,
+    value: &'de Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumRefDeserializer<'de> {
+    type Error = Error;
+
+    type Variant = VariantRefDeserializer<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, VariantRefDeserializer { value: self.value }))
+    }
+}
+
+struct VariantRefDeserializer<'de> {
+    value: &'de Value,
+}
+
+impl<'de> de::VariantAccess<'de> for VariantRefDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.value)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Array(v) => visit_array_ref(v, visitor),
+            other => Err(Error::mismatched_type(other.unexpected(), &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Object(v) => visit_object_ref(v, visitor),
+            other => Err(Error::mismatched_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+struct BigIntegerDeserializer<B> {
+    buf: Option<B>,
+}
+
+impl<'de, B> MapAccess<'de> for BigIntegerDeserializer<B>
+where
+    B: AsRef<[u8]>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.buf.is_none() {
+            return Ok(None);
+        }
+
+        seed.deserialize(SyntheticKey::IntegerValue).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let buf = self.buf.take().expect("next_value_seed called after end");
+        seed.deserialize(BytesDeserializer::new(buf.as_ref()))
+    }
+}
+
+enum BigDecimalStage {
+    Scale,
+    Value,
+    Done,
+}
+
+struct BigDecimalDeserializer<B> {
+    value: B,
+    stage: BigDecimalStage,
+}
+
+impl<B> BigDecimalDeserializer<B> {
+    fn new(value: B) -> Self {
+        BigDecimalDeserializer {
+            value,
+            stage: BigDecimalStage::Scale,
+        }
+    }
+}
+
+impl<'de, B> MapAccess<'de> for BigDecimalDeserializer<B>
+where
+    B: Borrow<BigDecimal>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let key = match self.stage {
+            BigDecimalStage::Scale => SyntheticKey::DecimalScale,
+            BigDecimalStage::Value => SyntheticKey::DecimalValue,
+            BigDecimalStage::Done => return Ok(None),
+        };
+
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.stage {
+            BigDecimalStage::Scale => {
+                self.stage = BigDecimalStage::Value;
+                seed.deserialize(self.value.borrow().scale().into_deserializer())
+            }
+            BigDecimalStage::Value => {
+                self.stage = BigDecimalStage::Done;
+                seed.deserialize(BytesDeserializer::new(
+                    self.value.borrow().unscaled_value().as_be_bytes(),
+                ))
+            }
+            BigDecimalStage::Done => panic!("next_value_seed called after end"),
+        }
+    }
+}
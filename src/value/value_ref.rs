@@ -0,0 +1,349 @@
+use crate::value::big_decimal::BigDecimalVisitor;
+use crate::value::big_integer::BigIntegerVisitor;
+use crate::value::synthetic_key::{SyntheticKey, SyntheticMap};
+use crate::value::{binary, string, BigDecimal, BigInteger, Map, Value};
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A representation of a Smile value which borrows strings and binary data from its input where possible.
+///
+/// Parsing a [`Value`] always allocates for every string and binary payload. When deserializing from a slice with
+/// [`from_slice`](crate::from_slice) or [`from_mut_slice`](crate::from_mut_slice), a `ValueRef` instead borrows
+/// strings, keys, and binary data directly from the input. Values which can't be borrowed, such as shared string
+/// back-references or 7-bit encoded binary in an immutable slice, are stored owned.
+///
+/// Unlike [`Map`], object entries are stored in a `Vec` in their encoded order, and duplicate keys are preserved.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValueRef<'a> {
+    /// A null value.
+    Null,
+    /// A boolean value.
+    Boolean(bool),
+    /// An integer value.
+    Integer(i32),
+    /// A long value.
+    Long(i64),
+    /// A big integer value.
+    BigInteger(BigInteger),
+    /// A float value.
+    Float(f32),
+    /// A double value.
+    Double(f64),
+    /// A big decimal value.
+    BigDecimal(BigDecimal),
+    /// A string value.
+    String(Cow<'a, str>),
+    /// A binary value.
+    Binary(Cow<'a, [u8]>),
+    /// An array value.
+    Array(Vec<ValueRef<'a>>),
+    /// An object value.
+    Object(Vec<(Cow<'a, str>, ValueRef<'a>)>),
+}
+
+impl ValueRef<'_> {
+    /// Converts the `ValueRef` into an owned [`Value`].
+    ///
+    /// If an object contains duplicate keys, the last value wins.
+    pub fn to_owned(&self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Boolean(v) => Value::Boolean(*v),
+            ValueRef::Integer(v) => Value::Integer(*v),
+            ValueRef::Long(v) => Value::Long(*v),
+            ValueRef::BigInteger(v) => Value::BigInteger(v.clone()),
+            ValueRef::Float(v) => Value::Float(*v),
+            ValueRef::Double(v) => Value::Double(*v),
+            ValueRef::BigDecimal(v) => Value::BigDecimal(v.clone()),
+            ValueRef::String(v) => Value::String(string::from_str(v)),
+            ValueRef::Binary(v) => Value::Binary(binary::from_slice(v)),
+            ValueRef::Array(v) => Value::Array(v.iter().map(ValueRef::to_owned).collect()),
+            ValueRef::Object(v) => {
+                let mut map = Map::with_capacity(v.len());
+                for (key, value) in v {
+                    map.insert(key.to_string(), value.to_owned());
+                }
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+impl Serialize for ValueRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ValueRef::Null => serializer.serialize_unit(),
+            ValueRef::Boolean(v) => serializer.serialize_bool(*v),
+            ValueRef::Integer(v) => serializer.serialize_i32(*v),
+            ValueRef::Long(v) => serializer.serialize_i64(*v),
+            ValueRef::BigInteger(v) => v.serialize(serializer),
+            ValueRef::Float(v) => serializer.serialize_f32(*v),
+            ValueRef::Double(v) => serializer.serialize_f64(*v),
+            ValueRef::BigDecimal(v) => v.serialize(serializer),
+            ValueRef::String(v) => serializer.serialize_str(v),
+            ValueRef::Binary(v) => serializer.serialize_bytes(v),
+            ValueRef::Array(v) => v.serialize(serializer),
+            ValueRef::Object(v) => {
+                let mut map = serializer.serialize_map(Some(v.len()))?;
+                for (key, value) in v {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for ValueRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // See the Deserialize implementation for Value.
+        deserializer.deserialize_struct(
+            BigInteger::STRUCT_NAME,
+            &[BigInteger::FIELD_NAME],
+            ValueRefVisitor,
+        )
+    }
+}
+
+struct ValueRefVisitor;
+
+impl<'de> Visitor<'de> for ValueRefVisitor {
+    type Value = ValueRef<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any Smile value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Boolean(v))
+    }
+
+    fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Integer(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Long(v))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Ok(ValueRef::BigInteger(BigInteger::from(v))),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_u128(u128::from(v))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(v) {
+            Ok(v) => self.visit_i64(v),
+            Err(_) => Ok(ValueRef::BigInteger(BigInteger::from(v))),
+        }
+    }
+
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Float(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Double(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::String(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::String(Cow::Borrowed(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::String(Cow::Owned(v)))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Binary(Cow::Owned(v.to_vec())))
+    }
+
+    fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Binary(Cow::Borrowed(v)))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Binary(Cow::Owned(v)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ValueRef::Null)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = vec![];
+        while let Some(value) = seq.next_element()? {
+            vec.push(value);
+        }
+        Ok(ValueRef::Array(vec))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut v = vec![];
+
+        let key = match map.next_key_seed(KeySeed { first: true })? {
+            Some(Key::BigInteger) => {
+                return BigIntegerVisitor.finish_map(map).map(ValueRef::BigInteger)
+            }
+            Some(Key::BigDecimal) => {
+                return BigDecimalVisitor.finish_map(map).map(ValueRef::BigDecimal)
+            }
+            Some(Key::Other(key)) => key,
+            None => return Ok(ValueRef::Object(v)),
+        };
+
+        v.push((key, map.next_value()?));
+        while let Some(key) = map.next_key_seed(KeySeed { first: false })? {
+            let key = match key {
+                Key::Other(key) => key,
+                Key::BigInteger | Key::BigDecimal => unreachable!(),
+            };
+            v.push((key, map.next_value()?));
+        }
+
+        Ok(ValueRef::Object(v))
+    }
+}
+
+enum Key<'de> {
+    BigInteger,
+    BigDecimal,
+    Other(Cow<'de, str>),
+}
+
+struct KeySeed {
+    first: bool,
+}
+
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Key<'de>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // only the first key can identify a synthetic map
+        if self.first {
+            deserializer.deserialize_newtype_struct(SyntheticKey::MARKER, self)
+        } else {
+            deserializer.deserialize_str(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed {
+    type Value = Key<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Key::Other(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Key::Other(Cow::Borrowed(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Key::Other(Cow::Owned(v)))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        match SyntheticMap::from_enum(data)? {
+            SyntheticMap::BigInteger => Ok(Key::BigInteger),
+            SyntheticMap::BigDecimal => Ok(Key::BigDecimal),
+        }
+    }
+}
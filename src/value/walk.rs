@@ -0,0 +1,213 @@
+use crate::value::diff::push_token;
+use crate::value::Value;
+use std::fmt::{self, Write};
+
+/// The location of a value visited by [`Value::walk`] or [`Value::walk_mut`].
+#[derive(Debug)]
+pub struct Path {
+    pointer: String,
+    depth: usize,
+}
+
+impl Path {
+    fn new() -> Self {
+        Path {
+            pointer: String::new(),
+            depth: 0,
+        }
+    }
+
+    /// Returns the path as a JSON Pointer.
+    ///
+    /// The pointer is empty for the root value, and can be passed to [`Value::pointer`] to look the value up again.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Returns the number of arrays and objects containing the value.
+    ///
+    /// The root value has a depth of 0.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn set(&mut self, parent: &Parent, token: &Token<'_>) {
+        self.pointer.truncate(parent.len);
+        self.depth = parent.depth;
+        match token {
+            Token::Root => {}
+            Token::Key(key) => {
+                push_token(&mut self.pointer, key);
+                self.depth += 1;
+            }
+            Token::Index(index) => {
+                write!(self.pointer, "/{}", index).unwrap();
+                self.depth += 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pointer)
+    }
+}
+
+/// The action to take after visiting a value in [`Value::walk`] or [`Value::walk_mut`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum WalkControl {
+    /// Continue the walk, descending into the value if it is an array or object.
+    Continue,
+    /// Continue the walk, but skip the contents of the value.
+    Skip,
+    /// End the walk without visiting any more values.
+    Stop,
+}
+
+// The location of a value's container in the path buffer.
+#[derive(Copy, Clone)]
+struct Parent {
+    len: usize,
+    depth: usize,
+}
+
+enum Token<'a> {
+    Root,
+    Key(&'a str),
+    Index(usize),
+}
+
+impl Value {
+    /// Visits the value and everything it contains.
+    ///
+    /// The callback is invoked with the path and contents of each value in depth-first pre-order: a container is
+    /// visited before its contents, array elements in index order, and object entries in iteration order. The
+    /// callback's return value determines whether the contents of the visited value are walked.
+    ///
+    /// The walk uses an explicit stack rather than recursion, so arbitrarily deeply nested values can be walked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::smile;
+    /// use serde_smile::value::WalkControl;
+    ///
+    /// let value = smile!({"a": ["x", 1], "b": {"c": "y"}});
+    ///
+    /// let mut strings = vec![];
+    /// value.walk(|path, value| {
+    ///     if let Some(s) = value.as_str() {
+    ///         strings.push(format!("{}={}", path, s));
+    ///     }
+    ///     WalkControl::Continue
+    /// });
+    /// assert_eq!(strings, ["/a/0=x", "/b/c=y"]);
+    /// ```
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(&Path, &Value) -> WalkControl,
+    {
+        let mut path = Path::new();
+        let root = Parent { len: 0, depth: 0 };
+        let mut stack = vec![(root, Token::Root, self)];
+
+        while let Some((parent, token, value)) = stack.pop() {
+            path.set(&parent, &token);
+            match f(&path, value) {
+                WalkControl::Continue => {}
+                WalkControl::Skip => continue,
+                WalkControl::Stop => return,
+            }
+
+            let parent = Parent {
+                len: path.pointer.len(),
+                depth: path.depth,
+            };
+            // children are pushed in reverse so they're popped in order
+            match value {
+                Value::Array(values) => stack.extend(
+                    values
+                        .iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(i, value)| (parent, Token::Index(i), value)),
+                ),
+                Value::Object(map) => {
+                    let start = stack.len();
+                    stack.extend(
+                        map.iter()
+                            .map(|(key, value)| (parent, Token::Key(key), value)),
+                    );
+                    stack[start..].reverse();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Visits the value and everything it contains, allowing each to be modified.
+    ///
+    /// This behaves like [`Value::walk`]. The contents of a container are walked after the callback returns, so any
+    /// changes the callback makes to a container, like removing entries from an object, are reflected in the rest of
+    /// the walk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_smile::smile;
+    /// use serde_smile::value::{Value, WalkControl};
+    ///
+    /// let mut value = smile!({"user": {"name": "alice", "password": "hunter2"}});
+    ///
+    /// value.walk_mut(|_, value| {
+    ///     if let Value::Object(map) = value {
+    ///         map.remove("password");
+    ///     }
+    ///     WalkControl::Continue
+    /// });
+    /// assert_eq!(value, smile!({"user": {"name": "alice"}}));
+    /// ```
+    pub fn walk_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Path, &mut Value) -> WalkControl,
+    {
+        let mut path = Path::new();
+        let root = Parent { len: 0, depth: 0 };
+        let mut stack = vec![(root, Token::Root, self)];
+
+        while let Some((parent, token, value)) = stack.pop() {
+            path.set(&parent, &token);
+            match f(&path, value) {
+                WalkControl::Continue => {}
+                WalkControl::Skip => continue,
+                WalkControl::Stop => return,
+            }
+
+            let parent = Parent {
+                len: path.pointer.len(),
+                depth: path.depth,
+            };
+            match value {
+                Value::Array(values) => stack.extend(
+                    values
+                        .iter_mut()
+                        .enumerate()
+                        .rev()
+                        .map(|(i, value)| (parent, Token::Index(i), value)),
+                ),
+                Value::Object(map) => {
+                    let start = stack.len();
+                    stack.extend(
+                        map.iter_mut()
+                            .map(|(key, value)| (parent, Token::Key(key), value)),
+                    );
+                    stack[start..].reverse();
+                }
+                _ => {}
+            }
+        }
+    }
+}
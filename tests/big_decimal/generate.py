@@ -10,3 +10,13 @@ test(0.0000025)
 test(100.25)
 test(-0.0000025)
 test(-100.25)
+
+# string values are parsed exactly by Jackson, preserving the scale
+def test_str(n):
+    with open(f"{n}.json", "w") as file:
+        json.dump({"value": n}, file)
+
+test_str("1E+5")
+test_str("-12345")
+test_str("1.23456")
+test_str("1E-1000000")
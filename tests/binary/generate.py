@@ -14,3 +14,7 @@ test(b"", "empty")
 for i in range(1, 9):
     test(b"a" * i, "a" * i)
     test(b"\xaa" * i, f"{i}pattern")
+
+# lengths around the 7 byte chunks of the 7-bit encoding and the vint length boundaries
+for n in [0, 1, 6, 7, 8, 13, 14, 63, 64, 1000, 65535]:
+    test(bytes(i % 256 for i in range(n)), f"length-{n}")